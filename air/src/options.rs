@@ -6,6 +6,7 @@
 use alloc::vec::Vec;
 use fri::FriOptions;
 use math::{StarkField, ToElements};
+use stir::StirOptions;
 use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
 
 // CONSTANTS
@@ -23,6 +24,14 @@ const MAX_GRINDING_FACTOR: u32 = 32;
 const FRI_MIN_FOLDING_FACTOR: usize = 2;
 const FRI_MAX_FOLDING_FACTOR: usize = 16;
 const FRI_MAX_REMAINDER_DEGREE: usize = 255;
+const FRI_MAX_FOLDING_SCHEDULE_LEN: usize = 8;
+
+const MAX_MERKLE_CAP_HEIGHT: usize = 8;
+
+const MIN_MERKLE_ARITY: usize = 2;
+const MAX_MERKLE_ARITY: usize = 16;
+
+const MAX_PARTITION_SIZE: usize = 255;
 
 // TYPES AND INTERFACES
 // ================================================================================================
@@ -51,6 +60,21 @@ pub enum FieldExtension {
     Cubic = 3,
 }
 
+/// Defines the protocol used to prove that the DEEP composition polynomial is of low degree.
+///
+/// Both protocols are configured using the same set of parameters (i.e., folding factors, maximum
+/// remainder degree, number of queries etc.), and both offer roughly the same level of security
+/// for the same parameters. However, STIR proofs are usually noticeably smaller than FRI proofs,
+/// while being somewhat more expensive to generate and to verify.
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum LowDegreeTest {
+    /// Low-degree test is performed using the FRI protocol.
+    Fri = 1,
+    /// Low-degree test is performed using the STIR protocol.
+    Stir = 2,
+}
+
 /// STARK protocol parameters.
 ///
 /// These parameters have a direct impact on proof soundness, proof generation time, and proof
@@ -68,6 +92,32 @@ pub enum FieldExtension {
 /// 4. Grinding factor - higher values increase proof soundness, but also may increase proof
 ///    generation time. More precisely, conjectured proof soundness is bounded by
 ///    `num_queries * log2(blowup_factor) + grinding_factor`.
+/// 5. Merkle cap height - when set to k > 0, the prover commits to the 2^k nodes at height k of
+///    each Merkle tree rather than to the tree root. This does not affect proof soundness, but
+///    makes commitments larger while making every authentication path k nodes shorter. For
+///    proofs with many queries this usually results in smaller proofs.
+/// 6. Merkle tree arity - the arity of Merkle trees used to commit to the trace, the constraint
+///    evaluations and the FRI layers can be set independently. This does not affect proof
+///    soundness; higher arities make authentication paths shorter but wider, which usually
+///    increases proof size but may reduce the cost of verifying proofs recursively when an
+///    algebraic hash function is used.
+/// 7. Partition sizes - when set to n > 0, rows of the extended execution trace (or of the
+///    constraint evaluation matrix) which are wider than n elements are hashed in partitions of
+///    n elements, and the partition digests are then merged into a single leaf digest. This does
+///    not affect proof soundness or proof size, but may reduce the cost of verifying proofs for
+///    wide traces recursively.
+/// 8. FRI folding schedule - FRI layers can be folded using different folding factors; for
+///    example, the first few layers can be folded by 16 and the remaining layers by 4. This does
+///    not materially affect proof soundness, but allows balancing proof size against proof
+///    generation time.
+/// 9. Low-degree test - the DEEP composition polynomial can be tested for low degree using
+///    either FRI or STIR protocols. For the same parameters, STIR usually results in smaller
+///    proofs, but requires more work from the prover and the verifier.
+/// 10. Commit grinding factor - when set to k > 0, the prover must perform k bits of
+///     Proof-of-Work before the DEEP composition coefficients and each FRI folding challenge are
+///     drawn. This increases soundness of the errors associated with these challenges by k bits,
+///     at the cost of additional prover work for every FRI layer. Challenges drawn earlier (i.e.,
+///     the out-of-domain point and constraint composition coefficients) are not affected.
 ///
 /// Another important parameter in defining STARK security level, which is not a part of [ProofOptions]
 /// is the hash function used in the protocol. The soundness of a STARK proof is limited by the
@@ -78,9 +128,18 @@ pub struct ProofOptions {
     num_queries: u8,
     blowup_factor: u8,
     grinding_factor: u8,
+    commit_grinding_factor: u8,
     field_extension: FieldExtension,
     fri_folding_factor: u8,
+    fri_folding_schedule: Vec<u8>,
     fri_remainder_max_degree: u8,
+    merkle_cap_height: u8,
+    trace_merkle_arity: u8,
+    constraint_merkle_arity: u8,
+    fri_merkle_arity: u8,
+    trace_partition_size: u8,
+    constraint_partition_size: u8,
+    low_degree_test: LowDegreeTest,
 }
 
 // PROOF OPTIONS IMPLEMENTATION
@@ -96,6 +155,18 @@ impl ProofOptions {
     /// have a blowup factor smaller than 2.
     pub const MIN_BLOWUP_FACTOR: usize = MIN_BLOWUP_FACTOR;
 
+    /// Largest allowed Merkle tree cap height which is currently set to 8.
+    pub const MAX_MERKLE_CAP_HEIGHT: usize = MAX_MERKLE_CAP_HEIGHT;
+
+    /// Largest allowed Merkle tree arity which is currently set to 16.
+    pub const MAX_MERKLE_ARITY: usize = MAX_MERKLE_ARITY;
+
+    /// Largest allowed row partition size which is currently set to 255.
+    pub const MAX_PARTITION_SIZE: usize = MAX_PARTITION_SIZE;
+
+    /// Largest allowed number of entries in a FRI folding schedule which is currently set to 8.
+    pub const MAX_FRI_FOLDING_SCHEDULE_LEN: usize = FRI_MAX_FOLDING_SCHEDULE_LEN;
+
     // CONSTRUCTORS
     // --------------------------------------------------------------------------------------------
     /// Returns a new instance of [ProofOptions] struct constructed from the specified parameters.
@@ -143,12 +214,149 @@ impl ProofOptions {
             num_queries: num_queries as u8,
             blowup_factor: blowup_factor as u8,
             grinding_factor: grinding_factor as u8,
+            commit_grinding_factor: 0,
             field_extension,
             fri_folding_factor: fri_folding_factor as u8,
+            fri_folding_schedule: Vec::new(),
             fri_remainder_max_degree: fri_remainder_max_degree as u8,
+            merkle_cap_height: 0,
+            trace_merkle_arity: 2,
+            constraint_merkle_arity: 2,
+            fri_merkle_arity: 2,
+            trace_partition_size: 0,
+            constraint_partition_size: 0,
+            low_degree_test: LowDegreeTest::Fri,
         }
     }
 
+    /// Updates the provided [ProofOptions] instance to commit to Merkle tree caps of the
+    /// specified height instead of to Merkle tree roots.
+    ///
+    /// For trees which are not deep enough to have a cap at the specified height, the cap
+    /// height is reduced to one less than the depth of the tree.
+    ///
+    /// # Panics
+    /// Panics if `merkle_cap_height` is greater than 8.
+    pub const fn with_merkle_cap_height(mut self, merkle_cap_height: usize) -> ProofOptions {
+        assert!(
+            merkle_cap_height <= MAX_MERKLE_CAP_HEIGHT,
+            "Merkle cap height cannot be greater than 8"
+        );
+        self.merkle_cap_height = merkle_cap_height as u8;
+        self
+    }
+
+    /// Updates the provided [ProofOptions] instance to commit to the execution trace, the
+    /// constraint evaluations, and the FRI layers using Merkle trees of the specified arities.
+    ///
+    /// By default, all Merkle trees are binary. When a tree has an arity of 2^k, the Merkle cap
+    /// height is rounded down to the nearest multiple of k levels above the leaves of the tree.
+    ///
+    /// # Panics
+    /// Panics if any of the arities is not a power of two between 2 and 16.
+    pub const fn with_merkle_arities(
+        mut self,
+        trace_arity: usize,
+        constraint_arity: usize,
+        fri_arity: usize,
+    ) -> ProofOptions {
+        assert!(is_valid_merkle_arity(trace_arity), "trace Merkle tree arity is not supported");
+        assert!(
+            is_valid_merkle_arity(constraint_arity),
+            "constraint Merkle tree arity is not supported"
+        );
+        assert!(is_valid_merkle_arity(fri_arity), "FRI Merkle tree arity is not supported");
+        self.trace_merkle_arity = trace_arity as u8;
+        self.constraint_merkle_arity = constraint_arity as u8;
+        self.fri_merkle_arity = fri_arity as u8;
+        self
+    }
+
+    /// Updates the provided [ProofOptions] instance to hash rows of the extended execution trace
+    /// and of the constraint evaluation matrix in partitions of the specified sizes.
+    ///
+    /// By default, both partition sizes are set to zero, which means that each row is hashed as
+    /// a single sequence of elements. Otherwise, rows wider than the partition size are hashed
+    /// in partitions, and the resulting digests are merged into a single digest.
+    ///
+    /// # Panics
+    /// Panics if any of the partition sizes is greater than 255.
+    pub const fn with_partition_sizes(
+        mut self,
+        trace_partition_size: usize,
+        constraint_partition_size: usize,
+    ) -> ProofOptions {
+        assert!(
+            trace_partition_size <= MAX_PARTITION_SIZE,
+            "trace partition size cannot be greater than 255"
+        );
+        assert!(
+            constraint_partition_size <= MAX_PARTITION_SIZE,
+            "constraint partition size cannot be greater than 255"
+        );
+        self.trace_partition_size = trace_partition_size as u8;
+        self.constraint_partition_size = constraint_partition_size as u8;
+        self
+    }
+
+    /// Updates the provided [ProofOptions] instance to fold the first FRI layers using the
+    /// specified folding schedule.
+    ///
+    /// The i-th entry of the schedule specifies the folding factor for the i-th FRI layer; layers
+    /// beyond the end of the schedule are folded using `fri_folding_factor`. For example, a
+    /// schedule of `[16, 16]` with `fri_folding_factor` set to 4 folds the first two layers by 16
+    /// and all subsequent layers by 4.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// - The schedule contains more than 8 entries.
+    /// - Any of the folding factors in the schedule is not 2, 4, 8, or 16.
+    pub fn with_fri_folding_schedule(mut self, folding_schedule: &[usize]) -> ProofOptions {
+        assert!(
+            folding_schedule.len() <= FRI_MAX_FOLDING_SCHEDULE_LEN,
+            "FRI folding schedule cannot contain more than 8 entries"
+        );
+        for &folding_factor in folding_schedule {
+            assert!(
+                is_valid_fri_folding_factor(folding_factor),
+                "FRI folding factor {folding_factor} is not supported"
+            );
+        }
+        self.fri_folding_schedule = folding_schedule.iter().map(|&f| f as u8).collect();
+        self
+    }
+
+    /// Updates the provided [ProofOptions] instance to test the DEEP composition polynomial for
+    /// low degree using the specified protocol.
+    ///
+    /// By default, FRI protocol is used. When STIR protocol is used, FRI folding factor, FRI
+    /// folding schedule, FRI remainder degree and FRI Merkle tree arity are used to configure
+    /// STIR rounds instead.
+    pub const fn with_low_degree_test(mut self, low_degree_test: LowDegreeTest) -> ProofOptions {
+        self.low_degree_test = low_degree_test;
+        self
+    }
+
+    /// Updates the provided [ProofOptions] instance to require Proof-of-Work of the specified
+    /// number of bits before the DEEP composition coefficients and each FRI folding challenge
+    /// are drawn.
+    ///
+    /// By default, commit grinding factor is set to zero, which means that no Proof-of-Work is
+    /// performed in the commit phase of the protocol. Commit-phase grinding is not supported by
+    /// STIR; when STIR is used as the low-degree test, grinding is applied only to the DEEP
+    /// composition coefficients.
+    ///
+    /// # Panics
+    /// Panics if `grinding_factor` is greater than 32.
+    pub const fn with_commit_grinding_factor(mut self, grinding_factor: u32) -> ProofOptions {
+        assert!(
+            grinding_factor <= MAX_GRINDING_FACTOR,
+            "grinding factor cannot be greater than 32"
+        );
+        self.commit_grinding_factor = grinding_factor as u8;
+        self
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -182,6 +390,15 @@ impl ProofOptions {
         self.grinding_factor as u32
     }
 
+    /// Returns commit-phase grinding factor for a STARK proof.
+    ///
+    /// When non-zero, the prover performs Proof-of-Work of this many bits before the DEEP
+    /// composition coefficients and each FRI folding challenge are drawn. This forces a dishonest
+    /// prover to redo the work every time they try to re-sample any of these challenges.
+    pub const fn commit_grinding_factor(&self) -> u32 {
+        self.commit_grinding_factor as u32
+    }
+
     /// Specifies whether composition polynomial should be constructed in an extension field
     /// of STARK protocol.
     ///
@@ -199,26 +416,129 @@ impl ProofOptions {
         B::GENERATOR
     }
 
+    /// Returns the factor by which the degree of a polynomial is reduced at each FRI layer which
+    /// is not covered by the FRI folding schedule.
+    pub const fn fri_folding_factor(&self) -> usize {
+        self.fri_folding_factor as usize
+    }
+
+    /// Returns the folding factors used for the first FRI layers.
+    ///
+    /// The schedule is empty if all FRI layers are folded using the same folding factor.
+    pub fn fri_folding_schedule(&self) -> Vec<usize> {
+        self.fri_folding_schedule.iter().map(|&f| f as usize).collect()
+    }
+
+    /// Returns the height of the Merkle tree caps to which the prover commits.
+    ///
+    /// A cap of height zero consists of the root of a tree only; a cap of height k consists of
+    /// 2^k nodes at height k of a tree. Higher caps increase the size of commitments but reduce
+    /// the length of authentication paths by k nodes per query.
+    pub const fn merkle_cap_height(&self) -> usize {
+        self.merkle_cap_height as usize
+    }
+
+    /// Returns the arity of Merkle trees used to commit to the execution trace.
+    pub const fn trace_merkle_arity(&self) -> usize {
+        self.trace_merkle_arity as usize
+    }
+
+    /// Returns the arity of the Merkle tree used to commit to the constraint evaluations.
+    pub const fn constraint_merkle_arity(&self) -> usize {
+        self.constraint_merkle_arity as usize
+    }
+
+    /// Returns the arity of Merkle trees used to commit to FRI layers.
+    pub const fn fri_merkle_arity(&self) -> usize {
+        self.fri_merkle_arity as usize
+    }
+
+    /// Returns the size of partitions in which rows of the extended execution trace are hashed.
+    ///
+    /// A partition size of zero means that the rows are not partitioned.
+    pub const fn trace_partition_size(&self) -> usize {
+        self.trace_partition_size as usize
+    }
+
+    /// Returns the size of partitions in which rows of the constraint evaluation matrix are
+    /// hashed.
+    ///
+    /// A partition size of zero means that the rows are not partitioned.
+    pub const fn constraint_partition_size(&self) -> usize {
+        self.constraint_partition_size as usize
+    }
+
+    /// Returns the protocol used to test the DEEP composition polynomial for low degree.
+    pub const fn low_degree_test(&self) -> LowDegreeTest {
+        self.low_degree_test
+    }
+
     /// Returns options for FRI protocol instantiated with parameters from this proof options.
     pub fn to_fri_options(&self) -> FriOptions {
         let folding_factor = self.fri_folding_factor as usize;
         let remainder_max_degree = self.fri_remainder_max_degree as usize;
         FriOptions::new(self.blowup_factor(), folding_factor, remainder_max_degree)
+            .with_folding_schedule(self.fri_folding_schedule())
+            .with_merkle_cap_height(self.merkle_cap_height())
+            .with_merkle_arity(self.fri_merkle_arity())
+            .with_grinding_factor(self.commit_grinding_factor())
+    }
+
+    /// Returns options for STIR protocol instantiated with parameters from this proof options.
+    pub fn to_stir_options(&self) -> StirOptions {
+        let folding_factor = self.fri_folding_factor as usize;
+        let remainder_max_degree = self.fri_remainder_max_degree as usize;
+        StirOptions::new(
+            self.blowup_factor(),
+            folding_factor,
+            remainder_max_degree,
+            self.num_queries(),
+        )
+        .with_folding_schedule(self.fri_folding_schedule())
+        .with_merkle_cap_height(self.merkle_cap_height())
+        .with_merkle_arity(self.fri_merkle_arity())
     }
 }
 
 impl<E: StarkField> ToElements<E> for ProofOptions {
     fn to_elements(&self) -> Vec<E> {
-        // encode field extension and FRI parameters into a single field element
-        let mut buf = self.field_extension as u32;
+        // encode Merkle cap height, field extension and FRI parameters into a single field
+        // element
+        let mut buf = self.merkle_cap_height as u32;
+        buf = (buf << 8) | self.field_extension as u32;
         buf = (buf << 8) | self.fri_folding_factor as u32;
         buf = (buf << 8) | self.fri_remainder_max_degree as u32;
 
+        // encode Merkle tree arities into a single field element
+        let mut arities = self.trace_merkle_arity as u32;
+        arities = (arities << 8) | self.constraint_merkle_arity as u32;
+        arities = (arities << 8) | self.fri_merkle_arity as u32;
+
+        // encode partition sizes into a single field element
+        let partition_sizes =
+            (self.trace_partition_size as u32) << 8 | self.constraint_partition_size as u32;
+
+        // encode FRI folding schedule into a single field element; each folding factor is encoded
+        // as its log2 in 4 bits, and since folding factors are at least 2, empty slots are zeros
+        let folding_schedule = self
+            .fri_folding_schedule
+            .iter()
+            .rev()
+            .fold(0u32, |acc, &f| (acc << 4) | f.trailing_zeros());
+
+        // encode commit-phase and query grinding factors into a single field element
+        let grinding_factors =
+            (self.commit_grinding_factor as u32) << 8 | self.grinding_factor as u32;
+
         vec![
             E::from(buf),
-            E::from(self.grinding_factor),
+            E::from(grinding_factors),
             E::from(self.blowup_factor),
             E::from(self.num_queries),
+            E::from(arities),
+            E::from(partition_sizes),
+            E::from(folding_schedule),
+            E::from(self.low_degree_test as u8),
         ]
     }
 }
@@ -232,6 +552,16 @@ impl Serializable for ProofOptions {
         target.write(self.field_extension);
         target.write_u8(self.fri_folding_factor);
         target.write_u8(self.fri_remainder_max_degree);
+        target.write_u8(self.merkle_cap_height);
+        target.write_u8(self.trace_merkle_arity);
+        target.write_u8(self.constraint_merkle_arity);
+        target.write_u8(self.fri_merkle_arity);
+        target.write_u8(self.trace_partition_size);
+        target.write_u8(self.constraint_partition_size);
+        target.write_u8(self.fri_folding_schedule.len() as u8);
+        target.write_bytes(&self.fri_folding_schedule);
+        target.write(self.low_degree_test);
+        target.write_u8(self.commit_grinding_factor);
     }
 }
 
@@ -241,17 +571,86 @@ impl Deserializable for ProofOptions {
     /// # Errors
     /// Returns an error of a valid proof options could not be read from the specified `source`.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
-        Ok(ProofOptions::new(
+        let options = ProofOptions::new(
             source.read_u8()? as usize,
             source.read_u8()? as usize,
             source.read_u8()? as u32,
             FieldExtension::read_from(source)?,
             source.read_u8()? as usize,
             source.read_u8()? as usize,
-        ))
+        );
+
+        let merkle_cap_height = source.read_u8()? as usize;
+        if merkle_cap_height > MAX_MERKLE_CAP_HEIGHT {
+            return Err(DeserializationError::InvalidValue(format!(
+                "Merkle cap height cannot be greater than {MAX_MERKLE_CAP_HEIGHT}, but was {merkle_cap_height}"
+            )));
+        }
+        let options = options.with_merkle_cap_height(merkle_cap_height);
+
+        let trace_arity = source.read_u8()? as usize;
+        let constraint_arity = source.read_u8()? as usize;
+        let fri_arity = source.read_u8()? as usize;
+        for arity in [trace_arity, constraint_arity, fri_arity] {
+            if !is_valid_merkle_arity(arity) {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "Merkle tree arity {arity} is not supported"
+                )));
+            }
+        }
+
+        let trace_partition_size = source.read_u8()? as usize;
+        let constraint_partition_size = source.read_u8()? as usize;
+
+        let schedule_len = source.read_u8()? as usize;
+        if schedule_len > FRI_MAX_FOLDING_SCHEDULE_LEN {
+            return Err(DeserializationError::InvalidValue(format!(
+                "FRI folding schedule cannot contain more than {FRI_MAX_FOLDING_SCHEDULE_LEN} entries, but was {schedule_len}"
+            )));
+        }
+        let mut folding_schedule = Vec::with_capacity(schedule_len);
+        for _ in 0..schedule_len {
+            let folding_factor = source.read_u8()? as usize;
+            if !is_valid_fri_folding_factor(folding_factor) {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "FRI folding factor {folding_factor} is not supported"
+                )));
+            }
+            folding_schedule.push(folding_factor);
+        }
+
+        let options = options
+            .with_merkle_arities(trace_arity, constraint_arity, fri_arity)
+            .with_partition_sizes(trace_partition_size, constraint_partition_size)
+            .with_fri_folding_schedule(&folding_schedule)
+            .with_low_degree_test(LowDegreeTest::read_from(source)?);
+
+        let commit_grinding_factor = source.read_u8()? as u32;
+        if commit_grinding_factor > MAX_GRINDING_FACTOR {
+            return Err(DeserializationError::InvalidValue(format!(
+                "commit grinding factor cannot be greater than {MAX_GRINDING_FACTOR}, but was {commit_grinding_factor}"
+            )));
+        }
+
+        Ok(options.with_commit_grinding_factor(commit_grinding_factor))
     }
 }
 
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns true if the specified FRI `folding_factor` is a power of two between 2 and 16.
+const fn is_valid_fri_folding_factor(folding_factor: usize) -> bool {
+    folding_factor.is_power_of_two()
+        && folding_factor >= FRI_MIN_FOLDING_FACTOR
+        && folding_factor <= FRI_MAX_FOLDING_FACTOR
+}
+
+/// Returns true if the specified Merkle tree `arity` is a power of two between 2 and 16.
+const fn is_valid_merkle_arity(arity: usize) -> bool {
+    arity.is_power_of_two() && arity >= MIN_MERKLE_ARITY && arity <= MAX_MERKLE_ARITY
+}
+
 // FIELD EXTENSION IMPLEMENTATION
 // ================================================================================================
 
@@ -300,13 +699,43 @@ impl Deserializable for FieldExtension {
     }
 }
 
+// LOW-DEGREE TEST SERIALIZATION
+// ================================================================================================
+
+impl Serializable for LowDegreeTest {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(*self as u8);
+    }
+
+    /// Returns an estimate of how many bytes are needed to represent self.
+    fn get_size_hint(&self) -> usize {
+        1
+    }
+}
+
+impl Deserializable for LowDegreeTest {
+    /// Reads a low-degree test enum from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            1 => Ok(LowDegreeTest::Fri),
+            2 => Ok(LowDegreeTest::Stir),
+            value => Err(DeserializationError::InvalidValue(format!(
+                "value {value} cannot be deserialized as LowDegreeTest enum"
+            ))),
+        }
+    }
+}
+
 // TESTS
 // ================================================================================================
 
 #[cfg(test)]
 mod tests {
-    use super::{FieldExtension, ProofOptions, ToElements};
+    use super::{FieldExtension, LowDegreeTest, ProofOptions, ToElements};
+    use alloc::string::ToString;
     use math::fields::f64::BaseElement;
+    use utils::{Deserializable, DeserializationError, Serializable};
 
     #[test]
     fn proof_options_to_elements() {
@@ -328,6 +757,10 @@ mod tests {
             BaseElement::from(grinding_factor),
             BaseElement::from(blowup_factor as u32),
             BaseElement::from(num_queries as u32),
+            BaseElement::from(0x020202u32),
+            BaseElement::from(0u32),
+            BaseElement::from(0u32),
+            BaseElement::from(LowDegreeTest::Fri as u8),
         ];
 
         let options = ProofOptions::new(
@@ -339,5 +772,64 @@ mod tests {
             fri_remainder_max_degree as usize,
         );
         assert_eq!(expected, options.to_elements());
+
+        let merkle_cap_height = 4;
+        let ext_fri_cap = u32::from_le_bytes([
+            fri_remainder_max_degree,
+            fri_folding_factor,
+            field_extension as u8,
+            merkle_cap_height,
+        ]);
+        let options = options.with_merkle_cap_height(merkle_cap_height as usize);
+        assert_eq!(BaseElement::from(ext_fri_cap), options.to_elements()[0]);
+
+        let options = options.with_merkle_arities(4, 8, 16);
+        let arities = u32::from_le_bytes([16, 8, 4, 0]);
+        assert_eq!(BaseElement::from(arities), options.to_elements()[4]);
+
+        let options = options.with_partition_sizes(8, 4);
+        let partition_sizes = u32::from_le_bytes([4, 8, 0, 0]);
+        assert_eq!(BaseElement::from(partition_sizes), options.to_elements()[5]);
+
+        // folding factors are encoded as their log2, starting from the lowest 4 bits
+        let options = options.with_fri_folding_schedule(&[16, 16, 4]);
+        assert_eq!(BaseElement::from(0x244u32), options.to_elements()[6]);
+
+        let options = options.with_low_degree_test(LowDegreeTest::Stir);
+        assert_eq!(BaseElement::from(LowDegreeTest::Stir as u8), options.to_elements()[7]);
+
+        let options = options.with_commit_grinding_factor(12);
+        let grinding_factors = u32::from_le_bytes([grinding_factor as u8, 12, 0, 0]);
+        assert_eq!(BaseElement::from(grinding_factors), options.to_elements()[1]);
+    }
+
+    #[test]
+    fn proof_options_serialization() {
+        let options = ProofOptions::new(30, 8, 20, FieldExtension::Quadratic, 4, 31)
+            .with_fri_folding_schedule(&[16, 8]);
+        let bytes = options.to_bytes();
+        assert_eq!(options, ProofOptions::read_from_bytes(&bytes).unwrap());
+        assert_eq!(vec![16, 8], options.to_fri_options().folding_schedule());
+
+        let options = options.with_low_degree_test(LowDegreeTest::Stir);
+        let bytes = options.to_bytes();
+        assert_eq!(options, ProofOptions::read_from_bytes(&bytes).unwrap());
+        assert_eq!(&[16, 8], options.to_stir_options().folding_schedule());
+
+        let options = options.with_commit_grinding_factor(16);
+        let bytes = options.to_bytes();
+        assert_eq!(options, ProofOptions::read_from_bytes(&bytes).unwrap());
+        assert_eq!(16, options.to_fri_options().grinding_factor());
+
+        // Merkle cap height is the 7th byte of serialized options
+        let mut bytes = options.with_merkle_cap_height(8).to_bytes();
+        assert_eq!(8, bytes[6]);
+        bytes[6] = 9;
+        assert_eq!(
+            Err(DeserializationError::InvalidValue(
+                "Merkle cap height cannot be greater than 8, but was 9".to_string()
+            )),
+            ProofOptions::read_from_bytes(&bytes)
+        );
     }
 }
@@ -3,6 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use crate::ProofOptions;
 use alloc::vec::Vec;
 use crypto::Hasher;
 use utils::{
@@ -19,6 +20,9 @@ use utils::{
 /// * Commitment to the evaluations of constraint composition polynomial over LDE domain.
 /// * Commitments to the evaluations of polynomials at all FRI layers.
 ///
+/// Each commitment is a Merkle tree cap: a list of 2^k nodes at height k of a Merkle tree. When
+/// the cap height is zero, the cap consists of a single tree root.
+///
 /// Internally, the commitments are stored as a sequence of bytes. Thus, to retrieve the
 /// commitments, [parse()](Commitments::parse) function should be used.
 #[derive(Debug, Clone, Default, Eq, PartialEq)]
@@ -28,24 +32,49 @@ impl Commitments {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     /// Returns a new Commitments struct initialized with the provided commitments.
+    ///
+    /// # Panics
+    /// Panics if the number of nodes in any of the caps is not a power of two.
     pub fn new<H: Hasher>(
-        trace_roots: Vec<H::Digest>,
-        constraint_root: H::Digest,
-        fri_roots: Vec<H::Digest>,
+        trace_caps: Vec<Vec<H::Digest>>,
+        constraint_cap: Vec<H::Digest>,
+        fri_caps: Vec<Vec<H::Digest>>,
     ) -> Self {
-        let mut bytes = Vec::new();
-        bytes.write_many(&trace_roots);
-        bytes.write(constraint_root);
-        bytes.write_many(&fri_roots);
-        Commitments(bytes)
+        let mut commitments = Commitments::default();
+        for trace_cap in trace_caps.iter() {
+            commitments.add::<H>(trace_cap);
+        }
+        commitments.add::<H>(&constraint_cap);
+        for fri_cap in fri_caps.iter() {
+            commitments.add::<H>(fri_cap);
+        }
+        commitments
     }
 
     // PUBLIC METHODS
     // --------------------------------------------------------------------------------------------
 
     /// Adds the specified commitment to the list of commitments.
-    pub fn add<H: Hasher>(&mut self, commitment: &H::Digest) {
-        commitment.write_into(&mut self.0);
+    ///
+    /// The commitment is a Merkle tree cap; it is recorded as the height of the cap followed by
+    /// all nodes of the cap.
+    ///
+    /// # Panics
+    /// Panics if the number of nodes in the `commitment` is not a power of two, or if the height
+    /// of the cap is greater than [ProofOptions::MAX_MERKLE_CAP_HEIGHT].
+    pub fn add<H: Hasher>(&mut self, commitment: &[H::Digest]) {
+        assert!(
+            commitment.len().is_power_of_two(),
+            "number of nodes in a Merkle cap must be a power of two"
+        );
+        let cap_height = commitment.len().ilog2() as usize;
+        assert!(
+            cap_height <= ProofOptions::MAX_MERKLE_CAP_HEIGHT,
+            "Merkle cap height cannot be greater than {}",
+            ProofOptions::MAX_MERKLE_CAP_HEIGHT
+        );
+        self.0.write_u8(cap_height as u8);
+        self.0.write_many(commitment);
     }
 
     // PARSING
@@ -58,25 +87,36 @@ impl Commitments {
     /// 2. Constraint composition polynomial evaluation commitment.
     /// 3. FRI layer commitments.
     ///
+    /// Each of the commitments is a Merkle tree cap. When STIR is used as the low-degree test,
+    /// `num_fri_layers` should be set to the number of STIR rounds; the commitment to the
+    /// initial function and the commitments made in every STIR round are then returned in place
+    /// of FRI layer commitments.
+    ///
     /// # Errors
     /// Returns an error if the bytes stored in self could not be parsed into the requested number
-    /// of commitments, or if there are any unconsumed bytes remaining after the parsing completes.
+    /// of commitments, if any of the caps is higher than [ProofOptions::MAX_MERKLE_CAP_HEIGHT],
+    /// or if there are any unconsumed bytes remaining after the parsing completes.
     #[allow(clippy::type_complexity)]
     pub fn parse<H: Hasher>(
         self,
         num_trace_segments: usize,
         num_fri_layers: usize,
-    ) -> Result<(Vec<H::Digest>, H::Digest, Vec<H::Digest>), DeserializationError> {
+    ) -> Result<(Vec<Vec<H::Digest>>, Vec<H::Digest>, Vec<Vec<H::Digest>>), DeserializationError>
+    {
         let mut reader = SliceReader::new(&self.0);
 
         // parse trace commitments
-        let trace_commitments = reader.read_many(num_trace_segments)?;
+        let trace_commitments = (0..num_trace_segments)
+            .map(|_| read_cap::<H>(&mut reader))
+            .collect::<Result<_, _>>()?;
 
         // parse constraint evaluation commitment:
-        let constraint_commitment = reader.read()?;
+        let constraint_commitment = read_cap::<H>(&mut reader)?;
 
         // read FRI commitments (+ 1 for remainder polynomial commitment)
-        let fri_commitments = reader.read_many(num_fri_layers + 1)?;
+        let fri_commitments = (0..num_fri_layers + 1)
+            .map(|_| read_cap::<H>(&mut reader))
+            .collect::<Result<_, _>>()?;
 
         // make sure we consumed all available commitment bytes
         if reader.has_more_bytes() {
@@ -86,20 +126,36 @@ impl Commitments {
     }
 }
 
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Reads a single Merkle tree cap from the specified `reader`.
+fn read_cap<H: Hasher>(reader: &mut SliceReader) -> Result<Vec<H::Digest>, DeserializationError> {
+    let cap_height = reader.read_u8()? as usize;
+    if cap_height > ProofOptions::MAX_MERKLE_CAP_HEIGHT {
+        return Err(DeserializationError::InvalidValue(format!(
+            "Merkle cap height cannot be greater than {}, but was {}",
+            ProofOptions::MAX_MERKLE_CAP_HEIGHT,
+            cap_height
+        )));
+    }
+    reader.read_many(1 << cap_height)
+}
+
 // SERIALIZATION
 // ================================================================================================
 
 impl Serializable for Commitments {
     /// Serializes `self` and writes the resulting bytes into the `target`.
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        assert!(self.0.len() < u16::MAX as usize);
-        target.write_u16(self.0.len() as u16);
+        assert!(self.0.len() < u32::MAX as usize);
+        target.write_u32(self.0.len() as u32);
         target.write_bytes(&self.0);
     }
 
     /// Returns an estimate of how many bytes are needed to represent self.
     fn get_size_hint(&self) -> usize {
-        self.0.len() + 2
+        self.0.len() + 4
     }
 }
 
@@ -110,7 +166,7 @@ impl Deserializable for Commitments {
     /// Returns an error of a valid Commitments struct could not be read from the specified
     /// `source`.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
-        let num_bytes = source.read_u16()? as usize;
+        let num_bytes = source.read_u32()? as usize;
         let result = source.read_vec(num_bytes)?;
         Ok(Commitments(result))
     }
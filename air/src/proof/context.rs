@@ -270,6 +270,10 @@ mod tests {
             BaseElement::from(grinding_factor),
             BaseElement::from(blowup_factor as u32),
             BaseElement::from(num_queries as u32),
+            BaseElement::from(0x020202u32), // Merkle tree arities
+            BaseElement::from(0x1008u32),   // trace and constraint partition sizes
+            BaseElement::from(0x24u32),     // FRI folding schedule
+            BaseElement::from(1_u32),       // low-degree test
             BaseElement::from(trace_length as u32),
         ];
 
@@ -280,7 +284,9 @@ mod tests {
             field_extension,
             fri_folding_factor as usize,
             fri_remainder_max_degree as usize,
-        );
+        )
+        .with_partition_sizes(16, 8)
+        .with_fri_folding_schedule(&[16, 4]);
         let layout =
             TraceLayout::new(main_width as usize, [aux_width as usize], [aux_rands as usize]);
         let trace_info = TraceInfo::new_multi_segment(layout, trace_length, vec![]);
@@ -0,0 +1,60 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::LowDegreeTest;
+use fri::FriProof;
+use stir::StirProof;
+use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+// LOW-DEGREE PROOF
+// ================================================================================================
+/// Proof that the DEEP composition polynomial is of low degree.
+///
+/// Depending on the [LowDegreeTest] specified in the proof options, this is either a FRI proof or
+/// a STIR proof.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum LowDegreeProof {
+    /// Low-degree proof generated using the FRI protocol.
+    Fri(FriProof),
+    /// Low-degree proof generated using the STIR protocol.
+    Stir(StirProof),
+}
+
+impl LowDegreeProof {
+    /// Returns the protocol which was used to generate this proof.
+    pub fn low_degree_test(&self) -> LowDegreeTest {
+        match self {
+            Self::Fri(_) => LowDegreeTest::Fri,
+            Self::Stir(_) => LowDegreeTest::Stir,
+        }
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for LowDegreeProof {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write(self.low_degree_test());
+        match self {
+            Self::Fri(proof) => proof.write_into(target),
+            Self::Stir(proof) => proof.write_into(target),
+        }
+    }
+}
+
+impl Deserializable for LowDegreeProof {
+    /// Reads a low-degree proof from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid proof could not be read from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match LowDegreeTest::read_from(source)? {
+            LowDegreeTest::Fri => Ok(Self::Fri(FriProof::read_from(source)?)),
+            LowDegreeTest::Stir => Ok(Self::Stir(StirProof::read_from(source)?)),
+        }
+    }
+}
@@ -5,7 +5,7 @@
 
 //! Contains STARK proof struct and associated components.
 
-use crate::{ProofOptions, TraceInfo, TraceLayout};
+use crate::{LowDegreeTest, ProofOptions, TraceInfo, TraceLayout};
 use alloc::vec::Vec;
 use core::cmp;
 use crypto::Hasher;
@@ -28,6 +28,9 @@ pub use ood_frame::OodFrame;
 mod table;
 pub use table::Table;
 
+mod low_degree;
+pub use low_degree::LowDegreeProof;
+
 #[cfg(test)]
 mod tests;
 
@@ -69,9 +72,12 @@ pub struct StarkProof {
     /// Trace and constraint polynomial evaluations at an out-of-domain point.
     pub ood_frame: OodFrame,
     /// Low-degree proof for a DEEP composition polynomial.
-    pub fri_proof: FriProof,
+    pub ldt_proof: LowDegreeProof,
     /// Proof-of-work nonce for query seed grinding.
     pub pow_nonce: u64,
+    /// Proof-of-work nonce for grinding performed before drawing DEEP composition coefficients;
+    /// this is set to zero if the proof was generated with commit grinding factor of zero.
+    pub deep_pow_nonce: u64,
 }
 
 impl StarkProof {
@@ -147,7 +153,7 @@ impl StarkProof {
     pub fn new_dummy() -> Self {
         use crate::FieldExtension;
         use crypto::hashers::Blake3_192 as DummyHasher;
-        use crypto::BatchMerkleProof;
+        use crypto::{BatchMerkleProof, MerkleTree};
         use math::fields::f64::BaseElement as DummyField;
 
         Self {
@@ -158,17 +164,19 @@ impl StarkProof {
             num_unique_queries: 0,
             commitments: Commitments::default(),
             trace_queries: Vec::new(),
-            constraint_queries: Queries::new::<_, DummyField>(
+            constraint_queries: Queries::new::<_, DummyField, MerkleTree<_>>(
                 BatchMerkleProof::<DummyHasher<DummyField>> {
                     leaves: Vec::new(),
                     nodes: Vec::new(),
                     depth: 0,
+                    arity: 2,
                 },
                 vec![vec![DummyField::ONE]],
             ),
             ood_frame: OodFrame::default(),
-            fri_proof: FriProof::new_dummy(),
+            ldt_proof: LowDegreeProof::Fri(FriProof::new_dummy()),
             pow_nonce: 0,
+            deep_pow_nonce: 0,
         }
     }
 }
@@ -184,8 +192,9 @@ impl Serializable for StarkProof {
         target.write_many(&self.trace_queries);
         self.constraint_queries.write_into(target);
         self.ood_frame.write_into(target);
-        self.fri_proof.write_into(target);
-        self.pow_nonce.write_into(target)
+        self.ldt_proof.write_into(target);
+        self.pow_nonce.write_into(target);
+        self.deep_pow_nonce.write_into(target)
     }
 }
 
@@ -207,8 +216,9 @@ impl Deserializable for StarkProof {
             trace_queries,
             constraint_queries: Queries::read_from(source)?,
             ood_frame: OodFrame::read_from(source)?,
-            fri_proof: FriProof::read_from(source)?,
+            ldt_proof: LowDegreeProof::read_from(source)?,
             pow_nonce: source.read_u64()?,
+            deep_pow_nonce: source.read_u64()?,
         };
         Ok(proof)
     }
@@ -226,11 +236,21 @@ fn get_conjectured_security(
 ) -> u32 {
     // compute max security we can get for a given field size
     let field_size = base_field_bits * options.field_extension().degree();
+    // commit-phase grinding does not increase this bound because the out-of-domain point and
+    // constraint composition coefficients are drawn before any grinding is applied
     let field_security = field_size - (trace_domain_size * options.blowup_factor()).ilog2();
 
-    // compute security we get by executing multiple query rounds
-    let security_per_query = options.blowup_factor().ilog2();
-    let mut query_security = security_per_query * options.num_queries() as u32;
+    // compute security we get by executing multiple query rounds; for STIR, the number of queries
+    // is different in every round, and thus, security is limited by the weakest round
+    let mut query_security = match options.low_degree_test() {
+        LowDegreeTest::Fri => {
+            let security_per_query = options.blowup_factor().ilog2();
+            security_per_query * options.num_queries() as u32
+        }
+        LowDegreeTest::Stir => {
+            get_stir_query_security(options, trace_domain_size * options.blowup_factor())
+        }
+    };
 
     // include grinding factor contributions only for proofs adequate security
     if query_security >= GRINDING_CONTRIBUTION_FLOOR {
@@ -283,7 +303,6 @@ fn proven_security_protocol_for_m(
     m: usize,
 ) -> u64 {
     let extension_field_bits = (base_field_bits * options.field_extension().degree()) as f64;
-    let num_fri_queries = options.num_queries() as f64;
     let m = m as f64;
     let rho = 1.0 / options.blowup_factor() as f64;
     let alpha = (1.0 + 0.5 / m) * sqrt(rho);
@@ -312,22 +331,19 @@ fn proven_security_protocol_for_m(
     let alpha_plus = (1.0 + 0.5 / m_plus) * sqrt(rho_plus);
     let theta_plus = 1.0 - alpha_plus;
 
-    // Computes FRI commit-phase (i.e., pre-query) soundness error.
-    // This considers only the first term given in eq. 7 in https://eprint.iacr.org/2022/1216.pdf,
-    // i.e. 0.5 * (m + 0.5)^7 * n^2 / (rho^1.5.q) as all other terms are negligible in comparison.
-    let fri_commit_err_bits = extension_field_bits
-        - log2((0.5 * powf(m + 0.5, 7.0) / powf(rho, 1.5)) * powf(lde_domain_size, 2.0));
-
-    // Compute FRI query-phase soundness error
-    let fri_queries_err_bits =
-        options.grinding_factor() as f64 - log2(powf(1.0 - theta_plus, num_fri_queries));
-
-    // Combined error for FRI
-    let fri_err_bits = cmp::min(fri_commit_err_bits as u64, fri_queries_err_bits as u64);
-    if fri_err_bits < 1 {
+    // Computes soundness error of the low-degree test
+    let ldt_err_bits = match options.low_degree_test() {
+        LowDegreeTest::Fri => {
+            get_fri_err_bits(options, extension_field_bits, lde_domain_size, m, theta_plus)
+        }
+        LowDegreeTest::Stir => {
+            get_stir_err_bits(options, extension_field_bits, lde_domain_size, m, theta_plus)
+        }
+    };
+    if ldt_err_bits < 1 {
         return 0;
     }
-    let fri_err_bits = fri_err_bits - 1;
+    let ldt_err_bits = ldt_err_bits - 1;
 
     // List size
     let l_plus = (2.0 * m_plus + 1.0) / (2.0 * sqrt(rho_plus));
@@ -338,12 +354,19 @@ fn proven_security_protocol_for_m(
 
     // DEEP related soundness error. Note that this uses that the denominator |F| - |D ∪ H|
     // can be approximated by |F| for all practical domain sizes. We also use the blow-up factor
-    // as an upper bound for the maximal constraint degree.
+    // as an upper bound for the maximal constraint degree. The out-of-domain point is drawn
+    // before commit-phase grinding is applied, and thus, grinding does not affect this error.
     let deep_err_bits = -log2(
         l_plus * (max_deg * (trace_domain_size + num_openings - 1.0) + (trace_domain_size - 1.0)),
     ) + extension_field_bits;
 
-    let min = cmp::min(cmp::min(fri_err_bits, ali_err_bits as u64), deep_err_bits as u64);
+    // Soundness error of linear batching with DEEP composition coefficients. The coefficients
+    // are drawn after commit-phase grinding, and thus, the grinding factor is added to the error.
+    let deep_batching_err_bits =
+        -log2(l_plus) + extension_field_bits + options.commit_grinding_factor() as f64;
+
+    let min = cmp::min(cmp::min(ldt_err_bits, ali_err_bits as u64), deep_err_bits as u64);
+    let min = cmp::min(min, deep_batching_err_bits as u64);
     if min < 1 {
         return 0;
     }
@@ -354,6 +377,123 @@ fn proven_security_protocol_for_m(
 // HELPER FUNCTIONS
 // ================================================================================================
 
+/// Returns the sum of (k_i - 1) over all FRI layers, where k_i is the folding factor of the i-th
+/// layer, for an LDE domain of the specified size.
+fn get_fri_folding_sum(options: &ProofOptions, lde_domain_size: usize) -> usize {
+    let fri_options = options.to_fri_options();
+    (0..fri_options.num_fri_layers(lde_domain_size))
+        .map(|i| fri_options.folding_factor_at(i) - 1)
+        .sum()
+}
+
+/// Computes soundness error (in bits) of FRI protocol for a fixed value of the proximity
+/// parameter m.
+fn get_fri_err_bits(
+    options: &ProofOptions,
+    extension_field_bits: f64,
+    lde_domain_size: f64,
+    m: f64,
+    theta_plus: f64,
+) -> u64 {
+    let num_fri_queries = options.num_queries() as f64;
+    let rho = 1.0 / options.blowup_factor() as f64;
+
+    // Computes FRI commit-phase (i.e., pre-query) soundness error.
+    // This considers the terms given in eq. 7 in https://eprint.iacr.org/2022/1216.pdf, i.e.
+    // 0.5 * (m + 0.5)^7 * n^2 / (rho^1.5.q) and (2m + 1) * (n + 1) * sum(k_i - 1) / (rho^0.5.q),
+    // where k_i is the folding factor of the i-th FRI layer; the second term depends on the FRI
+    // folding schedule but it is usually negligible in comparison to the first one. Since every
+    // folding challenge is drawn after commit-phase grinding, the grinding factor is added to
+    // the error.
+    let fri_folding_sum = get_fri_folding_sum(options, lde_domain_size as usize) as f64;
+    let fri_commit_err_bits = extension_field_bits + options.commit_grinding_factor() as f64
+        - log2(
+            (0.5 * powf(m + 0.5, 7.0) / powf(rho, 1.5)) * powf(lde_domain_size, 2.0)
+                + (2.0 * m + 1.0) * (lde_domain_size + 1.0) * fri_folding_sum / sqrt(rho),
+        );
+
+    // Compute FRI query-phase soundness error
+    let fri_queries_err_bits =
+        options.grinding_factor() as f64 - log2(powf(1.0 - theta_plus, num_fri_queries));
+
+    // Combined error for FRI
+    cmp::min(fri_commit_err_bits as u64, fri_queries_err_bits as u64)
+}
+
+/// Computes soundness error (in bits) of STIR protocol for a fixed value of the proximity
+/// parameter m.
+///
+/// This follows the round-by-round soundness analysis of https://eprint.iacr.org/2024/390.pdf in
+/// the list-decoding regime. For every round, the folding error (bounded in the same way as the
+/// commit-phase error of a single FRI layer), the out-of-domain sampling error, and the error of
+/// the queries made against the function of the round are considered; the combined error is
+/// bounded by the largest of these.
+fn get_stir_err_bits(
+    options: &ProofOptions,
+    extension_field_bits: f64,
+    lde_domain_size: f64,
+    m: f64,
+    theta_plus: f64,
+) -> u64 {
+    let stir_options = options.to_stir_options();
+    let domain_size = lde_domain_size as usize;
+    let grinding_factor = options.grinding_factor() as f64;
+
+    let mut stir_err_bits = extension_field_bits;
+    for round in 0..=stir_options.num_rounds(domain_size) {
+        let n = stir_options.round_domain_size(domain_size, round) as f64;
+        let d = stir_options.round_degree(domain_size, round) as f64;
+        let k = stir_options.folding_factor_at(round) as f64;
+        let rho = d / n;
+
+        let fold_err_bits = extension_field_bits
+            - log2(
+                (0.5 * powf(m + 0.5, 7.0) / powf(rho, 1.5)) * powf(n, 2.0)
+                    + (2.0 * m + 1.0) * (n + 1.0) * (k - 1.0) / sqrt(rho),
+            );
+
+        // queries against the initial function are made at the rate in function field F(Z)
+        let alpha = if round == 0 {
+            1.0 - theta_plus
+        } else {
+            (1.0 + 0.5 / m) * sqrt(rho)
+        };
+        let num_queries = stir_options.num_round_queries(domain_size, round) as f64;
+        let query_err_bits = grinding_factor - log2(powf(alpha, num_queries));
+
+        stir_err_bits = stir_err_bits.min(fold_err_bits).min(query_err_bits);
+
+        // the function committed to in every round after the initial one is sampled at an
+        // out-of-domain point; the error depends on the list size at the rate of the round
+        if round > 0 {
+            let list_size = (2.0 * m + 1.0) / (2.0 * sqrt(rho));
+            let ood_err_bits = extension_field_bits - log2(0.5 * powf(list_size, 2.0) * d);
+            stir_err_bits = stir_err_bits.min(ood_err_bits);
+        }
+    }
+
+    stir_err_bits as u64
+}
+
+/// Returns conjectured security (in bits) provided by the queries of STIR protocol for an LDE
+/// domain of the specified size.
+///
+/// Every query against a function of rate rho is conjectured to provide log2(1 / rho) bits of
+/// security; since the number of queries is different in every round, the security is limited by
+/// the weakest round.
+fn get_stir_query_security(options: &ProofOptions, lde_domain_size: usize) -> u32 {
+    let stir_options = options.to_stir_options();
+    (0..=stir_options.num_rounds(lde_domain_size))
+        .map(|round| {
+            let round_blowup = stir_options.round_domain_size(lde_domain_size, round)
+                / stir_options.round_degree(lde_domain_size, round);
+            let num_queries = stir_options.num_round_queries(lde_domain_size, round) as u32;
+            round_blowup.ilog2() * num_queries
+        })
+        .min()
+        .expect("STIR protocol must have at least one round")
+}
+
 /// Computes the largest proximity parameter m needed for Theorem 8
 /// in <https://eprint.iacr.org/2022/1216.pdf> to work.
 fn compute_upper_m(h: usize) -> f64 {
@@ -409,7 +549,10 @@ pub fn ceil(value: f64) -> f64 {
 #[cfg(test)]
 mod prove_security_tests {
     use super::ProofOptions;
-    use crate::{proof::get_proven_security, FieldExtension};
+    use crate::{
+        proof::{get_conjectured_security, get_proven_security},
+        FieldExtension, LowDegreeTest,
+    };
     use math::{fields::f64::BaseElement, StarkField};
 
     #[test]
@@ -657,4 +800,131 @@ mod prove_security_tests {
 
         assert!(security_1 < security_2);
     }
+
+    #[test]
+    fn stir_security() {
+        let field_extension = FieldExtension::Cubic;
+        let base_field_bits = BaseElement::MODULUS_BITS;
+        let fri_folding_factor = 8;
+        let fri_remainder_max_degree = 127;
+        let grinding_factor = 20;
+        let blowup_factor = 8;
+        let num_queries = 40;
+        let collision_resistance = 256;
+        let trace_length = 2_usize.pow(18);
+
+        let options = ProofOptions::new(
+            num_queries,
+            blowup_factor,
+            grinding_factor,
+            field_extension,
+            fri_folding_factor as usize,
+            fri_remainder_max_degree as usize,
+        );
+        let fri_proven =
+            get_proven_security(&options, base_field_bits, trace_length, collision_resistance);
+        let fri_conjectured =
+            get_conjectured_security(&options, base_field_bits, trace_length, collision_resistance);
+
+        let options = options.with_low_degree_test(LowDegreeTest::Stir);
+        let stir_proven =
+            get_proven_security(&options, base_field_bits, trace_length, collision_resistance);
+        let stir_conjectured =
+            get_conjectured_security(&options, base_field_bits, trace_length, collision_resistance);
+
+        assert_eq!(77, fri_proven);
+        assert_eq!(139, fri_conjectured);
+
+        // the number of queries in later STIR rounds is chosen so that every round provides at
+        // least as much security as the queries against the initial function
+        assert_eq!(fri_proven, stir_proven);
+        assert_eq!(fri_conjectured, stir_conjectured);
+    }
+
+    #[test]
+    fn commit_grinding_security() {
+        let field_extension = FieldExtension::Quadratic;
+        let base_field_bits = BaseElement::MODULUS_BITS;
+        let fri_folding_factor = 8;
+        let fri_remainder_max_degree = 127;
+        let grinding_factor = 20;
+        let blowup_factor = 8;
+        let num_queries = 80;
+        let collision_resistance = 256;
+        let trace_length = 2_usize.pow(18);
+
+        let options = ProofOptions::new(
+            num_queries,
+            blowup_factor,
+            grinding_factor,
+            field_extension,
+            fri_folding_factor as usize,
+            fri_remainder_max_degree as usize,
+        );
+        let security_1 =
+            get_conjectured_security(&options, base_field_bits, trace_length, collision_resistance);
+        let proven_1 =
+            get_proven_security(&options, base_field_bits, trace_length, collision_resistance);
+
+        let options = options.with_commit_grinding_factor(16);
+        let security_2 =
+            get_conjectured_security(&options, base_field_bits, trace_length, collision_resistance);
+        let proven_2 =
+            get_proven_security(&options, base_field_bits, trace_length, collision_resistance);
+
+        // conjectured security is limited by the size of the field, and thus, it increases by
+        // the full commit grinding factor
+        // conjectured security is limited by the out-of-domain point and constraint composition
+        // coefficients which are drawn before any grinding, and thus, it does not change
+        assert_eq!(106, security_1);
+        assert_eq!(106, security_2);
+
+        // for these parameters, proven security is limited by the FRI commit-phase error, and
+        // thus, it increases by the full commit grinding factor
+        assert_eq!(67, proven_1);
+        assert_eq!(83, proven_2);
+
+        // commit grinding does not apply to STIR folding challenges
+        let options = options.with_low_degree_test(LowDegreeTest::Stir);
+        let proven_3 =
+            get_proven_security(&options, base_field_bits, trace_length, collision_resistance);
+        let proven_4 = get_proven_security(
+            &options.with_commit_grinding_factor(0),
+            base_field_bits,
+            trace_length,
+            collision_resistance,
+        );
+        assert_eq!(proven_4, proven_3);
+    }
+
+    #[test]
+    fn collision_resistance_limit() {
+        let field_extension = FieldExtension::Cubic;
+        let base_field_bits = BaseElement::MODULUS_BITS;
+        let fri_folding_factor = 8;
+        let fri_remainder_max_degree = 127;
+        let grinding_factor = 20;
+        let blowup_factor = 16;
+        let num_queries = 60;
+        let trace_length = 2_usize.pow(18);
+
+        let options = ProofOptions::new(
+            num_queries,
+            blowup_factor,
+            grinding_factor,
+            field_extension,
+            fri_folding_factor as usize,
+            fri_remainder_max_degree as usize,
+        );
+
+        // security is limited by the collision resistance of hash functions with smaller digests
+        // (e.g., 128 bits for BLAKE3 with 256-bit output and 160 bits for Tip5)
+        assert_eq!(128, get_conjectured_security(&options, base_field_bits, trace_length, 128));
+        assert_eq!(160, get_conjectured_security(&options, base_field_bits, trace_length, 160));
+
+        // with larger digests (e.g., BLAKE3 with 384-bit output), security is limited by the
+        // size of the field
+        assert_eq!(169, get_conjectured_security(&options, base_field_bits, trace_length, 192));
+        assert_eq!(169, get_conjectured_security(&options, base_field_bits, trace_length, 256));
+    }
 }
@@ -5,7 +5,7 @@
 
 use super::Table;
 use alloc::vec::Vec;
-use crypto::{BatchMerkleProof, ElementHasher, Hasher};
+use crypto::{ElementHasher, Hasher, VectorCommitment, VectorCommitmentOptions};
 use math::FieldElement;
 use utils::{
     ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader,
@@ -15,18 +15,19 @@ use utils::{
 // ================================================================================================
 /// Decommitments to evaluations of a set of functions at multiple points.
 ///
-/// Given a set of functions evaluated over a domain *D*, a commitment is assumed to be a Merkle
-/// tree where a leaf at position *i* contains evaluations of all functions at *x<sub>i</sub>*.
-/// Thus, a query (i.e. a single decommitment) for position *i* includes evaluations of all
-/// functions at *x<sub>i</sub>*, accompanied by a Merkle authentication path from the leaf *i* to
-/// the tree root.
+/// Given a set of functions evaluated over a domain *D*, a commitment is assumed to be a vector
+/// commitment (e.g., a Merkle tree) where an item at position *i* is a hash of evaluations of all
+/// functions at *x<sub>i</sub>*. Thus, a query (i.e. a single decommitment) for position *i*
+/// includes evaluations of all functions at *x<sub>i</sub>*, accompanied by an opening proof for
+/// the item *i* (e.g., a Merkle authentication path from the leaf *i* to the tree root).
 ///
 /// This struct can contain one or more queries. In cases when more than one query is stored,
-/// Merkle authentication paths are compressed to remove redundant nodes.
+/// a single opening proof is used for all of them (e.g., Merkle authentication paths are
+/// compressed to remove redundant nodes).
 ///
-/// Internally, all Merkle paths and query values are stored as a sequence of bytes. Thus, to
-/// retrieve query values and the corresponding Merkle authentication paths,
-/// [parse()](Queries::parse) function should be used.
+/// Internally, all opening proofs and query values are stored as a sequence of bytes. Thus, to
+/// retrieve query values and the corresponding opening proof, [parse()](Queries::parse) function
+/// should be used.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Queries {
     paths: Vec<u8>,
@@ -37,25 +38,25 @@ impl Queries {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     /// Returns queries constructed from evaluations of a set of functions at some number of points
-    /// in a domain and their corresponding Merkle authentication paths.
+    /// in a domain and their corresponding opening proof for the vector commitment scheme `V`.
     ///
     /// For each evaluation point, the same number of values must be provided, and a hash of
-    /// these values must be equal to a leaf node in the corresponding Merkle authentication path.
+    /// these values must be equal to the corresponding item opened by the proof.
     ///
     /// # Panics
     /// Panics if:
     /// * No queries were provided (`query_values` is an empty vector).
     /// * Any of the queries does not contain any evaluations.
     /// * Not all queries contain the same number of evaluations.
-    pub fn new<H: Hasher, E: FieldElement>(
-        merkle_proof: BatchMerkleProof<H>,
+    pub fn new<H: Hasher, E: FieldElement, V: VectorCommitment<H>>(
+        opening_proof: V::MultiProof,
         query_values: Vec<Vec<E>>,
     ) -> Self {
         assert!(!query_values.is_empty(), "query values cannot be empty");
         let elements_per_query = query_values[0].len();
         assert_ne!(elements_per_query, 0, "a query must contain at least one evaluation");
 
-        // TODO: add debug check that values actually hash into the leaf nodes of the batch proof
+        // TODO: add debug check that values actually hash into the items opened by the proof
 
         // concatenate all elements together into a single vector of bytes
         let num_queries = query_values.len();
@@ -69,32 +70,39 @@ impl Queries {
             values.write_many(elements);
         }
 
-        // serialize internal nodes of the batch Merkle proof; we care about internal nodes only
-        // because leaf nodes can be reconstructed from hashes of query values
-        let paths = merkle_proof.serialize_nodes();
+        // serialize the opening proof; opened items are not serialized because they can be
+        // reconstructed from hashes of query values
+        let paths = V::serialize_multiproof(&opening_proof);
 
         Queries { paths, values }
     }
 
     // PARSER
     // --------------------------------------------------------------------------------------------
-    /// Convert internally stored bytes into a set of query values and the corresponding Merkle
-    /// authentication paths.
+    /// Convert internally stored bytes into a set of query values and the corresponding opening
+    /// proof for the vector commitment scheme `V` built with the specified `commitment_options`.
+    ///
+    /// Query values are hashed into the items opened by the proof in partitions of
+    /// `partition_size` elements (see [ElementHasher::hash_elements_in_partitions()]); a
+    /// partition size of zero means that each query is hashed as a single sequence of elements.
     ///
     /// # Panics
     /// Panics if:
     /// * `domain_size` is not a power of two.
     /// * `num_queries` is zero.
     /// * `values_per_query` is zero.
-    pub fn parse<H, E>(
+    pub fn parse<H, E, V>(
         self,
         domain_size: usize,
         num_queries: usize,
         values_per_query: usize,
-    ) -> Result<(BatchMerkleProof<H>, Table<E>), DeserializationError>
+        partition_size: usize,
+        commitment_options: VectorCommitmentOptions,
+    ) -> Result<(V::MultiProof, Table<E>), DeserializationError>
     where
         E: FieldElement,
         H: ElementHasher<BaseField = E::BaseField>,
+        V: VectorCommitment<H>,
     {
         assert!(domain_size.is_power_of_two(), "domain size must be a power of two");
         assert!(num_queries > 0, "there must be at least one query");
@@ -112,19 +120,26 @@ impl Queries {
         }
 
         // read bytes corresponding to each query, convert them into field elements,
-        // and also hash them to build leaf nodes of the batch Merkle proof
+        // and also hash them to build the items opened by the proof
         let query_values = Table::<E>::from_bytes(&self.values, num_queries, values_per_query)?;
-        let hashed_queries = query_values.rows().map(|row| H::hash_elements(row)).collect();
+        let hashed_queries = query_values
+            .rows()
+            .map(|row| H::hash_elements_in_partitions(row, partition_size))
+            .collect();
 
-        // build batch Merkle proof
+        // build the opening proof
         let mut reader = SliceReader::new(&self.paths);
-        let tree_depth = domain_size.ilog2() as u8;
-        let merkle_proof = BatchMerkleProof::deserialize(&mut reader, hashed_queries, tree_depth)?;
+        let opening_proof = V::deserialize_multiproof(
+            &mut reader,
+            hashed_queries,
+            domain_size,
+            commitment_options,
+        )?;
         if reader.has_more_bytes() {
             return Err(DeserializationError::UnconsumedBytes);
         }
 
-        Ok((merkle_proof, query_values))
+        Ok((opening_proof, query_values))
     }
 }
 
@@ -3,9 +3,34 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::StarkProof;
+use super::{Commitments, StarkProof};
+use alloc::vec::Vec;
+use crypto::{hashers::Blake3_256, Hasher};
+use math::fields::f128::BaseElement;
 
 #[test]
 pub fn starkproof_new_dummy_doesnt_panic() {
     let _ = StarkProof::new_dummy();
 }
+
+#[test]
+pub fn commitments_parse_caps() {
+    type Blake3 = Blake3_256<BaseElement>;
+    let digests = (0..8u8).map(|i| Blake3::hash(&[i])).collect::<Vec<_>>();
+
+    let trace_caps = vec![digests[..4].to_vec(), digests[4..5].to_vec()];
+    let constraint_cap = digests[..2].to_vec();
+    let fri_caps = vec![digests.clone(), digests[5..6].to_vec()];
+    let commitments =
+        Commitments::new::<Blake3>(trace_caps.clone(), constraint_cap.clone(), fri_caps.clone());
+
+    let (parsed_trace_caps, parsed_constraint_cap, parsed_fri_caps) =
+        commitments.clone().parse::<Blake3>(2, 1).unwrap();
+    assert_eq!(trace_caps, parsed_trace_caps);
+    assert_eq!(constraint_cap, parsed_constraint_cap);
+    assert_eq!(fri_caps, parsed_fri_caps);
+
+    // parsing fails if the number of FRI layers does not match
+    assert!(commitments.clone().parse::<Blake3>(2, 2).is_err());
+    assert!(commitments.parse::<Blake3>(2, 0).is_err());
+}
@@ -7,19 +7,35 @@ use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion
 use math::fields::f128;
 use rand_utils::rand_value;
 use winter_crypto::{
-    hashers::{Blake3_256, Rp62_248, Rp64_256, RpJive64_256, Sha3_256},
+    hashers::{
+        Blake3_256, Blake3_384, Keccak256, Poseidon2_64_256, Rp62_248, Rp64_256, RpJive64_256,
+        Rpo64_256, Rpx64_256, Sha3_256, Sha3_512, Tip5,
+    },
     Hasher,
 };
 
 type Blake3 = Blake3_256<f128::BaseElement>;
 type Blake3Digest = <Blake3 as Hasher>::Digest;
 
+type Blake3_384Hasher = Blake3_384<f128::BaseElement>;
+type Blake3_384Digest = <Blake3_384Hasher as Hasher>::Digest;
+
 type Sha3 = Sha3_256<f128::BaseElement>;
 type Sha3Digest = <Sha3 as Hasher>::Digest;
 
+type Sha3_512Hasher = Sha3_512<f128::BaseElement>;
+type Sha3_512Digest = <Sha3_512Hasher as Hasher>::Digest;
+
+type Keccak = Keccak256<f128::BaseElement>;
+type KeccakDigest = <Keccak as Hasher>::Digest;
+
 type Rp62_248Digest = <Rp62_248 as Hasher>::Digest;
 type Rp64_256Digest = <Rp64_256 as Hasher>::Digest;
 type RpJive64_256Digest = <RpJive64_256 as Hasher>::Digest;
+type Poseidon2_64_256Digest = <Poseidon2_64_256 as Hasher>::Digest;
+type Rpo64_256Digest = <Rpo64_256 as Hasher>::Digest;
+type Rpx64_256Digest = <Rpx64_256 as Hasher>::Digest;
+type Tip5Digest = <Tip5 as Hasher>::Digest;
 
 fn blake3(c: &mut Criterion) {
     let v: [Blake3Digest; 2] = [Blake3::hash(&[1u8]), Blake3::hash(&[2u8])];
@@ -39,6 +55,26 @@ fn blake3(c: &mut Criterion) {
     });
 }
 
+fn blake3_384(c: &mut Criterion) {
+    let v: [Blake3_384Digest; 2] = [Blake3_384Hasher::hash(&[1u8]), Blake3_384Hasher::hash(&[2u8])];
+    c.bench_function("hash_blake3_384 (cached)", |bench| {
+        bench.iter(|| Blake3_384Hasher::merge(black_box(&v)))
+    });
+
+    c.bench_function("hash_blake3_384 (random)", |b| {
+        b.iter_batched(
+            || {
+                [
+                    Blake3_384Hasher::hash(&rand_value::<u64>().to_le_bytes()),
+                    Blake3_384Hasher::hash(&rand_value::<u64>().to_le_bytes()),
+                ]
+            },
+            |state| Blake3_384Hasher::merge(&state),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
 fn sha3(c: &mut Criterion) {
     let v: [Sha3Digest; 2] = [Sha3::hash(&[1u8]), Sha3::hash(&[2u8])];
     c.bench_function("hash_sha3 (cached)", |bench| bench.iter(|| Sha3::merge(black_box(&v))));
@@ -57,6 +93,44 @@ fn sha3(c: &mut Criterion) {
     });
 }
 
+fn sha3_512(c: &mut Criterion) {
+    let v: [Sha3_512Digest; 2] = [Sha3_512Hasher::hash(&[1u8]), Sha3_512Hasher::hash(&[2u8])];
+    c.bench_function("hash_sha3_512 (cached)", |bench| {
+        bench.iter(|| Sha3_512Hasher::merge(black_box(&v)))
+    });
+
+    c.bench_function("hash_sha3_512 (random)", |b| {
+        b.iter_batched(
+            || {
+                [
+                    Sha3_512Hasher::hash(&rand_value::<u64>().to_le_bytes()),
+                    Sha3_512Hasher::hash(&rand_value::<u64>().to_le_bytes()),
+                ]
+            },
+            |state| Sha3_512Hasher::merge(&state),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn keccak(c: &mut Criterion) {
+    let v: [KeccakDigest; 2] = [Keccak::hash(&[1u8]), Keccak::hash(&[2u8])];
+    c.bench_function("hash_keccak (cached)", |bench| bench.iter(|| Keccak::merge(black_box(&v))));
+
+    c.bench_function("hash_keccak (random)", |b| {
+        b.iter_batched(
+            || {
+                [
+                    Keccak::hash(&rand_value::<u64>().to_le_bytes()),
+                    Keccak::hash(&rand_value::<u64>().to_le_bytes()),
+                ]
+            },
+            |state| Keccak::merge(&state),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
 fn rescue248(c: &mut Criterion) {
     let v: [Rp62_248Digest; 2] = [Rp62_248::hash(&[1u8]), Rp62_248::hash(&[2u8])];
     c.bench_function("hash_rp62_248 (cached)", |bench| {
@@ -117,5 +191,98 @@ fn rescue_jive256(c: &mut Criterion) {
     });
 }
 
-criterion_group!(hash_group, blake3, sha3, rescue248, rescue256, rescue_jive256);
+fn rpo256(c: &mut Criterion) {
+    let v: [Rpo64_256Digest; 2] = [Rpo64_256::hash(&[1u8]), Rpo64_256::hash(&[2u8])];
+    c.bench_function("hash_rpo64_256 (cached)", |bench| {
+        bench.iter(|| Rpo64_256::merge(black_box(&v)))
+    });
+
+    c.bench_function("hash_rpo64_256 (random)", |b| {
+        b.iter_batched(
+            || {
+                [
+                    Rpo64_256::hash(&rand_value::<u64>().to_le_bytes()),
+                    Rpo64_256::hash(&rand_value::<u64>().to_le_bytes()),
+                ]
+            },
+            |state| Rpo64_256::merge(&state),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn rpx256(c: &mut Criterion) {
+    let v: [Rpx64_256Digest; 2] = [Rpx64_256::hash(&[1u8]), Rpx64_256::hash(&[2u8])];
+    c.bench_function("hash_rpx64_256 (cached)", |bench| {
+        bench.iter(|| Rpx64_256::merge(black_box(&v)))
+    });
+
+    c.bench_function("hash_rpx64_256 (random)", |b| {
+        b.iter_batched(
+            || {
+                [
+                    Rpx64_256::hash(&rand_value::<u64>().to_le_bytes()),
+                    Rpx64_256::hash(&rand_value::<u64>().to_le_bytes()),
+                ]
+            },
+            |state| Rpx64_256::merge(&state),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn poseidon2_256(c: &mut Criterion) {
+    let v: [Poseidon2_64_256Digest; 2] =
+        [Poseidon2_64_256::hash(&[1u8]), Poseidon2_64_256::hash(&[2u8])];
+    c.bench_function("hash_poseidon2_64_256 (cached)", |bench| {
+        bench.iter(|| Poseidon2_64_256::merge(black_box(&v)))
+    });
+
+    c.bench_function("hash_poseidon2_64_256 (random)", |b| {
+        b.iter_batched(
+            || {
+                [
+                    Poseidon2_64_256::hash(&rand_value::<u64>().to_le_bytes()),
+                    Poseidon2_64_256::hash(&rand_value::<u64>().to_le_bytes()),
+                ]
+            },
+            |state| Poseidon2_64_256::merge(&state),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn tip5(c: &mut Criterion) {
+    let v: [Tip5Digest; 2] = [Tip5::hash(&[1u8]), Tip5::hash(&[2u8])];
+    c.bench_function("hash_tip5 (cached)", |bench| bench.iter(|| Tip5::merge(black_box(&v))));
+
+    c.bench_function("hash_tip5 (random)", |b| {
+        b.iter_batched(
+            || {
+                [
+                    Tip5::hash(&rand_value::<u64>().to_le_bytes()),
+                    Tip5::hash(&rand_value::<u64>().to_le_bytes()),
+                ]
+            },
+            |state| Tip5::merge(&state),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+criterion_group!(
+    hash_group,
+    blake3,
+    blake3_384,
+    sha3,
+    sha3_512,
+    keccak,
+    rescue248,
+    rescue256,
+    rescue_jive256,
+    rpo256,
+    rpx256,
+    poseidon2_256,
+    tip5
+);
 criterion_main!(hash_group);
@@ -0,0 +1,154 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::Hasher;
+use alloc::vec::Vec;
+use core::fmt::{Debug, Display};
+use utils::{ByteReader, DeserializationError};
+
+// VECTOR COMMITMENT TRAIT
+// ================================================================================================
+
+/// Defines a vector commitment scheme.
+///
+/// A vector commitment scheme allows committing to a vector of digests (items) such that any
+/// subset of the committed items can later be opened with a proof which can be verified against
+/// the commitment. The commitment itself may consist of one or more digests; for example, a
+/// Merkle tree can commit to its items via its root or via its cap.
+///
+/// The STARK prover and verifier, as well as FRI prover and verifier, use vector commitments to
+/// commit to the execution trace, constraint evaluations, and FRI layers.
+/// [MerkleTree](crate::MerkleTree) is the default implementation of this trait.
+pub trait VectorCommitment<H: Hasher>: Sized {
+    /// Proof of opening of multiple items of the committed vector.
+    ///
+    /// The proof contains the opened items.
+    type MultiProof;
+
+    /// Error returned by the commitment scheme.
+    type Error: Debug + Display;
+
+    // REQUIRED METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new commitment to the specified `items` built using the specified `options`.
+    ///
+    /// Schemes for which some of the options are not meaningful may ignore them.
+    ///
+    /// # Errors
+    /// Returns an error if the commitment could not be built for the specified items.
+    fn with_options(
+        items: Vec<H::Digest>,
+        options: VectorCommitmentOptions,
+    ) -> Result<Self, Self::Error>;
+
+    /// Returns the commitment to the vector of items.
+    fn commitment(&self) -> &[H::Digest];
+
+    /// Returns the number of items in the committed vector.
+    fn domain_len(&self) -> usize;
+
+    /// Opens the items at the specified `indexes` and returns a proof of their opening.
+    ///
+    /// # Errors
+    /// Returns an error if the items could not be opened (e.g., because any of the indexes is
+    /// out of bounds).
+    fn open_many(&self, indexes: &[usize]) -> Result<Self::MultiProof, Self::Error>;
+
+    /// Checks whether the specified `proof` opens items at the specified `indexes` against the
+    /// specified `commitment`.
+    ///
+    /// # Errors
+    /// Returns an error if the proof is not valid for the specified indexes and commitment.
+    fn verify_many(
+        commitment: &[H::Digest],
+        indexes: &[usize],
+        proof: &Self::MultiProof,
+    ) -> Result<(), Self::Error>;
+
+    /// Returns a single digest which binds the specified `commitment`.
+    ///
+    /// This digest is used to update public coins with the commitment.
+    fn commitment_digest(commitment: &[H::Digest]) -> H::Digest;
+
+    /// Serializes the specified `proof` into a vector of bytes.
+    ///
+    /// The opened items are not serialized as they are expected to be recomputed by the verifier
+    /// from the opened values.
+    fn serialize_multiproof(proof: &Self::MultiProof) -> Vec<u8>;
+
+    /// Reads a proof of opening of the specified `items` from the specified `source`.
+    ///
+    /// `domain_len` is the number of items in the committed vector, and `options` are the
+    /// options with which the commitment was built.
+    ///
+    /// # Errors
+    /// Returns an error if a valid proof could not be read from the specified `source`.
+    fn deserialize_multiproof<R: ByteReader>(
+        source: &mut R,
+        items: Vec<H::Digest>,
+        domain_len: usize,
+        options: VectorCommitmentOptions,
+    ) -> Result<Self::MultiProof, DeserializationError>;
+
+    // PROVIDED METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new commitment to the specified `items` built using default options.
+    ///
+    /// # Errors
+    /// Returns an error if the commitment could not be built for the specified items.
+    fn new(items: Vec<H::Digest>) -> Result<Self, Self::Error> {
+        Self::with_options(items, VectorCommitmentOptions::default())
+    }
+
+    /// Returns the number of digests in a commitment to a vector of the specified length built
+    /// using the specified `options`.
+    ///
+    /// The default implementation assumes that the scheme commits to a tree cap, and thus, that
+    /// the commitment consists of 2^`cap_height` digests.
+    fn commitment_len(_domain_len: usize, options: VectorCommitmentOptions) -> usize {
+        1 << options.cap_height()
+    }
+}
+
+// VECTOR COMMITMENT OPTIONS
+// ================================================================================================
+
+/// Options for building a vector commitment.
+///
+/// These options mirror the commitment parameters specified as a part of the STARK proof options:
+/// * `arity` - the arity of the underlying tree (for tree-based schemes).
+/// * `cap_height` - the height of the tree cap to which the scheme commits (for tree-based
+///   schemes). A cap height of zero means that the scheme commits to a single digest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VectorCommitmentOptions {
+    arity: usize,
+    cap_height: usize,
+}
+
+impl VectorCommitmentOptions {
+    /// Returns new [VectorCommitmentOptions] instantiated with the specified parameters.
+    pub fn new(arity: usize, cap_height: usize) -> Self {
+        Self { arity, cap_height }
+    }
+
+    /// Returns the arity of the underlying tree.
+    pub fn arity(&self) -> usize {
+        self.arity
+    }
+
+    /// Returns the height of the tree cap to which the scheme commits.
+    pub fn cap_height(&self) -> usize {
+        self.cap_height
+    }
+}
+
+impl Default for VectorCommitmentOptions {
+    /// Returns options for a binary tree committing to its root.
+    fn default() -> Self {
+        Self::new(2, 0)
+    }
+}
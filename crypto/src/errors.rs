@@ -23,8 +23,19 @@ pub enum MerkleTreeError {
     TooFewLeafIndexes,
     /// Too many leaf index were provided for a batch Merkle proof.
     TooManyLeafIndexes(usize, usize),
+    /// Height of a Merkle tree cap was not smaller than the depth of the tree.
+    InvalidCapHeight(usize, usize),
+    /// Arity of a Merkle tree was not a power of two between 2 and 16, or the requested operation
+    /// is not supported for trees of this arity.
+    InvalidArity(usize),
     /// Merkle proof is not valid for the specified position(s).
     InvalidProof,
+    /// Depth of a sparse Merkle tree was not between 1 and 256.
+    InvalidTreeDepth(usize),
+    /// A key did not fit into the key space of a sparse Merkle tree of the specified depth.
+    KeyOutOfBounds(usize),
+    /// Depth of a sparse Merkle proof did not match the depth of the tree it was verified against.
+    ProofDepthMismatch(usize, usize),
 }
 
 impl fmt::Display for MerkleTreeError {
@@ -54,9 +65,30 @@ impl fmt::Display for MerkleTreeError {
                     "number of leaf indexes cannot exceed {max_indexes}, but {num_indexes} was provided"
                 )
             }
+            Self::InvalidCapHeight(depth, cap_height) => {
+                write!(
+                    f,
+                    "cap height must be smaller than tree depth {depth}, but was {cap_height}"
+                )
+            }
+            Self::InvalidArity(arity) => {
+                write!(
+                    f,
+                    "arity {arity} is not a power of two between 2 and 16 or is not supported by this operation"
+                )
+            }
             Self::InvalidProof => {
                 write!(f, "Merkle proof is invalid")
             }
+            Self::InvalidTreeDepth(depth) => {
+                write!(f, "sparse Merkle tree depth must be between 1 and 256, but was {depth}")
+            }
+            Self::KeyOutOfBounds(depth) => {
+                write!(f, "key does not fit into a sparse Merkle tree of depth {depth}")
+            }
+            Self::ProofDepthMismatch(expected, actual) => {
+                write!(f, "expected a sparse Merkle proof of depth {expected}, but was {actual}")
+            }
         }
     }
 }
@@ -32,6 +32,10 @@ impl<B: StarkField> Hasher for Blake3_256<B> {
         ByteDigest(blake3::hash(ByteDigest::digests_as_bytes(values)).into())
     }
 
+    fn merge_many(values: &[Self::Digest]) -> Self::Digest {
+        ByteDigest(blake3::hash(ByteDigest::digests_as_bytes(values)).into())
+    }
+
     fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
         let mut data = [0; 40];
         data[..32].copy_from_slice(&seed.0);
@@ -82,6 +86,11 @@ impl<B: StarkField> Hasher for Blake3_192<B> {
         ByteDigest(result.as_bytes()[..24].try_into().unwrap())
     }
 
+    fn merge_many(values: &[Self::Digest]) -> Self::Digest {
+        let result = blake3::hash(ByteDigest::digests_as_bytes(values));
+        ByteDigest(result.as_bytes()[..24].try_into().unwrap())
+    }
+
     fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
         let mut data = [0; 32];
         data[..24].copy_from_slice(&seed.0);
@@ -113,6 +122,68 @@ impl<B: StarkField> ElementHasher for Blake3_192<B> {
     }
 }
 
+// BLAKE3 384-BIT OUTPUT
+// ================================================================================================
+
+/// Implementation of the [Hasher](super::Hasher) trait for BLAKE3 hash function with 384-bit
+/// output.
+///
+/// The output is read from the extendable output of BLAKE3, and thus, its first 256 bits are the
+/// same as the output of [Blake3_256].
+#[derive(Debug, PartialEq, Eq)]
+pub struct Blake3_384<B: StarkField>(PhantomData<B>);
+
+impl<B: StarkField> Hasher for Blake3_384<B> {
+    type Digest = ByteDigest<48>;
+
+    const COLLISION_RESISTANCE: u32 = 192;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        ByteDigest(hash_xof(bytes))
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        ByteDigest(hash_xof(ByteDigest::digests_as_bytes(values)))
+    }
+
+    fn merge_many(values: &[Self::Digest]) -> Self::Digest {
+        ByteDigest(hash_xof(ByteDigest::digests_as_bytes(values)))
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        let mut data = [0; 56];
+        data[..48].copy_from_slice(&seed.0);
+        data[48..].copy_from_slice(&value.to_le_bytes());
+        ByteDigest(hash_xof(&data))
+    }
+}
+
+impl<B: StarkField> ElementHasher for Blake3_384<B> {
+    type BaseField = B;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        if B::IS_CANONICAL {
+            // when element's internal and canonical representations are the same, we can hash
+            // element bytes directly
+            let bytes = E::elements_as_bytes(elements);
+            ByteDigest(hash_xof(bytes))
+        } else {
+            // when elements' internal and canonical representations differ, we need to serialize
+            // them before hashing
+            let mut hasher = BlakeHasher::new();
+            hasher.write_many(elements);
+            ByteDigest(hasher.finalize_xof())
+        }
+    }
+}
+
+/// Returns the first `N` bytes of the extendable output of BLAKE3 for the provided bytes.
+fn hash_xof<const N: usize>(bytes: &[u8]) -> [u8; N] {
+    let mut result = [0; N];
+    blake3::Hasher::new().update(bytes).finalize_xof().fill(&mut result);
+    result
+}
+
 // BLAKE HASHER
 // ================================================================================================
 
@@ -127,6 +198,12 @@ impl BlakeHasher {
     pub fn finalize(&self) -> [u8; 32] {
         *self.0.finalize().as_bytes()
     }
+
+    pub fn finalize_xof<const N: usize>(&self) -> [u8; N] {
+        let mut result = [0; N];
+        self.0.finalize_xof().fill(&mut result);
+        result
+    }
 }
 
 impl ByteWriter for BlakeHasher {
@@ -3,9 +3,11 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{Blake3_256, ElementHasher, Hasher};
+use super::{Blake3_256, Blake3_384, ElementHasher, Hasher};
+use crate::Digest;
 use math::{fields::f62::BaseElement, FieldElement};
 use rand_utils::rand_array;
+use utils::Serializable;
 
 #[test]
 fn hash_padding() {
@@ -28,3 +30,67 @@ fn hash_elements_padding() {
     let r2 = Blake3_256::hash_elements(&e2);
     assert_ne!(r1, r2);
 }
+
+#[test]
+fn hash_elements_in_partitions() {
+    type Blake3 = Blake3_256<BaseElement>;
+    let elements: [BaseElement; 10] = rand_array();
+
+    // partitioning is not applied when all elements fit into a single partition
+    let expected = Blake3::hash_elements(&elements);
+    assert_eq!(expected, Blake3::hash_elements_in_partitions(&elements, 0));
+    assert_eq!(expected, Blake3::hash_elements_in_partitions(&elements, 10));
+
+    // otherwise, partition digests are merged together
+    let digests = [
+        Blake3::hash_elements(&elements[..4]),
+        Blake3::hash_elements(&elements[4..8]),
+        Blake3::hash_elements(&elements[8..]),
+    ];
+    let expected = Blake3::merge_many(&digests);
+    assert_eq!(expected, Blake3::hash_elements_in_partitions(&elements, 4));
+    assert_ne!(expected, Blake3::hash_elements(&elements));
+}
+
+#[test]
+fn blake3_384_test_vector() {
+    // test vector for an empty input from the official BLAKE3 test vectors
+    let expected = "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262\
+        e00f03e7b69af26b7faaf09fcd333050";
+    let result = Blake3_384::<BaseElement>::hash(&[]);
+    assert_eq!(expected, to_hex(&result.as_bytes()[..48]));
+    assert_eq!([0; 16], result.as_bytes()[48..]);
+
+    // the first 32 bytes of the output are the same as the output of BLAKE3 with 256-bit output
+    let result_256 = Blake3_256::<BaseElement>::hash(&[]);
+    assert_eq!(result_256.as_bytes()[..32], result.as_bytes()[..32]);
+}
+
+#[test]
+fn blake3_384_hash_elements() {
+    let elements: [BaseElement; 5] = rand_array();
+
+    // elements are hashed using their canonical representation
+    let expected = Blake3_384::<BaseElement>::hash(&elements.to_bytes());
+    assert_eq!(expected, Blake3_384::hash_elements(&elements));
+}
+
+#[test]
+fn blake3_384_merge_with_int() {
+    let seed = Blake3_384::<BaseElement>::hash(&[1, 2, 3]);
+
+    let mut data = [0; 56];
+    data[..48].copy_from_slice(&seed.as_bytes()[..48]);
+    data[48..].copy_from_slice(&42_u64.to_le_bytes());
+    assert_eq!(
+        Blake3_384::<BaseElement>::hash(&data),
+        Blake3_384::<BaseElement>::merge_with_int(seed, 42)
+    );
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn to_hex(bytes: &[u8]) -> alloc::string::String {
+    bytes.iter().map(|b| alloc::format!("{b:02x}")).collect()
+}
@@ -0,0 +1,172 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ElementHasher, Hasher};
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use math::FieldElement;
+use utils::Serializable;
+
+#[cfg(test)]
+mod tests;
+
+// HASH DOMAIN
+// ================================================================================================
+
+/// Defines the contexts in which a hash function is used by the protocol.
+///
+/// Hash functions which provide domain separation compute hashes in different domains as if
+/// they were computed by independent hash functions.
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum HashDomain {
+    /// Hashing of committed values into leaves of a Merkle tree.
+    Leaf = 1,
+    /// Merging of child nodes into internal nodes of a Merkle tree.
+    Node = 2,
+    /// Updating the seed of a public coin (i.e., the Fiat-Shamir transcript).
+    Transcript = 3,
+    /// Checking proof-of-work and absorbing proof-of-work nonces into a public coin.
+    ProofOfWork = 4,
+}
+
+impl HashDomain {
+    /// Returns the tag which identifies this domain.
+    pub fn tag(&self) -> u8 {
+        *self as u8
+    }
+}
+
+// PERSONALIZATION
+// ================================================================================================
+
+/// Defines a personalization string for a [DomainSeparated] hash function.
+///
+/// Using different personalization strings for different applications ensures that hashes
+/// computed by one application cannot be reused in another one.
+pub trait Personalization {
+    /// The personalization string; it must not be longer than 255 bytes.
+    const PERSONALIZATION: &'static [u8];
+}
+
+/// An empty personalization string.
+impl Personalization for () {
+    const PERSONALIZATION: &'static [u8] = b"";
+}
+
+// DOMAIN-SEPARATED HASHER
+// ================================================================================================
+
+/// A wrapper which adds domain separation to the specified hash function `H`.
+///
+/// Domain-aware methods (e.g., [merge_in_domain()](Hasher::merge_in_domain)) compute the hash as
+/// `H::hash(tag || len || personalization || input)`, where `tag` is the tag of the
+/// [HashDomain], `len` is the length of the personalization string specified by `P` (as a single
+/// byte), and the input is serialized using its canonical byte representation. All other
+/// methods are delegated to `H` directly.
+///
+/// Since [MerkleTree](crate::MerkleTree), [BatchMerkleProof](crate::BatchMerkleProof), and
+/// [DefaultRandomCoin](crate::DefaultRandomCoin) compute all their hashes using domain-aware
+/// methods, domain separation can be enabled for a protocol by instantiating it with
+/// `DomainSeparated<H>` instead of `H`.
+///
+/// Domain-aware hashes are always computed over bytes, and thus, this wrapper is intended
+/// primarily for byte-oriented hash functions such as BLAKE3 and SHA3. Arithmetization-friendly
+/// hash functions can provide cheaper domain separation by overriding the domain-aware methods
+/// of the [Hasher] and [ElementHasher] traits directly.
+///
+/// # Examples
+/// ```
+/// # use winter_crypto::{DomainSeparated, Hasher, HashDomain, hashers::Blake3_256};
+/// # use math::fields::f128::BaseElement;
+/// type Blake3 = Blake3_256<BaseElement>;
+///
+/// let values = [Blake3::hash(&[1, 2, 3]), Blake3::hash(&[4, 5, 6])];
+/// let node = DomainSeparated::<Blake3>::merge_in_domain(&values, HashDomain::Node);
+/// let seed = DomainSeparated::<Blake3>::merge_in_domain(&values, HashDomain::Transcript);
+///
+/// assert_ne!(node, seed);
+/// assert_ne!(node, Blake3::merge(&values));
+/// ```
+#[derive(Debug, PartialEq, Eq)]
+pub struct DomainSeparated<H: Hasher, P: Personalization = ()>(PhantomData<(H, P)>);
+
+impl<H: Hasher, P: Personalization> DomainSeparated<H, P> {
+    /// Returns a vector of bytes which should precede the input hashed in the specified domain.
+    fn prefix(domain: HashDomain) -> Vec<u8> {
+        let len = u8::try_from(P::PERSONALIZATION.len())
+            .expect("personalization string must not be longer than 255 bytes");
+
+        let mut bytes = Vec::with_capacity(P::PERSONALIZATION.len() + 2);
+        bytes.push(domain.tag());
+        bytes.push(len);
+        bytes.extend_from_slice(P::PERSONALIZATION);
+        bytes
+    }
+}
+
+impl<H: Hasher, P: Personalization> Hasher for DomainSeparated<H, P> {
+    type Digest = H::Digest;
+
+    const COLLISION_RESISTANCE: u32 = H::COLLISION_RESISTANCE;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        H::hash(bytes)
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        H::merge(values)
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        H::merge_with_int(seed, value)
+    }
+
+    fn merge_many(values: &[Self::Digest]) -> Self::Digest {
+        H::merge_many(values)
+    }
+
+    fn merge_in_domain(values: &[Self::Digest; 2], domain: HashDomain) -> Self::Digest {
+        Self::merge_many_in_domain(values, domain)
+    }
+
+    fn merge_many_in_domain(values: &[Self::Digest], domain: HashDomain) -> Self::Digest {
+        let mut bytes = Self::prefix(domain);
+        for value in values {
+            value.write_into(&mut bytes);
+        }
+        H::hash(&bytes)
+    }
+
+    fn merge_with_int_in_domain(
+        seed: Self::Digest,
+        value: u64,
+        domain: HashDomain,
+    ) -> Self::Digest {
+        let mut bytes = Self::prefix(domain);
+        seed.write_into(&mut bytes);
+        bytes.extend_from_slice(&value.to_le_bytes());
+        H::hash(&bytes)
+    }
+}
+
+impl<H: ElementHasher, P: Personalization> ElementHasher for DomainSeparated<H, P> {
+    type BaseField = H::BaseField;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        H::hash_elements(elements)
+    }
+
+    fn hash_elements_in_domain<E>(elements: &[E], domain: HashDomain) -> Self::Digest
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        let mut bytes = Self::prefix(domain);
+        for element in elements {
+            element.write_into(&mut bytes);
+        }
+        H::hash(&bytes)
+    }
+}
@@ -0,0 +1,187 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{DomainSeparated, HashDomain, Personalization};
+use crate::{
+    hashers::Blake3_256, BatchMerkleProof, DefaultRandomCoin, Digest, ElementHasher, Hasher,
+    MerkleTree, RandomCoin,
+};
+use alloc::{vec, vec::Vec};
+use math::fields::f128::BaseElement;
+use rand_utils::rand_array;
+use utils::Serializable;
+
+type Blake3 = Blake3_256<BaseElement>;
+type Separated = DomainSeparated<Blake3>;
+
+struct TestPersonalization;
+
+impl Personalization for TestPersonalization {
+    const PERSONALIZATION: &'static [u8] = b"test";
+}
+
+const DOMAINS: [HashDomain; 4] = [
+    HashDomain::Leaf,
+    HashDomain::Node,
+    HashDomain::Transcript,
+    HashDomain::ProofOfWork,
+];
+
+#[test]
+fn default_domain_methods() {
+    let elements: [BaseElement; 8] = rand_array();
+    let values = [Blake3::hash(&[1, 2, 3]), Blake3::hash(&[4, 5, 6])];
+
+    // hash functions which do not provide domain separation ignore the domain
+    for domain in DOMAINS {
+        assert_eq!(Blake3::merge(&values), Blake3::merge_in_domain(&values, domain));
+        assert_eq!(Blake3::merge_many(&values), Blake3::merge_many_in_domain(&values, domain));
+        assert_eq!(
+            Blake3::merge_with_int(values[0], 42),
+            Blake3::merge_with_int_in_domain(values[0], 42, domain)
+        );
+        assert_eq!(
+            Blake3::hash_elements(&elements),
+            Blake3::hash_elements_in_domain(&elements, domain)
+        );
+    }
+}
+
+#[test]
+fn domain_separated_encoding() {
+    let elements: [BaseElement; 8] = rand_array();
+    let values = [Blake3::hash(&[1, 2, 3]), Blake3::hash(&[4, 5, 6])];
+
+    // domain-aware hashes are computed over the domain tag, an empty personalization, and the
+    // serialized input
+    let mut bytes = vec![HashDomain::Node.tag(), 0];
+    values.iter().for_each(|value| value.write_into(&mut bytes));
+    assert_eq!(Blake3::hash(&bytes), Separated::merge_in_domain(&values, HashDomain::Node));
+    assert_eq!(Blake3::hash(&bytes), Separated::merge_many_in_domain(&values, HashDomain::Node));
+
+    let mut bytes = vec![HashDomain::ProofOfWork.tag(), 0];
+    values[0].write_into(&mut bytes);
+    bytes.extend_from_slice(&42_u64.to_le_bytes());
+    assert_eq!(
+        Blake3::hash(&bytes),
+        Separated::merge_with_int_in_domain(values[0], 42, HashDomain::ProofOfWork)
+    );
+
+    let mut bytes = vec![HashDomain::Leaf.tag(), 0];
+    elements.iter().for_each(|element| element.write_into(&mut bytes));
+    assert_eq!(
+        Blake3::hash(&bytes),
+        Separated::hash_elements_in_domain(&elements, HashDomain::Leaf)
+    );
+
+    // methods which are not domain-aware are delegated to the underlying hash function
+    assert_eq!(Blake3::merge(&values), Separated::merge(&values));
+    assert_eq!(Blake3::hash_elements(&elements), Separated::hash_elements(&elements));
+}
+
+#[test]
+fn domain_separated_domains_and_personalization() {
+    let values = [Blake3::hash(&[1, 2, 3]), Blake3::hash(&[4, 5, 6])];
+
+    // hashes computed in different domains are different
+    let hashes = DOMAINS
+        .iter()
+        .map(|&domain| Separated::merge_in_domain(&values, domain))
+        .collect::<Vec<_>>();
+    for (i, a) in hashes.iter().enumerate() {
+        assert_ne!(Blake3::merge(&values), *a);
+        for b in hashes.iter().skip(i + 1) {
+            assert_ne!(a, b);
+        }
+    }
+
+    // hashes computed with different personalization strings are different
+    type Personalized = DomainSeparated<Blake3, TestPersonalization>;
+    let mut bytes = vec![HashDomain::Node.tag(), 4];
+    bytes.extend_from_slice(b"test");
+    values.iter().for_each(|value| value.write_into(&mut bytes));
+
+    let result = Personalized::merge_in_domain(&values, HashDomain::Node);
+    assert_eq!(Blake3::hash(&bytes), result);
+    assert_ne!(Separated::merge_in_domain(&values, HashDomain::Node), result);
+}
+
+#[test]
+fn domain_separated_partitions() {
+    let elements: [BaseElement; 10] = rand_array();
+
+    // partitions and their digests are hashed in the leaf domain
+    let digests = [
+        Separated::hash_elements_in_domain(&elements[..4], HashDomain::Leaf),
+        Separated::hash_elements_in_domain(&elements[4..8], HashDomain::Leaf),
+        Separated::hash_elements_in_domain(&elements[8..], HashDomain::Leaf),
+    ];
+    let expected = Separated::merge_many_in_domain(&digests, HashDomain::Leaf);
+    assert_eq!(expected, Separated::hash_elements_in_partitions(&elements, 4));
+
+    let expected = Separated::hash_elements_in_domain(&elements, HashDomain::Leaf);
+    assert_eq!(expected, Separated::hash_elements_in_partitions(&elements, 0));
+}
+
+#[test]
+fn domain_separated_merkle_tree() {
+    let leaves = (0..16_u8).map(|i| Blake3::hash(&[i])).collect::<Vec<_>>();
+
+    let tree = MerkleTree::<Separated>::new(leaves.clone()).unwrap();
+    let plain_tree = MerkleTree::<Blake3>::new(leaves.clone()).unwrap();
+    assert_ne!(plain_tree.root(), tree.root());
+
+    // internal nodes are merged in the node domain
+    let node = Separated::merge_in_domain(&[leaves[0], leaves[1]], HashDomain::Node);
+    let path = tree.prove(0).unwrap();
+    assert_eq!(node, Separated::merge_in_domain(&[path[0], path[1]], HashDomain::Node));
+    assert!(MerkleTree::<Separated>::verify(*tree.root(), 0, &path).is_ok());
+    assert!(MerkleTree::<Blake3>::verify(*tree.root(), 0, &path).is_err());
+
+    // batch proofs are verified using the same domain
+    let indexes = [1, 5, 11];
+    let proof: BatchMerkleProof<Separated> = tree.prove_batch(&indexes).unwrap();
+    assert_eq!(*tree.root(), proof.get_root(&indexes).unwrap());
+}
+
+#[test]
+fn domain_separated_random_coin() {
+    let seed: [BaseElement; 4] = rand_array();
+
+    let mut coin = DefaultRandomCoin::<Separated>::new(&seed);
+    let mut plain_coin = DefaultRandomCoin::<Blake3>::new(&seed);
+    assert_ne!(plain_coin.draw::<BaseElement>().unwrap(), coin.draw::<BaseElement>().unwrap());
+
+    // proof-of-work checks are computed in the proof-of-work domain and are consistent with the
+    // seed derived from the nonce
+    let coin = DefaultRandomCoin::<Separated>::new(&seed);
+    let nonce = (0..).find(|&nonce| coin.check_leading_zeros(nonce) >= 4).unwrap();
+
+    let seed_digest = Separated::hash_elements_in_domain(&seed, HashDomain::Transcript);
+    let pow = Separated::merge_with_int_in_domain(seed_digest, nonce, HashDomain::ProofOfWork);
+    let head = u64::from_le_bytes(pow.as_bytes()[..8].try_into().unwrap());
+    assert!(head.trailing_zeros() >= 4);
+}
+
+#[test]
+fn domain_separated_batch_methods() {
+    let values = (0..5_u8)
+        .map(|i| [Blake3::hash(&[i]), Blake3::hash(&[i, 1])])
+        .collect::<Vec<_>>();
+    let rows = (0..5).map(|_| rand_array::<BaseElement, 10>()).collect::<Vec<_>>();
+    let rows = rows.iter().map(|row| row.as_slice()).collect::<Vec<_>>();
+
+    // batch methods are computed in the same domains as their single-input counterparts
+    let mut result = vec![Default::default(); values.len()];
+    Separated::merge_batch_in_domain(&values, HashDomain::Node, &mut result);
+    for (pair, digest) in values.iter().zip(result.iter()) {
+        assert_eq!(Separated::merge_in_domain(pair, HashDomain::Node), *digest);
+    }
+
+    Separated::hash_rows_in_partitions(&rows, 4, &mut result);
+    for (row, digest) in rows.iter().zip(result.iter()) {
+        assert_eq!(Separated::hash_elements_in_partitions(row, 4), *digest);
+    }
+}
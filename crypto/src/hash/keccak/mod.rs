@@ -0,0 +1,117 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ByteDigest, ElementHasher, Hasher};
+use core::marker::PhantomData;
+use math::{FieldElement, StarkField};
+use sha3::Digest;
+use utils::ByteWriter;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Size of an EVM word in bytes.
+const WORD_SIZE: usize = 32;
+
+// KECCAK WITH 256-BIT OUTPUT
+// ================================================================================================
+
+/// Implementation of the [Hasher](super::Hasher) trait for Keccak-256 hash function.
+///
+/// This is the original Keccak-256 function used by the Ethereum Virtual Machine (i.e., the
+/// `keccak256` opcode), which differs from [Sha3_256](super::Sha3_256) only in the padding rule.
+///
+/// All inputs are encoded so that the hashes are cheap to reproduce in a smart contract:
+/// * [merge()](Keccak256::merge) and [merge_many()](Keccak256::merge_many) hash the
+///   concatenation of digests, i.e., `keccak256(abi.encodePacked(left, right))` for two `bytes32`
+///   values.
+/// * [merge_with_int()](Keccak256::merge_with_int) hashes the seed followed by the value encoded
+///   as 8 big-endian bytes, i.e., `keccak256(abi.encodePacked(seed, uint64(value)))`.
+/// * [hash_elements()](Keccak256::hash_elements) encodes every base field element as a 32-byte
+///   big-endian word holding its canonical integer value (extension field elements are encoded
+///   as a sequence of their base field coefficients), i.e., the input is the ABI encoding of a
+///   `uint256[]` array without the length prefix.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Keccak256<B: StarkField>(PhantomData<B>);
+
+impl<B: StarkField> Hasher for Keccak256<B> {
+    type Digest = ByteDigest<32>;
+
+    const COLLISION_RESISTANCE: u32 = 128;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        ByteDigest(sha3::Keccak256::digest(bytes).into())
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        ByteDigest(sha3::Keccak256::digest(ByteDigest::digests_as_bytes(values)).into())
+    }
+
+    fn merge_many(values: &[Self::Digest]) -> Self::Digest {
+        ByteDigest(sha3::Keccak256::digest(ByteDigest::digests_as_bytes(values)).into())
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        let mut data = [0; 40];
+        data[..32].copy_from_slice(&seed.0);
+        data[32..].copy_from_slice(&value.to_be_bytes());
+        ByteDigest(sha3::Keccak256::digest(data).into())
+    }
+}
+
+impl<B: StarkField> ElementHasher for Keccak256<B> {
+    type BaseField = B;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        let mut hasher = sha3::Keccak256::new();
+        for element in E::slice_as_base_elements(elements) {
+            hasher.update(to_evm_word(element));
+        }
+        ByteDigest(hasher.finalize().into())
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Encodes the canonical integer value of the provided element as a 32-byte big-endian word.
+fn to_evm_word<B: StarkField>(element: &B) -> [u8; WORD_SIZE] {
+    let mut writer = WordWriter::default();
+    element.write_into(&mut writer);
+    writer.into_word()
+}
+
+/// Collects the little-endian bytes written by a field element serializer and converts them into
+/// a big-endian EVM word.
+#[derive(Default)]
+struct WordWriter {
+    bytes: [u8; WORD_SIZE],
+    len: usize,
+}
+
+impl WordWriter {
+    fn into_word(self) -> [u8; WORD_SIZE] {
+        let mut word = [0; WORD_SIZE];
+        for (w, &b) in word.iter_mut().rev().zip(self.bytes[..self.len].iter()) {
+            *w = b;
+        }
+        word
+    }
+}
+
+impl ByteWriter for WordWriter {
+    fn write_u8(&mut self, value: u8) {
+        assert!(self.len < WORD_SIZE, "field element does not fit into an EVM word");
+        self.bytes[self.len] = value;
+        self.len += 1;
+    }
+
+    fn write_bytes(&mut self, values: &[u8]) {
+        values.iter().for_each(|&value| self.write_u8(value));
+    }
+}
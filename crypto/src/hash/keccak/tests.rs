@@ -0,0 +1,108 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ElementHasher, Hasher, Keccak256};
+use crate::{hash::ByteDigest, DefaultRandomCoin, Digest, RandomCoin};
+use alloc::string::String;
+use math::{
+    fields::{f128, f64},
+    FieldElement, StarkField,
+};
+use rand_utils::rand_array;
+
+#[test]
+fn hash_test_vectors() {
+    // keccak256("") as computed by the EVM
+    let r = Keccak256::<f64::BaseElement>::hash(&[]);
+    assert_eq!(
+        "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+        to_hex(&r.as_bytes()[..32])
+    );
+
+    // keccak256("abc") as computed by the EVM
+    let r = Keccak256::<f64::BaseElement>::hash(b"abc");
+    assert_eq!(
+        "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45",
+        to_hex(&r.as_bytes()[..32])
+    );
+}
+
+#[test]
+fn hash_elements_encoding() {
+    // every element is encoded as a 32-byte big-endian word of its canonical value
+    let elements = [f64::BaseElement::new(1), f64::BaseElement::new(u64::MAX)];
+    let mut expected = [0u8; 64];
+    expected[31] = 1;
+    expected[32 + 24..].copy_from_slice(&(u64::MAX % f64::BaseElement::MODULUS).to_be_bytes());
+
+    let r1 = Keccak256::hash_elements(&elements);
+    let r2 = Keccak256::<f64::BaseElement>::hash(&expected);
+    assert_eq!(r1, r2);
+
+    // same as above but for a field with 128-bit elements
+    let elements = [f128::BaseElement::new(3), f128::BaseElement::new(u128::MAX)];
+    let mut expected = [0u8; 64];
+    expected[31] = 3;
+    expected[32 + 16..].copy_from_slice(&(u128::MAX % f128::BaseElement::MODULUS).to_be_bytes());
+
+    let r1 = Keccak256::hash_elements(&elements);
+    let r2 = Keccak256::<f128::BaseElement>::hash(&expected);
+    assert_eq!(r1, r2);
+}
+
+#[test]
+fn merge_with_int_encoding() {
+    let seed = ByteDigest::new(rand_array());
+    let value = 0x0102030405060708u64;
+
+    let mut expected = [0u8; 40];
+    expected[..32].copy_from_slice(&seed.as_bytes()[..32]);
+    expected[32..].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+
+    let r1 = Keccak256::<f64::BaseElement>::merge_with_int(seed, value);
+    let r2 = Keccak256::<f64::BaseElement>::hash(&expected);
+    assert_eq!(r1, r2);
+}
+
+#[test]
+fn hash_elements_padding() {
+    let e1: [f64::BaseElement; 2] = rand_array();
+    let e2 = [e1[0], e1[1], f64::BaseElement::ZERO];
+
+    // adding a zero element at the end of a list of elements should result in a different hash
+    let r1 = Keccak256::hash_elements(&e1);
+    let r2 = Keccak256::hash_elements(&e2);
+    assert_ne!(r1, r2);
+}
+
+#[test]
+fn random_coin() {
+    type Coin = DefaultRandomCoin<Keccak256<f64::BaseElement>>;
+
+    let seed: [f64::BaseElement; 4] = rand_array();
+    let mut coin1 = Coin::new(&seed);
+    let mut coin2 = Coin::new(&seed);
+
+    // coins with the same seed should produce the same values
+    let e1: f64::BaseElement = coin1.draw().unwrap();
+    let e2: f64::BaseElement = coin2.draw().unwrap();
+    assert_eq!(e1, e2);
+
+    // reseeding should affect subsequent draws
+    coin1.reseed_with_int(42);
+    let e1: f64::BaseElement = coin1.draw().unwrap();
+    let e2: f64::BaseElement = coin2.draw().unwrap();
+    assert_ne!(e1, e2);
+
+    let positions = coin2.draw_integers(16, 64, 0).unwrap();
+    assert!(positions.iter().all(|&p| p < 64));
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
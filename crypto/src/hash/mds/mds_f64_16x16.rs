@@ -0,0 +1,46 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+// CIRCULANT MDS MULTIPLICATION HELPER FUNCTIONS
+// ================================================================================================
+
+use math::fields::f64::BaseElement;
+
+// This module contains helper functions as well as constants used to perform a 16x16 vector-matrix
+// multiplication with the circulant MDS matrix used by the Tip5 hash function. All entries of the
+// matrix are smaller than 2^16; thus, a dot product of a row of the matrix with a vector of 16
+// canonical field elements is smaller than 2^84. This allows us to accumulate the products in
+// 128-bit integers and perform a single modular reduction per output element.
+
+/// The first column of the circulant MDS matrix.
+pub(crate) const MDS_FIRST_COLUMN: [u64; 16] = [
+    61402, 1108, 28750, 33823, 7454, 43244, 53865, 12034, 56951, 27521, 41351, 40901, 12021, 59689,
+    26798, 17845,
+];
+
+pub(crate) fn mds_multiply(state: &mut [BaseElement; 16]) {
+    let values = state.map(|s| s.as_int() as u128);
+
+    for (i, s) in state.iter_mut().enumerate() {
+        let mut acc = 0u128;
+        for (j, &v) in values.iter().enumerate() {
+            acc += MDS_FIRST_COLUMN[(16 + i - j) % 16] as u128 * v;
+        }
+        *s = reduce_u128(acc);
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Reduces a 128-bit integer smaller than 2^96 modulo the field modulus.
+#[inline(always)]
+fn reduce_u128(x: u128) -> BaseElement {
+    // since 2^64 = 2^32 - 1 (mod p), we have x = lo + hi * (2^32 - 1) (mod p); for x < 2^96 the
+    // product hi * (2^32 - 1) fits into 64 bits.
+    let lo = x as u64;
+    let hi = (x >> 64) as u64;
+    BaseElement::new(lo) + BaseElement::new((hi << 32) - hi)
+}
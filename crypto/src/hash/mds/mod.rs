@@ -3,8 +3,13 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-//! An implementation of matrix-vector products for several MDS matrices.
+//! An implementation of matrix-vector products for several MDS matrices, as well as for the
+//! linear layers of the Poseidon2 permutation.
 
 pub mod mds_f64_8x8;
 
 pub mod mds_f64_12x12;
+
+pub mod mds_f64_16x16;
+
+pub mod poseidon2_f64_12x12;
@@ -0,0 +1,85 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+// POSEIDON2 LINEAR LAYER HELPER FUNCTIONS
+// ================================================================================================
+
+use math::{fields::f64::BaseElement, FieldElement};
+
+// This module contains helper functions as well as constants used to compute the two linear
+// layers of the Poseidon2 permutation over a state of 12 elements of the 64-bit field.
+//
+// The external matrix is built from the 4x4 matrix M4 described in section 5.1 of
+// <https://eprint.iacr.org/2023/323.pdf>. The state is split into three chunks of 4 elements,
+// each chunk is multiplied by M4, and then the sum of all chunks is added to every chunk; this is
+// equivalent to multiplying the state by circ(2 * M4, M4, M4).
+// The internal matrix has the form 1 + diag(d), where 1 is the all-ones matrix. Thus, the product
+// can be computed as s[i] * d[i] + sum(s), which requires only 12 multiplications.
+
+/// Diagonal of the internal matrix minus the identity; these are the values used by the reference
+/// implementation for Goldilocks field with state width of 12.
+pub(crate) const INTERNAL_DIAG_M_1: [BaseElement; 12] = [
+    BaseElement::new(14102670999874605824),
+    BaseElement::new(15585654191999307702),
+    BaseElement::new(940187017142450255),
+    BaseElement::new(8747386241522630711),
+    BaseElement::new(6750641561540124747),
+    BaseElement::new(7440998025584530007),
+    BaseElement::new(6136358134615751536),
+    BaseElement::new(12413576830284969611),
+    BaseElement::new(11675438539028694709),
+    BaseElement::new(17580553691069642926),
+    BaseElement::new(892707462476851331),
+    BaseElement::new(15167485180850043744),
+];
+
+/// Multiplies the state by the external Poseidon2 matrix.
+#[inline(always)]
+pub(crate) fn external_multiply(state: &mut [BaseElement; 12]) {
+    // multiply each 4-element chunk of the state by M4
+    for chunk in state.chunks_exact_mut(4) {
+        m4_multiply(chunk);
+    }
+
+    // add the sum of the same-position elements of all chunks to each element of the state
+    let mut sums = [BaseElement::ZERO; 4];
+    for (i, &s) in state.iter().enumerate() {
+        sums[i % 4] += s;
+    }
+    for (i, s) in state.iter_mut().enumerate() {
+        *s += sums[i % 4];
+    }
+}
+
+/// Multiplies the state by the internal Poseidon2 matrix.
+#[inline(always)]
+pub(crate) fn internal_multiply(state: &mut [BaseElement; 12]) {
+    let sum = state.iter().fold(BaseElement::ZERO, |acc, &s| acc + s);
+    for (s, &d) in state.iter_mut().zip(INTERNAL_DIAG_M_1.iter()) {
+        *s = *s * d + sum;
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Multiplies a 4-element chunk by the matrix M4 = [[5, 7, 1, 3], [4, 6, 1, 1], [1, 3, 5, 7],
+/// [1, 1, 4, 6]] using the addition chain from appendix B of <https://eprint.iacr.org/2023/323.pdf>.
+#[inline(always)]
+fn m4_multiply(x: &mut [BaseElement]) {
+    let t0 = x[0] + x[1];
+    let t1 = x[2] + x[3];
+    let t2 = x[1].double() + t1;
+    let t3 = x[3].double() + t0;
+    let t4 = t1.double().double() + t3;
+    let t5 = t0.double().double() + t2;
+    let t6 = t3 + t5;
+    let t7 = t2 + t4;
+
+    x[0] = t6;
+    x[1] = t5;
+    x[2] = t7;
+    x[3] = t4;
+}
@@ -3,20 +3,33 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use core::{fmt::Debug, slice};
+use alloc::vec::Vec;
+use core::{fmt::Debug, ops::Range, slice};
 use math::{FieldElement, StarkField};
 use utils::{ByteReader, Deserializable, DeserializationError, Serializable};
 
 mod blake;
-pub use blake::{Blake3_192, Blake3_256};
+pub use blake::{Blake3_192, Blake3_256, Blake3_384};
 
 mod sha;
-pub use sha::Sha3_256;
+pub use sha::{Sha3_256, Sha3_512};
+
+mod domain;
+pub use domain::{DomainSeparated, HashDomain, Personalization};
+
+mod keccak;
+pub use keccak::Keccak256;
 
 mod mds;
 
+mod poseidon2;
+pub use poseidon2::Poseidon2_64_256;
+
 mod rescue;
-pub use rescue::{Rp62_248, Rp64_256, RpJive64_256};
+pub use rescue::{Rp62_248, Rp64_256, RpJive64_256, Rpo64_256, Rpx64_256};
+
+mod tip5;
+pub use tip5::Tip5;
 
 // HASHER TRAITS
 // ================================================================================================
@@ -26,6 +39,7 @@ pub use rescue::{Rp62_248, Rp64_256, RpJive64_256};
 /// This trait defines hash procedures for the following inputs:
 /// * A sequence of bytes.
 /// * Two digests - this is intended for use in Merkle tree constructions.
+/// * A sequence of digests - this is intended for use in higher-arity Merkle tree constructions.
 /// * A digests and a u64 value - this intended for use in PRNG or PoW contexts.
 pub trait Hasher {
     /// Specifies a digest type returned by this hasher.
@@ -43,12 +57,86 @@ pub trait Hasher {
 
     /// Returns hash(`seed` || `value`). This method is intended for use in PRNG and PoW contexts.
     fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest;
+
+    /// Returns a hash of the provided sequence of digests. This method is intended for use in
+    /// construction of Merkle trees with arity greater than two.
+    ///
+    /// The default implementation hashes the concatenation of serialized digests using
+    /// [hash()](Hasher::hash); hash functions which can absorb digests more efficiently should
+    /// override it.
+    fn merge_many(values: &[Self::Digest]) -> Self::Digest {
+        let mut bytes = Vec::new();
+        for value in values {
+            value.write_into(&mut bytes);
+        }
+        Self::hash(&bytes)
+    }
+
+    // DOMAIN-SEPARATED HASHING
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a hash of two digests computed in the specified `domain`.
+    ///
+    /// The default implementation ignores the domain and is equivalent to
+    /// [merge()](Hasher::merge); hash functions which provide domain separation (e.g.,
+    /// [DomainSeparated]) should override it.
+    fn merge_in_domain(values: &[Self::Digest; 2], domain: HashDomain) -> Self::Digest {
+        let _ = domain;
+        Self::merge(values)
+    }
+
+    /// Returns a hash of the provided sequence of digests computed in the specified `domain`.
+    ///
+    /// The default implementation ignores the domain and is equivalent to
+    /// [merge_many()](Hasher::merge_many).
+    fn merge_many_in_domain(values: &[Self::Digest], domain: HashDomain) -> Self::Digest {
+        let _ = domain;
+        Self::merge_many(values)
+    }
+
+    /// Returns hash(`seed` || `value`) computed in the specified `domain`.
+    ///
+    /// The default implementation ignores the domain and is equivalent to
+    /// [merge_with_int()](Hasher::merge_with_int).
+    fn merge_with_int_in_domain(
+        seed: Self::Digest,
+        value: u64,
+        domain: HashDomain,
+    ) -> Self::Digest {
+        let _ = domain;
+        Self::merge_with_int(seed, value)
+    }
+
+    // BATCH HASHING
+    // --------------------------------------------------------------------------------------------
+
+    /// Computes hashes of pairs of digests in the specified `domain`, and writes the i-th hash
+    /// into the i-th element of `result`.
+    ///
+    /// This method is intended for building Merkle trees. The default implementation merges each
+    /// pair using [merge_in_domain()](Hasher::merge_in_domain); hash functions which can process
+    /// many inputs at once more efficiently (e.g., [Rp64_256](crate::hashers::Rp64_256)) should
+    /// override it.
+    ///
+    /// # Panics
+    /// Panics if `values` and `result` have different lengths.
+    fn merge_batch_in_domain(
+        values: &[[Self::Digest; 2]],
+        domain: HashDomain,
+        result: &mut [Self::Digest],
+    ) {
+        assert_eq!(values.len(), result.len(), "number of values must equal result length");
+        for (target, pair) in result.iter_mut().zip(values) {
+            *target = Self::merge_in_domain(pair, domain);
+        }
+    }
 }
 
 /// Defines a cryptographic hash function for hashing field elements.
 ///
 /// This trait defines a hash procedure for a sequence of field elements. The elements can be
 /// either in the base field specified for this hasher, or in an extension of the base field.
+/// Long sequences of elements can also be hashed in fixed-size partitions.
 pub trait ElementHasher: Hasher {
     /// Specifies a base field for elements which can be hashed with this hasher.
     type BaseField: StarkField;
@@ -57,6 +145,96 @@ pub trait ElementHasher: Hasher {
     fn hash_elements<E>(elements: &[E]) -> Self::Digest
     where
         E: FieldElement<BaseField = Self::BaseField>;
+
+    /// Returns a hash of the provided field elements computed in the specified `domain`.
+    ///
+    /// The default implementation ignores the domain and is equivalent to
+    /// [hash_elements()](ElementHasher::hash_elements); hash functions which provide domain
+    /// separation (e.g., [DomainSeparated]) should override it.
+    fn hash_elements_in_domain<E>(elements: &[E], domain: HashDomain) -> Self::Digest
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        let _ = domain;
+        Self::hash_elements(elements)
+    }
+
+    /// Returns a hash of the provided field elements computed by hashing the elements in
+    /// partitions of `partition_size` elements, and then merging the resulting digests using
+    /// [merge_many()](Hasher::merge_many).
+    ///
+    /// When `partition_size` is zero, or is greater than or equal to the number of elements, the
+    /// result is the same as for [hash_elements()](ElementHasher::hash_elements). Hashing long
+    /// sequences of elements in partitions is intended to reduce the cost of verifying the
+    /// hashes in recursive proofs.
+    ///
+    /// This method is intended for hashing rows of committed matrices into Merkle tree leaves,
+    /// and thus, both the partitions and the resulting digests are hashed in the
+    /// [HashDomain::Leaf] domain.
+    fn hash_elements_in_partitions<E>(elements: &[E], partition_size: usize) -> Self::Digest
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        if partition_size == 0 || elements.len() <= partition_size {
+            return Self::hash_elements_in_domain(elements, HashDomain::Leaf);
+        }
+
+        let digests = elements
+            .chunks(partition_size)
+            .map(|partition| Self::hash_elements_in_domain(partition, HashDomain::Leaf))
+            .collect::<Vec<_>>();
+        Self::merge_many_in_domain(&digests, HashDomain::Leaf)
+    }
+
+    /// Computes hashes of the provided rows of field elements in the same way as
+    /// [hash_elements_in_partitions()](ElementHasher::hash_elements_in_partitions), and writes
+    /// the i-th hash into the i-th element of `result`.
+    ///
+    /// This method is intended for hashing rows of committed matrices into Merkle tree leaves.
+    /// The default implementation hashes the rows one by one; hash functions which can process
+    /// many inputs at once more efficiently (e.g., [Rp64_256](crate::hashers::Rp64_256)) should
+    /// override it. Such implementations are usually most efficient when all rows have the same
+    /// length.
+    ///
+    /// # Panics
+    /// Panics if `rows` and `result` have different lengths.
+    fn hash_rows_in_partitions<E>(rows: &[&[E]], partition_size: usize, result: &mut [Self::Digest])
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        assert_eq!(rows.len(), result.len(), "number of rows must equal result length");
+        for (target, row) in result.iter_mut().zip(rows) {
+            *target = Self::hash_elements_in_partitions(row, partition_size);
+        }
+    }
+}
+
+// ALGEBRAIC SPONGE TRAIT
+// ================================================================================================
+
+/// Defines an arithmetization-friendly hash function built as a sponge over a prime field.
+///
+/// This trait exposes the permutation and the layout of the sponge state so that constructions
+/// which operate on the state directly (e.g., [SpongeRandomCoin](crate::SpongeRandomCoin)) can be
+/// instantiated with any such hash function.
+pub trait AlgebraicSponge: ElementHasher {
+    /// Number of field elements in the sponge state.
+    const STATE_WIDTH: usize;
+
+    /// Range of state elements which form the rate portion of the sponge.
+    const RATE_RANGE: Range<usize>;
+
+    /// Range of state elements which form the capacity portion of the sponge.
+    const CAPACITY_RANGE: Range<usize>;
+
+    /// Applies the permutation underlying this hash function to the provided state.
+    ///
+    /// # Panics
+    /// Panics if the length of `state` is not equal to `STATE_WIDTH`.
+    fn permute(state: &mut [Self::BaseField]);
+
+    /// Returns the field elements which make up the provided digest.
+    fn digest_as_elements(digest: &Self::Digest) -> &[Self::BaseField];
 }
 
 // DIGEST TRAIT
@@ -70,9 +248,9 @@ pub trait Digest:
     ///
     /// Ideally, the length of the returned array should be defined by an associated constant, but
     /// using associated constants in const generics is not supported by Rust yet. Thus, we put an
-    /// upper limit on the possible digest size. For digests which are smaller than 32 bytes, the
-    /// unused bytes should be set to 0.
-    fn as_bytes(&self) -> [u8; 32];
+    /// upper limit of 64 bytes on the possible digest size. For digests which are smaller than 64
+    /// bytes, the unused bytes should be set to 0.
+    fn as_bytes(&self) -> [u8; 64];
 }
 
 // BYTE DIGEST
@@ -102,8 +280,8 @@ impl<const N: usize> ByteDigest<N> {
 }
 
 impl<const N: usize> Digest for ByteDigest<N> {
-    fn as_bytes(&self) -> [u8; 32] {
-        let mut result = [0; 32];
+    fn as_bytes(&self) -> [u8; 64] {
+        let mut result = [0; 64];
         result[..N].copy_from_slice(&self.0);
         result
     }
@@ -133,12 +311,17 @@ mod tests {
 
     #[test]
     fn byte_digest_as_bytes() {
-        let d = ByteDigest::new([255_u8; 32]);
-        assert_eq!([255_u8; 32], d.as_bytes());
+        let d = ByteDigest::new([255_u8; 64]);
+        assert_eq!([255_u8; 64], d.as_bytes());
 
         let d = ByteDigest::new([255_u8; 31]);
-        let mut expected = [255_u8; 32];
-        expected[31] = 0;
+        let mut expected = [0_u8; 64];
+        expected[..31].fill(255);
+        assert_eq!(expected, d.as_bytes());
+
+        let d = ByteDigest::new([255_u8; 48]);
+        let mut expected = [0_u8; 64];
+        expected[..48].fill(255);
         assert_eq!(expected, d.as_bytes());
     }
 }
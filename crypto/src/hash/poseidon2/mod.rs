@@ -0,0 +1,9 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{AlgebraicSponge, Digest, ElementHasher, Hasher};
+
+mod p2_64_256;
+pub use p2_64_256::Poseidon2_64_256;
@@ -3,7 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{Digest, ElementHasher, Hasher, StarkField};
+use super::{AlgebraicSponge, Digest, ElementHasher, HashDomain, Hasher, StarkField};
 
 mod rp62_248;
 pub use rp62_248::Rp62_248;
@@ -14,6 +14,12 @@ pub use rp64_256::Rp64_256;
 mod rp64_256_jive;
 pub use rp64_256_jive::RpJive64_256;
 
+mod rpo64_256;
+pub use rpo64_256::Rpo64_256;
+
+mod rpx64_256;
+pub use rpx64_256::Rpx64_256;
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -0,0 +1,297 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    exp_acc, ElementDigest, ElementHasher, Rp64_256, ARK1, ARK2, CAPACITY_RANGE, DIGEST_RANGE,
+    NUM_ROUNDS, RATE_RANGE, RATE_WIDTH, STATE_WIDTH,
+};
+use alloc::vec::Vec;
+use math::{fields::f64::BaseElement, FieldElement};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of states processed together by the batch permutation.
+pub const BATCH_SIZE: usize = 8;
+
+/// Number of field elements in a batch of states.
+const BATCH_STATE_SIZE: usize = STATE_WIDTH * BATCH_SIZE;
+
+/// First row of the circulant MDS matrix; row i of the matrix is this row rotated right by i
+/// positions.
+const MDS_ROW: [u64; STATE_WIDTH] = [7, 23, 8, 26, 13, 10, 9, 7, 6, 22, 21, 8];
+
+// BATCH STATE
+// ================================================================================================
+
+/// A batch of Rescue states stored in a transposed (structure-of-arrays) layout.
+///
+/// Element j of state k is stored at position j * BATCH_SIZE + k. Thus, every step of the
+/// permutation applies the same operation to runs of BATCH_SIZE consecutive elements, which
+/// allows the compiler to vectorize it using SIMD instructions (e.g., AVX2 or NEON). The layout
+/// also makes it possible to replace individual steps with explicit SIMD implementations.
+#[derive(Clone, Copy)]
+pub(super) struct BatchState([BaseElement; BATCH_STATE_SIZE]);
+
+impl BatchState {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a batch of states initialized to all zeros.
+    pub fn new() -> Self {
+        Self([BaseElement::ZERO; BATCH_STATE_SIZE])
+    }
+
+    /// Returns a batch of the provided states; if fewer than BATCH_SIZE states are provided, the
+    /// remaining states in the batch are set to all zeros.
+    pub fn from_states(states: &[[BaseElement; STATE_WIDTH]]) -> Self {
+        debug_assert!(states.len() <= BATCH_SIZE);
+        let mut result = Self::new();
+        for (k, state) in states.iter().enumerate() {
+            for (j, &element) in state.iter().enumerate() {
+                result.0[j * BATCH_SIZE + k] = element;
+            }
+        }
+        result
+    }
+
+    // ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a mutable reference to element j of state k.
+    #[inline(always)]
+    pub fn get_mut(&mut self, j: usize, k: usize) -> &mut BaseElement {
+        &mut self.0[j * BATCH_SIZE + k]
+    }
+
+    /// Writes states of this batch into the provided slice of states.
+    pub fn write_states(&self, states: &mut [[BaseElement; STATE_WIDTH]]) {
+        debug_assert!(states.len() <= BATCH_SIZE);
+        for (k, state) in states.iter_mut().enumerate() {
+            for (j, element) in state.iter_mut().enumerate() {
+                *element = self.0[j * BATCH_SIZE + k];
+            }
+        }
+    }
+
+    /// Returns the digest contained in state k.
+    pub fn digest(&self, k: usize) -> ElementDigest {
+        ElementDigest::new(core::array::from_fn(|j| {
+            self.0[(DIGEST_RANGE.start + j) * BATCH_SIZE + k]
+        }))
+    }
+
+    // RESCUE PERMUTATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Applies Rescue-XLIX permutation to all states in the batch.
+    pub fn apply_permutation(&mut self) {
+        for i in 0..NUM_ROUNDS {
+            self.apply_round(i);
+        }
+    }
+
+    /// Rescue-XLIX round function applied to all states in the batch.
+    #[inline(always)]
+    fn apply_round(&mut self, round: usize) {
+        // apply first half of Rescue round
+        self.apply_sbox();
+        self.apply_mds();
+        self.add_constants(&ARK1[round]);
+
+        // apply second half of Rescue round
+        self.apply_inv_sbox();
+        self.apply_mds();
+        self.add_constants(&ARK2[round]);
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    #[inline(always)]
+    fn apply_sbox(&mut self) {
+        self.0.iter_mut().for_each(|s| *s = s.exp7());
+    }
+
+    #[inline(always)]
+    fn apply_inv_sbox(&mut self) {
+        // compute base^10540996611094048183 using 72 multiplications per array element in the
+        // same way as Rp64_256::apply_inv_sbox()
+        let mut t1 = self.0;
+        t1.iter_mut().for_each(|t| *t = t.square());
+
+        let mut t2 = t1;
+        t2.iter_mut().for_each(|t| *t = t.square());
+
+        let t3 = exp_acc::<BaseElement, BATCH_STATE_SIZE, 3>(t2, t2);
+        let t4 = exp_acc::<BaseElement, BATCH_STATE_SIZE, 6>(t3, t3);
+        let t5 = exp_acc::<BaseElement, BATCH_STATE_SIZE, 12>(t4, t4);
+        let t6 = exp_acc::<BaseElement, BATCH_STATE_SIZE, 6>(t5, t3);
+        let t7 = exp_acc::<BaseElement, BATCH_STATE_SIZE, 31>(t6, t6);
+
+        for (i, s) in self.0.iter_mut().enumerate() {
+            let a = (t7[i].square() * t6[i]).square().square();
+            let b = t1[i] * t2[i] * *s;
+            *s = a * b;
+        }
+    }
+
+    /// Multiplies each state in the batch by the MDS matrix.
+    ///
+    /// Since MDS matrix coefficients are small, each element is split into its low and high
+    /// 32-bit halves, and the products for each half are accumulated in 64-bit integers without
+    /// overflow; the two halves are combined and reduced only once per element.
+    #[inline(always)]
+    fn apply_mds(&mut self) {
+        let mut state_l = [0u64; BATCH_STATE_SIZE];
+        let mut state_h = [0u64; BATCH_STATE_SIZE];
+        for (i, s) in self.0.iter().enumerate() {
+            let s = s.inner();
+            state_l[i] = (s as u32) as u64;
+            state_h[i] = s >> 32;
+        }
+
+        for i in 0..STATE_WIDTH {
+            let mut acc_l = [0u64; BATCH_SIZE];
+            let mut acc_h = [0u64; BATCH_SIZE];
+            for j in 0..STATE_WIDTH {
+                let c = MDS_ROW[(j + STATE_WIDTH - i) % STATE_WIDTH];
+                let lanes = j * BATCH_SIZE..(j + 1) * BATCH_SIZE;
+                for (acc, &s) in acc_l.iter_mut().zip(&state_l[lanes.clone()]) {
+                    *acc += c * s;
+                }
+                for (acc, &s) in acc_h.iter_mut().zip(&state_h[lanes]) {
+                    *acc += c * s;
+                }
+            }
+
+            for k in 0..BATCH_SIZE {
+                self.0[i * BATCH_SIZE + k] = reduce(acc_l[k], acc_h[k]);
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn add_constants(&mut self, ark: &[BaseElement; STATE_WIDTH]) {
+        for (lanes, &k) in self.0.chunks_exact_mut(BATCH_SIZE).zip(ark) {
+            lanes.iter_mut().for_each(|s| *s += k);
+        }
+    }
+}
+
+// BATCH HASHING
+// ================================================================================================
+
+/// Hashes each of the provided sequences of elements in the same way as
+/// [Rp64_256::hash_elements()] does, and writes the i-th hash into the i-th element of `result`.
+///
+/// All sequences must have the same length, and at most BATCH_SIZE sequences can be provided.
+fn hash_elements_batch(inputs: &[&[BaseElement]], result: &mut [ElementDigest]) {
+    debug_assert!(inputs.len() <= BATCH_SIZE);
+    let len = inputs[0].len();
+    debug_assert!(inputs.iter().all(|input| input.len() == len));
+
+    // initialize the first capacity element of each state to the number of elements to be hashed
+    let mut state = BatchState::new();
+    for k in 0..inputs.len() {
+        *state.get_mut(CAPACITY_RANGE.start, k) = BaseElement::new(len as u64);
+    }
+
+    // absorb elements into the rate portion of the states, and apply the permutation whenever
+    // the rate portion is filled up
+    let mut i = 0;
+    for pos in 0..len {
+        for (k, input) in inputs.iter().enumerate() {
+            *state.get_mut(RATE_RANGE.start + i, k) += input[pos];
+        }
+        i += 1;
+        if i == RATE_WIDTH {
+            state.apply_permutation();
+            i = 0;
+        }
+    }
+    if i > 0 {
+        state.apply_permutation();
+    }
+
+    for (k, target) in result.iter_mut().enumerate() {
+        *target = state.digest(k);
+    }
+}
+
+/// Merges each pair of digests in the same way as [Rp64_256::merge()] does, and writes the i-th
+/// hash into the i-th element of `result`.
+pub(super) fn merge_batch(values: &[[ElementDigest; 2]], result: &mut [ElementDigest]) {
+    let mut inputs: [&[BaseElement]; BATCH_SIZE] = [&[]; BATCH_SIZE];
+    for (values, result) in values.chunks(BATCH_SIZE).zip(result.chunks_mut(BATCH_SIZE)) {
+        for (input, pair) in inputs.iter_mut().zip(values) {
+            *input = ElementDigest::digests_as_elements(pair);
+        }
+        hash_elements_batch(&inputs[..values.len()], result);
+    }
+}
+
+/// Hashes each of the provided rows in the same way as [Rp64_256::hash_elements_in_partitions()]
+/// does, and writes the i-th hash into the i-th element of `result`; `partition_size` is
+/// measured in base field elements.
+pub(super) fn hash_rows(
+    rows: &[&[BaseElement]],
+    partition_size: usize,
+    result: &mut [ElementDigest],
+) {
+    let mut digests = [ElementDigest::default(); BATCH_SIZE];
+    let mut partition_digests: [Vec<BaseElement>; BATCH_SIZE] = Default::default();
+
+    for (rows, result) in rows.chunks(BATCH_SIZE).zip(result.chunks_mut(BATCH_SIZE)) {
+        // rows of different lengths cannot be hashed together, and thus are hashed one by one
+        let width = rows[0].len();
+        if rows.iter().any(|row| row.len() != width) {
+            for (target, row) in result.iter_mut().zip(rows) {
+                *target = Rp64_256::hash_elements_in_partitions(row, partition_size);
+            }
+            continue;
+        }
+
+        if partition_size == 0 || width <= partition_size {
+            hash_elements_batch(rows, result);
+            continue;
+        }
+
+        // hash the rows partition by partition, and then hash the digests of the partitions
+        partition_digests.iter_mut().for_each(|digests| digests.clear());
+        for start in (0..width).step_by(partition_size) {
+            let end = usize::min(start + partition_size, width);
+            let mut inputs: [&[BaseElement]; BATCH_SIZE] = [&[]; BATCH_SIZE];
+            for (input, row) in inputs.iter_mut().zip(rows) {
+                *input = &row[start..end];
+            }
+            hash_elements_batch(&inputs[..rows.len()], &mut digests[..rows.len()]);
+            for (target, digest) in partition_digests.iter_mut().zip(&digests[..rows.len()]) {
+                target.extend_from_slice(digest.as_elements());
+            }
+        }
+
+        let mut inputs: [&[BaseElement]; BATCH_SIZE] = [&[]; BATCH_SIZE];
+        for (input, digests) in inputs.iter_mut().zip(partition_digests.iter()) {
+            *input = digests;
+        }
+        hash_elements_batch(&inputs[..rows.len()], result);
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Combines the low and the high halves of an MDS row product and reduces the result into a field
+/// element.
+#[inline(always)]
+fn reduce(l: u64, h: u64) -> BaseElement {
+    let s = l as u128 + ((h as u128) << 32);
+    let s_hi = (s >> 64) as u64;
+    let s_lo = s as u64;
+    let z = (s_hi << 32) - s_hi;
+    let (res, over) = s_lo.overflowing_add(z);
+    BaseElement::from_mont(res.wrapping_add(0u32.wrapping_sub(over as u32) as u64))
+}
@@ -31,13 +31,13 @@ impl ElementDigest {
 }
 
 impl Digest for ElementDigest {
-    fn as_bytes(&self) -> [u8; 32] {
-        let mut result = [0; 32];
+    fn as_bytes(&self) -> [u8; 64] {
+        let mut result = [0; 64];
 
         result[..8].copy_from_slice(&self.0[0].as_int().to_le_bytes());
         result[8..16].copy_from_slice(&self.0[1].as_int().to_le_bytes());
         result[16..24].copy_from_slice(&self.0[2].as_int().to_le_bytes());
-        result[24..].copy_from_slice(&self.0[3].as_int().to_le_bytes());
+        result[24..32].copy_from_slice(&self.0[3].as_int().to_le_bytes());
 
         result
     }
@@ -51,7 +51,7 @@ impl Default for ElementDigest {
 
 impl Serializable for ElementDigest {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        target.write_bytes(&self.as_bytes());
+        target.write_bytes(&self.as_bytes()[..32]);
     }
 }
 
@@ -81,7 +81,7 @@ impl From<ElementDigest> for [BaseElement; DIGEST_SIZE] {
 
 impl From<ElementDigest> for [u8; 32] {
     fn from(value: ElementDigest) -> Self {
-        value.as_bytes()
+        value.as_bytes()[..32].try_into().unwrap()
     }
 }
 
@@ -4,10 +4,13 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::super::mds::mds_f64_12x12::mds_multiply;
-use super::{exp_acc, Digest, ElementHasher, Hasher};
+use super::{exp_acc, AlgebraicSponge, Digest, ElementHasher, HashDomain, Hasher};
 use core::ops::Range;
 use math::{fields::f64::BaseElement, FieldElement, StarkField};
 
+mod batch;
+use batch::BatchState;
+
 mod digest;
 pub use digest::ElementDigest;
 
@@ -190,6 +193,10 @@ impl Hasher for Rp64_256 {
         ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
     }
 
+    fn merge_many(values: &[Self::Digest]) -> Self::Digest {
+        Self::hash_elements(ElementDigest::digests_as_elements(values))
+    }
+
     fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
         // initialize the state as follows:
         // - seed is copied into the first 4 elements of the rate portion of the state.
@@ -212,6 +219,17 @@ impl Hasher for Rp64_256 {
         Self::apply_permutation(&mut state);
         ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
     }
+
+    fn merge_batch_in_domain(
+        values: &[[Self::Digest; 2]],
+        domain: HashDomain,
+        result: &mut [Self::Digest],
+    ) {
+        // this hash function does not provide domain separation, and thus, the domain is ignored
+        let _ = domain;
+        assert_eq!(values.len(), result.len(), "number of values must equal result length");
+        batch::merge_batch(values, result);
+    }
 }
 
 impl ElementHasher for Rp64_256 {
@@ -251,6 +269,40 @@ impl ElementHasher for Rp64_256 {
         // return the first 4 elements of the state as hash result
         ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
     }
+
+    fn hash_rows_in_partitions<E>(rows: &[&[E]], partition_size: usize, result: &mut [Self::Digest])
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        assert_eq!(rows.len(), result.len(), "number of rows must equal result length");
+
+        // rows are hashed as sequences of base field elements; thus, partition size needs to be
+        // measured in base field elements as well
+        let partition_size = partition_size * E::EXTENSION_DEGREE;
+        let mut base_rows: [&[BaseElement]; batch::BATCH_SIZE] = [&[]; batch::BATCH_SIZE];
+        for (rows, result) in
+            rows.chunks(batch::BATCH_SIZE).zip(result.chunks_mut(batch::BATCH_SIZE))
+        {
+            for (base_row, row) in base_rows.iter_mut().zip(rows) {
+                *base_row = E::slice_as_base_elements(row);
+            }
+            batch::hash_rows(&base_rows[..rows.len()], partition_size, result);
+        }
+    }
+}
+
+impl AlgebraicSponge for Rp64_256 {
+    const STATE_WIDTH: usize = STATE_WIDTH;
+    const RATE_RANGE: Range<usize> = RATE_RANGE;
+    const CAPACITY_RANGE: Range<usize> = CAPACITY_RANGE;
+
+    fn permute(state: &mut [BaseElement]) {
+        Self::apply_permutation(state.try_into().expect("invalid sponge state width"));
+    }
+
+    fn digest_as_elements(digest: &Self::Digest) -> &[BaseElement] {
+        digest.as_elements()
+    }
 }
 
 // HASH FUNCTION IMPLEMENTATION
@@ -288,9 +340,28 @@ impl Rp64_256 {
     /// Round constants added to the hasher state in the second half of the Rescue Prime round.
     pub const ARK2: [[BaseElement; STATE_WIDTH]; NUM_ROUNDS] = ARK2;
 
+    /// Number of states processed together by [apply_permutation_many()](Self::apply_permutation_many)
+    /// and by the batch hashing methods.
+    pub const BATCH_SIZE: usize = batch::BATCH_SIZE;
+
     // RESCUE PERMUTATION
     // --------------------------------------------------------------------------------------------
 
+    /// Applies Rescue-XLIX permutation to each of the provided states.
+    ///
+    /// The states are processed in batches of [BATCH_SIZE](Self::BATCH_SIZE) states. Each batch
+    /// is transposed so that the same elements of all states in the batch are stored next to each
+    /// other, which allows the permutation to be applied to all of them using SIMD instructions.
+    /// The result is the same as applying [apply_permutation()](Self::apply_permutation) to each
+    /// state individually.
+    pub fn apply_permutation_many(states: &mut [[BaseElement; STATE_WIDTH]]) {
+        for states in states.chunks_mut(batch::BATCH_SIZE) {
+            let mut batch = BatchState::from_states(states);
+            batch.apply_permutation();
+            batch.write_states(states);
+        }
+    }
+
     /// Applies Rescue-XLIX permutation to the provided state.
     pub fn apply_permutation(state: &mut [BaseElement; STATE_WIDTH]) {
         // implementation is based on algorithm 3 from <https://eprint.iacr.org/2020/1143.pdf>
@@ -4,12 +4,14 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{
-    BaseElement, ElementDigest, ElementHasher, FieldElement, Hasher, Rp64_256, StarkField, ALPHA,
-    INV_ALPHA, INV_MDS, MDS, STATE_WIDTH,
+    BaseElement, ElementDigest, ElementHasher, FieldElement, HashDomain, Hasher, Rp64_256,
+    StarkField, ALPHA, INV_ALPHA, INV_MDS, MDS, STATE_WIDTH,
 };
+use alloc::vec::Vec;
+use math::fields::QuadExtension;
 use proptest::prelude::*;
 
-use rand_utils::{rand_array, rand_value};
+use rand_utils::{rand_array, rand_value, rand_vector};
 
 #[test]
 fn mds_inv_test() {
@@ -177,6 +179,74 @@ fn hash_elements_padding() {
     assert_ne!(r1, r2);
 }
 
+#[test]
+fn apply_permutation_many() {
+    // include a partial batch of states
+    let mut states = (0..2 * Rp64_256::BATCH_SIZE + 3)
+        .map(|_| rand_array::<BaseElement, STATE_WIDTH>())
+        .collect::<Vec<_>>();
+
+    let mut expected = states.clone();
+    expected.iter_mut().for_each(Rp64_256::apply_permutation);
+
+    Rp64_256::apply_permutation_many(&mut states);
+    assert_eq!(expected, states);
+}
+
+#[test]
+fn merge_batch() {
+    let values = (0..Rp64_256::BATCH_SIZE + 5)
+        .map(|i| [Rp64_256::hash(&[i as u8]), Rp64_256::hash(&[i as u8, 1])])
+        .collect::<Vec<_>>();
+
+    let expected = values.iter().map(Rp64_256::merge).collect::<Vec<_>>();
+    let mut result = vec![ElementDigest::default(); values.len()];
+    Rp64_256::merge_batch_in_domain(&values, HashDomain::Node, &mut result);
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn hash_rows_in_partitions() {
+    // rows of the same length, with and without partitions
+    let rows = (0..Rp64_256::BATCH_SIZE * 2 + 1)
+        .map(|_| rand_vector::<BaseElement>(19))
+        .collect::<Vec<_>>();
+    for partition_size in [0, 1, 4, 8, 19, 32] {
+        check_hash_rows(&rows, partition_size);
+    }
+
+    // rows of different lengths
+    let rows = (0..Rp64_256::BATCH_SIZE + 2)
+        .map(|i| rand_vector::<BaseElement>(i + 1))
+        .collect::<Vec<_>>();
+    for partition_size in [0, 3, 8] {
+        check_hash_rows(&rows, partition_size);
+    }
+
+    // rows of extension field elements
+    let rows = (0..Rp64_256::BATCH_SIZE + 1)
+        .map(|_| rand_vector::<QuadExtension<BaseElement>>(13))
+        .collect::<Vec<_>>();
+    for partition_size in [0, 4, 13] {
+        check_hash_rows(&rows, partition_size);
+    }
+}
+
+fn check_hash_rows<E: FieldElement<BaseField = BaseElement>>(
+    rows: &[Vec<E>],
+    partition_size: usize,
+) {
+    let rows = rows.iter().map(|row| row.as_slice()).collect::<Vec<_>>();
+    let expected = rows
+        .iter()
+        .map(|row| Rp64_256::hash_elements_in_partitions(row, partition_size))
+        .collect::<Vec<_>>();
+
+    let mut result = vec![ElementDigest::default(); rows.len()];
+    Rp64_256::hash_rows_in_partitions(&rows, partition_size, &mut result);
+    assert_eq!(expected, result);
+}
+
 #[inline(always)]
 fn apply_mds_naive(state: &mut [BaseElement; STATE_WIDTH]) {
     let mut result = [BaseElement::ZERO; STATE_WIDTH];
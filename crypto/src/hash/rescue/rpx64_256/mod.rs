@@ -0,0 +1,340 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{AlgebraicSponge, Digest, ElementHasher, Hasher, Rpo64_256};
+use core::ops::Range;
+use math::{fields::f64::BaseElement, ExtensibleField, FieldElement, StarkField};
+
+mod digest;
+pub use digest::ElementDigest;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Sponge state is set to 12 field elements or 96 bytes; 8 elements are reserved for rate and
+/// the remaining 4 elements are reserved for capacity.
+const STATE_WIDTH: usize = 12;
+
+/// The rate portion of the state is located in elements 4 through 11.
+const RATE_RANGE: Range<usize> = 4..12;
+const RATE_WIDTH: usize = RATE_RANGE.end - RATE_RANGE.start;
+
+const INPUT1_RANGE: Range<usize> = 4..8;
+const INPUT2_RANGE: Range<usize> = 8..12;
+
+/// The capacity portion of the state is located in elements 0, 1, 2, and 3.
+const CAPACITY_RANGE: Range<usize> = 0..4;
+
+/// The output of the hash function is a digest which consists of 4 field elements or 32 bytes.
+///
+/// The digest is returned from state elements 4, 5, 6, and 7 (the first four elements of the
+/// rate portion).
+const DIGEST_RANGE: Range<usize> = 4..8;
+const DIGEST_SIZE: usize = DIGEST_RANGE.end - DIGEST_RANGE.start;
+
+/// The number of rounds is set to 7; RPX alternates between 3 full (FB) rounds and 3 extension
+/// (E) rounds, and finishes with a single linear (M) round.
+const NUM_ROUNDS: usize = 7;
+
+/// The constant is defined for tests only because the exponentiations in the code are unrolled
+/// for efficiency reasons.
+#[cfg(test)]
+const ALPHA: u64 = 7;
+
+// HASHER IMPLEMENTATION
+// ================================================================================================
+
+/// Implementation of [Hasher] trait for RPX hash function with 256-bit output.
+///
+/// The hash function is implemented according to the XHash12 construction described in
+/// [this paper](https://eprint.iacr.org/2023/1045.pdf). The permutation is a variant of the
+/// [Rpo64_256](super::Rpo64_256) permutation in which some of the rounds are replaced with
+/// cheaper rounds working over the cubic extension of the base field:
+/// * (FB) rounds are identical to RPO rounds.
+/// * (E) rounds add round constants to the state and then raise each of the 4 cubic extension
+///   field elements which make up the state to the power 7.
+/// * (M) round multiplies the state by the MDS matrix and adds round constants to it.
+///
+/// The permutation consists of the following sequence of rounds: (FB) (E) (FB) (E) (FB) (E) (M).
+/// This makes RPX roughly twice as fast as RPO natively, at the expense of a somewhat more
+/// complicated AIR. The MDS matrix and the round constants are the same as for RPO.
+///
+/// The sponge construction (i.e., the state layout, the padding rule, and the way field elements
+/// and bytes are absorbed) is identical to the one used by [Rpo64_256](super::Rpo64_256).
+///
+/// The parameters used to instantiate the function are:
+/// * Field: 64-bit prime field with modulus 2^64 - 2^32 + 1.
+/// * State width: 12 field elements.
+/// * Capacity size: 4 field elements.
+/// * S-Box degree: 7.
+/// * Cubic extension field: defined by the irreducible polynomial x^3 - x - 1.
+///
+/// The above parameters target 128-bit security level. The digest consists of four field elements
+/// and it can be serialized into 32 bytes (256 bits).
+///
+/// ## Hash output consistency
+/// Functions [hash_elements()](Rpx64_256::hash_elements), [merge()](Rpx64_256::merge), and
+/// [merge_with_int()](Rpx64_256::merge_with_int) are internally consistent. That is, computing
+/// a hash for the same set of elements using these functions will always produce the same
+/// result. However, [hash()](Rpx64_256::hash) function is not consistent with functions
+/// mentioned above.
+pub struct Rpx64_256();
+
+impl Hasher for Rpx64_256 {
+    type Digest = ElementDigest;
+
+    const COLLISION_RESISTANCE: u32 = 128;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        // compute the number of elements required to represent the string; we will be processing
+        // the string in 7-byte chunks, thus the number of elements will be equal to the number
+        // of such chunks (including a potential partial chunk at the end).
+        let num_elements = if bytes.len() % 7 == 0 {
+            bytes.len() / 7
+        } else {
+            bytes.len() / 7 + 1
+        };
+
+        // initialize state to all zeros, except for the first element of the capacity part, which
+        // is set to the number of elements to be hashed modulo the rate width.
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        state[CAPACITY_RANGE.start] = BaseElement::new((num_elements % RATE_WIDTH) as u64);
+
+        // break the string into 7-byte chunks, convert each chunk into a field element, and
+        // absorb the element into the rate portion of the state. we use 7-byte chunks because
+        // every 7-byte chunk is guaranteed to map to some field element.
+        let mut i = 0;
+        let mut buf = [0_u8; 8];
+        for chunk in bytes.chunks(7) {
+            if i < num_elements - 1 {
+                buf[..7].copy_from_slice(chunk);
+            } else {
+                // if we are dealing with the last chunk, it may be smaller than 7 bytes long, so
+                // we need to handle it slightly differently. we also append a byte with value 1
+                // to the end of the string; this pads the string in such a way that adding
+                // trailing zeros results in different hash
+                let chunk_len = chunk.len();
+                buf = [0_u8; 8];
+                buf[..chunk_len].copy_from_slice(chunk);
+                buf[chunk_len] = 1;
+            }
+
+            // convert the bytes into a field element and absorb it into the rate portion of the
+            // state; if the rate is filled up, apply the RPX permutation and start absorbing
+            // again from zero index.
+            state[RATE_RANGE.start + i] = BaseElement::new(u64::from_le_bytes(buf));
+            i += 1;
+            if i % RATE_WIDTH == 0 {
+                Self::apply_permutation(&mut state);
+                i = 0;
+            }
+        }
+
+        // if we absorbed some elements but didn't apply a permutation to them (would happen when
+        // the number of elements is not a multiple of RATE_WIDTH), pad the rate and apply the
+        // RPX permutation.
+        if i > 0 {
+            Self::pad_and_apply_permutation(&mut state, i);
+        }
+
+        // return the first 4 elements of the rate portion of the state as hash result
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        // initialize the state by copying the digest elements into the rate portion of the state
+        // (8 total elements); the capacity is left as zeros because the number of elements to be
+        // hashed is a multiple of the rate width.
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        state[RATE_RANGE].copy_from_slice(Self::Digest::digests_as_elements(values));
+
+        // apply the RPX permutation and return the first four elements of the rate
+        Self::apply_permutation(&mut state);
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+
+    fn merge_many(values: &[Self::Digest]) -> Self::Digest {
+        Self::hash_elements(ElementDigest::digests_as_elements(values))
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        // initialize the state as follows:
+        // - seed is copied into the first 4 elements of the rate portion of the state.
+        // - if the value fits into a single field element, copy it into the fifth rate element
+        //   and set the first capacity element to 5 (the number of elements to be hashed).
+        // - if the value doesn't fit into a single field element, split it into two field
+        //   elements, copy them into rate elements 5 and 6, and set the first capacity element
+        //   to 6.
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        state[INPUT1_RANGE].copy_from_slice(seed.as_elements());
+        state[INPUT2_RANGE.start] = BaseElement::new(value);
+        let num_elements = if value < BaseElement::MODULUS {
+            DIGEST_SIZE + 1
+        } else {
+            state[INPUT2_RANGE.start + 1] = BaseElement::new(value / BaseElement::MODULUS);
+            DIGEST_SIZE + 2
+        };
+        state[CAPACITY_RANGE.start] = BaseElement::new(num_elements as u64);
+
+        // pad the rate, apply the RPX permutation, and return the first four elements of the rate
+        Self::pad_and_apply_permutation(&mut state, num_elements);
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+}
+
+impl ElementHasher for Rpx64_256 {
+    type BaseField = BaseElement;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        // convert the elements into a list of base field elements
+        let elements = E::slice_as_base_elements(elements);
+
+        // initialize state to all zeros, except for the first element of the capacity part, which
+        // is set to the number of elements to be hashed modulo the rate width.
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        state[CAPACITY_RANGE.start] = BaseElement::new((elements.len() % RATE_WIDTH) as u64);
+
+        // absorb elements into the state one by one until the rate portion of the state is filled
+        // up; then apply the RPX permutation and start absorbing again; repeat until all elements
+        // have been absorbed
+        let mut i = 0;
+        for &element in elements.iter() {
+            state[RATE_RANGE.start + i] = element;
+            i += 1;
+            if i % RATE_WIDTH == 0 {
+                Self::apply_permutation(&mut state);
+                i = 0;
+            }
+        }
+
+        // if we absorbed some elements but didn't apply a permutation to them (would happen when
+        // the number of elements is not a multiple of RATE_WIDTH), pad the rate and apply the
+        // RPX permutation.
+        if i > 0 {
+            Self::pad_and_apply_permutation(&mut state, i);
+        }
+
+        // return the first 4 elements of the rate portion of the state as hash result
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+}
+
+impl AlgebraicSponge for Rpx64_256 {
+    const STATE_WIDTH: usize = STATE_WIDTH;
+    const RATE_RANGE: Range<usize> = RATE_RANGE;
+    const CAPACITY_RANGE: Range<usize> = CAPACITY_RANGE;
+
+    fn permute(state: &mut [BaseElement]) {
+        Self::apply_permutation(state.try_into().expect("invalid sponge state width"));
+    }
+
+    fn digest_as_elements(digest: &Self::Digest) -> &[BaseElement] {
+        digest.as_elements()
+    }
+}
+
+// HASH FUNCTION IMPLEMENTATION
+// ================================================================================================
+
+impl Rpx64_256 {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// The number of rounds in the RPX permutation.
+    pub const NUM_ROUNDS: usize = NUM_ROUNDS;
+
+    /// Sponge state is set to 12 field elements or 96 bytes; 8 elements are reserved for rate and
+    /// the remaining 4 elements are reserved for capacity.
+    pub const STATE_WIDTH: usize = STATE_WIDTH;
+
+    /// The rate portion of the state is located in elements 4 through 11 (inclusive).
+    pub const RATE_RANGE: Range<usize> = RATE_RANGE;
+
+    /// The capacity portion of the state is located in elements 0, 1, 2, and 3.
+    pub const CAPACITY_RANGE: Range<usize> = CAPACITY_RANGE;
+
+    /// The output of the hash function can be read from state elements 4, 5, 6, and 7.
+    pub const DIGEST_RANGE: Range<usize> = DIGEST_RANGE;
+
+    /// MDS matrix used for computing the linear layer in (FB) and (M) rounds.
+    pub const MDS: [[BaseElement; STATE_WIDTH]; STATE_WIDTH] = Rpo64_256::MDS;
+
+    /// Round constants added to the hasher state in the first half of (FB) rounds, as well as in
+    /// (E) and (M) rounds.
+    pub const ARK1: [[BaseElement; STATE_WIDTH]; NUM_ROUNDS] = Rpo64_256::ARK1;
+
+    /// Round constants added to the hasher state in the second half of (FB) rounds.
+    pub const ARK2: [[BaseElement; STATE_WIDTH]; NUM_ROUNDS] = Rpo64_256::ARK2;
+
+    // RPX PERMUTATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Applies RPX permutation to the provided state.
+    pub fn apply_permutation(state: &mut [BaseElement; STATE_WIDTH]) {
+        Self::apply_fb_round(state, 0);
+        Self::apply_ext_round(state, 1);
+        Self::apply_fb_round(state, 2);
+        Self::apply_ext_round(state, 3);
+        Self::apply_fb_round(state, 4);
+        Self::apply_ext_round(state, 5);
+        Self::apply_final_round(state, 6);
+    }
+
+    /// RPX (FB) round function; this is the same as the RPO round function.
+    #[inline(always)]
+    pub fn apply_fb_round(state: &mut [BaseElement; STATE_WIDTH], round: usize) {
+        Rpo64_256::apply_round(state, round);
+    }
+
+    /// RPX (E) round function.
+    #[inline(always)]
+    pub fn apply_ext_round(state: &mut [BaseElement; STATE_WIDTH], round: usize) {
+        Rpo64_256::add_constants(state, &Self::ARK1[round]);
+        Self::apply_ext_sbox(state);
+    }
+
+    /// RPX (M) round function.
+    #[inline(always)]
+    pub fn apply_final_round(state: &mut [BaseElement; STATE_WIDTH], round: usize) {
+        Rpo64_256::apply_mds(state);
+        Rpo64_256::add_constants(state, &Self::ARK1[round]);
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Pads the rate portion of the state with ZERO elements starting at the specified rate
+    /// index, and applies the RPX permutation.
+    ///
+    /// No other padding is needed because the first capacity element is always set to the number
+    /// of elements in the last (partial) block of the input.
+    #[inline(always)]
+    fn pad_and_apply_permutation(state: &mut [BaseElement; STATE_WIDTH], rate_idx: usize) {
+        state[RATE_RANGE.start + rate_idx..RATE_RANGE.end].fill(BaseElement::ZERO);
+        Self::apply_permutation(state);
+    }
+
+    /// Interprets the state as 4 elements in the cubic extension of the base field and raises
+    /// each of these elements to the power 7.
+    #[inline(always)]
+    fn apply_ext_sbox(state: &mut [BaseElement; STATE_WIDTH]) {
+        for chunk in state.chunks_exact_mut(3) {
+            let a: [BaseElement; 3] = [chunk[0], chunk[1], chunk[2]];
+            chunk.copy_from_slice(&Self::ext_exp7(a));
+        }
+    }
+
+    /// Computes a^7 in the cubic extension of the base field.
+    #[inline(always)]
+    fn ext_exp7(a: [BaseElement; 3]) -> [BaseElement; 3] {
+        let a2 = <BaseElement as ExtensibleField<3>>::square(a);
+        let a3 = <BaseElement as ExtensibleField<3>>::mul(a2, a);
+        let a6 = <BaseElement as ExtensibleField<3>>::square(a3);
+        <BaseElement as ExtensibleField<3>>::mul(a6, a)
+    }
+}
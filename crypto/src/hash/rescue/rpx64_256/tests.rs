@@ -0,0 +1,226 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    BaseElement, ElementDigest, ElementHasher, FieldElement, Hasher, Rpo64_256, Rpx64_256,
+    StarkField, ALPHA, NUM_ROUNDS, STATE_WIDTH,
+};
+
+use math::fields::CubeExtension;
+use rand_utils::{rand_array, rand_value};
+
+#[test]
+fn test_ext_sbox() {
+    let state: [BaseElement; STATE_WIDTH] = rand_array();
+
+    let mut expected = state;
+    for chunk in expected.chunks_exact_mut(3) {
+        let e = CubeExtension::new(chunk[0], chunk[1], chunk[2]).exp(ALPHA);
+        chunk.copy_from_slice(CubeExtension::<BaseElement>::slice_as_base_elements(&[e]));
+    }
+
+    let mut actual = state;
+    Rpx64_256::apply_ext_sbox(&mut actual);
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn apply_permutation() {
+    let mut state: [BaseElement; STATE_WIDTH] =
+        core::array::from_fn(|i| BaseElement::new(i as u64));
+
+    // compute the expected result directly from the specification: (FB) rounds are RPO rounds,
+    // (E) rounds add round constants and raise each cubic extension element of the state to the
+    // power 7, and the (M) round multiplies the state by the MDS matrix and adds round constants
+    let mut expected = state;
+    for round in 0..NUM_ROUNDS - 1 {
+        if round % 2 == 0 {
+            Rpo64_256::apply_round(&mut expected, round);
+        } else {
+            add_constants(&mut expected, &Rpx64_256::ARK1[round]);
+            for chunk in expected.chunks_exact_mut(3) {
+                let e = CubeExtension::new(chunk[0], chunk[1], chunk[2]).exp(ALPHA);
+                chunk.copy_from_slice(CubeExtension::<BaseElement>::slice_as_base_elements(&[e]));
+            }
+        }
+    }
+    let mut result = [BaseElement::ZERO; STATE_WIDTH];
+    for (r, row) in result.iter_mut().zip(Rpx64_256::MDS.iter()) {
+        *r = row
+            .iter()
+            .zip(expected.iter())
+            .fold(BaseElement::ZERO, |acc, (&m, &e)| acc + m * e);
+    }
+    add_constants(&mut result, &Rpx64_256::ARK1[NUM_ROUNDS - 1]);
+
+    Rpx64_256::apply_permutation(&mut state);
+
+    assert_eq!(result, state);
+}
+
+#[test]
+fn hash_elements_test_vectors() {
+    let elements: [BaseElement; 10] = core::array::from_fn(|i| BaseElement::new(i as u64));
+
+    for (i, expected) in EXPECTED.iter().enumerate() {
+        let digest = Rpx64_256::hash_elements(&elements[..i + 1]);
+        assert_eq!(expected, &<[BaseElement; 4]>::from(digest));
+    }
+}
+
+#[test]
+fn hash_elements_vs_merge() {
+    let elements: [BaseElement; 8] = rand_array();
+
+    let digests: [ElementDigest; 2] = [
+        ElementDigest::new(elements[..4].try_into().unwrap()),
+        ElementDigest::new(elements[4..].try_into().unwrap()),
+    ];
+
+    let m_result = Rpx64_256::merge(&digests);
+    let h_result = Rpx64_256::hash_elements(&elements);
+    assert_eq!(m_result, h_result);
+}
+
+#[test]
+fn hash_elements_vs_merge_with_int() {
+    let seed = ElementDigest::new(rand_array());
+
+    // ----- value fits into a field element ------------------------------------------------------
+    let val: BaseElement = rand_value();
+    let m_result = Rpx64_256::merge_with_int(seed, val.as_int());
+
+    let mut elements = seed.as_elements().to_vec();
+    elements.push(val);
+    let h_result = Rpx64_256::hash_elements(&elements);
+
+    assert_eq!(m_result, h_result);
+
+    // ----- value does not fit into a field element ----------------------------------------------
+    let val = BaseElement::MODULUS + 2;
+    let m_result = Rpx64_256::merge_with_int(seed, val);
+
+    let mut elements = seed.as_elements().to_vec();
+    elements.push(BaseElement::new(val));
+    elements.push(BaseElement::new(1));
+    let h_result = Rpx64_256::hash_elements(&elements);
+
+    assert_eq!(m_result, h_result);
+}
+
+#[test]
+fn hash_padding() {
+    // adding a zero bytes at the end of a byte string should result in a different hash
+    let r1 = Rpx64_256::hash(&[1_u8, 2, 3]);
+    let r2 = Rpx64_256::hash(&[1_u8, 2, 3, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with bigger inputs
+    let r1 = Rpx64_256::hash(&[1_u8, 2, 3, 4, 5, 6]);
+    let r2 = Rpx64_256::hash(&[1_u8, 2, 3, 4, 5, 6, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with input splitting over two elements
+    let r1 = Rpx64_256::hash(&[1_u8, 2, 3, 4, 5, 6, 7]);
+    let r2 = Rpx64_256::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with multiple zeros
+    let r1 = Rpx64_256::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0, 0]);
+    let r2 = Rpx64_256::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0, 0, 0, 0]);
+    assert_ne!(r1, r2);
+}
+
+#[test]
+fn hash_elements_padding() {
+    let e1: [BaseElement; 2] = rand_array();
+    let e2 = [e1[0], e1[1], BaseElement::ZERO];
+
+    let r1 = Rpx64_256::hash_elements(&e1);
+    let r2 = Rpx64_256::hash_elements(&e2);
+    assert_ne!(r1, r2);
+
+    // padding with a ONE element should also result in a different hash
+    let e3 = [e1[0], e1[1], BaseElement::ONE];
+    let r3 = Rpx64_256::hash_elements(&e3);
+    assert_ne!(r1, r3);
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn add_constants(state: &mut [BaseElement; STATE_WIDTH], ark: &[BaseElement; STATE_WIDTH]) {
+    state.iter_mut().zip(ark).for_each(|(s, &k)| *s += k);
+}
+
+// TEST VECTORS
+// ================================================================================================
+
+/// Digests of [0, 1, ..., i] for i in 0..10; these are computed using the same padding rule as
+/// the Miden reference implementation (https://github.com/0xPolygonMiden/crypto), with the
+/// permutation checked against its specification by the test above.
+const EXPECTED: [[BaseElement; 4]; 10] = [
+    [
+        BaseElement::new(15293807115397414812),
+        BaseElement::new(15290017247514670316),
+        BaseElement::new(10548590320248089637),
+        BaseElement::new(9459855167724924903),
+    ],
+    [
+        BaseElement::new(12186327779210739392),
+        BaseElement::new(12437198001472812457),
+        BaseElement::new(17431583359007807548),
+        BaseElement::new(5889070798901825636),
+    ],
+    [
+        BaseElement::new(109841543348983755),
+        BaseElement::new(17705465395673162594),
+        BaseElement::new(5228101643025463311),
+        BaseElement::new(7748133072458912307),
+    ],
+    [
+        BaseElement::new(12729520246190904536),
+        BaseElement::new(6715713369175329478),
+        BaseElement::new(13802021724186903884),
+        BaseElement::new(16589532398625893763),
+    ],
+    [
+        BaseElement::new(3191491209909564984),
+        BaseElement::new(4336372174992679659),
+        BaseElement::new(3812090377223784023),
+        BaseElement::new(16173224027531585338),
+    ],
+    [
+        BaseElement::new(6461289079179018348),
+        BaseElement::new(10449674711255412289),
+        BaseElement::new(5054891760098348434),
+        BaseElement::new(10721040246835958771),
+    ],
+    [
+        BaseElement::new(16191592956183275197),
+        BaseElement::new(746532334447080722),
+        BaseElement::new(15358793909583453268),
+        BaseElement::new(9513601171909830185),
+    ],
+    [
+        BaseElement::new(12373829276206882697),
+        BaseElement::new(10138650388065685463),
+        BaseElement::new(15520480835694974951),
+        BaseElement::new(2510219987660336228),
+    ],
+    [
+        BaseElement::new(14898769958092295192),
+        BaseElement::new(14076282783168040015),
+        BaseElement::new(8476014900264177995),
+        BaseElement::new(17336863755113979084),
+    ],
+    [
+        BaseElement::new(17237194195242105781),
+        BaseElement::new(6087397938124003113),
+        BaseElement::new(1345882193144969073),
+        BaseElement::new(14783461183116020251),
+    ],
+];
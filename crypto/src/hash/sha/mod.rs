@@ -9,6 +9,9 @@ use math::{FieldElement, StarkField};
 use sha3::Digest;
 use utils::ByteWriter;
 
+#[cfg(test)]
+mod tests;
+
 // SHA3 WITH 256-BIT OUTPUT
 // ================================================================================================
 
@@ -29,6 +32,10 @@ impl<B: StarkField> Hasher for Sha3_256<B> {
         ByteDigest(sha3::Sha3_256::digest(ByteDigest::digests_as_bytes(values)).into())
     }
 
+    fn merge_many(values: &[Self::Digest]) -> Self::Digest {
+        ByteDigest(sha3::Sha3_256::digest(ByteDigest::digests_as_bytes(values)).into())
+    }
+
     fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
         let mut data = [0; 40];
         data[..32].copy_from_slice(&seed.0);
@@ -49,9 +56,60 @@ impl<B: StarkField> ElementHasher for Sha3_256<B> {
         } else {
             // when elements' internal and canonical representations differ, we need to serialize
             // them before hashing
-            let mut hasher = ShaHasher::new();
+            let mut hasher = ShaHasher::<sha3::Sha3_256>::new();
+            hasher.write_many(elements);
+            ByteDigest(hasher.finalize().into())
+        }
+    }
+}
+
+// SHA3 WITH 512-BIT OUTPUT
+// ================================================================================================
+
+/// Implementation of the [Hasher](super::Hasher) trait for SHA3 hash function with 512-bit
+/// output.
+pub struct Sha3_512<B: StarkField>(PhantomData<B>);
+
+impl<B: StarkField> Hasher for Sha3_512<B> {
+    type Digest = ByteDigest<64>;
+
+    const COLLISION_RESISTANCE: u32 = 256;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        ByteDigest(sha3::Sha3_512::digest(bytes).into())
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        ByteDigest(sha3::Sha3_512::digest(ByteDigest::digests_as_bytes(values)).into())
+    }
+
+    fn merge_many(values: &[Self::Digest]) -> Self::Digest {
+        ByteDigest(sha3::Sha3_512::digest(ByteDigest::digests_as_bytes(values)).into())
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        let mut data = [0; 72];
+        data[..64].copy_from_slice(&seed.0);
+        data[64..].copy_from_slice(&value.to_le_bytes());
+        ByteDigest(sha3::Sha3_512::digest(data).into())
+    }
+}
+
+impl<B: StarkField> ElementHasher for Sha3_512<B> {
+    type BaseField = B;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        if B::IS_CANONICAL {
+            // when element's internal and canonical representations are the same, we can hash
+            // element bytes directly
+            let bytes = E::elements_as_bytes(elements);
+            ByteDigest(sha3::Sha3_512::digest(bytes).into())
+        } else {
+            // when elements' internal and canonical representations differ, we need to serialize
+            // them before hashing
+            let mut hasher = ShaHasher::<sha3::Sha3_512>::new();
             hasher.write_many(elements);
-            ByteDigest(hasher.finalize())
+            ByteDigest(hasher.finalize().into())
         }
     }
 }
@@ -60,19 +118,19 @@ impl<B: StarkField> ElementHasher for Sha3_256<B> {
 // ================================================================================================
 
 /// Wrapper around SHA3 hasher to implement [ByteWriter] trait for it.
-struct ShaHasher(sha3::Sha3_256);
+struct ShaHasher<D: Digest>(D);
 
-impl ShaHasher {
+impl<D: Digest> ShaHasher<D> {
     pub fn new() -> Self {
-        Self(sha3::Sha3_256::new())
+        Self(D::new())
     }
 
-    pub fn finalize(self) -> [u8; 32] {
-        self.0.finalize().into()
+    pub fn finalize(self) -> sha3::digest::Output<D> {
+        self.0.finalize()
     }
 }
 
-impl ByteWriter for ShaHasher {
+impl<D: Digest> ByteWriter for ShaHasher<D> {
     fn write_u8(&mut self, value: u8) {
         self.0.update([value]);
     }
@@ -0,0 +1,68 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ElementHasher, Hasher, Sha3_256, Sha3_512};
+use crate::Digest;
+use alloc::string::String;
+use math::fields::{f128, f62};
+use rand_utils::rand_array;
+use utils::Serializable;
+
+#[test]
+fn sha3_512_test_vectors() {
+    // test vectors from FIPS 202
+    let expected = "a69f73cca23a9ac5c8b567dc185a756e97c982164fe25859e0d1dcc1475c80a6\
+        15b2123af1f5f94c11e3e9402c3ac558f500199d95b6d3e301758586281dcd26";
+    assert_eq!(expected, to_hex(&Sha3_512::<f62::BaseElement>::hash(&[]).as_bytes()));
+
+    let expected = "b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e\
+        10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0";
+    assert_eq!(expected, to_hex(&Sha3_512::<f62::BaseElement>::hash(b"abc").as_bytes()));
+}
+
+#[test]
+fn sha3_hash_elements() {
+    // elements with non-canonical internal representation are serialized before hashing
+    let elements: [f62::BaseElement; 5] = rand_array();
+    let expected = Sha3_512::<f62::BaseElement>::hash(&elements.to_bytes());
+    assert_eq!(expected, Sha3_512::hash_elements(&elements));
+
+    let expected = Sha3_256::<f62::BaseElement>::hash(&elements.to_bytes());
+    assert_eq!(expected, Sha3_256::hash_elements(&elements));
+
+    // elements with canonical internal representation are hashed directly
+    let elements: [f128::BaseElement; 5] = rand_array();
+    let expected = Sha3_512::<f128::BaseElement>::hash(&elements.to_bytes());
+    assert_eq!(expected, Sha3_512::hash_elements(&elements));
+}
+
+#[test]
+fn sha3_512_merge() {
+    let d1 = Sha3_512::<f62::BaseElement>::hash(&[1, 2, 3]);
+    let d2 = Sha3_512::<f62::BaseElement>::hash(&[4, 5, 6]);
+
+    let mut data = [0; 128];
+    data[..64].copy_from_slice(&d1.as_bytes());
+    data[64..].copy_from_slice(&d2.as_bytes());
+    assert_eq!(
+        Sha3_512::<f62::BaseElement>::hash(&data),
+        Sha3_512::<f62::BaseElement>::merge(&[d1, d2])
+    );
+
+    let mut data = [0; 72];
+    data[..64].copy_from_slice(&d1.as_bytes());
+    data[64..].copy_from_slice(&7_u64.to_le_bytes());
+    assert_eq!(
+        Sha3_512::<f62::BaseElement>::hash(&data),
+        Sha3_512::<f62::BaseElement>::merge_with_int(d1, 7)
+    );
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| alloc::format!("{b:02x}")).collect()
+}
@@ -0,0 +1,252 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    BaseElement, ElementDigest, ElementHasher, FieldElement, Hasher, StarkField, Tip5, ALPHA,
+    LOOKUP_TABLE, MDS_FIRST_COLUMN, NUM_SPLIT_AND_LOOKUP, ROUND_CONSTANTS, STATE_WIDTH,
+};
+use alloc::{vec, vec::Vec};
+use proptest::prelude::*;
+
+use rand_utils::{rand_array, rand_value};
+
+#[test]
+fn test_lookup_table() {
+    // the lookup table is the offset Fermat cube map x -> (x + 1)^3 - 1 over F_257, which fixes
+    // 0 and 255, and thus, maps bytes to bytes
+    for (i, &b) in LOOKUP_TABLE.iter().enumerate() {
+        assert_eq!(offset_fermat_cube_map(i as u8), b);
+    }
+    assert_eq!(0, LOOKUP_TABLE[0]);
+    assert_eq!(255, LOOKUP_TABLE[255]);
+}
+
+#[test]
+fn test_split_and_lookup() {
+    // ZERO and ONE are fixed points of the split-and-lookup map because their Montgomery
+    // representations consist of bytes 0 and 255 only
+    assert_eq!(BaseElement::ZERO, Tip5::split_and_lookup(BaseElement::ZERO));
+    assert_eq!(BaseElement::ONE, Tip5::split_and_lookup(BaseElement::ONE));
+
+    let mut elements = vec![BaseElement::new(12345), -BaseElement::ONE];
+    elements.extend_from_slice(&rand_array::<BaseElement, 16>());
+    for element in elements {
+        assert_eq!(split_and_lookup_naive(element), Tip5::split_and_lookup(element));
+    }
+}
+
+#[test]
+fn test_sbox() {
+    let state: [BaseElement; STATE_WIDTH] = rand_array();
+
+    let mut expected = state;
+    expected
+        .iter_mut()
+        .take(NUM_SPLIT_AND_LOOKUP)
+        .for_each(|v| *v = Tip5::split_and_lookup(*v));
+    expected.iter_mut().skip(NUM_SPLIT_AND_LOOKUP).for_each(|v| *v = v.exp(ALPHA));
+
+    let mut actual = state;
+    Tip5::apply_sbox(&mut actual);
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn apply_permutation() {
+    let mut state: [BaseElement; STATE_WIDTH] =
+        core::array::from_fn(|i| BaseElement::new(i as u64));
+
+    // compute the expected result directly from the specification: every round applies the
+    // split-and-lookup map to the first 4 elements and the power map to the remaining elements,
+    // multiplies the state by the circulant MDS matrix, and adds round constants
+    let mut expected = state;
+    for round_constants in ROUND_CONSTANTS.iter() {
+        for (i, v) in expected.iter_mut().enumerate() {
+            *v = if i < NUM_SPLIT_AND_LOOKUP {
+                split_and_lookup_naive(*v)
+            } else {
+                v.exp(ALPHA)
+            };
+        }
+        apply_mds_naive(&mut expected);
+        expected.iter_mut().zip(round_constants).for_each(|(v, &k)| *v += k);
+    }
+
+    Tip5::apply_permutation(&mut state);
+
+    assert_eq!(expected, state);
+}
+
+#[test]
+fn merge_test_vector() {
+    // this test vector comes from the reference implementation (hash10_test_vectors() test in
+    // https://github.com/Neptune-Crypto/twenty-first): starting with 10 zero elements,
+    // the preimage is repeatedly overwritten with the digest of itself at increasing offsets
+    let mut preimage = [BaseElement::ZERO; 10];
+    for i in 0..6 {
+        let digest = merge_elements(&preimage);
+        preimage[i..i + 5].copy_from_slice(digest.as_elements());
+    }
+    let digest = merge_elements(&preimage);
+
+    let expected = [
+        BaseElement::new(10869784347448351760),
+        BaseElement::new(1853783032222938415),
+        BaseElement::new(6856460589287344822),
+        BaseElement::new(17178399545409290325),
+        BaseElement::new(7650660984651717733),
+    ];
+    assert_eq!(expected, <[BaseElement; 5]>::from(digest));
+}
+
+#[test]
+fn hash_elements_test_vectors() {
+    // this test vector comes from the reference implementation (hash_varlen_test_vectors() test
+    // in https://github.com/Neptune-Crypto/twenty-first): it is the sum of the digests of
+    // [0, 1, ..., i - 1] for i in 0..20
+    let mut digest_sum = [BaseElement::ZERO; 5];
+    for i in 0..20 {
+        let elements = (0..i).map(BaseElement::new).collect::<Vec<_>>();
+        let digest = <[BaseElement; 5]>::from(Tip5::hash_elements(&elements));
+        digest_sum.iter_mut().zip(digest).for_each(|(s, d)| *s += d);
+    }
+
+    let expected = [
+        BaseElement::new(7610004073009036015),
+        BaseElement::new(5725198067541094245),
+        BaseElement::new(4721320565792709122),
+        BaseElement::new(1732504843634706218),
+        BaseElement::new(259800783350288362),
+    ];
+    assert_eq!(expected, digest_sum);
+}
+
+#[test]
+fn hash_elements_vs_merge_with_int() {
+    let seed = ElementDigest::new(rand_array());
+
+    // ----- value fits into a field element ------------------------------------------------------
+    let val: BaseElement = rand_value();
+    let m_result = Tip5::merge_with_int(seed, val.as_int());
+
+    let mut elements = seed.as_elements().to_vec();
+    elements.push(val);
+    let h_result = Tip5::hash_elements(&elements);
+
+    assert_eq!(m_result, h_result);
+
+    // ----- value does not fit into a field element ----------------------------------------------
+    let val = BaseElement::MODULUS + 2;
+    let m_result = Tip5::merge_with_int(seed, val);
+
+    let mut elements = seed.as_elements().to_vec();
+    elements.push(BaseElement::new(val));
+    elements.push(BaseElement::new(1));
+    let h_result = Tip5::hash_elements(&elements);
+
+    assert_eq!(m_result, h_result);
+}
+
+#[test]
+fn hash_elements_vs_merge() {
+    // merging is done in the fixed-length domain, and thus, the result must differ from hashing
+    // the same elements as a variable-length input
+    let elements: [BaseElement; 10] = rand_array();
+    let digests = [
+        ElementDigest::new(elements[..5].try_into().unwrap()),
+        ElementDigest::new(elements[5..].try_into().unwrap()),
+    ];
+
+    assert_ne!(Tip5::merge(&digests), Tip5::hash_elements(&elements));
+    assert_eq!(Tip5::merge_many(&digests), Tip5::hash_elements(&elements));
+}
+
+#[test]
+fn hash_padding() {
+    // adding a zero bytes at the end of a byte string should result in a different hash
+    let r1 = Tip5::hash(&[1_u8, 2, 3]);
+    let r2 = Tip5::hash(&[1_u8, 2, 3, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with bigger inputs
+    let r1 = Tip5::hash(&[1_u8, 2, 3, 4, 5, 6]);
+    let r2 = Tip5::hash(&[1_u8, 2, 3, 4, 5, 6, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with input splitting over two elements
+    let r1 = Tip5::hash(&[1_u8, 2, 3, 4, 5, 6, 7]);
+    let r2 = Tip5::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with multiple zeros
+    let r1 = Tip5::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0, 0]);
+    let r2 = Tip5::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0, 0, 0, 0]);
+    assert_ne!(r1, r2);
+}
+
+#[test]
+fn hash_elements_padding() {
+    let e1: [BaseElement; 2] = rand_array();
+    let e2 = [e1[0], e1[1], BaseElement::ZERO];
+
+    let r1 = Tip5::hash_elements(&e1);
+    let r2 = Tip5::hash_elements(&e2);
+    assert_ne!(r1, r2);
+
+    // padding with a ONE element should also result in a different hash
+    let e3 = [e1[0], e1[1], BaseElement::ONE];
+    let r3 = Tip5::hash_elements(&e3);
+    assert_ne!(r1, r3);
+}
+
+proptest! {
+    #[test]
+    fn mds_proptest(a in any::<[u64; STATE_WIDTH]>()) {
+        let mut v1: [BaseElement; STATE_WIDTH] = a.map(BaseElement::new);
+        let mut v2 = v1;
+
+        apply_mds_naive(&mut v1);
+        Tip5::apply_mds(&mut v2);
+
+        prop_assert_eq!(v1, v2);
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn merge_elements(elements: &[BaseElement; 10]) -> ElementDigest {
+    let digests = [
+        ElementDigest::new(elements[..5].try_into().unwrap()),
+        ElementDigest::new(elements[5..].try_into().unwrap()),
+    ];
+    Tip5::merge(&digests)
+}
+
+/// Computes x -> (x + 1)^3 - 1 over F_257.
+fn offset_fermat_cube_map(x: u8) -> u8 {
+    let x = x as u32 + 1;
+    ((x * x * x) % 257 - 1) as u8
+}
+
+/// Applies the split-and-lookup map to the specified element using Montgomery arithmetic over
+/// integers rather than the internal representation of the field element.
+fn split_and_lookup_naive(element: BaseElement) -> BaseElement {
+    // 2^64 mod p, i.e., the Montgomery radix
+    let r = BaseElement::new(u32::MAX as u64);
+    let bytes = (element * r).as_int().to_le_bytes().map(offset_fermat_cube_map);
+    BaseElement::new(u64::from_le_bytes(bytes)) * r.inv()
+}
+
+fn apply_mds_naive(state: &mut [BaseElement; STATE_WIDTH]) {
+    let mut result = [BaseElement::ZERO; STATE_WIDTH];
+    for (i, r) in result.iter_mut().enumerate() {
+        for (j, &s) in state.iter().enumerate() {
+            *r += BaseElement::new(MDS_FIRST_COLUMN[(STATE_WIDTH + i - j) % STATE_WIDTH]) * s;
+        }
+    }
+    *state = result;
+}
@@ -21,27 +21,47 @@
 #[macro_use]
 extern crate alloc;
 
+#[cfg(feature = "std")]
+extern crate std;
+
 mod hash;
-pub use hash::{Digest, ElementHasher, Hasher};
+pub use hash::{
+    AlgebraicSponge, Digest, DomainSeparated, ElementHasher, HashDomain, Hasher, Personalization,
+};
 pub mod hashers {
     //! Contains implementations of currently supported hash functions.
 
     pub use super::hash::Blake3_192;
     pub use super::hash::Blake3_256;
+    pub use super::hash::Blake3_384;
+    pub use super::hash::Keccak256;
+    pub use super::hash::Poseidon2_64_256;
     pub use super::hash::Rp62_248;
     pub use super::hash::Rp64_256;
     pub use super::hash::RpJive64_256;
+    pub use super::hash::Rpo64_256;
+    pub use super::hash::Rpx64_256;
     pub use super::hash::Sha3_256;
+    pub use super::hash::Sha3_512;
+    pub use super::hash::Tip5;
 }
 
+mod commitment;
+pub use commitment::{VectorCommitment, VectorCommitmentOptions};
+
 mod merkle;
-pub use merkle::{build_merkle_nodes, BatchMerkleProof, MerkleTree};
+pub use merkle::{
+    build_merkle_nodes, build_merkle_nodes_with_arity, BatchMerkleProof, MerkleTree, SmtKey,
+    SparseMerkleProof, SparseMerkleTree,
+};
 
 #[cfg(feature = "concurrent")]
 pub use merkle::concurrent;
 
 mod random;
-pub use random::{DefaultRandomCoin, RandomCoin};
+#[cfg(feature = "std")]
+pub use random::{CoinEvent, CoinTranscript, RecordingRandomCoin};
+pub use random::{DefaultRandomCoin, RandomCoin, SpongeRandomCoin};
 
 mod errors;
 pub use errors::{MerkleTreeError, RandomCoinError};
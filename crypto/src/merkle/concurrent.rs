@@ -3,7 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::Hasher;
+use crate::{HashDomain, Hasher};
 use alloc::vec::Vec;
 use core::slice;
 use utils::{iterators::*, rayon};
@@ -13,6 +13,10 @@ use utils::{iterators::*, rayon};
 
 pub const MIN_CONCURRENT_LEAVES: usize = 1024;
 
+/// Number of nodes merged together by a single call to [Hasher::merge_batch_in_domain()] when
+/// building the first row of internal nodes.
+const MERGE_BATCH_SIZE: usize = 64;
+
 // PUBLIC FUNCTIONS
 // ================================================================================================
 
@@ -30,9 +34,9 @@ pub fn build_merkle_nodes<H: Hasher>(leaves: &[H::Digest]) -> Vec<H::Digest> {
     // build first row of internal nodes (parents of leaves)
     let two_leaves = unsafe { slice::from_raw_parts(leaves.as_ptr() as *const [H::Digest; 2], n) };
     nodes[n..]
-        .par_iter_mut()
-        .zip(two_leaves.par_iter())
-        .for_each(|(target, source)| *target = H::merge(source));
+        .par_chunks_mut(MERGE_BATCH_SIZE)
+        .zip(two_leaves.par_chunks(MERGE_BATCH_SIZE))
+        .for_each(|(target, source)| H::merge_batch_in_domain(source, HashDomain::Node, target));
 
     // calculate all other tree nodes, we can't use regular iterators  here because
     // access patterns are rather complicated - so, we use regular threads instead
@@ -52,9 +56,12 @@ pub fn build_merkle_nodes<H: Hasher>(leaves: &[H::Digest]) -> Vec<H::Digest> {
                 let mut batch_size = batch_size / 2;
                 let mut start_idx = n / 2 + batch_size * i;
                 while start_idx >= num_subtrees {
-                    for k in (start_idx..(start_idx + batch_size)).rev() {
-                        nodes[k] = H::merge(&two_nodes[k]);
-                    }
+                    let range = start_idx..(start_idx + batch_size);
+                    H::merge_batch_in_domain(
+                        &two_nodes[range.clone()],
+                        HashDomain::Node,
+                        &mut nodes[range],
+                    );
                     start_idx /= 2;
                     batch_size /= 2;
                 }
@@ -64,7 +71,40 @@ pub fn build_merkle_nodes<H: Hasher>(leaves: &[H::Digest]) -> Vec<H::Digest> {
 
     // finish the tip of the tree
     for i in (1..num_subtrees).rev() {
-        nodes[i] = H::merge(&two_nodes[i]);
+        nodes[i] = H::merge_in_domain(&two_nodes[i], HashDomain::Node);
+    }
+
+    nodes
+}
+
+/// Builds all internal nodes of the Merkle tree of the specified `arity` using all available
+/// threads and stores the results in a single vector laid out in the same way as the vector
+/// returned by [build_merkle_nodes()].
+pub fn build_merkle_nodes_with_arity<H: Hasher>(
+    leaves: &[H::Digest],
+    arity: usize,
+) -> Vec<H::Digest> {
+    let n = leaves.len();
+    let steps = super::get_level_steps(n.ilog2() as usize, arity);
+    let mut nodes = vec![H::Digest::default(); n];
+
+    // build first row of internal nodes (parents of leaves)
+    let mut level_start = n >> steps[0];
+    nodes[level_start..(2 * level_start)]
+        .par_iter_mut()
+        .zip(leaves.par_chunks(1 << steps[0]))
+        .for_each(|(target, children)| *target = super::merge_nodes::<H>(children));
+
+    // calculate all other tree nodes level by level; nodes within a level are computed in
+    // parallel
+    for &step in steps.iter().skip(1) {
+        let (parents, children) = nodes.split_at_mut(level_start);
+        let parent_start = level_start >> step;
+        parents[parent_start..(2 * parent_start)]
+            .par_iter_mut()
+            .zip(children[..level_start].par_chunks(1 << step))
+            .for_each(|(target, children)| *target = super::merge_nodes::<H>(children));
+        level_start = parent_start;
     }
 
     nodes
@@ -88,5 +128,15 @@ mod tests {
             let concurrent = super::build_merkle_nodes::<Sha3_256<BaseElement>>(&leaves);
             assert_eq!(concurrent, sequential);
         }
+
+        #[test]
+        fn build_merkle_nodes_with_arity_concurrent(ref data in vec(any::<[u8; 32]>(), 2048..2049).no_shrink()) {
+            let leaves = ByteDigest::bytes_as_digests(data).to_vec();
+            for arity in [4, 8, 16] {
+                let sequential = super::super::build_merkle_nodes_with_arity::<Sha3_256<BaseElement>>(&leaves, arity);
+                let concurrent = super::build_merkle_nodes_with_arity::<Sha3_256<BaseElement>>(&leaves, arity);
+                assert_eq!(concurrent, sequential);
+            }
+        }
     }
 }
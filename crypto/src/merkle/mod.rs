@@ -3,22 +3,36 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::{errors::MerkleTreeError, hash::Hasher};
+use crate::{
+    errors::MerkleTreeError,
+    hash::{HashDomain, Hasher},
+    VectorCommitment, VectorCommitmentOptions,
+};
 use alloc::{
     collections::{BTreeMap, BTreeSet},
     vec::Vec,
 };
 use core::slice;
+use utils::{ByteReader, DeserializationError};
 
 mod proofs;
 pub use proofs::BatchMerkleProof;
 
+mod sparse;
+pub use sparse::{SmtKey, SparseMerkleProof, SparseMerkleTree};
+
 #[cfg(feature = "concurrent")]
 pub mod concurrent;
 
 #[cfg(test)]
 mod tests;
 
+// CONSTANTS
+// ================================================================================================
+
+/// Maximum arity of a Merkle tree.
+const MAX_ARITY: usize = 16;
+
 // TYPES AND INTERFACES
 // ================================================================================================
 
@@ -60,6 +74,20 @@ mod tests;
 /// To verify proofs, [MerkleTree::verify()] and [MerkleTree::verify_batch()] functions can be
 /// used respectively.
 ///
+/// Instead of committing to a single root, a tree can also commit to a *cap*: the 2^k nodes at
+/// height k of the tree (the root is thus a cap of height 0). Such a tree can be built using
+/// [MerkleTree::with_cap_height()] function. Batch proofs generated by these trees terminate at
+/// the cap rather than at the root, and thus, are shorter by k nodes per path; they can be
+/// verified using [MerkleTree::verify_batch_with_cap()] function.
+///
+/// By default, Merkle trees are binary. A tree with a higher arity (e.g., 4 or 8) can be built
+/// using [MerkleTree::with_arity()] function. Such trees are shallower, and thus, Merkle paths
+/// through them require fewer hash function invocations to verify, which is beneficial for
+/// algebraic hash functions (e.g., when proofs are verified recursively). Internally, nodes of a
+/// tree of arity 2^k are laid out in the same way as the nodes of a binary tree, but only every
+/// k-th level of the binary tree is populated; the depth and the cap height of the tree are still
+/// measured in binary levels.
+///
 /// # Examples
 /// ```
 /// # use winter_crypto::{MerkleTree, Hasher, hashers::Blake3_256};
@@ -90,6 +118,8 @@ mod tests;
 pub struct MerkleTree<H: Hasher> {
     nodes: Vec<H::Digest>,
     leaves: Vec<H::Digest>,
+    cap_height: usize,
+    arity: usize,
 }
 
 // MERKLE TREE IMPLEMENTATION
@@ -109,24 +139,92 @@ impl<H: Hasher> MerkleTree<H> {
     /// * Fewer than two leaves were provided.
     /// * Number of leaves is not a power of two.
     pub fn new(leaves: Vec<H::Digest>) -> Result<Self, MerkleTreeError> {
+        Self::with_arity(leaves, 2, 0)
+    }
+
+    /// Returns new Merkle tree built from the provided leaves which commits to the cap of the
+    /// tree at the specified `cap_height`.
+    ///
+    /// A cap at height `k` consists of 2^k nodes, and batch proofs generated by such a tree
+    /// include only the nodes below the cap. A cap of height zero is the root of the tree.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * Fewer than two leaves were provided.
+    /// * Number of leaves is not a power of two.
+    /// * `cap_height` is greater than or equal to the depth of the tree.
+    pub fn with_cap_height(
+        leaves: Vec<H::Digest>,
+        cap_height: usize,
+    ) -> Result<Self, MerkleTreeError> {
+        Self::with_arity(leaves, 2, cap_height)
+    }
+
+    /// Returns new Merkle tree of the specified `arity` built from the provided leaves which
+    /// commits to the cap of the tree at the specified `cap_height`.
+    ///
+    /// The cap height is measured in binary levels. Since a tree of arity 2^k populates only
+    /// every k-th binary level (counting from the leaves), the cap height is rounded down to the
+    /// nearest populated level; the actual cap height can be retrieved via
+    /// [MerkleTree::cap_height()] method.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * Fewer than two leaves were provided.
+    /// * Number of leaves is not a power of two.
+    /// * `arity` is not a power of two between 2 and 16.
+    /// * `cap_height` is greater than or equal to the depth of the tree.
+    pub fn with_arity(
+        leaves: Vec<H::Digest>,
+        arity: usize,
+        cap_height: usize,
+    ) -> Result<Self, MerkleTreeError> {
         if leaves.len() < 2 {
             return Err(MerkleTreeError::TooFewLeaves(2, leaves.len()));
         }
         if !leaves.len().is_power_of_two() {
             return Err(MerkleTreeError::NumberOfLeavesNotPowerOfTwo(leaves.len()));
         }
+        if !is_valid_arity(arity) {
+            return Err(MerkleTreeError::InvalidArity(arity));
+        }
+        let depth = leaves.len().ilog2() as usize;
+        if cap_height >= depth {
+            return Err(MerkleTreeError::InvalidCapHeight(depth, cap_height));
+        }
+
+        let nodes = if arity == 2 {
+            #[cfg(not(feature = "concurrent"))]
+            let nodes = build_merkle_nodes::<H>(&leaves);
 
-        #[cfg(not(feature = "concurrent"))]
-        let nodes = build_merkle_nodes::<H>(&leaves);
+            #[cfg(feature = "concurrent")]
+            let nodes = if leaves.len() <= concurrent::MIN_CONCURRENT_LEAVES {
+                build_merkle_nodes::<H>(&leaves)
+            } else {
+                concurrent::build_merkle_nodes::<H>(&leaves)
+            };
 
-        #[cfg(feature = "concurrent")]
-        let nodes = if leaves.len() <= concurrent::MIN_CONCURRENT_LEAVES {
-            build_merkle_nodes::<H>(&leaves)
+            nodes
         } else {
-            concurrent::build_merkle_nodes::<H>(&leaves)
+            #[cfg(not(feature = "concurrent"))]
+            let nodes = build_merkle_nodes_with_arity::<H>(&leaves, arity);
+
+            #[cfg(feature = "concurrent")]
+            let nodes = if leaves.len() <= concurrent::MIN_CONCURRENT_LEAVES {
+                build_merkle_nodes_with_arity::<H>(&leaves, arity)
+            } else {
+                concurrent::build_merkle_nodes_with_arity::<H>(&leaves, arity)
+            };
+
+            nodes
         };
 
-        Ok(MerkleTree { nodes, leaves })
+        Ok(MerkleTree {
+            nodes,
+            leaves,
+            cap_height: round_cap_height(depth, arity, cap_height),
+            arity,
+        })
     }
 
     /// Forms a MerkleTree from a list of nodes and leaves.
@@ -151,7 +249,12 @@ impl<H: Hasher> MerkleTree<H> {
             return Err(MerkleTreeError::NumberOfLeavesNotPowerOfTwo(leaves.len()));
         }
         assert_eq!(nodes.len(), leaves.len());
-        Ok(MerkleTree { nodes, leaves })
+        Ok(MerkleTree {
+            nodes,
+            leaves,
+            cap_height: 0,
+            arity: 2,
+        })
     }
 
     // PUBLIC ACCESSORS
@@ -162,10 +265,29 @@ impl<H: Hasher> MerkleTree<H> {
         &self.nodes[1]
     }
 
+    /// Returns the cap of the tree.
+    ///
+    /// The cap consists of 2^k nodes at height k of the tree, where k is the cap height of this
+    /// tree. For trees with the cap height of zero, the cap consists of a single root node.
+    pub fn cap(&self) -> &[H::Digest] {
+        &self.nodes[(1 << self.cap_height)..(2 << self.cap_height)]
+    }
+
+    /// Returns the height of the cap to which this tree commits.
+    pub fn cap_height(&self) -> usize {
+        self.cap_height
+    }
+
+    /// Returns the arity of the tree.
+    pub fn arity(&self) -> usize {
+        self.arity
+    }
+
     /// Returns depth of the tree.
     ///
     /// The depth of a tree is zero-based. Thus, a tree with two leaves has depth 1, a tree with
-    /// four leaves has depth 2 etc.
+    /// four leaves has depth 2 etc. For trees with arity greater than two, the depth is still
+    /// measured in binary levels.
     pub fn depth(&self) -> usize {
         self.leaves.len().ilog2() as usize
     }
@@ -180,7 +302,9 @@ impl<H: Hasher> MerkleTree<H> {
 
     /// Returns a Merkle path to a leaf at the specified `index`.
     ///
-    /// The leaf itself will be the first element in the path.
+    /// The leaf itself will be the first element in the path. For trees with arity greater than
+    /// two, the path contains all siblings of a node at each level in ascending order of their
+    /// positions.
     ///
     /// # Errors
     /// Returns an error if the specified index is greater than or equal to the number of leaves
@@ -190,12 +314,23 @@ impl<H: Hasher> MerkleTree<H> {
             return Err(MerkleTreeError::LeafIndexOutOfBounds(self.leaves.len(), index));
         }
 
-        let mut proof = vec![self.leaves[index], self.leaves[index ^ 1]];
-
-        let mut index = (index + self.nodes.len()) >> 1;
-        while index > 1 {
-            proof.push(self.nodes[index ^ 1]);
-            index >>= 1;
+        let steps = get_level_steps(self.depth(), self.arity);
+
+        // add the leaf and its siblings to the proof
+        let start = index & !((1 << steps[0]) - 1);
+        let mut proof = vec![self.leaves[index]];
+        proof.extend(
+            (start..start + (1 << steps[0])).filter(|&i| i != index).map(|i| self.leaves[i]),
+        );
+
+        // add siblings of all other nodes on the path to the root
+        let mut index = (index + self.leaves.len()) >> steps[0];
+        for &step in steps.iter().skip(1) {
+            let parent_index = index >> step;
+            proof.extend(
+                get_children(parent_index, step).filter(|&i| i != index).map(|i| self.nodes[i]),
+            );
+            index = parent_index;
         }
 
         Ok(proof)
@@ -203,6 +338,9 @@ impl<H: Hasher> MerkleTree<H> {
 
     /// Computes Merkle paths for the provided indexes and compresses the paths into a single proof.
     ///
+    /// The paths terminate at the cap of the tree; thus, for trees with a non-zero cap height, the
+    /// proof can be verified only against the cap (and not against the root) of the tree.
+    ///
     /// # Errors
     /// Returns an error if:
     /// * No indexes were provided (i.e., `indexes` is an empty slice).
@@ -218,8 +356,10 @@ impl<H: Hasher> MerkleTree<H> {
             return Err(MerkleTreeError::TooManyLeafIndexes(proofs::MAX_PATHS, indexes.len()));
         }
 
+        let steps = get_cap_steps(self.depth(), self.arity, self.cap_height)
+            .expect("cap height must be at a populated level of the tree");
         let index_map = map_indexes(indexes, self.depth())?;
-        let indexes = normalize_indexes(indexes);
+        let indexes = normalize_indexes(indexes, steps[0]);
         let mut leaves = vec![H::Digest::default(); index_map.len()];
         let mut nodes: Vec<Vec<H::Digest>> = Vec::with_capacity(indexes.len());
 
@@ -227,7 +367,7 @@ impl<H: Hasher> MerkleTree<H> {
         let n = self.leaves.len();
         let mut next_indexes: Vec<usize> = Vec::new();
         for index in indexes {
-            let missing: Vec<H::Digest> = (index..index + 2)
+            let missing: Vec<H::Digest> = (index..index + (1 << steps[0]))
                 .flat_map(|i| {
                     let v = self.leaves[i];
                     if let Some(idx) = index_map.get(&i) {
@@ -240,27 +380,30 @@ impl<H: Hasher> MerkleTree<H> {
                 .collect();
             nodes.push(missing);
 
-            next_indexes.push((index + n) >> 1);
+            next_indexes.push((index + n) >> steps[0]);
         }
 
         // add required internal nodes to the proof, skipping redundancies
-        for _ in 1..self.depth() {
-            let indexes = next_indexes.clone();
-            next_indexes.truncate(0);
+        for &step in steps.iter().skip(1) {
+            let indexes = core::mem::take(&mut next_indexes);
 
             let mut i = 0;
             while i < indexes.len() {
-                let sibling_index = indexes[i] ^ 1;
-                if i + 1 < indexes.len() && indexes[i + 1] == sibling_index {
-                    i += 1;
-                } else {
-                    nodes[i].push(self.nodes[sibling_index]);
+                // nodes with the same parent are next to each other in the list of indexes
+                let parent_index = indexes[i] >> step;
+                let group_end = get_group_end(&indexes, i, step);
+
+                // add siblings which are not in the list of indexes to the proof
+                for child_index in get_children(parent_index, step) {
+                    if !indexes[i..group_end].contains(&child_index) {
+                        nodes[i].push(self.nodes[child_index]);
+                    }
                 }
 
                 // add parent index to the set of next indexes
-                next_indexes.push(sibling_index >> 1);
+                next_indexes.push(parent_index);
 
-                i += 1;
+                i = group_end;
             }
         }
 
@@ -268,6 +411,7 @@ impl<H: Hasher> MerkleTree<H> {
             leaves,
             nodes,
             depth: self.depth() as u8,
+            arity: self.arity as u8,
         })
     }
 
@@ -276,6 +420,8 @@ impl<H: Hasher> MerkleTree<H> {
 
     /// Checks whether the `proof` for the specified `index` is valid.
     ///
+    /// This method supports only proofs generated by binary trees.
+    ///
     /// # Errors
     /// Returns an error if the specified `proof` (which is a Merkle path) does not resolve to the
     /// specified `root`.
@@ -285,14 +431,14 @@ impl<H: Hasher> MerkleTree<H> {
         proof: &[H::Digest],
     ) -> Result<(), MerkleTreeError> {
         let r = index & 1;
-        let mut v = H::merge(&[proof[r], proof[1 - r]]);
+        let mut v = H::merge_in_domain(&[proof[r], proof[1 - r]], HashDomain::Node);
 
         let mut index = (index + 2usize.pow((proof.len() - 1) as u32)) >> 1;
         for &p in proof.iter().skip(2) {
             v = if index & 1 == 0 {
-                H::merge(&[v, p])
+                H::merge_in_domain(&[v, p], HashDomain::Node)
             } else {
-                H::merge(&[p, v])
+                H::merge_in_domain(&[p, v], HashDomain::Node)
             };
             index >>= 1;
         }
@@ -323,6 +469,130 @@ impl<H: Hasher> MerkleTree<H> {
         }
         Ok(())
     }
+
+    /// Checks whether the batch proof contains Merkle paths from the specified `indexes` to the
+    /// specified tree `cap`.
+    ///
+    /// The height of the cap is inferred from the number of nodes in it.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * Number of nodes in the `cap` is not a power of two.
+    /// * No indexes were provided (i.e., `indexes` is an empty slice).
+    /// * Number of provided indexes is greater than 255.
+    /// * Height of the cap is greater than or equal to the depth of the tree from which the
+    ///   batch proof was generated.
+    /// * Any of the specified `indexes` is greater than or equal to the number of leaves in the
+    ///   tree from which the batch proof was generated.
+    /// * List of indexes contains duplicates.
+    /// * Any of the paths in the batch proof does not resolve to the specified `cap`.
+    pub fn verify_batch_with_cap(
+        cap: &[H::Digest],
+        indexes: &[usize],
+        proof: &BatchMerkleProof<H>,
+    ) -> Result<(), MerkleTreeError> {
+        if !cap.len().is_power_of_two() {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+        let cap_height = cap.len().ilog2() as usize;
+        for (position, node) in proof.get_cap_nodes(indexes, cap_height)? {
+            if cap[position] != node {
+                return Err(MerkleTreeError::InvalidProof);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the root of a binary Merkle tree with the specified `cap`.
+    ///
+    /// The returned digest is a binding commitment to the cap. For binary trees it is equal to the
+    /// root of the tree from which the cap was taken, but for trees of higher arity this is not
+    /// necessarily the case.
+    ///
+    /// # Panics
+    /// Panics if the number of nodes in the `cap` is not a power of two.
+    pub fn get_root_from_cap(cap: &[H::Digest]) -> H::Digest {
+        assert!(cap.len().is_power_of_two(), "number of cap nodes must be a power of two");
+        if cap.len() == 1 {
+            cap[0]
+        } else {
+            build_merkle_nodes::<H>(cap)[1]
+        }
+    }
+}
+
+// VECTOR COMMITMENT IMPLEMENTATION
+// ================================================================================================
+
+impl<H: Hasher> VectorCommitment<H> for MerkleTree<H> {
+    type MultiProof = BatchMerkleProof<H>;
+    type Error = MerkleTreeError;
+
+    fn with_options(
+        items: Vec<H::Digest>,
+        options: VectorCommitmentOptions,
+    ) -> Result<Self, Self::Error> {
+        Self::with_arity(items, options.arity(), options.cap_height())
+    }
+
+    fn commitment(&self) -> &[H::Digest] {
+        self.cap()
+    }
+
+    fn domain_len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    fn open_many(&self, indexes: &[usize]) -> Result<Self::MultiProof, Self::Error> {
+        self.prove_batch(indexes)
+    }
+
+    fn verify_many(
+        commitment: &[H::Digest],
+        indexes: &[usize],
+        proof: &Self::MultiProof,
+    ) -> Result<(), Self::Error> {
+        Self::verify_batch_with_cap(commitment, indexes, proof)
+    }
+
+    fn commitment_digest(commitment: &[H::Digest]) -> H::Digest {
+        Self::get_root_from_cap(commitment)
+    }
+
+    /// The cap height of trees with arity greater than two is rounded down to the nearest level
+    /// of the tree, and thus, the commitment may consist of fewer than 2^`cap_height` digests.
+    fn commitment_len(domain_len: usize, options: VectorCommitmentOptions) -> usize {
+        let depth = domain_len.checked_ilog2().unwrap_or(0) as usize;
+        if !is_valid_arity(options.arity()) {
+            return 1 << options.cap_height();
+        }
+        1 << round_cap_height(depth, options.arity(), options.cap_height().min(depth))
+    }
+
+    fn serialize_multiproof(proof: &Self::MultiProof) -> Vec<u8> {
+        proof.serialize_nodes()
+    }
+
+    fn deserialize_multiproof<R: ByteReader>(
+        source: &mut R,
+        items: Vec<H::Digest>,
+        domain_len: usize,
+        options: VectorCommitmentOptions,
+    ) -> Result<Self::MultiProof, DeserializationError> {
+        if !domain_len.is_power_of_two() || domain_len < 2 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of leaves must be a power of two greater than one, but was {domain_len}"
+            )));
+        }
+        if options.arity() > MAX_ARITY {
+            return Err(DeserializationError::InvalidValue(format!(
+                "tree arity cannot exceed {MAX_ARITY}, but was {}",
+                options.arity()
+            )));
+        }
+        let depth = domain_len.ilog2() as u8;
+        BatchMerkleProof::deserialize(source, items, depth, options.arity() as u8)
+    }
 }
 
 // HELPER FUNCTIONS
@@ -346,21 +616,144 @@ pub fn build_merkle_nodes<H: Hasher>(leaves: &[H::Digest]) -> Vec<H::Digest> {
     let two_leaves = unsafe { slice::from_raw_parts(leaves.as_ptr() as *const [H::Digest; 2], n) };
 
     // build first row of internal nodes (parents of leaves)
-    for (i, j) in (0..n).zip(n..nodes.len()) {
-        nodes[j] = H::merge(&two_leaves[i]);
+    H::merge_batch_in_domain(two_leaves, HashDomain::Node, &mut nodes[n..]);
+
+    // calculate all other tree nodes level by level; nodes of a level occupy positions
+    // [k, 2k), and their children occupy positions [2k, 4k)
+    let mut level_start = n / 2;
+    while level_start > 0 {
+        let (parents, children) = nodes.split_at_mut(2 * level_start);
+        let two_children = unsafe {
+            slice::from_raw_parts(children.as_ptr() as *const [H::Digest; 2], level_start)
+        };
+        H::merge_batch_in_domain(two_children, HashDomain::Node, &mut parents[level_start..]);
+        level_start /= 2;
     }
 
-    // re-interpret nodes as an array of two nodes fused together
-    let two_nodes = unsafe { slice::from_raw_parts(nodes.as_ptr() as *const [H::Digest; 2], n) };
+    nodes
+}
+
+/// Returns the internal nodes of a Merkle tree of the specified `arity` defined by the specified
+/// leaves.
+///
+/// The nodes are laid out in the same way as the nodes returned by [build_merkle_nodes()]: the
+/// root is stored at position 1, and the children of a node at position `i` of a tree of arity
+/// 2^k are stored at positions [i * 2^k, (i + 1) * 2^k). The positions which do not correspond to
+/// any node of the tree are set to the default digest.
+///
+/// If the depth of the tree is not a multiple of k, the root of the tree has fewer than 2^k
+/// children.
+///
+/// This function is exposed primarily for benchmarking purposes. It is not intended to be used
+/// directly by the end users of the crate.
+pub fn build_merkle_nodes_with_arity<H: Hasher>(
+    leaves: &[H::Digest],
+    arity: usize,
+) -> Vec<H::Digest> {
+    let n = leaves.len();
+    let steps = get_level_steps(n.ilog2() as usize, arity);
+    let mut nodes = vec![H::Digest::default(); n];
+
+    // build first row of internal nodes (parents of leaves)
+    let mut level_start = n >> steps[0];
+    for (target, children) in nodes[level_start..].iter_mut().zip(leaves.chunks(1 << steps[0])) {
+        *target = merge_nodes::<H>(children);
+    }
 
     // calculate all other tree nodes
-    for i in (1..n).rev() {
-        nodes[i] = H::merge(&two_nodes[i]);
+    for &step in steps.iter().skip(1) {
+        let (parents, children) = nodes.split_at_mut(level_start);
+        let parent_start = level_start >> step;
+        for (target, children) in parents[parent_start..]
+            .iter_mut()
+            .zip(children[..level_start].chunks(1 << step))
+        {
+            *target = merge_nodes::<H>(children);
+        }
+        level_start = parent_start;
     }
 
     nodes
 }
 
+/// Returns a hash of the provided child nodes.
+///
+/// Two nodes are merged using [Hasher::merge_in_domain()] so that binary trees are not affected
+/// by the arity, while larger sets of nodes are merged using [Hasher::merge_many_in_domain()].
+/// In both cases, the nodes are merged in the [HashDomain::Node] domain.
+fn merge_nodes<H: Hasher>(children: &[H::Digest]) -> H::Digest {
+    match children {
+        [left, right] => H::merge_in_domain(&[*left, *right], HashDomain::Node),
+        _ => H::merge_many_in_domain(children, HashDomain::Node),
+    }
+}
+
+/// Returns true if the `arity` is a power of two between 2 and 16.
+fn is_valid_arity(arity: usize) -> bool {
+    arity.is_power_of_two() && (2..=MAX_ARITY).contains(&arity)
+}
+
+/// Returns the number of binary levels spanned by each level of a tree of the specified `depth`
+/// and `arity`, starting from the leaves.
+///
+/// For a tree of arity 2^k, all levels span k binary levels, except for the topmost level which
+/// spans fewer levels if the depth of the tree is not a multiple of k.
+fn get_level_steps(depth: usize, arity: usize) -> Vec<usize> {
+    let log_arity = arity.ilog2() as usize;
+    let mut steps = vec![log_arity; depth / log_arity];
+    if depth % log_arity != 0 {
+        steps.push(depth % log_arity);
+    }
+    steps
+}
+
+/// Returns the level steps (see [get_level_steps()]) from the leaves to the level at the
+/// specified `cap_height`, or None if a tree of the specified `depth` and `arity` does not have a
+/// level at this height.
+fn get_cap_steps(depth: usize, arity: usize, cap_height: usize) -> Option<Vec<usize>> {
+    let mut steps = get_level_steps(depth, arity);
+    let mut height = depth;
+    for (i, &step) in steps.iter().enumerate() {
+        if height == cap_height {
+            steps.truncate(i);
+            return Some(steps);
+        }
+        height -= step;
+    }
+    (height == cap_height).then_some(steps)
+}
+
+/// Rounds the specified `cap_height` down to the nearest level of a tree of the specified `depth`
+/// and `arity`.
+fn round_cap_height(depth: usize, arity: usize, cap_height: usize) -> usize {
+    let mut height = depth;
+    for step in get_level_steps(depth, arity) {
+        if height <= cap_height {
+            break;
+        }
+        height -= step;
+    }
+    height
+}
+
+/// Returns positions of the children of the node at the specified position, where `step` is the
+/// number of binary levels spanned by the level of the children.
+fn get_children(index: usize, step: usize) -> core::ops::Range<usize> {
+    (index << step)..((index + 1) << step)
+}
+
+/// Returns the end of a group of consecutive `indexes` which starts at position `start` and share
+/// the same parent, where `step` is the number of binary levels spanned by the level of the
+/// indexes.
+fn get_group_end(indexes: &[usize], start: usize, step: usize) -> usize {
+    let parent_index = indexes[start] >> step;
+    let mut end = start + 1;
+    while end < indexes.len() && indexes[end] >> step == parent_index {
+        end += 1;
+    }
+    end
+}
+
 fn map_indexes(
     indexes: &[usize],
     tree_depth: usize,
@@ -381,10 +774,12 @@ fn map_indexes(
     Ok(map)
 }
 
-fn normalize_indexes(indexes: &[usize]) -> Vec<usize> {
+/// Replaces the indexes with the indexes of the first leaves in their groups of siblings, where
+/// each group contains 2^`step` leaves, and sorts them in ascending order removing duplicates.
+fn normalize_indexes(indexes: &[usize], step: usize) -> Vec<usize> {
     let mut set = BTreeSet::new();
     for &index in indexes {
-        set.insert(index - (index & 1));
+        set.insert(index & !((1 << step) - 1));
     }
     set.into_iter().collect()
 }
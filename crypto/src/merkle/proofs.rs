@@ -3,7 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::{errors::MerkleTreeError, Hasher};
+use crate::{errors::MerkleTreeError, HashDomain, Hasher};
 use alloc::{collections::BTreeMap, string::ToString, vec::Vec};
 use utils::{ByteReader, DeserializationError, Serializable};
 
@@ -24,6 +24,10 @@ pub(super) const MAX_PATHS: usize = 255;
 ///
 /// Currently, at most 255 paths can be aggregated into a single proof. This limitation is
 /// imposed primarily for serialization purposes.
+///
+/// For trees with arity greater than two, each vector of proof nodes at the leaf level contains
+/// all leaves of a group of siblings which are not being proven, and at all other levels all
+/// nodes of a group of siblings which cannot be computed from the nodes at the level below.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BatchMerkleProof<H: Hasher> {
     /// The leaves being proven
@@ -32,11 +36,15 @@ pub struct BatchMerkleProof<H: Hasher> {
     pub nodes: Vec<Vec<H::Digest>>,
     /// Depth of the leaves
     pub depth: u8,
+    /// Arity of the tree from which the proof was generated
+    pub arity: u8,
 }
 
 impl<H: Hasher> BatchMerkleProof<H> {
     /// Constructs a batch Merkle proof from individual Merkle authentication paths.
     ///
+    /// The paths are assumed to come from a binary Merkle tree.
+    ///
     /// # Panics
     /// Panics if:
     /// * No paths have been provided (i.e., `paths` is an empty slice).
@@ -104,11 +112,16 @@ impl<H: Hasher> BatchMerkleProof<H> {
             leaves,
             nodes,
             depth: (depth - 1) as u8,
+            arity: 2,
         }
     }
 
     /// Computes a node to which all Merkle paths aggregated in this proof resolve.
     ///
+    /// This is intended for proofs generated from trees with a cap height of zero; for proofs
+    /// which terminate at a higher cap, [get_cap_nodes()](BatchMerkleProof::get_cap_nodes)
+    /// should be used instead.
+    ///
     /// # Errors
     /// Returns an error if:
     /// * No indexes were provided (i.e., `indexes` is an empty slice).
@@ -118,19 +131,58 @@ impl<H: Hasher> BatchMerkleProof<H> {
     /// * List of indexes contains duplicates.
     /// * The proof does not resolve to a single root.
     pub fn get_root(&self, indexes: &[usize]) -> Result<H::Digest, MerkleTreeError> {
+        let cap_nodes = self.get_cap_nodes(indexes, 0)?;
+        match cap_nodes[..] {
+            [(0, root)] => Ok(root),
+            _ => Err(MerkleTreeError::InvalidProof),
+        }
+    }
+
+    /// Computes the nodes of the Merkle tree cap at the specified `cap_height` to which the
+    /// Merkle paths aggregated in this proof resolve.
+    ///
+    /// The nodes are returned as a list of (position, node) tuples sorted by position, where
+    /// position is the index of the node within the cap. Only the cap nodes which are on the
+    /// paths from the specified `indexes` are returned.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * No indexes were provided (i.e., `indexes` is an empty slice).
+    /// * Number of provided indexes is greater than 255.
+    /// * `cap_height` is greater than or equal to the depth of the proof.
+    /// * Any of the specified `indexes` is greater than or equal to the number of leaves in the
+    ///   tree for which this batch proof was generated.
+    /// * List of indexes contains duplicates.
+    /// * The proof does not resolve to the nodes at the specified cap height.
+    pub fn get_cap_nodes(
+        &self,
+        indexes: &[usize],
+        cap_height: usize,
+    ) -> Result<Vec<(usize, H::Digest)>, MerkleTreeError> {
         if indexes.is_empty() {
             return Err(MerkleTreeError::TooFewLeafIndexes);
         }
         if indexes.len() > MAX_PATHS {
             return Err(MerkleTreeError::TooManyLeafIndexes(MAX_PATHS, indexes.len()));
         }
+        if cap_height >= self.depth as usize {
+            return Err(MerkleTreeError::InvalidCapHeight(self.depth as usize, cap_height));
+        }
 
-        let mut buf = [H::Digest::default(); 2];
+        let arity = self.arity as usize;
+        if !super::is_valid_arity(arity) {
+            return Err(MerkleTreeError::InvalidArity(arity));
+        }
+        let steps = super::get_cap_steps(self.depth as usize, arity, cap_height)
+            .ok_or(MerkleTreeError::InvalidCapHeight(self.depth as usize, cap_height))?;
+
+        let mut buf = Vec::with_capacity(arity);
         let mut v = BTreeMap::new();
 
-        // replace odd indexes, offset, and sort in ascending order
+        // replace indexes with indexes of the first leaves in their groups of siblings, and sort
+        // them in ascending order
         let index_map = super::map_indexes(indexes, self.depth as usize)?;
-        let indexes = super::normalize_indexes(indexes);
+        let indexes = super::normalize_indexes(indexes, steps[0]);
         if indexes.len() != self.nodes.len() {
             return Err(MerkleTreeError::InvalidProof);
         }
@@ -140,118 +192,85 @@ impl<H: Hasher> BatchMerkleProof<H> {
         let mut next_indexes: Vec<usize> = Vec::new();
         let mut proof_pointers: Vec<usize> = Vec::with_capacity(indexes.len());
         for (i, index) in indexes.into_iter().enumerate() {
-            // copy values of leaf sibling leaf nodes into the buffer
-            match index_map.get(&index) {
-                Some(&index1) => {
-                    if self.leaves.len() <= index1 {
-                        return Err(MerkleTreeError::InvalidProof);
-                    }
-                    buf[0] = self.leaves[index1];
-                    match index_map.get(&(index + 1)) {
-                        Some(&index2) => {
-                            if self.leaves.len() <= index2 {
-                                return Err(MerkleTreeError::InvalidProof);
-                            }
-                            buf[1] = self.leaves[index2];
-                            proof_pointers.push(0);
-                        }
-                        None => {
-                            if self.nodes[i].is_empty() {
-                                return Err(MerkleTreeError::InvalidProof);
-                            }
-                            buf[1] = self.nodes[i][0];
-                            proof_pointers.push(1);
-                        }
+            // copy values of sibling leaf nodes into the buffer; leaves which are not being
+            // proven are taken from the proof
+            buf.clear();
+            let mut pointer = 0;
+            for leaf_index in index..index + (1 << steps[0]) {
+                let leaf = match index_map.get(&leaf_index) {
+                    Some(&position) => self.leaves.get(position),
+                    None => {
+                        pointer += 1;
+                        self.nodes[i].get(pointer - 1)
                     }
-                }
-                None => {
-                    if self.nodes[i].is_empty() {
-                        return Err(MerkleTreeError::InvalidProof);
-                    }
-                    buf[0] = self.nodes[i][0];
-                    match index_map.get(&(index + 1)) {
-                        Some(&index2) => {
-                            if self.leaves.len() <= index2 {
-                                return Err(MerkleTreeError::InvalidProof);
-                            }
-                            buf[1] = self.leaves[index2];
-                        }
-                        None => return Err(MerkleTreeError::InvalidProof),
-                    }
-                    proof_pointers.push(1);
-                }
+                };
+                buf.push(*leaf.ok_or(MerkleTreeError::InvalidProof)?);
             }
+            proof_pointers.push(pointer);
 
             // hash sibling nodes into their parent
-            let parent = H::merge(&buf);
+            let parent = super::merge_nodes::<H>(&buf);
 
-            let parent_index = (offset + index) >> 1;
+            let parent_index = (offset + index) >> steps[0];
             v.insert(parent_index, parent);
             next_indexes.push(parent_index);
         }
 
-        // iteratively move up, until we get to the root
-        for _ in 1..self.depth {
-            let indexes = next_indexes.clone();
-            next_indexes.truncate(0);
+        // iteratively move up, until we get to the cap
+        for &step in steps.iter().skip(1) {
+            let indexes = core::mem::take(&mut next_indexes);
 
             let mut i = 0;
             while i < indexes.len() {
-                let node_index = indexes[i];
-                let sibling_index = node_index ^ 1;
-
-                // determine the sibling
-                let sibling: H::Digest;
-                if i + 1 < indexes.len() && indexes[i + 1] == sibling_index {
-                    sibling = match v.get(&sibling_index) {
-                        Some(sibling) => *sibling,
-                        None => return Err(MerkleTreeError::InvalidProof),
+                // nodes with the same parent are next to each other in the list of indexes
+                let parent_index = indexes[i] >> step;
+                let group_end = super::get_group_end(&indexes, i, step);
+
+                // collect all children of the parent; children which were not computed at the
+                // previous level are taken from the proof
+                buf.clear();
+                for child_index in super::get_children(parent_index, step) {
+                    let child = if indexes[i..group_end].contains(&child_index) {
+                        v.get(&child_index)
+                    } else {
+                        proof_pointers[i] += 1;
+                        self.nodes[i].get(proof_pointers[i] - 1)
                     };
-                    i += 1;
-                } else {
-                    let pointer = proof_pointers[i];
-                    if self.nodes[i].len() <= pointer {
-                        return Err(MerkleTreeError::InvalidProof);
-                    }
-                    sibling = self.nodes[i][pointer];
-                    proof_pointers[i] += 1;
+                    buf.push(*child.ok_or(MerkleTreeError::InvalidProof)?);
                 }
 
-                // get the node from the map of hashed nodes
-                let node = match v.get(&node_index) {
-                    Some(node) => node,
-                    None => return Err(MerkleTreeError::InvalidProof),
-                };
-
-                // compute parent node from node and sibling
-                if node_index & 1 != 0 {
-                    buf[0] = sibling;
-                    buf[1] = *node;
-                } else {
-                    buf[0] = *node;
-                    buf[1] = sibling;
-                }
-                let parent = H::merge(&buf);
-
                 // add the parent node to the next set of nodes
-                let parent_index = node_index >> 1;
+                let parent = super::merge_nodes::<H>(&buf);
                 v.insert(parent_index, parent);
                 next_indexes.push(parent_index);
 
-                i += 1;
+                i = group_end;
             }
         }
-        v.remove(&1).ok_or(MerkleTreeError::InvalidProof)
+
+        // next indexes now point to the computed nodes at the cap height
+        let cap_offset = 1 << cap_height;
+        next_indexes
+            .into_iter()
+            .map(|index| match v.remove(&index) {
+                Some(node) => Ok((index - cap_offset, node)),
+                None => Err(MerkleTreeError::InvalidProof),
+            })
+            .collect()
     }
 
     /// Computes the uncompressed Merkle paths which aggregate to this proof.
     ///
     /// # Errors
     /// Returns an error if:
+    /// * The proof was generated from a tree with arity other than two.
     /// * No indexes were provided (i.e., `indexes` is an empty slice).
     /// * Number of provided indexes is greater than 255.
     /// * Number of provided indexes does not match the number of leaf nodes in the proof.
     pub fn into_paths(self, indexes: &[usize]) -> Result<Vec<Vec<H::Digest>>, MerkleTreeError> {
+        if self.arity != 2 {
+            return Err(MerkleTreeError::InvalidArity(self.arity as usize));
+        }
         if indexes.is_empty() {
             return Err(MerkleTreeError::TooFewLeafIndexes);
         }
@@ -274,7 +293,7 @@ impl<H: Hasher> BatchMerkleProof<H> {
         // replace odd indexes, offset, and sort in ascending order
         let original_indexes = indexes;
         let index_map = super::map_indexes(indexes, self.depth as usize)?;
-        let indexes = super::normalize_indexes(indexes);
+        let indexes = super::normalize_indexes(indexes, 1);
         if indexes.len() != self.nodes.len() {
             return Err(MerkleTreeError::InvalidProof);
         }
@@ -327,7 +346,7 @@ impl<H: Hasher> BatchMerkleProof<H> {
             }
 
             // hash sibling nodes into their parent and add it to partial_tree
-            let parent = H::merge(&buf);
+            let parent = H::merge_in_domain(&buf, HashDomain::Node);
             partial_tree_map.insert(offset + index, buf[0]);
             partial_tree_map.insert((offset + index) ^ 1, buf[1]);
             let parent_index = (offset + index) >> 1;
@@ -371,9 +390,9 @@ impl<H: Hasher> BatchMerkleProof<H> {
                 // compute parent node from node and sibling
                 partial_tree_map.insert(node_index ^ 1, sibling);
                 let parent = if node_index & 1 != 0 {
-                    H::merge(&[sibling, *node])
+                    H::merge_in_domain(&[sibling, *node], HashDomain::Node)
                 } else {
-                    H::merge(&[*node, sibling])
+                    H::merge_in_domain(&[*node, sibling], HashDomain::Node)
                 };
 
                 // add the parent node to the next set of nodes and partial_tree
@@ -422,24 +441,33 @@ impl<H: Hasher> BatchMerkleProof<H> {
     }
 
     /// Parses internal nodes from the provided `node_bytes`, and constructs a batch Merkle proof
-    /// from these nodes, provided `leaves`, and provided tree `depth`.
+    /// from these nodes, provided `leaves`, and provided tree `depth` and `arity`.
     ///
     /// # Errors
     /// Returns an error if:
     /// * No leaves were provided (i.e., `leaves` is an empty slice).
     /// * Number of provided leaves is greater than 255.
     /// * Tree `depth` was set to zero.
+    /// * Tree `arity` is not a power of two between 2 and 16.
     /// * `node_bytes` could not be deserialized into a valid set of internal nodes.
     pub fn deserialize<R: ByteReader>(
         node_bytes: &mut R,
         leaves: Vec<H::Digest>,
         depth: u8,
+        arity: u8,
     ) -> Result<Self, DeserializationError> {
         if depth == 0 {
             return Err(DeserializationError::InvalidValue(
                 "tree depth must be greater than zero".to_string(),
             ));
         }
+        if !super::is_valid_arity(arity as usize) {
+            return Err(DeserializationError::InvalidValue(format!(
+                "tree arity must be a power of two between 2 and {}, but was {}",
+                super::MAX_ARITY,
+                arity
+            )));
+        }
         if leaves.is_empty() {
             return Err(DeserializationError::InvalidValue(
                 "at lease one leaf must be provided".to_string(),
@@ -468,6 +496,7 @@ impl<H: Hasher> BatchMerkleProof<H> {
             leaves,
             nodes,
             depth,
+            arity,
         })
     }
 }
@@ -0,0 +1,455 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{
+    errors::MerkleTreeError,
+    hash::{HashDomain, Hasher},
+};
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    string::ToString,
+    vec::Vec,
+};
+use math::StarkField;
+use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+mod proof;
+pub use proof::SparseMerkleProof;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Maximum depth of a sparse Merkle tree; at this depth, every 256-bit key maps to its own leaf.
+const MAX_SPARSE_TREE_DEPTH: usize = 256;
+
+/// Number of bytes in a sparse Merkle tree key.
+const KEY_BYTES: usize = 32;
+
+// SPARSE MERKLE TREE KEY
+// ================================================================================================
+
+/// A key of a leaf in a [SparseMerkleTree].
+///
+/// A key is a 256-bit unsigned integer stored in big-endian byte order. In a tree of depth `d`,
+/// a key is interpreted as the index of a leaf, and thus, only keys smaller than 2^d are valid.
+/// Bits of the key define the path from the leaf to the root: the least significant bit selects
+/// the position of the leaf among its siblings, the next bit selects the position of its parent,
+/// and so on.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SmtKey([u8; KEY_BYTES]);
+
+impl SmtKey {
+    /// Key of the leftmost leaf in a tree of any depth.
+    pub const ZERO: Self = Self([0; KEY_BYTES]);
+
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a key from the provided big-endian bytes.
+    pub const fn new(bytes: [u8; KEY_BYTES]) -> Self {
+        Self(bytes)
+    }
+
+    /// Returns a key equal to the provided integer.
+    pub fn from_u64(value: u64) -> Self {
+        let mut bytes = [0; KEY_BYTES];
+        bytes[KEY_BYTES - 8..].copy_from_slice(&value.to_be_bytes());
+        Self(bytes)
+    }
+
+    /// Returns a key equal to the canonical integer representation of the provided field element.
+    pub fn from_element<B: StarkField>(element: B) -> Self {
+        let mut bytes = [0; KEY_BYTES];
+        for (byte, value) in bytes.iter_mut().rev().zip(element.to_bytes()) {
+            *byte = value;
+        }
+        Self(bytes)
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns big-endian bytes of this key.
+    pub fn as_bytes(&self) -> &[u8; KEY_BYTES] {
+        &self.0
+    }
+
+    /// Returns the value of the bit at the specified position; position 0 refers to the least
+    /// significant bit of the key.
+    ///
+    /// # Panics
+    /// Panics if `position` is greater than or equal to 256.
+    pub fn bit(&self, position: usize) -> bool {
+        let byte = self.0[KEY_BYTES - 1 - position / 8];
+        (byte >> (position % 8)) & 1 == 1
+    }
+
+    /// Returns true if this key is a valid leaf index in a tree of the specified depth.
+    pub fn fits_depth(&self, depth: usize) -> bool {
+        *self >> depth == Self::ZERO
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the index of the sibling of the node with this index.
+    fn sibling(&self) -> Self {
+        let mut result = *self;
+        result.0[KEY_BYTES - 1] ^= 1;
+        result
+    }
+
+    /// Returns the index of the left child of the node with this index.
+    ///
+    /// The most significant bit of this index is assumed to be zero.
+    fn left_child(&self) -> Self {
+        let mut result = Self::ZERO;
+        for i in 0..KEY_BYTES {
+            let carry = if i + 1 < KEY_BYTES { self.0[i + 1] >> 7 } else { 0 };
+            result.0[i] = (self.0[i] << 1) | carry;
+        }
+        result
+    }
+}
+
+impl core::ops::Shr<usize> for SmtKey {
+    type Output = Self;
+
+    /// Returns the index of the ancestor `shift` levels above the node with this index.
+    fn shr(self, shift: usize) -> Self::Output {
+        let mut result = Self::ZERO;
+        let byte_shift = shift / 8;
+        let bit_shift = shift % 8;
+        for i in (byte_shift..KEY_BYTES).rev() {
+            let source = i - byte_shift;
+            let mut value = self.0[source] >> bit_shift;
+            if bit_shift > 0 && source > 0 {
+                value |= self.0[source - 1] << (8 - bit_shift);
+            }
+            result.0[i] = value;
+        }
+        result
+    }
+}
+
+impl From<u64> for SmtKey {
+    fn from(value: u64) -> Self {
+        Self::from_u64(value)
+    }
+}
+
+impl From<[u8; KEY_BYTES]> for SmtKey {
+    fn from(bytes: [u8; KEY_BYTES]) -> Self {
+        Self::new(bytes)
+    }
+}
+
+impl Serializable for SmtKey {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_bytes(&self.0);
+    }
+}
+
+impl Deserializable for SmtKey {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Self(source.read_array()?))
+    }
+}
+
+// SPARSE MERKLE TREE
+// ================================================================================================
+
+/// A binary Merkle tree of a fixed depth in which most of the leaves are empty.
+///
+/// A tree of depth `d` has 2^d leaves, each identified by an [SmtKey] smaller than 2^d. Leaves
+/// which have not been set are empty, and an empty leaf is equal to the default value of the
+/// digest specified by the [Hasher] used to build the tree. Only non-empty leaves and internal
+/// nodes which have at least one non-empty leaf below them are stored; all other nodes are roots
+/// of empty subtrees and are computed once, when the tree is created. Thus, trees with depth of
+/// up to 256 can be used, e.g., to key leaves by 256-bit hashes.
+///
+/// Similarly to [MerkleTree](crate::MerkleTree), the user is responsible for hashing values into
+/// leaves, and internal nodes are computed using [Hasher::merge_in_domain()] function in the
+/// [HashDomain::Node] domain.
+///
+/// Leaves can be set one at a time using [SparseMerkleTree::insert()], or in batches using
+/// [SparseMerkleTree::update_many()]; in the latter case, each affected internal node is
+/// recomputed only once. Setting a leaf to the empty value removes it from the tree.
+///
+/// [SparseMerkleTree::prove()] method returns a [SparseMerkleProof] for any key. For keys of
+/// non-empty leaves, such proofs are proofs of inclusion, and for all other keys, they are proofs
+/// of non-inclusion. A proof can also be used to compute the root of the tree after its leaf has
+/// been updated.
+///
+/// # Examples
+/// ```
+/// # use winter_crypto::{SmtKey, SparseMerkleTree, Hasher, hashers::Blake3_256};
+/// # use math::fields::f128::BaseElement;
+/// type Blake3 = Blake3_256::<BaseElement>;
+///
+/// // build a tree keyed by 64-bit integers
+/// let mut tree = SparseMerkleTree::<Blake3>::new(64).unwrap();
+/// let key = SmtKey::from_u64(42);
+/// tree.insert(key, Blake3::hash(&[1u8])).unwrap();
+///
+/// // prove inclusion of the leaf and non-inclusion of some other leaf
+/// let proof = tree.prove(&key).unwrap();
+/// assert!(proof.verify_inclusion(tree.root(), 64, &key, Blake3::hash(&[1u8])).is_ok());
+///
+/// let other_key = SmtKey::from_u64(43);
+/// let proof = tree.prove(&other_key).unwrap();
+/// assert!(proof.verify_non_inclusion(tree.root(), 64, &other_key).is_ok());
+/// ```
+#[derive(Debug, PartialEq, Eq)]
+pub struct SparseMerkleTree<H: Hasher> {
+    depth: usize,
+    leaves: BTreeMap<SmtKey, H::Digest>,
+    nodes: BTreeMap<(usize, SmtKey), H::Digest>,
+    empty_hashes: Vec<H::Digest>,
+}
+
+impl<H: Hasher> SparseMerkleTree<H> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new sparse Merkle tree of the specified depth with all leaves empty.
+    ///
+    /// # Errors
+    /// Returns an error if `depth` is zero or greater than 256.
+    pub fn new(depth: usize) -> Result<Self, MerkleTreeError> {
+        if depth == 0 || depth > MAX_SPARSE_TREE_DEPTH {
+            return Err(MerkleTreeError::InvalidTreeDepth(depth));
+        }
+
+        Ok(Self {
+            depth,
+            leaves: BTreeMap::new(),
+            nodes: BTreeMap::new(),
+            empty_hashes: build_empty_hashes::<H>(depth),
+        })
+    }
+
+    /// Returns a new sparse Merkle tree of the specified depth with the provided leaves set.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * `depth` is zero or greater than 256.
+    /// * Any of the keys does not fit into a tree of the specified depth.
+    pub fn with_leaves<I>(depth: usize, leaves: I) -> Result<Self, MerkleTreeError>
+    where
+        I: IntoIterator<Item = (SmtKey, H::Digest)>,
+    {
+        let mut tree = Self::new(depth)?;
+        tree.update_many(leaves)?;
+        Ok(tree)
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the root of the tree.
+    pub fn root(&self) -> H::Digest {
+        self.get_node(self.depth, &SmtKey::ZERO)
+    }
+
+    /// Returns the depth of the tree.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the number of non-empty leaves in the tree.
+    pub fn num_leaves(&self) -> usize {
+        self.leaves.len()
+    }
+
+    /// Returns the leaf at the specified key; for keys of empty leaves, the default digest is
+    /// returned.
+    ///
+    /// # Errors
+    /// Returns an error if the key does not fit into this tree.
+    pub fn get_leaf(&self, key: &SmtKey) -> Result<H::Digest, MerkleTreeError> {
+        self.check_key(key)?;
+        Ok(self.get_node(0, key))
+    }
+
+    /// Returns an iterator over all non-empty leaves of the tree ordered by their keys.
+    pub fn leaves(&self) -> impl Iterator<Item = (&SmtKey, &H::Digest)> {
+        self.leaves.iter()
+    }
+
+    /// Returns the root of an empty subtree of the specified height; height 0 refers to an empty
+    /// leaf.
+    ///
+    /// # Panics
+    /// Panics if `height` is greater than the depth of the tree.
+    pub fn empty_root(&self, height: usize) -> H::Digest {
+        self.empty_hashes[height]
+    }
+
+    // UPDATES
+    // --------------------------------------------------------------------------------------------
+
+    /// Sets the leaf at the specified key to the provided value, and returns the previous value
+    /// of the leaf. Setting a leaf to the default digest removes it from the tree.
+    ///
+    /// # Errors
+    /// Returns an error if the key does not fit into this tree.
+    pub fn insert(&mut self, key: SmtKey, value: H::Digest) -> Result<H::Digest, MerkleTreeError> {
+        let old_value = self.get_leaf(&key)?;
+        self.update_many([(key, value)])?;
+        Ok(old_value)
+    }
+
+    /// Sets the leaves at the specified keys to the provided values, and recomputes all affected
+    /// internal nodes of the tree. If a key appears more than once, the last value is used.
+    ///
+    /// Every internal node is recomputed at most once, and thus, updating leaves in a batch is
+    /// more efficient than updating them one by one.
+    ///
+    /// # Errors
+    /// Returns an error if any of the keys does not fit into this tree; in this case, the tree
+    /// is left unchanged.
+    pub fn update_many<I>(&mut self, updates: I) -> Result<(), MerkleTreeError>
+    where
+        I: IntoIterator<Item = (SmtKey, H::Digest)>,
+    {
+        let updates = updates.into_iter().collect::<Vec<_>>();
+        for (key, _) in updates.iter() {
+            self.check_key(key)?;
+        }
+
+        // update the leaves and keep track of the nodes which need to be recomputed
+        let mut dirty = BTreeSet::new();
+        for (key, value) in updates {
+            if value == H::Digest::default() {
+                self.leaves.remove(&key);
+            } else {
+                self.leaves.insert(key, value);
+            }
+            dirty.insert(key);
+        }
+
+        // recompute the affected nodes level by level; since nodes of each level are computed
+        // from the nodes at the level below, every node is recomputed only once
+        for height in 1..=self.depth {
+            dirty = dirty.into_iter().map(|index| index >> 1).collect();
+            for index in dirty.iter() {
+                let left_index = index.left_child();
+                let left = self.get_node(height - 1, &left_index);
+                let right = self.get_node(height - 1, &left_index.sibling());
+                let node = H::merge_in_domain(&[left, right], HashDomain::Node);
+
+                if node == self.empty_hashes[height] {
+                    self.nodes.remove(&(height, *index));
+                } else {
+                    self.nodes.insert((height, *index), node);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // PROVING
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a proof for the leaf at the specified key.
+    ///
+    /// For a key of a non-empty leaf, the proof is a proof of inclusion of the leaf; otherwise,
+    /// it is a proof of non-inclusion of the key.
+    ///
+    /// # Errors
+    /// Returns an error if the key does not fit into this tree.
+    pub fn prove(&self, key: &SmtKey) -> Result<SparseMerkleProof<H>, MerkleTreeError> {
+        self.check_key(key)?;
+
+        let path = (0..self.depth)
+            .map(|height| self.get_node(height, &(*key >> height).sibling()))
+            .collect();
+        Ok(SparseMerkleProof::new(path))
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the node at the specified height and index; height 0 refers to the leaves.
+    fn get_node(&self, height: usize, index: &SmtKey) -> H::Digest {
+        let node = if height == 0 {
+            self.leaves.get(index)
+        } else {
+            self.nodes.get(&(height, *index))
+        };
+        node.copied().unwrap_or(self.empty_hashes[height])
+    }
+
+    fn check_key(&self, key: &SmtKey) -> Result<(), MerkleTreeError> {
+        if !key.fits_depth(self.depth) {
+            return Err(MerkleTreeError::KeyOutOfBounds(self.depth));
+        }
+        Ok(())
+    }
+}
+
+impl<H: Hasher> Clone for SparseMerkleTree<H> {
+    fn clone(&self) -> Self {
+        Self {
+            depth: self.depth,
+            leaves: self.leaves.clone(),
+            nodes: self.nodes.clone(),
+            empty_hashes: self.empty_hashes.clone(),
+        }
+    }
+}
+
+// SERIALIZATION / DESERIALIZATION
+// ================================================================================================
+
+impl<H: Hasher> Serializable for SparseMerkleTree<H> {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    ///
+    /// Only the depth and the non-empty leaves of the tree are serialized; internal nodes are
+    /// recomputed during deserialization.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u16(self.depth as u16);
+        target.write_usize(self.leaves.len());
+        for (key, leaf) in self.leaves.iter() {
+            key.write_into(target);
+            leaf.write_into(target);
+        }
+    }
+}
+
+impl<H: Hasher> Deserializable for SparseMerkleTree<H> {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let depth = source.read_u16()? as usize;
+        let num_leaves = source.read_usize()?;
+        let mut leaves = Vec::new();
+        for _ in 0..num_leaves {
+            let key = SmtKey::read_from(source)?;
+            let leaf = H::Digest::read_from(source)?;
+            leaves.push((key, leaf));
+        }
+
+        Self::with_leaves(depth, leaves)
+            .map_err(|err| DeserializationError::InvalidValue(err.to_string()))
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns roots of empty subtrees of heights 0 through `depth`.
+fn build_empty_hashes<H: Hasher>(depth: usize) -> Vec<H::Digest> {
+    let mut result = Vec::with_capacity(depth + 1);
+    result.push(H::Digest::default());
+    for height in 0..depth {
+        let node = result[height];
+        result.push(H::merge_in_domain(&[node, node], HashDomain::Node));
+    }
+    result
+}
@@ -0,0 +1,195 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_empty_hashes, SmtKey, MAX_SPARSE_TREE_DEPTH};
+use crate::{errors::MerkleTreeError, HashDomain, Hasher};
+use alloc::{format, vec, vec::Vec};
+use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+// SPARSE MERKLE PROOF
+// ================================================================================================
+
+/// A proof of inclusion or non-inclusion of a leaf in a [SparseMerkleTree](super::SparseMerkleTree).
+///
+/// The proof consists of the siblings of all nodes on the path from the leaf to the root, with
+/// the sibling of the leaf coming first. The same proof can be used to verify that a key maps to
+/// a given leaf, that a key maps to an empty leaf (i.e., non-inclusion), and to compute the root
+/// of the tree after the leaf has been updated (see [SparseMerkleProof::compute_root()]).
+///
+/// When serialized, siblings which are roots of empty subtrees are omitted; thus, proofs for
+/// sparsely populated trees are much smaller than their depth suggests.
+#[derive(Debug, PartialEq, Eq)]
+pub struct SparseMerkleProof<H: Hasher> {
+    path: Vec<H::Digest>,
+}
+
+impl<H: Hasher> SparseMerkleProof<H> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new proof consisting of the provided siblings ordered from the leaf level up.
+    ///
+    /// # Panics
+    /// Panics if the path is empty or contains more than 256 siblings.
+    pub fn new(path: Vec<H::Digest>) -> Self {
+        assert!(!path.is_empty(), "path must contain at least one sibling");
+        assert!(
+            path.len() <= MAX_SPARSE_TREE_DEPTH,
+            "path cannot contain more than {MAX_SPARSE_TREE_DEPTH} siblings"
+        );
+        Self { path }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the depth of the tree for which this proof was generated.
+    pub fn depth(&self) -> usize {
+        self.path.len()
+    }
+
+    /// Returns the siblings of the nodes on the path from the leaf to the root.
+    pub fn path(&self) -> &[H::Digest] {
+        &self.path
+    }
+
+    // VERIFICATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the root of a tree in which the leaf at the specified key is equal to `leaf` and
+    /// all other nodes are as described by this proof.
+    ///
+    /// When `leaf` is the current value of the leaf, this is the root of the tree from which
+    /// the proof was generated; when `leaf` is a new value, this is the root of the tree after
+    /// the leaf has been updated.
+    ///
+    /// # Errors
+    /// Returns an error if the key does not fit into a tree of the depth of this proof.
+    pub fn compute_root(
+        &self,
+        key: &SmtKey,
+        leaf: H::Digest,
+    ) -> Result<H::Digest, MerkleTreeError> {
+        if !key.fits_depth(self.depth()) {
+            return Err(MerkleTreeError::KeyOutOfBounds(self.depth()));
+        }
+
+        let mut node = leaf;
+        for (height, &sibling) in self.path.iter().enumerate() {
+            let children = if key.bit(height) {
+                [sibling, node]
+            } else {
+                [node, sibling]
+            };
+            node = H::merge_in_domain(&children, HashDomain::Node);
+        }
+        Ok(node)
+    }
+
+    /// Checks whether the leaf at the specified key of a tree with the specified root and depth
+    /// is equal to `leaf`.
+    ///
+    /// The depth of the tree must be provided by the verifier rather than implied by the proof:
+    /// otherwise, an internal node of the tree could be passed off as a leaf of a shallower tree
+    /// with the same root.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The depth of this proof is not equal to `depth`.
+    /// * The key does not fit into a tree of the specified depth.
+    /// * The proof does not resolve to the specified root.
+    pub fn verify_inclusion(
+        &self,
+        root: H::Digest,
+        depth: usize,
+        key: &SmtKey,
+        leaf: H::Digest,
+    ) -> Result<(), MerkleTreeError> {
+        if self.depth() != depth {
+            return Err(MerkleTreeError::ProofDepthMismatch(depth, self.depth()));
+        }
+        if self.compute_root(key, leaf)? != root {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+        Ok(())
+    }
+
+    /// Checks whether the leaf at the specified key of a tree with the specified root and depth
+    /// is empty.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The depth of this proof is not equal to `depth`.
+    /// * The key does not fit into a tree of the specified depth.
+    /// * The proof does not resolve to the specified root.
+    pub fn verify_non_inclusion(
+        &self,
+        root: H::Digest,
+        depth: usize,
+        key: &SmtKey,
+    ) -> Result<(), MerkleTreeError> {
+        self.verify_inclusion(root, depth, key, H::Digest::default())
+    }
+}
+
+impl<H: Hasher> Clone for SparseMerkleProof<H> {
+    fn clone(&self) -> Self {
+        Self {
+            path: self.path.clone(),
+        }
+    }
+}
+
+// SERIALIZATION / DESERIALIZATION
+// ================================================================================================
+
+impl<H: Hasher> Serializable for SparseMerkleProof<H> {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    ///
+    /// The depth of the proof is followed by a bitmask of siblings which are not roots of empty
+    /// subtrees, and then by these siblings.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        let empty_hashes = build_empty_hashes::<H>(self.depth());
+
+        let mut bitmask = vec![0_u8; self.depth().div_ceil(8)];
+        for (height, sibling) in self.path.iter().enumerate() {
+            if *sibling != empty_hashes[height] {
+                bitmask[height / 8] |= 1 << (height % 8);
+            }
+        }
+
+        target.write_u16(self.depth() as u16);
+        target.write_bytes(&bitmask);
+        for (height, sibling) in self.path.iter().enumerate() {
+            if *sibling != empty_hashes[height] {
+                sibling.write_into(target);
+            }
+        }
+    }
+}
+
+impl<H: Hasher> Deserializable for SparseMerkleProof<H> {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let depth = source.read_u16()? as usize;
+        if depth == 0 || depth > MAX_SPARSE_TREE_DEPTH {
+            return Err(DeserializationError::InvalidValue(format!(
+                "sparse Merkle proof depth must be between 1 and {MAX_SPARSE_TREE_DEPTH}, but was {depth}"
+            )));
+        }
+
+        let bitmask = source.read_vec(depth.div_ceil(8))?;
+        let empty_hashes = build_empty_hashes::<H>(depth);
+        let mut path = Vec::with_capacity(depth);
+        for (height, &empty_hash) in empty_hashes.iter().take(depth).enumerate() {
+            if (bitmask[height / 8] >> (height % 8)) & 1 == 1 {
+                path.push(H::Digest::read_from(source)?);
+            } else {
+                path.push(empty_hash);
+            }
+        }
+
+        Ok(Self { path })
+    }
+}
@@ -0,0 +1,302 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::*;
+use crate::{hash::ByteDigest, Digest, MerkleTree};
+use math::fields::{f128::BaseElement, f64::BaseElement as BaseElement64};
+use proptest::prelude::*;
+
+type Blake3_256 = crate::hash::Blake3_256<BaseElement>;
+type Digest256 = ByteDigest<32>;
+
+// KEY TESTS
+// ================================================================================================
+
+#[test]
+fn smt_key_bits() {
+    let key = SmtKey::from_u64(0b1011_0000_0001);
+    assert!(key.bit(0));
+    assert!(!key.bit(1));
+    assert!(key.bit(8));
+    assert!(key.bit(11));
+    assert!(!key.bit(255));
+
+    assert_eq!(SmtKey::from_u64(0b1011), key >> 8);
+    assert_eq!(SmtKey::ZERO, key >> 12);
+    assert_eq!(SmtKey::ZERO, key >> 256);
+    assert_eq!(SmtKey::from_u64(0b1011_0000_0000), key.sibling());
+    assert_eq!(SmtKey::from_u64(0b1_0110_0000_0010), key.left_child());
+
+    assert!(key.fits_depth(12));
+    assert!(!key.fits_depth(11));
+    assert!(SmtKey::new([255; 32]).fits_depth(256));
+    assert!(!SmtKey::new([255; 32]).fits_depth(255));
+
+    let mut bytes = [0; 32];
+    bytes[0] = 0b1000_0000;
+    assert!(SmtKey::new(bytes).bit(255));
+    assert_eq!(SmtKey::from_u64(1), SmtKey::new(bytes) >> 255);
+}
+
+#[test]
+fn smt_key_from_element() {
+    let element = BaseElement64::new(0x0123_4567_89ab_cdef);
+    assert_eq!(SmtKey::from_u64(0x0123_4567_89ab_cdef), SmtKey::from_element(element));
+
+    let element = BaseElement::new(u128::MAX >> 2);
+    let key = SmtKey::from_element(element);
+    assert!(key.fits_depth(128));
+    assert_eq!(SmtKey::from_u64(u64::MAX >> 2), key >> 64);
+}
+
+// TREE TESTS
+// ================================================================================================
+
+#[test]
+fn new_sparse_tree() {
+    assert_eq!(
+        MerkleTreeError::InvalidTreeDepth(0),
+        SparseMerkleTree::<Blake3_256>::new(0).unwrap_err()
+    );
+    assert_eq!(
+        MerkleTreeError::InvalidTreeDepth(257),
+        SparseMerkleTree::<Blake3_256>::new(257).unwrap_err()
+    );
+
+    // root of an empty tree is the root of an empty subtree of the same depth
+    let tree = SparseMerkleTree::<Blake3_256>::new(256).unwrap();
+    let node = Blake3_256::merge(&[Digest256::default(), Digest256::default()]);
+    assert_eq!(node, tree.empty_root(1));
+    assert_eq!(0, tree.num_leaves());
+
+    let mut root = Digest256::default();
+    for _ in 0..256 {
+        root = Blake3_256::merge(&[root, root]);
+    }
+    assert_eq!(root, tree.root());
+}
+
+#[test]
+fn sparse_tree_matches_dense_tree() {
+    let leaves = (0..16_u8).map(|i| Blake3_256::hash(&[i])).collect::<Vec<_>>();
+    let dense_tree = MerkleTree::<Blake3_256>::new(leaves.clone()).unwrap();
+
+    let sparse_leaves =
+        leaves.iter().enumerate().map(|(i, &leaf)| (SmtKey::from_u64(i as u64), leaf));
+    let sparse_tree = SparseMerkleTree::<Blake3_256>::with_leaves(4, sparse_leaves).unwrap();
+    assert_eq!(*dense_tree.root(), sparse_tree.root());
+
+    // a sparse proof consists of the same nodes as a dense proof
+    let dense_proof = dense_tree.prove(5).unwrap();
+    let sparse_proof = sparse_tree.prove(&SmtKey::from_u64(5)).unwrap();
+    assert_eq!(&dense_proof[1..], sparse_proof.path());
+
+    // empty leaves are the same as default digests
+    let mut leaves = leaves;
+    leaves[3] = Digest256::default();
+    leaves[12] = Digest256::default();
+    let dense_tree = MerkleTree::<Blake3_256>::new(leaves).unwrap();
+
+    let mut sparse_tree = sparse_tree;
+    sparse_tree.insert(SmtKey::from_u64(3), Digest256::default()).unwrap();
+    sparse_tree.insert(SmtKey::from_u64(12), Digest256::default()).unwrap();
+    assert_eq!(*dense_tree.root(), sparse_tree.root());
+    assert_eq!(14, sparse_tree.num_leaves());
+}
+
+#[test]
+fn insert_and_remove() {
+    let mut tree = SparseMerkleTree::<Blake3_256>::new(64).unwrap();
+    let empty_root = tree.root();
+    let key = SmtKey::from_u64(42);
+    let value = Blake3_256::hash(&[1, 2, 3]);
+
+    assert_eq!(Digest256::default(), tree.insert(key, value).unwrap());
+    assert_ne!(empty_root, tree.root());
+    assert_eq!(value, tree.get_leaf(&key).unwrap());
+    assert_eq!(1, tree.num_leaves());
+
+    // removing the only leaf makes the tree empty again
+    assert_eq!(value, tree.insert(key, Digest256::default()).unwrap());
+    assert_eq!(empty_root, tree.root());
+    assert_eq!(0, tree.num_leaves());
+    assert!(tree.nodes.is_empty());
+
+    // keys which do not fit into the tree are rejected
+    let mut bytes = [0; 32];
+    bytes[23] = 1;
+    let key = SmtKey::new(bytes);
+    assert_eq!(MerkleTreeError::KeyOutOfBounds(64), tree.insert(key, value).unwrap_err());
+    assert_eq!(MerkleTreeError::KeyOutOfBounds(64), tree.prove(&key).unwrap_err());
+}
+
+#[test]
+fn update_many() {
+    let updates = (0..32_u64)
+        .map(|i| (SmtKey::from_u64(i * 7919), Blake3_256::hash(&i.to_le_bytes())))
+        .collect::<Vec<_>>();
+
+    let mut tree = SparseMerkleTree::<Blake3_256>::new(32).unwrap();
+    for &(key, value) in updates.iter() {
+        tree.insert(key, value).unwrap();
+    }
+
+    let mut batch_tree = SparseMerkleTree::<Blake3_256>::new(32).unwrap();
+    batch_tree.update_many(updates.clone()).unwrap();
+    assert_eq!(tree, batch_tree);
+
+    // the last value of a repeated key is used
+    let key = updates[0].0;
+    let value = Blake3_256::hash(&[42]);
+    batch_tree.update_many([(key, Digest256::default()), (key, value)]).unwrap();
+    assert_eq!(value, batch_tree.get_leaf(&key).unwrap());
+
+    // invalid updates leave the tree unchanged
+    let tree = batch_tree.clone();
+    let invalid_key = SmtKey::from_u64(1 << 32);
+    assert!(batch_tree
+        .update_many([(key, Digest256::default()), (invalid_key, value)])
+        .is_err());
+    assert_eq!(tree, batch_tree);
+}
+
+// PROOF TESTS
+// ================================================================================================
+
+#[test]
+fn prove_and_verify() {
+    let tree = build_tree(256, 8);
+    let root = tree.root();
+
+    for (key, &leaf) in tree.leaves() {
+        let proof = tree.prove(key).unwrap();
+        assert_eq!(256, proof.depth());
+        assert!(proof.verify_inclusion(root, 256, key, leaf).is_ok());
+        assert!(proof.verify_non_inclusion(root, 256, key).is_err());
+        assert!(proof.verify_inclusion(root, 256, &key.sibling(), leaf).is_err());
+    }
+
+    let key = SmtKey::new([7; 32]);
+    let proof = tree.prove(&key).unwrap();
+    assert!(proof.verify_non_inclusion(root, 256, &key).is_ok());
+    assert!(proof.verify_inclusion(root, 256, &key, Blake3_256::hash(&[1])).is_err());
+}
+
+#[test]
+fn verify_proof_of_wrong_depth() {
+    let mut tree = SparseMerkleTree::<Blake3_256>::new(16).unwrap();
+    let key = SmtKey::from_u64(0x1234);
+    let leaf = Blake3_256::hash(&[1]);
+    tree.insert(key, leaf).unwrap();
+    let proof = tree.prove(&key).unwrap();
+    assert!(proof.verify_inclusion(tree.root(), 16, &key, leaf).is_ok());
+
+    // an internal node of the tree can be passed off as a leaf of a shallower tree with the same
+    // root; such a proof must be rejected when verified against the actual depth of the tree
+    let lower_proof = SparseMerkleProof::<Blake3_256>::new(proof.path()[..8].to_vec());
+    let node = lower_proof.compute_root(&SmtKey::from_u64(0x34), leaf).unwrap();
+    let forged_proof = SparseMerkleProof::<Blake3_256>::new(proof.path()[8..].to_vec());
+    let forged_key = SmtKey::from_u64(0x12);
+    assert_eq!(tree.root(), forged_proof.compute_root(&forged_key, node).unwrap());
+
+    assert_eq!(
+        Err(MerkleTreeError::ProofDepthMismatch(16, 8)),
+        forged_proof.verify_inclusion(tree.root(), 16, &forged_key, node)
+    );
+    assert_eq!(
+        Err(MerkleTreeError::ProofDepthMismatch(16, 8)),
+        forged_proof.verify_non_inclusion(tree.root(), 16, &forged_key)
+    );
+}
+
+#[test]
+fn compute_updated_root() {
+    let mut tree = build_tree(64, 16);
+    let old_root = tree.root();
+
+    // update an existing leaf
+    let (&key, &old_leaf) = tree.leaves().nth(3).unwrap();
+    let new_leaf = Blake3_256::hash(&[1, 2, 3]);
+    let proof = tree.prove(&key).unwrap();
+    tree.insert(key, new_leaf).unwrap();
+    assert_eq!(old_root, proof.compute_root(&key, old_leaf).unwrap());
+    assert_eq!(tree.root(), proof.compute_root(&key, new_leaf).unwrap());
+
+    // insert a new leaf
+    let old_root = tree.root();
+    let key = SmtKey::from_u64(u64::MAX);
+    let proof = tree.prove(&key).unwrap();
+    assert!(proof.verify_non_inclusion(old_root, 64, &key).is_ok());
+    tree.insert(key, new_leaf).unwrap();
+    assert_eq!(tree.root(), proof.compute_root(&key, new_leaf).unwrap());
+}
+
+// SERIALIZATION TESTS
+// ================================================================================================
+
+#[test]
+fn proof_serialization() {
+    let tree = build_tree(256, 4);
+    let key = *tree.leaves().next().unwrap().0;
+    let proof = tree.prove(&key).unwrap();
+
+    // siblings which are roots of empty subtrees are not serialized
+    let bytes = proof.to_bytes();
+    let num_siblings = proof
+        .path()
+        .iter()
+        .enumerate()
+        .filter(|&(height, sibling)| *sibling != tree.empty_root(height))
+        .count();
+    assert!(num_siblings <= 3);
+    assert_eq!(2 + 32 + 32 * num_siblings, bytes.len());
+    assert_eq!(proof, SparseMerkleProof::read_from_bytes(&bytes).unwrap());
+
+    // proofs of invalid depth are rejected
+    let mut bytes = bytes;
+    bytes[..2].copy_from_slice(&0_u16.to_le_bytes());
+    assert!(SparseMerkleProof::<Blake3_256>::read_from_bytes(&bytes).is_err());
+}
+
+#[test]
+fn tree_serialization() {
+    let tree = build_tree(64, 16);
+    let bytes = tree.to_bytes();
+    let result = SparseMerkleTree::<Blake3_256>::read_from_bytes(&bytes).unwrap();
+    assert_eq!(tree, result);
+    assert_eq!(tree.root(), result.root());
+}
+
+proptest! {
+    #[test]
+    fn proofs_resolve_to_root(keys in prop::collection::vec(any::<u64>(), 1..32), depth in 1..=64_usize) {
+        let leaves = keys
+            .iter()
+            .map(|&key| (SmtKey::from_u64(key >> (64 - depth)), Blake3_256::hash(&key.to_le_bytes())));
+        let tree = SparseMerkleTree::<Blake3_256>::with_leaves(depth, leaves).unwrap();
+
+        for &key in keys.iter() {
+            let key = SmtKey::from_u64(key >> (64 - depth));
+            let leaf = tree.get_leaf(&key).unwrap();
+            let proof = tree.prove(&key).unwrap();
+            prop_assert_eq!(tree.root(), proof.compute_root(&key, leaf).unwrap());
+
+            let proof = SparseMerkleProof::<Blake3_256>::read_from_bytes(&proof.to_bytes()).unwrap();
+            prop_assert!(proof.verify_inclusion(tree.root(), depth, &key, leaf).is_ok());
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn build_tree(depth: usize, num_leaves: u8) -> SparseMerkleTree<Blake3_256> {
+    let leaves = (0..num_leaves).map(|i| {
+        let hash = Blake3_256::hash(&[i]);
+        let key = SmtKey::new(hash.as_bytes()[..32].try_into().unwrap()) >> (256 - depth);
+        (key, Blake3_256::hash(&[i, i]))
+    });
+    SparseMerkleTree::with_leaves(depth, leaves).unwrap()
+}
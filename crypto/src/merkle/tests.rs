@@ -211,6 +211,171 @@ fn verify_batch() {
     assert!(MerkleTree::verify_batch(tree.root(), &[0, 1, 2, 3, 4, 5, 6, 7], &proof).is_ok());
 }
 
+#[test]
+fn new_tree_with_cap() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree = MerkleTree::<Blake3_256>::with_cap_height(leaves.clone(), 1).unwrap();
+    assert_eq!(1, tree.cap_height());
+    let cap = vec![
+        hash_2x1(hash_2x1(leaves[0], leaves[1]), hash_2x1(leaves[2], leaves[3])),
+        hash_2x1(hash_2x1(leaves[4], leaves[5]), hash_2x1(leaves[6], leaves[7])),
+    ];
+    assert_eq!(cap, tree.cap());
+    assert_eq!(tree.root(), &MerkleTree::<Blake3_256>::get_root_from_cap(tree.cap()));
+
+    let tree = MerkleTree::<Blake3_256>::new(leaves.clone()).unwrap();
+    assert_eq!(&[*tree.root()], tree.cap());
+
+    let result = MerkleTree::<Blake3_256>::with_cap_height(leaves, 3);
+    assert_eq!(Err(MerkleTreeError::InvalidCapHeight(3, 3)), result.map(|_| ()));
+}
+
+#[test]
+fn prove_batch_with_cap() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree = MerkleTree::<Blake3_256>::with_cap_height(leaves.clone(), 1).unwrap();
+
+    // the paths stop one level below the root
+    let proof = tree.prove_batch(&[1, 6]).unwrap();
+    let expected_nodes = vec![
+        vec![leaves[0], hash_2x1(leaves[2], leaves[3])],
+        vec![leaves[7], hash_2x1(leaves[4], leaves[5])],
+    ];
+    assert_eq!(expected_nodes, proof.nodes);
+    assert_eq!(3, proof.depth);
+
+    let expected_cap_nodes = vec![(0, tree.cap()[0]), (1, tree.cap()[1])];
+    assert_eq!(expected_cap_nodes, proof.get_cap_nodes(&[1, 6], 1).unwrap());
+
+    // the cap can be as low as the parents of the leaves
+    let tree = MerkleTree::<Blake3_256>::with_cap_height(leaves.clone(), 2).unwrap();
+    let proof = tree.prove_batch(&[1, 2]).unwrap();
+    assert_eq!(vec![vec![leaves[0]], vec![leaves[3]]], proof.nodes);
+}
+
+#[test]
+fn verify_batch_with_cap() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree = MerkleTree::<Blake3_256>::with_cap_height(leaves, 2).unwrap();
+
+    let proof = tree.prove_batch(&[1]).unwrap();
+    assert!(MerkleTree::verify_batch_with_cap(tree.cap(), &[1], &proof).is_ok());
+    assert!(MerkleTree::verify_batch_with_cap(tree.cap(), &[2], &proof).is_err());
+    assert!(MerkleTree::verify_batch(tree.root(), &[1], &proof).is_err());
+
+    let proof = tree.prove_batch(&[1, 3, 6]).unwrap();
+    assert!(MerkleTree::verify_batch_with_cap(tree.cap(), &[1, 3, 6], &proof).is_ok());
+    assert!(MerkleTree::verify_batch_with_cap(&tree.cap()[..3], &[1, 3, 6], &proof).is_err());
+    assert!(MerkleTree::verify_batch_with_cap(&tree.cap()[..2], &[1, 3, 6], &proof).is_err());
+
+    let proof = tree.prove_batch(&[0, 1, 2, 3, 4, 5, 6, 7]).unwrap();
+    let indexes = [0, 1, 2, 3, 4, 5, 6, 7];
+    assert!(MerkleTree::verify_batch_with_cap(tree.cap(), &indexes, &proof).is_ok());
+}
+
+#[test]
+fn new_tree_with_arity() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+
+    // depth 3 is not a multiple of 2, and thus, the root has only two children
+    let tree = MerkleTree::<Blake3_256>::with_arity(leaves.clone(), 4, 0).unwrap();
+    assert_eq!(4, tree.arity());
+    assert_eq!(3, tree.depth());
+    let node2 = hash_many(&leaves[..4]);
+    let node3 = hash_many(&leaves[4..]);
+    assert_eq!(hash_2x1(node2, node3), *tree.root());
+
+    // cap height is rounded down to the nearest level of the tree
+    let tree = MerkleTree::<Blake3_256>::with_arity(leaves.clone(), 4, 2).unwrap();
+    assert_eq!(1, tree.cap_height());
+    assert_eq!(&[node2, node3], tree.cap());
+
+    // a single level tree
+    let tree = MerkleTree::<Blake3_256>::with_arity(leaves.clone(), 8, 0).unwrap();
+    assert_eq!(hash_many(&leaves), *tree.root());
+
+    // binary trees are not affected by the arity
+    let tree = MerkleTree::<Blake3_256>::with_arity(leaves.clone(), 2, 0).unwrap();
+    assert_eq!(*MerkleTree::<Blake3_256>::new(leaves.clone()).unwrap().root(), *tree.root());
+
+    assert_eq!(
+        Some(MerkleTreeError::InvalidArity(3)),
+        MerkleTree::<Blake3_256>::with_arity(leaves.clone(), 3, 0).err()
+    );
+    assert_eq!(
+        Some(MerkleTreeError::InvalidArity(32)),
+        MerkleTree::<Blake3_256>::with_arity(leaves, 32, 0).err()
+    );
+}
+
+#[test]
+fn prove_with_arity() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree = MerkleTree::<Blake3_256>::with_arity(leaves.clone(), 4, 0).unwrap();
+
+    let proof = vec![leaves[5], leaves[4], leaves[6], leaves[7], hash_many(&leaves[..4])];
+    assert_eq!(proof, tree.prove(5).unwrap());
+}
+
+#[test]
+fn prove_batch_with_arity() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let leaves = [leaves.clone(), leaves].concat();
+    let tree = MerkleTree::<Blake3_256>::with_arity(leaves.clone(), 4, 0).unwrap();
+
+    let proof = tree.prove_batch(&[1, 2, 9]).unwrap();
+    let expected_nodes = vec![
+        vec![leaves[0], leaves[3], hash_many(&leaves[4..8]), hash_many(&leaves[12..])],
+        vec![leaves[8], leaves[10], leaves[11]],
+    ];
+    assert_eq!(expected_nodes, proof.nodes);
+    assert_eq!(4, proof.depth);
+    assert_eq!(4, proof.arity);
+    assert_eq!(vec![leaves[1], leaves[2], leaves[9]], proof.leaves);
+
+    assert!(MerkleTree::verify_batch(tree.root(), &[1, 2, 9], &proof).is_ok());
+    assert!(MerkleTree::verify_batch(tree.root(), &[1, 2, 10], &proof).is_err());
+    assert_eq!(Err(MerkleTreeError::InvalidArity(4)), proof.into_paths(&[1, 2, 9]));
+
+    // with a cap, paths stop at the level below the root
+    let tree = MerkleTree::<Blake3_256>::with_arity(leaves.clone(), 4, 3).unwrap();
+    assert_eq!(2, tree.cap_height());
+    let proof = tree.prove_batch(&[1, 2, 9]).unwrap();
+    assert_eq!(
+        vec![vec![leaves[0], leaves[3]], vec![leaves[8], leaves[10], leaves[11]]],
+        proof.nodes
+    );
+    assert!(MerkleTree::verify_batch_with_cap(tree.cap(), &[1, 2, 9], &proof).is_ok());
+    assert!(MerkleTree::verify_batch_with_cap(&tree.cap()[..2], &[1, 2, 9], &proof).is_err());
+}
+
+#[test]
+fn vector_commitment() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let leaves = [leaves.clone(), leaves].concat();
+    let options = VectorCommitmentOptions::new(2, 2);
+    let tree = <MerkleTree<Blake3_256> as VectorCommitment<Blake3_256>>::with_options(
+        leaves.clone(),
+        options,
+    )
+    .unwrap();
+    assert_eq!(tree.cap(), tree.commitment());
+    assert_eq!(16, tree.domain_len());
+    assert_eq!(*tree.root(), MerkleTree::<Blake3_256>::commitment_digest(tree.commitment()));
+
+    // opening proofs round-trip through serialization and verify against the commitment
+    let indexes = [1, 2, 9];
+    let proof = tree.open_many(&indexes).unwrap();
+    let proof_bytes = MerkleTree::<Blake3_256>::serialize_multiproof(&proof);
+    let opened = indexes.iter().map(|&i| leaves[i]).collect::<Vec<_>>();
+    let mut reader = utils::SliceReader::new(&proof_bytes);
+    let parsed =
+        MerkleTree::<Blake3_256>::deserialize_multiproof(&mut reader, opened, 16, options).unwrap();
+    assert_eq!(proof, parsed);
+    assert!(MerkleTree::verify_many(tree.commitment(), &indexes, &parsed).is_ok());
+    assert!(MerkleTree::verify_many(tree.commitment(), &[1, 2, 10], &parsed).is_err());
+}
+
 #[test]
 fn verify_into_paths() {
     let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
@@ -256,6 +421,22 @@ proptest! {
         prop_assert!(MerkleTree::verify_batch(tree.root(), &indices[..], &proof).is_ok());
     }
 
+    #[test]
+    fn prove_batch_n_verify_with_arity(data in prop::collection::vec(any::<[u8; 32]>(), 128),
+                      arity in prop::sample::select(vec![2usize, 4, 8, 16]),
+                      cap_height in 0..7usize,
+                      proof_indices in prop::collection::vec(any::<prop::sample::Index>(), 1..20)
+    )  {
+        let leaves = Digest256::bytes_as_digests(&data).to_vec();
+        let tree = MerkleTree::<Blake3_256>::with_arity(leaves, arity, cap_height).unwrap();
+        let options = VectorCommitmentOptions::new(arity, cap_height);
+        prop_assert_eq!(tree.cap().len(), MerkleTree::<Blake3_256>::commitment_len(128, options));
+        let mut indices: Vec<usize> = proof_indices.iter().map(|idx| idx.index(128)).collect();
+        indices.sort_unstable(); indices.dedup();
+        let proof = tree.prove_batch(&indices[..]).unwrap();
+        prop_assert!(MerkleTree::verify_batch_with_cap(tree.cap(), &indices[..], &proof).is_ok());
+    }
+
     #[test]
     fn batch_proof_from_paths(tree in random_blake3_merkle_tree(128),
                       proof_indices in prop::collection::vec(any::<prop::sample::Index>(), 10..20)
@@ -298,6 +479,10 @@ fn hash_2x1(v1: Digest256, v2: Digest256) -> Digest256 {
     Blake3_256::merge(&[v1, v2])
 }
 
+fn hash_many(values: &[Digest256]) -> Digest256 {
+    Blake3_256::merge_many(values)
+}
+
 pub fn random_blake3_merkle_tree(
     leave_count: usize,
 ) -> impl Strategy<Value = MerkleTree<Blake3_256>> {
@@ -3,7 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::{errors::RandomCoinError, Digest, ElementHasher, RandomCoin};
+use crate::{errors::RandomCoinError, Digest, ElementHasher, HashDomain, RandomCoin};
 use alloc::vec::Vec;
 use math::{FieldElement, StarkField};
 
@@ -26,6 +26,11 @@ use math::{FieldElement, StarkField};
 /// - We can also re-seed the coin with a new value. During the reseeding procedure, the
 ///   seed is set to hash(`old_seed` || `new_seed`), and the counter is reset to 0.
 ///
+/// The seed is updated and drawn from in the [HashDomain::Transcript] domain, while proof-of-work
+/// checks and nonces are hashed in the [HashDomain::ProofOfWork] domain. These domains affect the
+/// output only for hash functions which provide domain separation (e.g.,
+/// [DomainSeparated](crate::DomainSeparated)).
+///
 /// # Examples
 /// ```
 /// # use winter_crypto::{RandomCoin, DefaultRandomCoin, Hasher, hashers::Blake3_256};
@@ -69,7 +74,7 @@ impl<H: ElementHasher> DefaultRandomCoin<H> {
     /// Updates the state by incrementing the counter and returns hash(seed || counter)
     fn next(&mut self) -> H::Digest {
         self.counter += 1;
-        H::merge_with_int(self.seed, self.counter)
+        H::merge_with_int_in_domain(self.seed, self.counter, HashDomain::Transcript)
     }
 }
 
@@ -81,7 +86,7 @@ impl<B: StarkField, H: ElementHasher<BaseField = B>> RandomCoin for DefaultRando
     // --------------------------------------------------------------------------------------------
     /// Returns a new random coin instantiated with the provided `seed`.
     fn new(seed: &[Self::BaseField]) -> Self {
-        let seed = H::hash_elements(seed);
+        let seed = H::hash_elements_in_domain(seed, HashDomain::Transcript);
         Self { seed, counter: 0 }
     }
 
@@ -112,7 +117,17 @@ impl<B: StarkField, H: ElementHasher<BaseField = B>> RandomCoin for DefaultRando
     /// assert_ne!(e1, e2);
     /// ```
     fn reseed(&mut self, data: H::Digest) {
-        self.seed = H::merge(&[self.seed, data]);
+        self.seed = H::merge_in_domain(&[self.seed, data], HashDomain::Transcript);
+        self.counter = 0;
+    }
+
+    /// Reseeds the coin with the specified integer by setting the new seed to
+    /// hash(`seed` || `value`).
+    ///
+    /// This is used to absorb proof-of-work nonces into the coin so that subsequent draws depend
+    /// on them.
+    fn reseed_with_int(&mut self, value: u64) {
+        self.seed = H::merge_with_int_in_domain(self.seed, value, HashDomain::ProofOfWork);
         self.counter = 0;
     }
 
@@ -122,7 +137,7 @@ impl<B: StarkField, H: ElementHasher<BaseField = B>> RandomCoin for DefaultRando
     /// Computes hash(`seed` || `value`) and returns the number of leading zeros in the resulting
     /// value if it is interpreted as an integer in big-endian byte order.
     fn check_leading_zeros(&self, value: u64) -> u32 {
-        let new_seed = H::merge_with_int(self.seed, value);
+        let new_seed = H::merge_with_int_in_domain(self.seed, value, HashDomain::ProofOfWork);
         let bytes = new_seed.as_bytes();
         let seed_head = u64::from_le_bytes(bytes[..8].try_into().unwrap());
         seed_head.trailing_zeros()
@@ -195,7 +210,7 @@ impl<B: StarkField, H: ElementHasher<BaseField = B>> RandomCoin for DefaultRando
         assert!(num_values < domain_size, "number of values must be smaller than domain size");
 
         // reseed with nonce
-        self.seed = H::merge_with_int(self.seed, nonce);
+        self.seed = H::merge_with_int_in_domain(self.seed, nonce, HashDomain::ProofOfWork);
         self.counter = 0;
 
         // determine how many bits are needed to represent valid values in the domain
@@ -10,6 +10,14 @@ use math::{FieldElement, StarkField};
 mod default;
 pub use default::DefaultRandomCoin;
 
+mod sponge;
+pub use sponge::SpongeRandomCoin;
+
+#[cfg(feature = "std")]
+mod recording;
+#[cfg(feature = "std")]
+pub use recording::{CoinEvent, CoinTranscript, RecordingRandomCoin};
+
 // RANDOM COIN TRAIT
 // ================================================================================================
 
@@ -64,4 +72,16 @@ pub trait RandomCoin: Sync {
         domain_size: usize,
         nonce: u64,
     ) -> Result<Vec<usize>, RandomCoinError>;
+
+    // PROVIDED METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Reseeds the coin with the specified integer.
+    ///
+    /// By default, the coin is reseeded with the hash of the little-endian bytes of `value`;
+    /// implementations are encouraged to override this with a cheaper method which sets the new
+    /// seed to hash(`seed` || `value`).
+    fn reseed_with_int(&mut self, value: u64) {
+        self.reseed(<Self::Hasher as Hasher>::hash(&value.to_le_bytes()));
+    }
 }
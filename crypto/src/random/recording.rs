@@ -0,0 +1,440 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{errors::RandomCoinError, Hasher, RandomCoin};
+use alloc::vec::Vec;
+use math::{FieldElement, StarkField};
+use std::sync::Mutex;
+use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+// RECORDING RANDOM COIN
+// ================================================================================================
+
+/// Random coin which wraps another random coin and records all interactions with it.
+///
+/// Every call to the coin is forwarded to the wrapped coin `R`, and the inputs and outputs of the
+/// call are appended to a [CoinTranscript]. Thus, this coin produces exactly the same values as
+/// `R` does, and can be used in place of `R` to find out where transcripts of two parties (e.g.,
+/// a prover and a third-party verifier) diverge, or to generate test vectors for other
+/// implementations of the protocol.
+///
+/// Since [RandomCoin::check_leading_zeros()] takes `&self`, the transcript is kept behind a
+/// mutex, and thus, this coin is available only when the `std` feature is enabled. Recording
+/// adds noticeable overhead to proof-of-work checks, and therefore this coin should not be used
+/// in production.
+///
+/// # Examples
+/// ```
+/// # use winter_crypto::{
+/// #     CoinEvent, DefaultRandomCoin, RandomCoin, RecordingRandomCoin, hashers::Blake3_256,
+/// # };
+/// # use math::fields::f128::BaseElement;
+/// type Coin = DefaultRandomCoin<Blake3_256<BaseElement>>;
+///
+/// let seed = &[BaseElement::new(1), BaseElement::new(2), BaseElement::new(3), BaseElement::new(4)];
+/// let mut coin = Coin::new(seed);
+/// let mut recording_coin = RecordingRandomCoin::<Coin>::new(seed);
+///
+/// // the recording coin draws the same values as the wrapped coin
+/// let e1 = coin.draw::<BaseElement>().unwrap();
+/// let e2 = recording_coin.draw::<BaseElement>().unwrap();
+/// assert_eq!(e1, e2);
+///
+/// // and records all calls made to it
+/// let transcript = recording_coin.transcript();
+/// assert_eq!(2, transcript.len());
+/// assert_eq!(CoinEvent::Init { seed: seed.to_vec() }, transcript.events()[0]);
+/// assert_eq!(CoinEvent::Draw { element: vec![e2] }, transcript.events()[1]);
+/// ```
+pub struct RecordingRandomCoin<R: RandomCoin> {
+    coin: R,
+    transcript: Mutex<CoinTranscript<R::BaseField>>,
+}
+
+impl<R: RandomCoin> RecordingRandomCoin<R> {
+    /// Returns a copy of the transcript recorded by this coin so far.
+    pub fn transcript(&self) -> CoinTranscript<R::BaseField> {
+        self.transcript.lock().expect("transcript lock poisoned").clone()
+    }
+
+    /// Consumes this coin and returns the transcript recorded by it.
+    pub fn into_transcript(self) -> CoinTranscript<R::BaseField> {
+        self.transcript.into_inner().expect("transcript lock poisoned")
+    }
+
+    /// Returns a reference to the wrapped coin.
+    pub fn inner(&self) -> &R {
+        &self.coin
+    }
+
+    /// Appends the specified event to the transcript.
+    fn record(&self, event: CoinEvent<R::BaseField>) {
+        self.transcript.lock().expect("transcript lock poisoned").events.push(event);
+    }
+}
+
+impl<R: RandomCoin> RandomCoin for RecordingRandomCoin<R> {
+    type BaseField = R::BaseField;
+    type Hasher = R::Hasher;
+
+    fn new(seed: &[Self::BaseField]) -> Self {
+        let result = Self {
+            coin: R::new(seed),
+            transcript: Mutex::new(CoinTranscript::new()),
+        };
+        result.record(CoinEvent::Init {
+            seed: seed.to_vec(),
+        });
+        result
+    }
+
+    fn reseed(&mut self, data: <Self::Hasher as Hasher>::Digest) {
+        self.coin.reseed(data);
+        self.record(CoinEvent::Reseed {
+            data: data.to_bytes(),
+        });
+    }
+
+    fn reseed_with_int(&mut self, value: u64) {
+        self.coin.reseed_with_int(value);
+        self.record(CoinEvent::ReseedWithInt { value });
+    }
+
+    fn check_leading_zeros(&self, value: u64) -> u32 {
+        let leading_zeros = self.coin.check_leading_zeros(value);
+        self.record(CoinEvent::CheckLeadingZeros {
+            value,
+            leading_zeros,
+        });
+        leading_zeros
+    }
+
+    fn draw<E: FieldElement<BaseField = Self::BaseField>>(&mut self) -> Result<E, RandomCoinError> {
+        let element = self.coin.draw::<E>()?;
+        let base_elements = E::slice_as_base_elements(&[element]).to_vec();
+        self.record(CoinEvent::Draw {
+            element: base_elements,
+        });
+        Ok(element)
+    }
+
+    fn draw_integers(
+        &mut self,
+        num_values: usize,
+        domain_size: usize,
+        nonce: u64,
+    ) -> Result<Vec<usize>, RandomCoinError> {
+        let values = self.coin.draw_integers(num_values, domain_size, nonce)?;
+        self.record(CoinEvent::DrawIntegers {
+            num_values,
+            domain_size,
+            nonce,
+            values: values.clone(),
+        });
+        Ok(values)
+    }
+}
+
+// COIN TRANSCRIPT
+// ================================================================================================
+
+/// A sequence of calls made to a [RecordingRandomCoin], in the order in which they were made.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoinTranscript<B: StarkField> {
+    events: Vec<CoinEvent<B>>,
+}
+
+impl<B: StarkField> CoinTranscript<B> {
+    /// Returns a new empty transcript.
+    pub fn new() -> Self {
+        Self { events: Vec::new() }
+    }
+
+    /// Returns the events recorded in this transcript.
+    pub fn events(&self) -> &[CoinEvent<B>] {
+        &self.events
+    }
+
+    /// Returns the number of events recorded in this transcript.
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns true if no events have been recorded in this transcript.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Returns the index of the first event in this transcript which does not match the
+    /// corresponding event in the `other` transcript, or None if the transcripts match.
+    ///
+    /// Proof-of-work checks do not affect the state of a coin, and a prover usually checks many
+    /// nonces while a verifier checks only the one sent by the prover. Thus, such checks are
+    /// skipped in both transcripts. If one transcript is a prefix of the other one, the returned
+    /// index points to the first event which is absent from the shorter transcript (this index
+    /// may be equal to the length of this transcript).
+    pub fn first_divergence(&self, other: &Self) -> Option<usize> {
+        let mut other_events = other.events.iter().filter(|event| !event.is_pow_check());
+        for (i, event) in self.events.iter().enumerate() {
+            if event.is_pow_check() {
+                continue;
+            }
+            match other_events.next() {
+                Some(other_event) if other_event == event => continue,
+                _ => return Some(i),
+            }
+        }
+        other_events.next().map(|_| self.events.len())
+    }
+}
+
+impl<B: StarkField> Default for CoinTranscript<B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: StarkField> Serializable for CoinTranscript<B> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_usize(self.events.len());
+        target.write_many(&self.events);
+    }
+}
+
+impl<B: StarkField> Deserializable for CoinTranscript<B> {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let num_events = source.read_usize()?;
+        let events = source.read_many(num_events)?;
+        Ok(Self { events })
+    }
+}
+
+// COIN EVENT
+// ================================================================================================
+
+/// A single call made to a [RecordingRandomCoin] together with its inputs and outputs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CoinEvent<B: StarkField> {
+    /// The coin was instantiated with the specified seed.
+    Init { seed: Vec<B> },
+    /// The coin was reseeded with a digest; the digest is recorded in its serialized form.
+    Reseed { data: Vec<u8> },
+    /// The coin was reseeded with an integer.
+    ReseedWithInt { value: u64 },
+    /// The number of leading zeros for the specified nonce was computed.
+    CheckLeadingZeros { value: u64, leading_zeros: u32 },
+    /// A field element was drawn from the coin; elements in extension fields are recorded as
+    /// their base field elements.
+    Draw { element: Vec<B> },
+    /// A set of integers was drawn from the coin after reseeding it with the specified nonce.
+    DrawIntegers {
+        num_values: usize,
+        domain_size: usize,
+        nonce: u64,
+        values: Vec<usize>,
+    },
+}
+
+impl<B: StarkField> CoinEvent<B> {
+    /// Returns true if this event is a proof-of-work check.
+    pub fn is_pow_check(&self) -> bool {
+        matches!(self, Self::CheckLeadingZeros { .. })
+    }
+}
+
+impl<B: StarkField> Serializable for CoinEvent<B> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            Self::Init { seed } => {
+                target.write_u8(0);
+                seed.write_into(target);
+            }
+            Self::Reseed { data } => {
+                target.write_u8(1);
+                data.write_into(target);
+            }
+            Self::ReseedWithInt { value } => {
+                target.write_u8(2);
+                target.write_u64(*value);
+            }
+            Self::CheckLeadingZeros {
+                value,
+                leading_zeros,
+            } => {
+                target.write_u8(3);
+                target.write_u64(*value);
+                target.write_u32(*leading_zeros);
+            }
+            Self::Draw { element } => {
+                target.write_u8(4);
+                element.write_into(target);
+            }
+            Self::DrawIntegers {
+                num_values,
+                domain_size,
+                nonce,
+                values,
+            } => {
+                target.write_u8(5);
+                target.write_usize(*num_values);
+                target.write_usize(*domain_size);
+                target.write_u64(*nonce);
+                target.write_usize(values.len());
+                values.iter().for_each(|&value| target.write_usize(value));
+            }
+        }
+    }
+}
+
+impl<B: StarkField> Deserializable for CoinEvent<B> {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(Self::Init {
+                seed: Vec::read_from(source)?,
+            }),
+            1 => Ok(Self::Reseed {
+                data: Vec::read_from(source)?,
+            }),
+            2 => Ok(Self::ReseedWithInt {
+                value: source.read_u64()?,
+            }),
+            3 => {
+                let value = source.read_u64()?;
+                let leading_zeros = source.read_u32()?;
+                Ok(Self::CheckLeadingZeros {
+                    value,
+                    leading_zeros,
+                })
+            }
+            4 => Ok(Self::Draw {
+                element: Vec::read_from(source)?,
+            }),
+            5 => {
+                let num_values = source.read_usize()?;
+                let domain_size = source.read_usize()?;
+                let nonce = source.read_u64()?;
+                let num_read = source.read_usize()?;
+                let values =
+                    (0..num_read).map(|_| source.read_usize()).collect::<Result<Vec<_>, _>>()?;
+                Ok(Self::DrawIntegers {
+                    num_values,
+                    domain_size,
+                    nonce,
+                    values,
+                })
+            }
+            tag => Err(DeserializationError::InvalidValue(format!(
+                "invalid random coin event tag: {tag}"
+            ))),
+        }
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{CoinEvent, CoinTranscript, RandomCoin, RecordingRandomCoin};
+    use crate::{hashers::Blake3_256, DefaultRandomCoin, Hasher};
+    use math::{
+        fields::{f128::BaseElement, QuadExtension},
+        FieldElement,
+    };
+    use utils::{Deserializable, Serializable};
+
+    type Blake3 = Blake3_256<BaseElement>;
+    type Coin = DefaultRandomCoin<Blake3>;
+
+    #[test]
+    fn recording_coin_matches_inner_coin() {
+        let seed = [BaseElement::new(1), BaseElement::new(2)];
+        let mut coin = Coin::new(&seed);
+        let mut recording_coin = RecordingRandomCoin::<Coin>::new(&seed);
+
+        let digest = Blake3::hash(&[1, 2, 3]);
+        coin.reseed(digest);
+        recording_coin.reseed(digest);
+        coin.reseed_with_int(42);
+        recording_coin.reseed_with_int(42);
+
+        let e1 = coin.draw::<QuadExtension<BaseElement>>().unwrap();
+        let e2 = recording_coin.draw::<QuadExtension<BaseElement>>().unwrap();
+        assert_eq!(e1, e2);
+        assert_eq!(coin.check_leading_zeros(7), recording_coin.check_leading_zeros(7));
+
+        let v1 = coin.draw_integers(8, 64, 3).unwrap();
+        let v2 = recording_coin.draw_integers(8, 64, 3).unwrap();
+        assert_eq!(v1, v2);
+
+        let expected = vec![
+            CoinEvent::Init {
+                seed: seed.to_vec(),
+            },
+            CoinEvent::Reseed {
+                data: digest.to_bytes(),
+            },
+            CoinEvent::ReseedWithInt { value: 42 },
+            CoinEvent::Draw {
+                element: QuadExtension::slice_as_base_elements(&[e2]).to_vec(),
+            },
+            CoinEvent::CheckLeadingZeros {
+                value: 7,
+                leading_zeros: coin.check_leading_zeros(7),
+            },
+            CoinEvent::DrawIntegers {
+                num_values: 8,
+                domain_size: 64,
+                nonce: 3,
+                values: v2,
+            },
+        ];
+        assert_eq!(expected, recording_coin.into_transcript().events());
+    }
+
+    #[test]
+    fn transcript_divergence() {
+        let seed = [BaseElement::new(1), BaseElement::new(2)];
+        let mut coin1 = RecordingRandomCoin::<Coin>::new(&seed);
+        let mut coin2 = RecordingRandomCoin::<Coin>::new(&seed);
+
+        // proof-of-work checks are ignored
+        coin1.reseed(Blake3::hash(&[1]));
+        (0..5).for_each(|nonce| _ = coin1.check_leading_zeros(nonce));
+        coin1.reseed_with_int(4);
+        coin2.reseed(Blake3::hash(&[1]));
+        coin2.check_leading_zeros(4);
+        coin2.reseed_with_int(4);
+        assert_eq!(None, coin1.transcript().first_divergence(&coin2.transcript()));
+
+        // a missing event is reported at the end of the shorter transcript
+        coin1.draw::<BaseElement>().unwrap();
+        assert_eq!(Some(4), coin2.transcript().first_divergence(&coin1.transcript()));
+        assert_eq!(Some(8), coin1.transcript().first_divergence(&coin2.transcript()));
+
+        // diverging reseeds are reported at their position
+        coin2.reseed(Blake3::hash(&[2]));
+        assert_eq!(Some(4), coin2.transcript().first_divergence(&coin1.transcript()));
+    }
+
+    #[test]
+    fn transcript_serialization() {
+        let seed = [BaseElement::new(1), BaseElement::new(2)];
+        let mut coin = RecordingRandomCoin::<Coin>::new(&seed);
+        coin.reseed(Blake3::hash(&[1, 2, 3]));
+        coin.draw::<BaseElement>().unwrap();
+        coin.check_leading_zeros(5);
+        coin.draw_integers(4, 16, 5).unwrap();
+
+        let transcript = coin.into_transcript();
+        let bytes = transcript.to_bytes();
+        assert_eq!(transcript, CoinTranscript::read_from_bytes(&bytes).unwrap());
+
+        // events with unknown tags are rejected; the first byte encodes the number of events
+        let mut bytes = bytes;
+        bytes[1] = 6;
+        assert!(CoinTranscript::<BaseElement>::read_from_bytes(&bytes).is_err());
+    }
+}
@@ -0,0 +1,399 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{errors::RandomCoinError, AlgebraicSponge, Hasher, RandomCoin};
+use alloc::{vec, vec::Vec};
+use core::marker::PhantomData;
+use math::{FieldElement, StarkField};
+
+// SPONGE RANDOM COIN IMPLEMENTATION
+// ================================================================================================
+
+/// Pseudo-random element generator for finite fields built as a duplex sponge over an algebraic
+/// permutation.
+///
+/// Unlike [DefaultRandomCoin](crate::DefaultRandomCoin), this coin never hashes digests or
+/// counters; instead, it absorbs field elements directly into the rate portion of the sponge
+/// state and squeezes field elements directly out of it. This makes the transcript cheap to
+/// replicate inside an AIR: every reseeding costs a single permutation, and a single permutation
+/// yields as many pseudo-random elements as the width of the rate.
+///
+/// The coin works as follows:
+/// - At instantiation time, the first capacity element of the state is set to the number of seed
+///   elements, and the seed is absorbed into the state.
+/// - To absorb a sequence of elements, the elements are written into the rate portion of the
+///   state (overwriting its prior contents) in chunks of at most rate width elements, and the
+///   permutation is applied after each chunk. Digests are absorbed as their field elements, and
+///   integers are absorbed as two field elements containing their low and high 32-bit limbs.
+/// - To draw an element, the next unused rate element is returned. Once all rate elements have
+///   been used, the permutation is applied and drawing resumes from the start of the rate.
+///   Elements in an extension field are assembled from consecutive base field elements.
+/// - Integers are drawn by taking the low bits of the canonical representation of drawn base
+///   field elements.
+/// - Proof-of-work checks are computed over the first rate element produced after absorbing a
+///   nonce. When the coin is reseeded with an integer, this element is discarded so that values
+///   drawn afterwards are independent of the proof-of-work.
+///
+/// # Examples
+/// ```
+/// # use winter_crypto::{RandomCoin, SpongeRandomCoin, hashers::Rp64_256};
+/// # use math::fields::f64::BaseElement;
+/// // initial elements for seeding the random coin
+/// let seed = &[BaseElement::new(1), BaseElement::new(2), BaseElement::new(3), BaseElement::new(4)];
+///
+/// // instantiate a random coin using Rescue Prime as the permutation
+/// let mut coin = SpongeRandomCoin::<Rp64_256>::new(seed);
+///
+/// // should draw different elements each time
+/// let e1 = coin.draw::<BaseElement>().unwrap();
+/// let e2 = coin.draw::<BaseElement>().unwrap();
+/// assert_ne!(e1, e2);
+///
+/// // should draw same elements for the same seed
+/// let mut coin1 = SpongeRandomCoin::<Rp64_256>::new(seed);
+/// let mut coin2 = SpongeRandomCoin::<Rp64_256>::new(seed);
+/// assert_eq!(coin1.draw::<BaseElement>().unwrap(), coin2.draw::<BaseElement>().unwrap());
+///
+/// // should draw different elements based on seed
+/// let mut coin1 = SpongeRandomCoin::<Rp64_256>::new(seed);
+/// let seed = &[BaseElement::new(2), BaseElement::new(3), BaseElement::new(4), BaseElement::new(5)];
+/// let mut coin2 = SpongeRandomCoin::<Rp64_256>::new(seed);
+/// assert_ne!(coin1.draw::<BaseElement>().unwrap(), coin2.draw::<BaseElement>().unwrap());
+/// ```
+pub struct SpongeRandomCoin<H: AlgebraicSponge> {
+    state: Vec<H::BaseField>,
+    output_idx: usize,
+    _hasher: PhantomData<fn() -> H>,
+}
+
+impl<H: AlgebraicSponge> SpongeRandomCoin<H> {
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the current state of the underlying sponge.
+    pub fn state(&self) -> &[H::BaseField] {
+        &self.state
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Absorbs the low and high 32-bit limbs of the specified integer into the sponge.
+    fn absorb_int(&mut self, value: u64) {
+        self.absorb(&int_to_elements(value));
+    }
+
+    /// Writes the provided elements into the rate portion of the state in chunks, applying the
+    /// permutation after each chunk.
+    fn absorb(&mut self, elements: &[H::BaseField]) {
+        let rate_width = H::RATE_RANGE.end - H::RATE_RANGE.start;
+        for chunk in elements.chunks(rate_width) {
+            let start = H::RATE_RANGE.start;
+            self.state[start..start + chunk.len()].copy_from_slice(chunk);
+            H::permute(&mut self.state);
+            self.output_idx = start;
+        }
+    }
+
+    /// Returns the next unused rate element, applying the permutation first if all rate elements
+    /// have already been used.
+    fn squeeze(&mut self) -> H::BaseField {
+        if self.output_idx == H::RATE_RANGE.end {
+            H::permute(&mut self.state);
+            self.output_idx = H::RATE_RANGE.start;
+        }
+        let result = self.state[self.output_idx];
+        self.output_idx += 1;
+        result
+    }
+}
+
+impl<H: AlgebraicSponge> Clone for SpongeRandomCoin<H> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+            output_idx: self.output_idx,
+            _hasher: PhantomData,
+        }
+    }
+}
+
+impl<B: StarkField, H: AlgebraicSponge<BaseField = B>> RandomCoin for SpongeRandomCoin<H> {
+    type BaseField = B;
+    type Hasher = H;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new random coin instantiated with the provided `seed`.
+    ///
+    /// The number of seed elements is written into the capacity portion of the state before the
+    /// seed is absorbed.
+    fn new(seed: &[Self::BaseField]) -> Self {
+        let mut state = vec![B::ZERO; H::STATE_WIDTH];
+        state[H::CAPACITY_RANGE.start] = B::from(seed.len() as u32);
+
+        let mut coin = Self {
+            state,
+            output_idx: H::RATE_RANGE.end,
+            _hasher: PhantomData,
+        };
+        coin.absorb(seed);
+        coin
+    }
+
+    // RESEEDING
+    // --------------------------------------------------------------------------------------------
+
+    /// Reseeds the coin by absorbing the elements of the specified digest into the sponge.
+    fn reseed(&mut self, data: <Self::Hasher as Hasher>::Digest) {
+        self.absorb(H::digest_as_elements(&data));
+    }
+
+    /// Reseeds the coin by absorbing the low and high 32-bit limbs of the specified integer into
+    /// the sponge.
+    ///
+    /// The first rate element produced by the permutation is discarded because it is the element
+    /// used to check proof-of-work for `value`; otherwise, the first element drawn after reseeding
+    /// with a proof-of-work nonce would have its low bits set to zero.
+    fn reseed_with_int(&mut self, value: u64) {
+        self.absorb_int(value);
+        self.output_idx += 1;
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Absorbs `value` into a copy of the sponge and returns the number of trailing zeros in the
+    /// canonical representation of the first element squeezed out of it.
+    ///
+    /// Trailing rather than leading zeros are counted because the high bits of a canonical field
+    /// element are not uniformly distributed.
+    fn check_leading_zeros(&self, value: u64) -> u32 {
+        let mut coin = self.clone();
+        coin.absorb_int(value);
+        element_to_u64(coin.squeeze()).trailing_zeros()
+    }
+
+    // DRAW METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the next pseudo-random field element.
+    ///
+    /// Elements of extension fields are assembled from consecutive base field elements squeezed
+    /// out of the sponge. Drawing from the sponge never fails.
+    fn draw<E: FieldElement<BaseField = B>>(&mut self) -> Result<E, RandomCoinError> {
+        let elements = (0..E::EXTENSION_DEGREE).map(|_| self.squeeze()).collect::<Vec<_>>();
+        Ok(E::slice_from_base_elements(&elements)[0])
+    }
+
+    /// Returns a vector of integers selected from the range [0, domain_size) after reseeding the
+    /// coin with the specified `nonce`.
+    ///
+    /// Each integer is taken from the low bits of the canonical representation of a base field
+    /// element squeezed out of the sponge. Drawing from the sponge never fails.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// - `domain_size` is not a power of two.
+    /// - `num_values` is greater than or equal to `domain_size`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use winter_crypto::{RandomCoin, SpongeRandomCoin, hashers::Rp64_256};
+    /// # use math::fields::f64::BaseElement;
+    /// let seed = &[BaseElement::new(1), BaseElement::new(2), BaseElement::new(3), BaseElement::new(4)];
+    /// let mut coin = SpongeRandomCoin::<Rp64_256>::new(seed);
+    ///
+    /// let num_values = 20;
+    /// let domain_size = 64;
+    /// let values = coin.draw_integers(num_values, domain_size, 0).unwrap();
+    ///
+    /// assert_eq!(num_values, values.len());
+    /// for value in values {
+    ///     assert!(value < domain_size);
+    /// }
+    /// ```
+    fn draw_integers(
+        &mut self,
+        num_values: usize,
+        domain_size: usize,
+        nonce: u64,
+    ) -> Result<Vec<usize>, RandomCoinError> {
+        assert!(domain_size.is_power_of_two(), "domain size must be a power of two");
+        assert!(num_values < domain_size, "number of values must be smaller than domain size");
+
+        self.reseed_with_int(nonce);
+
+        let v_mask = (domain_size - 1) as u64;
+        let values = (0..num_values)
+            .map(|_| (element_to_u64(self.squeeze()) & v_mask) as usize)
+            .collect();
+
+        Ok(values)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Splits the provided integer into its low and high 32-bit limbs.
+fn int_to_elements<B: StarkField>(value: u64) -> [B; 2] {
+    [B::from(value as u32), B::from((value >> 32) as u32)]
+}
+
+/// Returns the low 64 bits of the canonical representation of the provided element.
+fn element_to_u64<B: StarkField>(element: B) -> u64 {
+    let bytes = element.to_bytes();
+    u64::from_le_bytes(bytes[..8].try_into().unwrap())
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{RandomCoin, SpongeRandomCoin};
+    use crate::{
+        hashers::{Poseidon2_64_256, Rp64_256, Rpo64_256, Tip5},
+        Hasher,
+    };
+    use math::{
+        fields::{f64::BaseElement, CubeExtension, QuadExtension},
+        FieldElement,
+    };
+
+    #[test]
+    fn sponge_coin_transcript() {
+        let seed = [BaseElement::new(1), BaseElement::new(2), BaseElement::new(3)];
+        let mut coin = SpongeRandomCoin::<Rp64_256>::new(&seed);
+
+        // the seed is absorbed with its length in the capacity
+        let mut state = [BaseElement::ZERO; 12];
+        state[0] = BaseElement::new(3);
+        state[4..7].copy_from_slice(&seed);
+        Rp64_256::apply_permutation(&mut state);
+        assert_eq!(state.as_slice(), coin.state());
+
+        // the first permutation yields 8 elements; the 9th requires another permutation
+        for element in state[4..12].iter() {
+            assert_eq!(*element, coin.draw::<BaseElement>().unwrap());
+        }
+        Rp64_256::apply_permutation(&mut state);
+        assert_eq!(state[4], coin.draw::<BaseElement>().unwrap());
+
+        // a digest overwrites the first rate elements and is followed by a permutation
+        let digest = Rp64_256::hash(&[1, 2, 3]);
+        coin.reseed(digest);
+        state[4..8].copy_from_slice(digest.as_elements());
+        Rp64_256::apply_permutation(&mut state);
+        assert_eq!(state.as_slice(), coin.state());
+        assert_eq!(state[4], coin.draw::<BaseElement>().unwrap());
+
+        // an integer is absorbed as two 32-bit limbs, and the first rate element produced after
+        // absorbing it is skipped
+        coin.reseed_with_int(0x0000_0005_0000_0007);
+        state[4] = BaseElement::new(7);
+        state[5] = BaseElement::new(5);
+        Rp64_256::apply_permutation(&mut state);
+        assert_eq!(state.as_slice(), coin.state());
+        assert_eq!(state[5], coin.draw::<BaseElement>().unwrap());
+    }
+
+    #[test]
+    fn sponge_coin_long_seed() {
+        // seeds longer than the rate are absorbed in multiple chunks
+        let seed = (0..20).map(BaseElement::new).collect::<alloc::vec::Vec<_>>();
+        let coin = SpongeRandomCoin::<Rpo64_256>::new(&seed);
+
+        let mut state = [BaseElement::ZERO; 12];
+        state[0] = BaseElement::new(20);
+        for chunk in seed.chunks(8) {
+            state[4..4 + chunk.len()].copy_from_slice(chunk);
+            Rpo64_256::apply_permutation(&mut state);
+        }
+        assert_eq!(state.as_slice(), coin.state());
+
+        // an empty seed still yields pseudo-random elements
+        let mut coin = SpongeRandomCoin::<Rpo64_256>::new(&[]);
+        assert_ne!(BaseElement::ZERO, coin.draw::<BaseElement>().unwrap());
+    }
+
+    #[test]
+    fn sponge_coin_draw_extension() {
+        let seed = [BaseElement::new(42)];
+        let mut coin1 = SpongeRandomCoin::<Poseidon2_64_256>::new(&seed);
+        let mut coin2 = SpongeRandomCoin::<Poseidon2_64_256>::new(&seed);
+
+        let e = coin1.draw::<QuadExtension<BaseElement>>().unwrap();
+        let b0 = coin2.draw::<BaseElement>().unwrap();
+        let b1 = coin2.draw::<BaseElement>().unwrap();
+        assert_eq!(QuadExtension::new(b0, b1), e);
+
+        let e = coin1.draw::<CubeExtension<BaseElement>>().unwrap();
+        let b0 = coin2.draw::<BaseElement>().unwrap();
+        let b1 = coin2.draw::<BaseElement>().unwrap();
+        let b2 = coin2.draw::<BaseElement>().unwrap();
+        assert_eq!(CubeExtension::new(b0, b1, b2), e);
+    }
+
+    #[test]
+    fn sponge_coin_draw_integers() {
+        let seed = [BaseElement::new(1), BaseElement::new(2)];
+        let mut coin1 = SpongeRandomCoin::<Tip5>::new(&seed);
+        let mut coin2 = coin1.clone();
+
+        let values = coin1.draw_integers(32, 1 << 10, 123).unwrap();
+        assert_eq!(32, values.len());
+
+        coin2.reseed_with_int(123);
+        for value in values {
+            let element = coin2.draw::<BaseElement>().unwrap();
+            assert_eq!((element.as_int() & 1023) as usize, value);
+        }
+    }
+
+    #[test]
+    fn sponge_coin_check_leading_zeros() {
+        let seed = [BaseElement::new(1), BaseElement::new(2)];
+        let coin = SpongeRandomCoin::<Rp64_256>::new(&seed);
+
+        // find a nonce with at least 4 trailing zeros and check that it is consistent with the
+        // first rate element produced after absorbing the nonce
+        let nonce = (0..).find(|&nonce| coin.check_leading_zeros(nonce) >= 4).unwrap();
+        let mut state = coin.state().to_vec();
+        state[4] = BaseElement::new(nonce);
+        state[5] = BaseElement::ZERO;
+        Rp64_256::apply_permutation(state.as_mut_slice().try_into().unwrap());
+        assert_eq!(state[4].as_int().trailing_zeros(), coin.check_leading_zeros(nonce));
+    }
+
+    #[test]
+    fn sponge_coin_draws_independent_of_pow() {
+        let seed = [BaseElement::new(1), BaseElement::new(2)];
+        let coin = SpongeRandomCoin::<Rp64_256>::new(&seed);
+
+        // for nonces satisfying a proof-of-work check with 4 bits, the first drawn integer and
+        // the first drawn element must not inherit the zero low bits of the proof-of-work output
+        let nonces = (0..).filter(|&nonce| coin.check_leading_zeros(nonce) >= 4).take(16);
+        let mut num_zero_integers = 0;
+        let mut num_zero_elements = 0;
+        for nonce in nonces {
+            let mut coin1 = coin.clone();
+            let index = coin1.draw_integers(8, 1 << 10, nonce).unwrap()[0];
+            if index & 0b1111 == 0 {
+                num_zero_integers += 1;
+            }
+
+            let mut coin2 = coin.clone();
+            coin2.reseed_with_int(nonce);
+            if coin2.draw::<BaseElement>().unwrap().as_int() & 0b1111 == 0 {
+                num_zero_elements += 1;
+            }
+        }
+        assert!(num_zero_integers < 16);
+        assert!(num_zero_elements < 16);
+    }
+}
@@ -4,9 +4,10 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{
-    BaseElement, DefaultRandomCoin, ElementHasher, FibAir, FieldElement, PhantomData, ProofOptions,
-    Prover, TRACE_WIDTH,
+    BaseElement, DefaultRandomCoin, ElementHasher, FibAir, FieldElement, MerkleTree, PhantomData,
+    ProofOptions, Prover, TRACE_WIDTH,
 };
+use winterfell::crypto::RandomCoin;
 use winterfell::{
     matrix::ColMatrix, AuxTraceRandElements, ConstraintCompositionCoefficients,
     DefaultConstraintEvaluator, DefaultTraceLde, StarkDomain, Trace, TraceInfo, TracePolyTable,
@@ -16,16 +17,20 @@ use winterfell::{
 // FIBONACCI PROVER
 // ================================================================================================
 
-pub struct FibProver<H: ElementHasher> {
+/// Prover for the Fibonacci sequence; the public coin defaults to [DefaultRandomCoin], but can be
+/// replaced (e.g., with a coin recording the transcript of the protocol).
+pub struct FibProver<H: ElementHasher, R = DefaultRandomCoin<H>> {
     options: ProofOptions,
     _hasher: PhantomData<H>,
+    _coin: PhantomData<R>,
 }
 
-impl<H: ElementHasher> FibProver<H> {
+impl<H: ElementHasher, R> FibProver<H, R> {
     pub fn new(options: ProofOptions) -> Self {
         Self {
             options,
             _hasher: PhantomData,
+            _coin: PhantomData,
         }
     }
 
@@ -50,15 +55,17 @@ impl<H: ElementHasher> FibProver<H> {
     }
 }
 
-impl<H: ElementHasher> Prover for FibProver<H>
+impl<H: ElementHasher, R> Prover for FibProver<H, R>
 where
     H: ElementHasher<BaseField = BaseElement>,
+    R: RandomCoin<BaseField = BaseElement, Hasher = H>,
 {
     type BaseField = BaseElement;
     type Air = FibAir;
     type Trace = TraceTable<BaseElement>;
     type HashFn = H;
-    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type RandomCoin = R;
+    type VC = MerkleTree<Self::HashFn>;
     type TraceLde<E: FieldElement<BaseField = Self::BaseField>> = DefaultTraceLde<E, Self::HashFn>;
     type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
         DefaultConstraintEvaluator<'a, Self::Air, E>;
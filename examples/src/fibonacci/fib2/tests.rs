@@ -3,7 +3,14 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{super::utils::build_proof_options, Blake3_256};
+use super::{
+    super::utils::build_proof_options, BaseElement, Blake3_256, Blake3_384, DefaultRandomCoin,
+    FibAir, FibProver, FieldElement, Keccak256, MerkleTree, Prover, Sha3_512,
+};
+use winterfell::{
+    crypto::{CoinEvent, DomainSeparated, ElementHasher, HashDomain, RecordingRandomCoin},
+    verify_with_coin, AcceptableOptions, Air, Serializable,
+};
 
 #[test]
 fn fib2_test_basic_proof_verification() {
@@ -22,3 +29,110 @@ fn fib2_test_basic_proof_verification_fail() {
     let fib = Box::new(super::FibExample::<Blake3_256>::new(16, build_proof_options(false)));
     crate::tests::test_basic_proof_verification_fail(fib);
 }
+
+#[test]
+fn fib2_test_basic_proof_verification_keccak() {
+    let fib = Box::new(super::FibExample::<Keccak256>::new(16, build_proof_options(false)));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
+#[test]
+fn fib2_test_basic_proof_verification_blake3_384() {
+    let fib = Box::new(super::FibExample::<Blake3_384>::new(16, build_proof_options(false)));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
+#[test]
+fn fib2_test_basic_proof_verification_sha3_512() {
+    let fib = Box::new(super::FibExample::<Sha3_512>::new(16, build_proof_options(false)));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
+#[test]
+fn fib2_test_basic_proof_verification_domain_separated() {
+    let fib = Box::new(super::FibExample::<DomainSeparated<Blake3_256>>::new(
+        16,
+        build_proof_options(false),
+    ));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
+#[test]
+fn fib2_test_prover_and_verifier_transcripts() {
+    type Coin = RecordingRandomCoin<DefaultRandomCoin<Blake3_256>>;
+    let options = build_proof_options(false);
+    let prover = FibProver::<Blake3_256, Coin>::new(options.clone());
+    let trace = prover.build_trace(16);
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    let (proof, prover_coin) = prover.prove_with_coin(trace).unwrap();
+    let prover_transcript = prover_coin.into_transcript();
+
+    let acceptable_options = AcceptableOptions::OptionSet(vec![options]);
+    let (result, verifier_coin) = verify_with_coin::<FibAir, Blake3_256, Coin, MerkleTree<_>>(
+        proof.clone(),
+        pub_inputs,
+        &acceptable_options,
+    );
+    assert!(result.is_ok());
+    let verifier_transcript = verifier_coin.unwrap().into_transcript();
+
+    // the verifier draws one more FRI folding challenge than the prover does (after reading the
+    // commitment to the remainder), and thus, the transcripts diverge right before the query
+    // positions are drawn; this does not affect the query positions because the coin is reseeded
+    // with the proof-of-work nonce before they are drawn
+    let divergence = prover_transcript.first_divergence(&verifier_transcript).unwrap();
+    assert!(matches!(prover_transcript.events()[divergence], CoinEvent::DrawIntegers { .. }));
+    assert_eq!(prover_transcript.events().last(), verifier_transcript.events().last());
+
+    // for wrong public inputs, the transcripts diverge when the coin is instantiated
+    let (result, verifier_coin) = verify_with_coin::<FibAir, Blake3_256, Coin, MerkleTree<_>>(
+        proof,
+        pub_inputs + FieldElement::ONE,
+        &acceptable_options,
+    );
+    assert!(result.is_err());
+    let verifier_transcript = verifier_coin.unwrap().into_transcript();
+    assert_eq!(Some(0), prover_transcript.first_divergence(&verifier_transcript));
+}
+
+#[test]
+fn fib2_test_transcript_domain_separation() {
+    type Hasher = DomainSeparated<Blake3_256>;
+    type Coin = RecordingRandomCoin<DefaultRandomCoin<Hasher>>;
+    let options = build_proof_options(false);
+    let prover = FibProver::<Hasher, Coin>::new(options.clone());
+    let trace = prover.build_trace(16);
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    let (proof, prover_coin) = prover.prove_with_coin(trace).unwrap();
+
+    let acceptable_options = AcceptableOptions::OptionSet(vec![options.clone()]);
+    let (result, verifier_coin) = verify_with_coin::<FibAir, Hasher, Coin, MerkleTree<_>>(
+        proof.clone(),
+        pub_inputs,
+        &acceptable_options,
+    );
+    assert!(result.is_ok());
+
+    // both the prover and the verifier must absorb the out-of-domain evaluations hashed in the
+    // transcript domain; if only one of them did, the transcripts would diverge at this point
+    let air = FibAir::new(proof.get_trace_info(), pub_inputs, options);
+    let num_evaluations = air.context().num_constraint_composition_columns();
+    let (trace_states, evaluations) =
+        proof.ood_frame.clone().parse::<BaseElement>(2, 0, num_evaluations).unwrap();
+    for values in [trace_states, evaluations] {
+        let tagged = Hasher::hash_elements_in_domain(&values, HashDomain::Transcript).to_bytes();
+        let untagged = Hasher::hash_elements(&values).to_bytes();
+        assert_ne!(tagged, untagged);
+
+        for transcript in [prover_coin.transcript(), verifier_coin.as_ref().unwrap().transcript()] {
+            let reseeded_with = |data: &Vec<u8>| {
+                transcript
+                    .events()
+                    .iter()
+                    .any(|event| event == &CoinEvent::Reseed { data: data.clone() })
+            };
+            assert!(reseeded_with(&tagged));
+            assert!(!reseeded_with(&untagged));
+        }
+    }
+}
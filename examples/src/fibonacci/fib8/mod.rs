@@ -4,14 +4,17 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::utils::compute_fib_term;
-use crate::{Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256};
+use crate::{
+    parse_pub_inputs, Blake3_192, Blake3_256, Blake3_384, Example, ExampleOptions, HashFunction,
+    Keccak256, Sha3_256, Sha3_512,
+};
 use core::marker::PhantomData;
 use std::time::Instant;
 use tracing::{field, info_span};
 use winterfell::{
-    crypto::{DefaultRandomCoin, ElementHasher},
+    crypto::{DefaultRandomCoin, ElementHasher, MerkleTree},
     math::{fields::f128::BaseElement, FieldElement},
-    ProofOptions, Prover, StarkProof, Trace, VerifierError,
+    ProofOptions, Prover, Serializable, StarkProof, Trace, VerifierError,
 };
 
 mod air;
@@ -47,6 +50,15 @@ pub fn get_example(
         HashFunction::Sha3_256 => {
             Ok(Box::new(Fib8Example::<Sha3_256>::new(sequence_length, options)))
         }
+        HashFunction::Keccak256 => {
+            Ok(Box::new(Fib8Example::<Keccak256>::new(sequence_length, options)))
+        }
+        HashFunction::Blake3_384 => {
+            Ok(Box::new(Fib8Example::<Blake3_384>::new(sequence_length, options)))
+        }
+        HashFunction::Sha3_512 => {
+            Ok(Box::new(Fib8Example::<Sha3_512>::new(sequence_length, options)))
+        }
         _ => Err("The specified hash function cannot be used with this example.".to_string()),
     }
 }
@@ -65,7 +77,7 @@ impl<H: ElementHasher> Fib8Example<H> {
         // compute Fibonacci sequence
         let now = Instant::now();
         let result = compute_fib_term(sequence_length);
-        println!(
+        eprintln!(
             "Computed Fibonacci sequence up to {}th term in {} ms",
             sequence_length,
             now.elapsed().as_millis()
@@ -88,7 +100,7 @@ where
     H: ElementHasher<BaseField = BaseElement>,
 {
     fn prove(&self) -> StarkProof {
-        println!(
+        eprintln!(
             "Generating proof for computing Fibonacci sequence (8 terms per step) up to {}th term",
             self.sequence_length
         );
@@ -109,12 +121,21 @@ where
         prover.prove(trace).unwrap()
     }
 
-    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+    fn get_pub_inputs(&self) -> Vec<u8> {
+        self.result.to_bytes()
+    }
+
+    fn verify_with_pub_inputs(
+        &self,
+        proof: StarkProof,
+        pub_inputs: &[u8],
+    ) -> Result<(), VerifierError> {
+        let pub_inputs = parse_pub_inputs(pub_inputs)?;
         let acceptable_options =
             winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
-        winterfell::verify::<Fib8Air, H, DefaultRandomCoin<H>>(
+        winterfell::verify::<Fib8Air, H, DefaultRandomCoin<H>, MerkleTree<H>>(
             proof,
-            self.result,
+            pub_inputs,
             &acceptable_options,
         )
     }
@@ -122,7 +143,7 @@ where
     fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
         let acceptable_options =
             winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
-        winterfell::verify::<Fib8Air, H, DefaultRandomCoin<H>>(
+        winterfell::verify::<Fib8Air, H, DefaultRandomCoin<H>, MerkleTree<H>>(
             proof,
             self.result + BaseElement::ONE,
             &acceptable_options,
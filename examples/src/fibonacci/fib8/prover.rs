@@ -4,7 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{
-    BaseElement, DefaultRandomCoin, ElementHasher, Fib8Air, FieldElement, PhantomData,
+    BaseElement, DefaultRandomCoin, ElementHasher, Fib8Air, FieldElement, MerkleTree, PhantomData,
     ProofOptions, Prover,
 };
 use winterfell::{
@@ -74,6 +74,7 @@ where
     type Trace = TraceTable<BaseElement>;
     type HashFn = H;
     type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type VC = MerkleTree<Self::HashFn>;
     type TraceLde<E: FieldElement<BaseField = Self::BaseField>> = DefaultTraceLde<E, Self::HashFn>;
     type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
         DefaultConstraintEvaluator<'a, Self::Air, E>;
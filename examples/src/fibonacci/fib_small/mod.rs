@@ -4,14 +4,14 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::utils::compute_fib_term;
-use crate::{Example, ExampleOptions, HashFunction};
+use crate::{parse_pub_inputs, Example, ExampleOptions, HashFunction};
 use core::marker::PhantomData;
 use std::time::Instant;
 use tracing::{field, info_span};
 use winterfell::{
-    crypto::{DefaultRandomCoin, ElementHasher},
+    crypto::{DefaultRandomCoin, ElementHasher, MerkleTree},
     math::{fields::f64::BaseElement, FieldElement},
-    ProofOptions, Prover, StarkProof, Trace, VerifierError,
+    ProofOptions, Prover, Serializable, StarkProof, Trace, VerifierError,
 };
 
 mod air;
@@ -31,8 +31,15 @@ const TRACE_WIDTH: usize = 2;
 type Blake3_192 = winterfell::crypto::hashers::Blake3_192<BaseElement>;
 type Blake3_256 = winterfell::crypto::hashers::Blake3_256<BaseElement>;
 type Sha3_256 = winterfell::crypto::hashers::Sha3_256<BaseElement>;
+type Keccak256 = winterfell::crypto::hashers::Keccak256<BaseElement>;
+type Blake3_384 = winterfell::crypto::hashers::Blake3_384<BaseElement>;
+type Sha3_512 = winterfell::crypto::hashers::Sha3_512<BaseElement>;
 type Rp64_256 = winterfell::crypto::hashers::Rp64_256;
 type RpJive64_256 = winterfell::crypto::hashers::RpJive64_256;
+type Rpo64_256 = winterfell::crypto::hashers::Rpo64_256;
+type Rpx64_256 = winterfell::crypto::hashers::Rpx64_256;
+type Poseidon2_64_256 = winterfell::crypto::hashers::Poseidon2_64_256;
+type Tip5 = winterfell::crypto::hashers::Tip5;
 
 // FIBONACCI EXAMPLE
 // ================================================================================================
@@ -53,12 +60,31 @@ pub fn get_example(
         HashFunction::Sha3_256 => {
             Ok(Box::new(FibExample::<Sha3_256>::new(sequence_length, options)))
         }
+        HashFunction::Keccak256 => {
+            Ok(Box::new(FibExample::<Keccak256>::new(sequence_length, options)))
+        }
+        HashFunction::Blake3_384 => {
+            Ok(Box::new(FibExample::<Blake3_384>::new(sequence_length, options)))
+        }
+        HashFunction::Sha3_512 => {
+            Ok(Box::new(FibExample::<Sha3_512>::new(sequence_length, options)))
+        }
         HashFunction::Rp64_256 => {
             Ok(Box::new(FibExample::<Rp64_256>::new(sequence_length, options)))
         }
         HashFunction::RpJive64_256 => {
             Ok(Box::new(FibExample::<RpJive64_256>::new(sequence_length, options)))
         }
+        HashFunction::Rpo64_256 => {
+            Ok(Box::new(FibExample::<Rpo64_256>::new(sequence_length, options)))
+        }
+        HashFunction::Rpx64_256 => {
+            Ok(Box::new(FibExample::<Rpx64_256>::new(sequence_length, options)))
+        }
+        HashFunction::Poseidon2_64_256 => {
+            Ok(Box::new(FibExample::<Poseidon2_64_256>::new(sequence_length, options)))
+        }
+        HashFunction::Tip5 => Ok(Box::new(FibExample::<Tip5>::new(sequence_length, options))),
     }
 }
 
@@ -76,7 +102,7 @@ impl<H: ElementHasher> FibExample<H> {
         // compute Fibonacci sequence
         let now = Instant::now();
         let result = compute_fib_term::<BaseElement>(sequence_length);
-        println!(
+        eprintln!(
             "Computed Fibonacci sequence up to {}th term in {} ms",
             sequence_length,
             now.elapsed().as_millis()
@@ -99,7 +125,7 @@ where
     H: ElementHasher<BaseField = BaseElement>,
 {
     fn prove(&self) -> StarkProof {
-        println!(
+        eprintln!(
             "Generating proof for computing Fibonacci sequence (2 terms per step) up to {}th term",
             self.sequence_length
         );
@@ -120,13 +146,22 @@ where
         prover.prove(trace).unwrap()
     }
 
-    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+    fn get_pub_inputs(&self) -> Vec<u8> {
+        self.result.to_bytes()
+    }
+
+    fn verify_with_pub_inputs(
+        &self,
+        proof: StarkProof,
+        pub_inputs: &[u8],
+    ) -> Result<(), VerifierError> {
+        let pub_inputs = parse_pub_inputs(pub_inputs)?;
         let acceptable_options =
             winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
 
-        winterfell::verify::<FibSmall, H, DefaultRandomCoin<H>>(
+        winterfell::verify::<FibSmall, H, DefaultRandomCoin<H>, MerkleTree<H>>(
             proof,
-            self.result,
+            pub_inputs,
             &acceptable_options,
         )
     }
@@ -134,7 +169,7 @@ where
     fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
         let acceptable_options =
             winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
-        winterfell::verify::<FibSmall, H, DefaultRandomCoin<H>>(
+        winterfell::verify::<FibSmall, H, DefaultRandomCoin<H>, MerkleTree<H>>(
             proof,
             self.result + BaseElement::ONE,
             &acceptable_options,
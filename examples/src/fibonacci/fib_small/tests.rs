@@ -3,7 +3,9 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{super::utils::build_proof_options, Rp64_256};
+use super::{
+    super::utils::build_proof_options, Poseidon2_64_256, Rp64_256, Rpo64_256, Rpx64_256, Tip5,
+};
 
 #[test]
 fn fib_small_test_basic_proof_verification() {
@@ -22,3 +24,27 @@ fn fib_small_test_basic_proof_verification_fail() {
     let fib = Box::new(super::FibExample::<Rp64_256>::new(128, build_proof_options(false)));
     crate::tests::test_basic_proof_verification_fail(fib);
 }
+
+#[test]
+fn fib_small_test_basic_proof_verification_poseidon2() {
+    let fib = Box::new(super::FibExample::<Poseidon2_64_256>::new(128, build_proof_options(false)));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
+#[test]
+fn fib_small_test_basic_proof_verification_rpo() {
+    let fib = Box::new(super::FibExample::<Rpo64_256>::new(128, build_proof_options(false)));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
+#[test]
+fn fib_small_test_basic_proof_verification_rpx() {
+    let fib = Box::new(super::FibExample::<Rpx64_256>::new(128, build_proof_options(false)));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
+#[test]
+fn fib_small_test_basic_proof_verification_tip5() {
+    let fib = Box::new(super::FibExample::<Tip5>::new(128, build_proof_options(false)));
+    crate::tests::test_basic_proof_verification(fib);
+}
@@ -4,8 +4,8 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{
-    BaseElement, DefaultRandomCoin, ElementHasher, FieldElement, MulFib8Air, PhantomData,
-    ProofOptions, Prover,
+    BaseElement, DefaultRandomCoin, ElementHasher, FieldElement, MerkleTree, MulFib8Air,
+    PhantomData, ProofOptions, Prover,
 };
 use winterfell::{
     matrix::ColMatrix, AuxTraceRandElements, ConstraintCompositionCoefficients,
@@ -67,6 +67,7 @@ where
     type Trace = TraceTable<BaseElement>;
     type HashFn = H;
     type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type VC = MerkleTree<Self::HashFn>;
     type TraceLde<E: FieldElement<BaseField = Self::BaseField>> = DefaultTraceLde<E, Self::HashFn>;
     type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
         DefaultConstraintEvaluator<'a, Self::Air, E>;
@@ -10,8 +10,8 @@ use crate::utils::{are_equal, is_binary, is_zero, not, EvaluationResult};
 use core_utils::flatten_slice_elements;
 use winterfell::{
     math::{fields::f128::BaseElement, FieldElement, ToElements},
-    Air, AirContext, Assertion, EvaluationFrame, ProofOptions, TraceInfo,
-    TransitionConstraintDegree,
+    Air, AirContext, Assertion, ByteReader, ByteWriter, Deserializable, DeserializationError,
+    EvaluationFrame, ProofOptions, Serializable, TraceInfo, TransitionConstraintDegree,
 };
 
 // CONSTANTS
@@ -36,6 +36,21 @@ impl ToElements<BaseElement> for PublicInputs {
     }
 }
 
+impl Serializable for PublicInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.pub_keys.write_into(target);
+        self.messages.write_into(target);
+    }
+}
+
+impl Deserializable for PublicInputs {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let pub_keys = Deserializable::read_from(source)?;
+        let messages = Deserializable::read_from(source)?;
+        Ok(PublicInputs { pub_keys, messages })
+    }
+}
+
 pub struct LamportAggregateAir {
     context: AirContext<BaseElement>,
     pub_keys: Vec<[BaseElement; 2]>,
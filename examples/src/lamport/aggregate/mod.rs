@@ -6,14 +6,17 @@
 use super::{
     message_to_elements, rescue, Example, PrivateKey, Signature, CYCLE_LENGTH, NUM_HASH_ROUNDS,
 };
-use crate::{Blake3_192, Blake3_256, ExampleOptions, HashFunction, Sha3_256};
+use crate::{
+    parse_pub_inputs, Blake3_192, Blake3_256, Blake3_384, ExampleOptions, HashFunction, Keccak256,
+    Sha3_256, Sha3_512,
+};
 use core::marker::PhantomData;
 use std::time::Instant;
 use tracing::{field, info_span};
 use winterfell::{
-    crypto::{DefaultRandomCoin, ElementHasher},
+    crypto::{DefaultRandomCoin, ElementHasher, MerkleTree},
     math::{fields::f128::BaseElement, get_power_series, FieldElement, StarkField},
-    ProofOptions, Prover, StarkProof, Trace, VerifierError,
+    ProofOptions, Prover, Serializable, StarkProof, Trace, VerifierError,
 };
 
 mod air;
@@ -46,6 +49,15 @@ pub fn get_example(
         HashFunction::Sha3_256 => {
             Ok(Box::new(LamportAggregateExample::<Sha3_256>::new(num_signatures, options)))
         }
+        HashFunction::Keccak256 => {
+            Ok(Box::new(LamportAggregateExample::<Keccak256>::new(num_signatures, options)))
+        }
+        HashFunction::Blake3_384 => {
+            Ok(Box::new(LamportAggregateExample::<Blake3_384>::new(num_signatures, options)))
+        }
+        HashFunction::Sha3_512 => {
+            Ok(Box::new(LamportAggregateExample::<Sha3_512>::new(num_signatures, options)))
+        }
         _ => Err("The specified hash function cannot be used with this example.".to_string()),
     }
 }
@@ -69,7 +81,7 @@ impl<H: ElementHasher> LamportAggregateExample<H> {
             private_keys.push(PrivateKey::from_seed([i as u8; 32]));
             public_keys.push(private_keys[i].pub_key().to_elements());
         }
-        println!(
+        eprintln!(
             "Generated {} private-public key pairs in {} ms",
             num_signatures,
             now.elapsed().as_millis()
@@ -84,7 +96,7 @@ impl<H: ElementHasher> LamportAggregateExample<H> {
             signatures.push(private_key.sign(msg.as_bytes()));
             messages.push(message_to_elements(msg.as_bytes()));
         }
-        println!("Signed {} messages in {} ms", num_signatures, now.elapsed().as_millis());
+        eprintln!("Signed {} messages in {} ms", num_signatures, now.elapsed().as_millis());
 
         // verify signature
         let now = Instant::now();
@@ -95,7 +107,7 @@ impl<H: ElementHasher> LamportAggregateExample<H> {
             let msg = format!("test message {i}");
             assert!(pk.verify(msg.as_bytes(), signature));
         }
-        println!("Verified {} signature in {} ms", num_signatures, now.elapsed().as_millis());
+        eprintln!("Verified {} signature in {} ms", num_signatures, now.elapsed().as_millis());
 
         LamportAggregateExample {
             options,
@@ -116,7 +128,7 @@ where
 {
     fn prove(&self) -> StarkProof {
         // generate the execution trace
-        println!("Generating proof for verifying {} Lamport+ signatures", self.signatures.len());
+        eprintln!("Generating proof for verifying {} Lamport+ signatures", self.signatures.len());
 
         // create a prover
         let prover =
@@ -135,14 +147,23 @@ where
         prover.prove(trace).unwrap()
     }
 
-    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+    fn get_pub_inputs(&self) -> Vec<u8> {
         let pub_inputs = PublicInputs {
             pub_keys: self.pub_keys.clone(),
             messages: self.messages.clone(),
         };
+        pub_inputs.to_bytes()
+    }
+
+    fn verify_with_pub_inputs(
+        &self,
+        proof: StarkProof,
+        pub_inputs: &[u8],
+    ) -> Result<(), VerifierError> {
+        let pub_inputs = parse_pub_inputs(pub_inputs)?;
         let acceptable_options =
             winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
-        winterfell::verify::<LamportAggregateAir, H, DefaultRandomCoin<H>>(
+        winterfell::verify::<LamportAggregateAir, H, DefaultRandomCoin<H>, MerkleTree<H>>(
             proof,
             pub_inputs,
             &acceptable_options,
@@ -158,7 +179,7 @@ where
         };
         let acceptable_options =
             winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
-        winterfell::verify::<LamportAggregateAir, H, DefaultRandomCoin<H>>(
+        winterfell::verify::<LamportAggregateAir, H, DefaultRandomCoin<H>, MerkleTree<H>>(
             proof,
             pub_inputs,
             &acceptable_options,
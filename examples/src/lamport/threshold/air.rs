@@ -10,8 +10,8 @@ use super::{
 use crate::utils::{are_equal, is_binary, is_zero, not, EvaluationResult};
 use winterfell::{
     math::{fields::f128::BaseElement, FieldElement, StarkField, ToElements},
-    Air, AirContext, Assertion, EvaluationFrame, ProofOptions, TraceInfo,
-    TransitionConstraintDegree,
+    Air, AirContext, Assertion, ByteReader, ByteWriter, Deserializable, DeserializationError,
+    EvaluationFrame, ProofOptions, Serializable, TraceInfo, TransitionConstraintDegree,
 };
 
 // CONSTANTS
@@ -39,6 +39,30 @@ impl ToElements<BaseElement> for PublicInputs {
     }
 }
 
+impl Serializable for PublicInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.pub_key_root.write_into(target);
+        self.num_pub_keys.write_into(target);
+        self.num_signatures.write_into(target);
+        self.message.write_into(target);
+    }
+}
+
+impl Deserializable for PublicInputs {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let pub_key_root = Deserializable::read_from(source)?;
+        let num_pub_keys = Deserializable::read_from(source)?;
+        let num_signatures = Deserializable::read_from(source)?;
+        let message = Deserializable::read_from(source)?;
+        Ok(PublicInputs {
+            pub_key_root,
+            num_pub_keys,
+            num_signatures,
+            message,
+        })
+    }
+}
+
 pub struct LamportThresholdAir {
     context: AirContext<BaseElement>,
     pub_key_root: [BaseElement; 2],
@@ -7,14 +7,17 @@ use super::{
     message_to_elements, rescue, Example, PrivateKey, Signature, CYCLE_LENGTH as HASH_CYCLE_LENGTH,
     NUM_HASH_ROUNDS,
 };
-use crate::{Blake3_192, Blake3_256, ExampleOptions, HashFunction, Sha3_256};
+use crate::{
+    parse_pub_inputs, Blake3_192, Blake3_256, Blake3_384, ExampleOptions, HashFunction, Keccak256,
+    Sha3_256, Sha3_512,
+};
 use core::marker::PhantomData;
 use std::time::Instant;
 use tracing::{field, info_span};
 use winterfell::{
-    crypto::{DefaultRandomCoin, ElementHasher},
+    crypto::{DefaultRandomCoin, ElementHasher, MerkleTree},
     math::{fields::f128::BaseElement, get_power_series, FieldElement, StarkField},
-    ProofOptions, Prover, StarkProof, Trace, VerifierError,
+    ProofOptions, Prover, Serializable, StarkProof, Trace, VerifierError,
 };
 
 mod signature;
@@ -51,6 +54,15 @@ pub fn get_example(
         HashFunction::Sha3_256 => {
             Ok(Box::new(LamportThresholdExample::<Sha3_256>::new(num_signers, options)))
         }
+        HashFunction::Keccak256 => {
+            Ok(Box::new(LamportThresholdExample::<Keccak256>::new(num_signers, options)))
+        }
+        HashFunction::Blake3_384 => {
+            Ok(Box::new(LamportThresholdExample::<Blake3_384>::new(num_signers, options)))
+        }
+        HashFunction::Sha3_512 => {
+            Ok(Box::new(LamportThresholdExample::<Sha3_512>::new(num_signers, options)))
+        }
         _ => Err("The specified hash function cannot be used with this example.".to_string()),
     }
 }
@@ -72,7 +84,7 @@ impl<H: ElementHasher> LamportThresholdExample<H> {
         // generate private/public key pairs for the specified number of signatures
         let now = Instant::now();
         let private_keys = build_keys(num_signers);
-        println!(
+        eprintln!(
             "Generated {} private-public key pairs in {} ms",
             num_signers,
             now.elapsed().as_millis()
@@ -91,7 +103,7 @@ impl<H: ElementHasher> LamportThresholdExample<H> {
         // build the aggregated public key
         let now = Instant::now();
         let pub_key = AggPublicKey::new(public_keys);
-        println!("Built aggregated public key in {} ms", now.elapsed().as_millis());
+        eprintln!("Built aggregated public key in {} ms", now.elapsed().as_millis());
 
         let (options, _) = options.to_proof_options(28, 8);
 
@@ -114,7 +126,7 @@ where
 {
     fn prove(&self) -> StarkProof {
         // generate the execution trace
-        println!(
+        eprintln!(
             "Generating proof for verifying {}-of-{} signature",
             self.signatures.len(),
             self.pub_key.num_keys(),
@@ -141,16 +153,25 @@ where
         prover.prove(trace).unwrap()
     }
 
-    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+    fn get_pub_inputs(&self) -> Vec<u8> {
         let pub_inputs = PublicInputs {
             pub_key_root: self.pub_key.root().to_elements(),
             num_pub_keys: self.pub_key.num_keys(),
             num_signatures: self.signatures.len(),
             message: self.message,
         };
+        pub_inputs.to_bytes()
+    }
+
+    fn verify_with_pub_inputs(
+        &self,
+        proof: StarkProof,
+        pub_inputs: &[u8],
+    ) -> Result<(), VerifierError> {
+        let pub_inputs = parse_pub_inputs(pub_inputs)?;
         let acceptable_options =
             winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
-        winterfell::verify::<LamportThresholdAir, H, DefaultRandomCoin<H>>(
+        winterfell::verify::<LamportThresholdAir, H, DefaultRandomCoin<H>, MerkleTree<H>>(
             proof,
             pub_inputs,
             &acceptable_options,
@@ -166,7 +187,7 @@ where
         };
         let acceptable_options =
             winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
-        winterfell::verify::<LamportThresholdAir, H, DefaultRandomCoin<H>>(
+        winterfell::verify::<LamportThresholdAir, H, DefaultRandomCoin<H>, MerkleTree<H>>(
             proof,
             pub_inputs,
             &acceptable_options,
@@ -5,9 +5,9 @@
 
 use structopt::StructOpt;
 use winterfell::{
-    crypto::hashers::{Rp64_256, RpJive64_256},
+    crypto::hashers::{Poseidon2_64_256, Rp64_256, RpJive64_256, Rpo64_256, Rpx64_256, Tip5},
     math::fields::f128::BaseElement,
-    FieldExtension, ProofOptions, StarkProof, VerifierError,
+    Deserializable, FieldExtension, LowDegreeTest, ProofOptions, StarkProof, VerifierError,
 };
 
 pub mod fibonacci;
@@ -15,11 +15,17 @@ pub mod fibonacci;
 pub mod lamport;
 #[cfg(feature = "std")]
 pub mod merkle;
+#[cfg(feature = "std")]
+pub mod report;
 pub mod rescue;
 #[cfg(feature = "std")]
 pub mod rescue_raps;
+#[cfg(feature = "std")]
+pub mod smt;
 pub mod utils;
 pub mod vdf;
+#[cfg(feature = "std")]
+pub mod vm;
 
 #[cfg(test)]
 mod tests;
@@ -29,22 +35,45 @@ mod tests;
 
 pub type Blake3_192 = winterfell::crypto::hashers::Blake3_192<BaseElement>;
 pub type Blake3_256 = winterfell::crypto::hashers::Blake3_256<BaseElement>;
+pub type Blake3_384 = winterfell::crypto::hashers::Blake3_384<BaseElement>;
 pub type Sha3_256 = winterfell::crypto::hashers::Sha3_256<BaseElement>;
+pub type Sha3_512 = winterfell::crypto::hashers::Sha3_512<BaseElement>;
+pub type Keccak256 = winterfell::crypto::hashers::Keccak256<BaseElement>;
 
 pub trait Example {
     fn prove(&self) -> StarkProof;
-    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError>;
+
+    /// Returns public inputs of this example serialized into a vector of bytes.
+    fn get_pub_inputs(&self) -> Vec<u8>;
+
+    /// Verifies the proof against public inputs deserialized from `pub_inputs`.
+    fn verify_with_pub_inputs(
+        &self,
+        proof: StarkProof,
+        pub_inputs: &[u8],
+    ) -> Result<(), VerifierError>;
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        self.verify_with_pub_inputs(proof, &self.get_pub_inputs())
+    }
+
     fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError>;
 }
 
+/// Deserializes public inputs of an example from the provided bytes.
+pub fn parse_pub_inputs<P: Deserializable>(bytes: &[u8]) -> Result<P, VerifierError> {
+    P::read_from_bytes(bytes)
+        .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))
+}
+
 // EXAMPLE OPTIONS
 // ================================================================================================
 
-#[derive(StructOpt, Debug)]
+#[derive(StructOpt, Debug, Clone)]
 #[structopt(name = "winterfell", about = "Winterfell examples")]
 pub struct ExampleOptions {
     #[structopt(subcommand)]
-    pub example: ExampleType,
+    pub command: Command,
 
     /// Hash function used in the protocol
     #[structopt(short = "h", long = "hash_fn", default_value = "blake3_256")]
@@ -62,6 +91,10 @@ pub struct ExampleOptions {
     #[structopt(short = "g", long = "grinding", default_value = "16")]
     grinding_factor: u32,
 
+    /// Grinding factor for DEEP composition coefficients and FRI folding challenges
+    #[structopt(long = "commit_grinding", default_value = "0")]
+    commit_grinding_factor: u32,
+
     /// Field extension degree for composition polynomial
     #[structopt(short = "e", long = "field_extension", default_value = "1")]
     field_extension: u32,
@@ -69,6 +102,26 @@ pub struct ExampleOptions {
     /// Folding factor for FRI protocol
     #[structopt(short = "f", long = "folding", default_value = "8")]
     folding_factor: usize,
+
+    /// Comma-separated folding factors for the first FRI layers (e.g. 16,16)
+    #[structopt(long = "folding_schedule", use_delimiter = true)]
+    folding_schedule: Vec<usize>,
+
+    /// Protocol used to test the DEEP composition polynomial for low degree (fri or stir)
+    #[structopt(long = "ldt", default_value = "fri")]
+    low_degree_test: String,
+
+    /// Height of Merkle tree caps used for commitments
+    #[structopt(short = "c", long = "merkle_cap", default_value = "0")]
+    merkle_cap_height: usize,
+
+    /// Arity of Merkle trees used for trace, constraint and FRI layer commitments
+    #[structopt(short = "a", long = "merkle_arity", default_value = "2")]
+    merkle_arity: usize,
+
+    /// Size of partitions in which trace and constraint evaluation rows are hashed (0 for none)
+    #[structopt(short = "p", long = "partition_size", default_value = "0")]
+    partition_size: usize,
 }
 
 impl ExampleOptions {
@@ -82,13 +135,13 @@ impl ExampleOptions {
             val => panic!("'{val}' is not a valid field extension option"),
         };
 
-        let hash_fn = match self.hash_fn.as_str() {
-            "blake3_192" => HashFunction::Blake3_192,
-            "blake3_256" => HashFunction::Blake3_256,
-            "sha3_256" => HashFunction::Sha3_256,
-            "rp64_256" => HashFunction::Rp64_256,
-            "rp_jive64_256" => HashFunction::RpJive64_256,
-            val => panic!("'{val}' is not a valid hash function option"),
+        let hash_fn = HashFunction::from_name(&self.hash_fn)
+            .unwrap_or_else(|| panic!("'{}' is not a valid hash function option", self.hash_fn));
+
+        let low_degree_test = match self.low_degree_test.as_str() {
+            "fri" => LowDegreeTest::Fri,
+            "stir" => LowDegreeTest::Stir,
+            val => panic!("'{val}' is not a valid low-degree test option"),
         };
 
         (
@@ -99,7 +152,13 @@ impl ExampleOptions {
                 field_extension,
                 self.folding_factor,
                 31,
-            ),
+            )
+            .with_merkle_cap_height(self.merkle_cap_height)
+            .with_merkle_arities(self.merkle_arity, self.merkle_arity, self.merkle_arity)
+            .with_partition_sizes(self.partition_size, self.partition_size)
+            .with_fri_folding_schedule(&self.folding_schedule)
+            .with_low_degree_test(low_degree_test)
+            .with_commit_grinding_factor(self.commit_grinding_factor),
             hash_fn,
         )
     }
@@ -109,17 +168,70 @@ impl ExampleOptions {
         let security_level = match self.hash_fn.as_str() {
             "blake3_192" => proof.security_level::<Blake3_192>(conjectured),
             "blake3_256" => proof.security_level::<Blake3_256>(conjectured),
+            "blake3_384" => proof.security_level::<Blake3_384>(conjectured),
             "sha3_256" => proof.security_level::<Sha3_256>(conjectured),
+            "sha3_512" => proof.security_level::<Sha3_512>(conjectured),
+            "keccak_256" => proof.security_level::<Keccak256>(conjectured),
             "rp64_256" => proof.security_level::<Rp64_256>(conjectured),
             "rp_jive64_256" => proof.security_level::<RpJive64_256>(conjectured),
+            "rpo64_256" => proof.security_level::<Rpo64_256>(conjectured),
+            "rpx64_256" => proof.security_level::<Rpx64_256>(conjectured),
+            "poseidon2_64_256" => proof.security_level::<Poseidon2_64_256>(conjectured),
+            "tip5" => proof.security_level::<Tip5>(conjectured),
             val => panic!("'{val}' is not a valid hash function option"),
         };
 
         security_level as usize
     }
+
+    /// Returns the name of the hash function used in the protocol.
+    pub fn hash_fn(&self) -> &str {
+        &self.hash_fn
+    }
+
+    /// Returns these options with the hash function replaced by the one with the specified name.
+    pub fn with_hash_fn(mut self, hash_fn: &str) -> Self {
+        self.hash_fn = hash_fn.to_string();
+        self
+    }
 }
 
-#[derive(StructOpt, Debug)]
+#[derive(StructOpt, Debug, Clone)]
+pub enum Command {
+    /// Generate a proof for an example and verify it right away
+    #[structopt(flatten)]
+    Run(ExampleType),
+    /// Generate a proof for an example and write it into a proof file
+    #[cfg(feature = "std")]
+    Prove {
+        /// File into which the proof is written
+        #[structopt(short = "o", long = "output", parse(from_os_str))]
+        output: std::path::PathBuf,
+
+        #[structopt(subcommand)]
+        example: ExampleType,
+    },
+    /// Verify a proof read from a proof file against the example it was generated for
+    #[cfg(feature = "std")]
+    Verify {
+        /// Proof file written by the prove command
+        #[structopt(parse(from_os_str))]
+        input: std::path::PathBuf,
+    },
+    /// Verify a proof read from a proof file and write a JSON report about it
+    #[cfg(feature = "std")]
+    Report {
+        /// Proof file written by the prove command
+        #[structopt(parse(from_os_str))]
+        input: std::path::PathBuf,
+
+        /// File into which the report is written; the report is printed if this is omitted
+        #[structopt(short = "o", long = "output", parse(from_os_str))]
+        output: Option<std::path::PathBuf>,
+    },
+}
+
+#[derive(StructOpt, Debug, Clone, PartialEq, Eq)]
 //#[structopt(about = "available examples")]
 pub enum ExampleType {
     /// Compute a Fibonacci sequence using trace table with 2 registers
@@ -184,6 +296,13 @@ pub enum ExampleType {
         #[structopt(short = "n", default_value = "7")]
         tree_depth: usize,
     },
+    /// Compute old and new roots of a sparse Merkle tree in which a single leaf was updated
+    #[cfg(feature = "std")]
+    Smt {
+        /// Depth of the sparse Merkle tree; must be one less than a power of two and less than 64
+        #[structopt(short = "n", default_value = "31")]
+        tree_depth: usize,
+    },
     /// Compute an aggregate Lamport+ signature
     #[cfg(feature = "std")]
     LamportA {
@@ -198,6 +317,143 @@ pub enum ExampleType {
         #[structopt(short = "n", default_value = "3")]
         num_signers: usize,
     },
+    /// Execute a random program on a simple VM with read/write memory
+    #[cfg(feature = "std")]
+    Vm {
+        /// Number of instructions in the program; must be at least 4
+        #[structopt(short = "n", default_value = "1021")]
+        num_instructions: usize,
+    },
+}
+
+impl ExampleType {
+    /// Returns the name of the subcommand which runs this example.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Fib { .. } => "fib",
+            Self::Fib8 { .. } => "fib8",
+            Self::Mulfib { .. } => "mulfib",
+            Self::Mulfib8 { .. } => "mulfib8",
+            Self::FibSmall { .. } => "fib-small",
+            Self::Vdf { .. } => "vdf",
+            Self::VdfExempt { .. } => "vdf-exempt",
+            Self::Rescue { .. } => "rescue",
+            #[cfg(feature = "std")]
+            Self::RescueRaps { .. } => "rescue-raps",
+            #[cfg(feature = "std")]
+            Self::Merkle { .. } => "merkle",
+            #[cfg(feature = "std")]
+            Self::Smt { .. } => "smt",
+            #[cfg(feature = "std")]
+            Self::LamportA { .. } => "lamport-a",
+            #[cfg(feature = "std")]
+            Self::LamportT { .. } => "lamport-t",
+            #[cfg(feature = "std")]
+            Self::Vm { .. } => "vm",
+        }
+    }
+
+    /// Returns the size parameter of this example (i.e., the value of its `-n` option).
+    pub fn size(&self) -> usize {
+        match *self {
+            Self::Fib { sequence_length }
+            | Self::Fib8 { sequence_length }
+            | Self::Mulfib { sequence_length }
+            | Self::Mulfib8 { sequence_length }
+            | Self::FibSmall { sequence_length } => sequence_length,
+            Self::Vdf { num_steps } | Self::VdfExempt { num_steps } => num_steps,
+            Self::Rescue { chain_length } => chain_length,
+            #[cfg(feature = "std")]
+            Self::RescueRaps { chain_length } => chain_length,
+            #[cfg(feature = "std")]
+            Self::Merkle { tree_depth } | Self::Smt { tree_depth } => tree_depth,
+            #[cfg(feature = "std")]
+            Self::LamportA { num_signatures } => num_signatures,
+            #[cfg(feature = "std")]
+            Self::LamportT { num_signers } => num_signers,
+            #[cfg(feature = "std")]
+            Self::Vm { num_instructions } => num_instructions,
+        }
+    }
+
+    /// Returns the example with the specified subcommand name and size parameter, or `None` if
+    /// there is no such example.
+    pub fn from_name(name: &str, size: usize) -> Option<Self> {
+        let example = match name {
+            "fib" => Self::Fib {
+                sequence_length: size,
+            },
+            "fib8" => Self::Fib8 {
+                sequence_length: size,
+            },
+            "mulfib" => Self::Mulfib {
+                sequence_length: size,
+            },
+            "mulfib8" => Self::Mulfib8 {
+                sequence_length: size,
+            },
+            "fib-small" => Self::FibSmall {
+                sequence_length: size,
+            },
+            "vdf" => Self::Vdf { num_steps: size },
+            "vdf-exempt" => Self::VdfExempt { num_steps: size },
+            "rescue" => Self::Rescue { chain_length: size },
+            #[cfg(feature = "std")]
+            "rescue-raps" => Self::RescueRaps { chain_length: size },
+            #[cfg(feature = "std")]
+            "merkle" => Self::Merkle { tree_depth: size },
+            #[cfg(feature = "std")]
+            "smt" => Self::Smt { tree_depth: size },
+            #[cfg(feature = "std")]
+            "lamport-a" => Self::LamportA {
+                num_signatures: size,
+            },
+            #[cfg(feature = "std")]
+            "lamport-t" => Self::LamportT { num_signers: size },
+            #[cfg(feature = "std")]
+            "vm" => Self::Vm {
+                num_instructions: size,
+            },
+            _ => return None,
+        };
+        Some(example)
+    }
+
+    /// Instantiates this example using the specified options.
+    pub fn get_example(&self, options: &ExampleOptions) -> Result<Box<dyn Example>, String> {
+        match *self {
+            Self::Fib { sequence_length } => fibonacci::fib2::get_example(options, sequence_length),
+            Self::Fib8 { sequence_length } => {
+                fibonacci::fib8::get_example(options, sequence_length)
+            }
+            Self::Mulfib { sequence_length } => {
+                fibonacci::mulfib2::get_example(options, sequence_length)
+            }
+            Self::Mulfib8 { sequence_length } => {
+                fibonacci::mulfib8::get_example(options, sequence_length)
+            }
+            Self::FibSmall { sequence_length } => {
+                fibonacci::fib_small::get_example(options, sequence_length)
+            }
+            Self::Vdf { num_steps } => vdf::regular::get_example(options, num_steps),
+            Self::VdfExempt { num_steps } => vdf::exempt::get_example(options, num_steps),
+            Self::Rescue { chain_length } => rescue::get_example(options, chain_length),
+            #[cfg(feature = "std")]
+            Self::RescueRaps { chain_length } => rescue_raps::get_example(options, chain_length),
+            #[cfg(feature = "std")]
+            Self::Merkle { tree_depth } => merkle::get_example(options, tree_depth),
+            #[cfg(feature = "std")]
+            Self::Smt { tree_depth } => smt::get_example(options, tree_depth),
+            #[cfg(feature = "std")]
+            Self::LamportA { num_signatures } => {
+                lamport::aggregate::get_example(options, num_signatures)
+            }
+            #[cfg(feature = "std")]
+            Self::LamportT { num_signers } => lamport::threshold::get_example(options, num_signers),
+            #[cfg(feature = "std")]
+            Self::Vm { num_instructions } => vm::get_example(options, num_instructions),
+        }
+    }
 }
 
 /// Defines a set of hash functions available for the provided examples. Some examples may not
@@ -219,11 +475,26 @@ pub enum HashFunction {
     /// When this function is used in the STARK protocol, proof security cannot exceed 128 bits.
     Blake3_256,
 
+    /// BLAKE3 hash function with 384 bit output.
+    ///
+    /// When this function is used in the STARK protocol, proof security cannot exceed 192 bits.
+    Blake3_384,
+
     /// SHA3 hash function with 256 bit output.
     ///
     /// When this function is used in the STARK protocol, proof security cannot exceed 128 bits.
     Sha3_256,
 
+    /// SHA3 hash function with 512 bit output.
+    ///
+    /// When this function is used in the STARK protocol, proof security cannot exceed 256 bits.
+    Sha3_512,
+
+    /// Keccak hash function with 256 bit output, as used by the Ethereum Virtual Machine.
+    ///
+    /// When this function is used in the STARK protocol, proof security cannot exceed 128 bits.
+    Keccak256,
+
     /// Rescue Prime hash function with 256 bit output. It only works in `f64` field.
     ///
     /// When this function is used in the STARK protocol, proof security cannot exceed 128 bits.
@@ -234,4 +505,47 @@ pub enum HashFunction {
     ///
     /// When this function is used in the STARK protocol, proof security cannot exceed 128 bits.
     RpJive64_256,
+
+    /// Rescue Prime Optimized hash function with 256 bit output. It only works in `f64` field.
+    ///
+    /// When this function is used in the STARK protocol, proof security cannot exceed 128 bits.
+    Rpo64_256,
+
+    /// RPX hash function with 256 bit output. It only works in `f64` field.
+    ///
+    /// When this function is used in the STARK protocol, proof security cannot exceed 128 bits.
+    Rpx64_256,
+
+    /// Poseidon2 hash function with 256 bit output. It only works in `f64` field.
+    ///
+    /// When this function is used in the STARK protocol, proof security cannot exceed 128 bits.
+    Poseidon2_64_256,
+
+    /// Tip5 hash function with 320 bit output. It only works in `f64` field.
+    ///
+    /// When this function is used in the STARK protocol, proof security cannot exceed 160 bits.
+    Tip5,
+}
+
+impl HashFunction {
+    /// Returns the hash function with the specified command-line name, or `None` if there is no
+    /// such hash function.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let hash_fn = match name {
+            "blake3_192" => Self::Blake3_192,
+            "blake3_256" => Self::Blake3_256,
+            "blake3_384" => Self::Blake3_384,
+            "sha3_256" => Self::Sha3_256,
+            "sha3_512" => Self::Sha3_512,
+            "keccak_256" => Self::Keccak256,
+            "rp64_256" => Self::Rp64_256,
+            "rp_jive64_256" => Self::RpJive64_256,
+            "rpo64_256" => Self::Rpo64_256,
+            "rpx64_256" => Self::Rpx64_256,
+            "poseidon2_64_256" => Self::Poseidon2_64_256,
+            "tip5" => Self::Tip5,
+            _ => return None,
+        };
+        Some(hash_fn)
+    }
 }
@@ -7,15 +7,17 @@ use std::time::Instant;
 use structopt::StructOpt;
 use tracing::info_span;
 #[cfg(feature = "tracing-forest")]
-use tracing_forest::ForestLayer;
+use tracing_forest::{ForestLayer, PrettyPrinter};
 #[cfg(not(feature = "tracing-forest"))]
 use tracing_subscriber::fmt::format::FmtSpan;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 use winterfell::StarkProof;
 
-use examples::{fibonacci, rescue, vdf, ExampleOptions, ExampleType};
 #[cfg(feature = "std")]
-use examples::{lamport, merkle, rescue_raps};
+use examples::report::{ProofFile, ProofReport, ProofSizeBreakdown};
+use examples::{Command, Example, ExampleOptions, ExampleType};
+#[cfg(feature = "std")]
+use std::path::Path;
 
 // EXAMPLE RUNNER
 // ================================================================================================
@@ -29,7 +31,9 @@ fn main() {
         tracing_subscriber::registry::Registry::default().with(EnvFilter::from_env("WINTER_LOG"));
 
     #[cfg(feature = "tracing-forest")]
-    registry.with(ForestLayer::default()).init();
+    registry
+        .with(ForestLayer::from(PrettyPrinter::new().writer(std::io::stderr)))
+        .init();
 
     #[cfg(not(feature = "tracing-forest"))]
     {
@@ -40,82 +44,176 @@ fn main() {
             .with_span_events(FmtSpan::CLOSE)
             .with_ansi(false)
             .with_timer(tracing_subscriber::fmt::time::SystemTime)
+            .with_writer(std::io::stderr)
             .compact();
 
         registry.with(format).init();
     }
 
-    // read command-line args
+    // read command-line args; all progress messages are printed to stderr, so that stdout can
+    // be used for the output of the report command
     let options = ExampleOptions::from_args();
 
-    // instantiate and prepare the example
-    let example = match options.example {
-        ExampleType::Fib { sequence_length } => {
-            fibonacci::fib2::get_example(&options, sequence_length)
-        }
-        ExampleType::Fib8 { sequence_length } => {
-            fibonacci::fib8::get_example(&options, sequence_length)
-        }
-        ExampleType::Mulfib { sequence_length } => {
-            fibonacci::mulfib2::get_example(&options, sequence_length)
-        }
-        ExampleType::Mulfib8 { sequence_length } => {
-            fibonacci::mulfib8::get_example(&options, sequence_length)
-        }
-        ExampleType::FibSmall { sequence_length } => {
-            fibonacci::fib_small::get_example(&options, sequence_length)
-        }
-        ExampleType::Vdf { num_steps } => vdf::regular::get_example(&options, num_steps),
-        ExampleType::VdfExempt { num_steps } => vdf::exempt::get_example(&options, num_steps),
-        ExampleType::Rescue { chain_length } => rescue::get_example(&options, chain_length),
+    match &options.command {
+        Command::Run(example) => run(&options, example),
         #[cfg(feature = "std")]
-        ExampleType::RescueRaps { chain_length } => {
-            rescue_raps::get_example(&options, chain_length)
-        }
-        #[cfg(feature = "std")]
-        ExampleType::Merkle { tree_depth } => merkle::get_example(&options, tree_depth),
+        Command::Prove { output, example } => prove(&options, example, output),
         #[cfg(feature = "std")]
-        ExampleType::LamportA { num_signatures } => {
-            lamport::aggregate::get_example(&options, num_signatures)
+        Command::Verify { input } => {
+            let report = verify(&options, input);
+            if !report.verified {
+                std::process::exit(1);
+            }
         }
         #[cfg(feature = "std")]
-        ExampleType::LamportT { num_signers } => {
-            lamport::threshold::get_example(&options, num_signers)
+        Command::Report { input, output } => {
+            let report = verify(&options, input);
+            match output {
+                Some(path) => {
+                    std::fs::write(path, report.to_json()).expect("failed to write report");
+                    eprintln!("Report written to {}", path.display());
+                }
+                None => print!("{}", report.to_json()),
+            }
+            if !report.verified {
+                std::process::exit(1);
+            }
         }
     }
-    .expect("The example failed to initialize.");
+}
+
+// COMMANDS
+// ================================================================================================
+
+/// Generates a proof for the specified example and verifies it right away.
+fn run(options: &ExampleOptions, example_type: &ExampleType) {
+    let example = instantiate(options, example_type);
+    let (proof, _) = generate_proof(example.as_ref());
+    print_proof_info(options, &proof);
+
+    // verify the proof
+    eprintln!("---------------------");
+    let parsed_proof = StarkProof::from_bytes(&proof.to_bytes()).unwrap();
+    assert_eq!(proof, parsed_proof);
+
+    let (result, verify_time_ms) = verify_proof(example.as_ref(), proof, &example.get_pub_inputs());
+    print_verification_result(&result, verify_time_ms);
+}
+
+/// Generates a proof for the specified example and writes it into a proof file together with
+/// everything needed to verify it later.
+#[cfg(feature = "std")]
+fn prove(options: &ExampleOptions, example_type: &ExampleType, path: &Path) {
+    let example = instantiate(options, example_type);
+    let (proof, prove_time_ms) = generate_proof(example.as_ref());
+    print_proof_info(options, &proof);
+
+    let proof_file = ProofFile {
+        example: example_type.clone(),
+        hash_fn: options.hash_fn().to_string(),
+        prove_time_ms,
+        pub_inputs: example.get_pub_inputs(),
+        proof,
+    };
+    proof_file.write(path).expect("failed to write proof file");
+    eprintln!("Proof saved to {}", path.display());
+}
+
+/// Reads a proof file and verifies the proof against the example and the hash function recorded
+/// in the file.
+#[cfg(feature = "std")]
+fn verify(options: &ExampleOptions, path: &Path) -> ProofReport {
+    let proof_file = ProofFile::read(path).expect("failed to read proof file");
+    eprintln!(
+        "Proof for '{} -n {}' using {} loaded from {}",
+        proof_file.example.name(),
+        proof_file.example.size(),
+        proof_file.hash_fn,
+        path.display()
+    );
+
+    // the proof is verified using the hash function it was generated with, regardless of the
+    // hash function specified on the command line
+    let options = options.clone().with_hash_fn(&proof_file.hash_fn);
+    let example = instantiate(&options, &proof_file.example);
+    let (conjectured_security, proven_security) = print_proof_info(&options, &proof_file.proof);
+    let proof_size = ProofSizeBreakdown::new(&proof_file.proof);
+
+    eprintln!("---------------------");
+    let (result, verify_time_ms) =
+        verify_proof(example.as_ref(), proof_file.proof, &proof_file.pub_inputs);
+    print_verification_result(&result, verify_time_ms);
+
+    ProofReport {
+        example: proof_file.example,
+        hash_fn: proof_file.hash_fn,
+        conjectured_security,
+        proven_security,
+        proof_size,
+        prove_time_ms: proof_file.prove_time_ms,
+        verify_time_ms,
+        verified: result.is_ok(),
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Instantiates and prepares the specified example.
+fn instantiate(options: &ExampleOptions, example_type: &ExampleType) -> Box<dyn Example> {
+    example_type.get_example(options).expect("The example failed to initialize.")
+}
+
+/// Generates a proof for the specified example and returns it together with the time it took to
+/// generate the proof in milliseconds.
+fn generate_proof(example: &dyn Example) -> (StarkProof, u64) {
+    let now = Instant::now();
+    let proof = info_span!("generate_proof").in_scope(|| example.prove());
+    let prove_time_ms = now.elapsed().as_millis() as u64;
+    eprintln!("---------------------\nProof generated in {} ms", prove_time_ms);
+    (proof, prove_time_ms)
+}
 
-    // generate proof
+/// Verifies the proof against the specified public inputs and returns the result together with
+/// the time it took to verify the proof in milliseconds.
+fn verify_proof(
+    example: &dyn Example,
+    proof: StarkProof,
+    pub_inputs: &[u8],
+) -> (Result<(), winterfell::VerifierError>, f64) {
     let now = Instant::now();
-    let proof = info_span!("generate_proof").in_scope(|| example.as_ref().prove());
-    println!("---------------------\nProof generated in {} ms", now.elapsed().as_millis());
+    let result = example.verify_with_pub_inputs(proof, pub_inputs);
+    (result, now.elapsed().as_micros() as f64 / 1000f64)
+}
 
+/// Prints size, security level and hash of the proof to stderr, and returns its conjectured and proven
+/// security levels.
+fn print_proof_info(options: &ExampleOptions, proof: &StarkProof) -> (usize, usize) {
     let proof_bytes = proof.to_bytes();
-    println!("Proof size: {:.1} KB", proof_bytes.len() as f64 / 1024f64);
-    let conjectured_security_level = options.get_proof_security_level(&proof, true);
+    eprintln!("Proof size: {:.1} KB", proof_bytes.len() as f64 / 1024f64);
+    let conjectured_security_level = options.get_proof_security_level(proof, true);
 
     #[cfg(feature = "std")]
     {
-        let proven_security_level = options.get_proof_security_level(&proof, false);
-        println!(
+        let proven_security_level = options.get_proof_security_level(proof, false);
+        eprintln!(
             "Proof security: {} bits ({} proven)",
             conjectured_security_level, proven_security_level,
         );
+        eprintln!("Proof hash: {}", hex::encode(blake3::hash(&proof_bytes).as_bytes()));
+        (conjectured_security_level, proven_security_level)
     }
 
     #[cfg(not(feature = "std"))]
-    println!("Proof security: {} bits", conjectured_security_level);
-
-    #[cfg(feature = "std")]
-    println!("Proof hash: {}", hex::encode(blake3::hash(&proof_bytes).as_bytes()));
+    {
+        eprintln!("Proof security: {} bits", conjectured_security_level);
+        (conjectured_security_level, 0)
+    }
+}
 
-    // verify the proof
-    println!("---------------------");
-    let parsed_proof = StarkProof::from_bytes(&proof_bytes).unwrap();
-    assert_eq!(proof, parsed_proof);
-    let now = Instant::now();
-    match example.verify(proof) {
-        Ok(_) => println!("Proof verified in {:.1} ms", now.elapsed().as_micros() as f64 / 1000f64),
-        Err(msg) => println!("Failed to verify proof: {}", msg),
+fn print_verification_result(result: &Result<(), winterfell::VerifierError>, verify_time_ms: f64) {
+    match result {
+        Ok(_) => eprintln!("Proof verified in {:.1} ms", verify_time_ms),
+        Err(msg) => eprintln!("Failed to verify proof: {}", msg),
     }
 }
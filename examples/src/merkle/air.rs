@@ -6,7 +6,8 @@
 use super::{rescue, BaseElement, FieldElement, HASH_CYCLE_LEN, HASH_STATE_WIDTH, TRACE_WIDTH};
 use crate::utils::{are_equal, is_binary, is_zero, not, EvaluationResult};
 use winterfell::{
-    math::ToElements, Air, AirContext, Assertion, EvaluationFrame, ProofOptions, TraceInfo,
+    math::ToElements, Air, AirContext, Assertion, ByteReader, ByteWriter, Deserializable,
+    DeserializationError, EvaluationFrame, ProofOptions, Serializable, TraceInfo,
     TransitionConstraintDegree,
 };
 
@@ -23,6 +24,19 @@ impl ToElements<BaseElement> for PublicInputs {
     }
 }
 
+impl Serializable for PublicInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.tree_root.write_into(target);
+    }
+}
+
+impl Deserializable for PublicInputs {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let tree_root = Deserializable::read_from(source)?;
+        Ok(PublicInputs { tree_root })
+    }
+}
+
 pub struct MerkleAir {
     context: AirContext<BaseElement>,
     tree_root: [BaseElement; 2],
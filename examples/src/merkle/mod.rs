@@ -8,8 +8,10 @@ use crate::utils::rescue::{
     STATE_WIDTH as HASH_STATE_WIDTH,
 };
 use crate::{
+    parse_pub_inputs,
     utils::rescue::{Hash, Rescue128},
-    Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256,
+    Blake3_192, Blake3_256, Blake3_384, Example, ExampleOptions, HashFunction, Keccak256, Sha3_256,
+    Sha3_512,
 };
 use core::marker::PhantomData;
 use rand_utils::{rand_value, rand_vector};
@@ -18,7 +20,7 @@ use tracing::{field, info_span};
 use winterfell::{
     crypto::{DefaultRandomCoin, Digest, ElementHasher, MerkleTree},
     math::{fields::f128::BaseElement, FieldElement, StarkField},
-    ProofOptions, Prover, StarkProof, Trace, VerifierError,
+    ProofOptions, Prover, Serializable, StarkProof, Trace, VerifierError,
 };
 
 mod air;
@@ -51,6 +53,13 @@ pub fn get_example(
             Ok(Box::new(MerkleExample::<Blake3_256>::new(tree_depth, options)))
         }
         HashFunction::Sha3_256 => Ok(Box::new(MerkleExample::<Sha3_256>::new(tree_depth, options))),
+        HashFunction::Keccak256 => {
+            Ok(Box::new(MerkleExample::<Keccak256>::new(tree_depth, options)))
+        }
+        HashFunction::Blake3_384 => {
+            Ok(Box::new(MerkleExample::<Blake3_384>::new(tree_depth, options)))
+        }
+        HashFunction::Sha3_512 => Ok(Box::new(MerkleExample::<Sha3_512>::new(tree_depth, options))),
         _ => Err("The specified hash function cannot be used with this example.".to_string()),
     }
 }
@@ -77,12 +86,12 @@ impl<H: ElementHasher> MerkleExample<H> {
         // build Merkle tree of the specified depth
         let now = Instant::now();
         let tree = build_merkle_tree(tree_depth, value, index);
-        println!("Built Merkle tree of depth {} in {} ms", tree_depth, now.elapsed().as_millis(),);
+        eprintln!("Built Merkle tree of depth {} in {} ms", tree_depth, now.elapsed().as_millis(),);
 
         // compute Merkle path form the leaf specified by the index
         let now = Instant::now();
         let path = tree.prove(index).unwrap();
-        println!(
+        eprintln!(
             "Computed Merkle path from leaf {} to root {} in {} ms",
             index,
             hex::encode(tree.root().as_bytes()),
@@ -109,7 +118,7 @@ where
 {
     fn prove(&self) -> StarkProof {
         // generate the execution trace
-        println!(
+        eprintln!(
             "Generating proof for proving membership in a Merkle tree of depth {}",
             self.path.len()
         );
@@ -129,13 +138,22 @@ where
         prover.prove(trace).unwrap()
     }
 
-    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+    fn get_pub_inputs(&self) -> Vec<u8> {
         let pub_inputs = PublicInputs {
             tree_root: self.tree_root.to_elements(),
         };
+        pub_inputs.to_bytes()
+    }
+
+    fn verify_with_pub_inputs(
+        &self,
+        proof: StarkProof,
+        pub_inputs: &[u8],
+    ) -> Result<(), VerifierError> {
+        let pub_inputs = parse_pub_inputs(pub_inputs)?;
         let acceptable_options =
             winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
-        winterfell::verify::<MerkleAir, H, DefaultRandomCoin<H>>(
+        winterfell::verify::<MerkleAir, H, DefaultRandomCoin<H>, MerkleTree<H>>(
             proof,
             pub_inputs,
             &acceptable_options,
@@ -149,7 +167,7 @@ where
         };
         let acceptable_options =
             winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
-        winterfell::verify::<MerkleAir, H, DefaultRandomCoin<H>>(
+        winterfell::verify::<MerkleAir, H, DefaultRandomCoin<H>, MerkleTree<H>>(
             proof,
             pub_inputs,
             &acceptable_options,
@@ -0,0 +1,168 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use std::{fs, io, path::Path};
+use winterfell::{
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, StarkProof,
+};
+
+use crate::{ExampleType, HashFunction};
+
+// PROOF FILE
+// ================================================================================================
+
+/// A STARK proof together with the example it was generated for, the hash function used in the
+/// protocol, the time it took to generate the proof, and the serialized public inputs against
+/// which it should be verified.
+///
+/// Proof files are written by the `prove` command of the examples runner, and can be verified
+/// later (possibly by a different version of the verifier or on a different machine) via the
+/// `verify` and `report` commands. Since the file records which example and hash function the
+/// proof is for, the verifier does not need to be told how the proof was generated.
+pub struct ProofFile {
+    pub example: ExampleType,
+    pub hash_fn: String,
+    pub prove_time_ms: u64,
+    pub pub_inputs: Vec<u8>,
+    pub proof: StarkProof,
+}
+
+impl ProofFile {
+    /// Reads a proof file from the specified path.
+    pub fn read(path: &Path) -> io::Result<Self> {
+        let bytes = fs::read(path)?;
+        Self::read_from_bytes(&bytes)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))
+    }
+
+    /// Writes this proof file into the specified path.
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_bytes())
+    }
+}
+
+impl Serializable for ProofFile {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.example.name().write_into(target);
+        target.write_usize(self.example.size());
+        self.hash_fn.write_into(target);
+        target.write_u64(self.prove_time_ms);
+        self.pub_inputs.write_into(target);
+        self.proof.write_into(target);
+    }
+}
+
+impl Deserializable for ProofFile {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let name = String::read_from(source)?;
+        let size = source.read_usize()?;
+        let example = ExampleType::from_name(&name, size).ok_or_else(|| {
+            DeserializationError::InvalidValue(format!("unknown example '{name}'"))
+        })?;
+
+        let hash_fn = String::read_from(source)?;
+        if HashFunction::from_name(&hash_fn).is_none() {
+            return Err(DeserializationError::InvalidValue(format!(
+                "unknown hash function '{hash_fn}'"
+            )));
+        }
+
+        let prove_time_ms = source.read_u64()?;
+        let pub_inputs = Vec::<u8>::read_from(source)?;
+        let proof = StarkProof::read_from(source)?;
+        Ok(ProofFile {
+            example,
+            hash_fn,
+            prove_time_ms,
+            pub_inputs,
+            proof,
+        })
+    }
+}
+
+// PROOF REPORT
+// ================================================================================================
+
+/// A structured summary of a proof read from a proof file.
+pub struct ProofReport {
+    pub example: ExampleType,
+    pub hash_fn: String,
+    pub conjectured_security: usize,
+    pub proven_security: usize,
+    pub proof_size: ProofSizeBreakdown,
+    pub prove_time_ms: u64,
+    pub verify_time_ms: f64,
+    pub verified: bool,
+}
+
+impl ProofReport {
+    /// Returns a JSON representation of this report.
+    pub fn to_json(&self) -> String {
+        format!(
+            concat!(
+                "{{\n",
+                "  \"example\": {{ \"name\": \"{}\", \"size\": {} }},\n",
+                "  \"hash_fn\": \"{}\",\n",
+                "  \"security\": {{ \"conjectured\": {}, \"proven\": {} }},\n",
+                "  \"proof_size\": {},\n",
+                "  \"timings_ms\": {{ \"prove\": {}, \"verify\": {:.1} }},\n",
+                "  \"verified\": {}\n",
+                "}}\n"
+            ),
+            self.example.name(),
+            self.example.size(),
+            self.hash_fn,
+            self.conjectured_security,
+            self.proven_security,
+            self.proof_size.to_json(),
+            self.prove_time_ms,
+            self.verify_time_ms,
+            self.verified,
+        )
+    }
+}
+
+/// Number of bytes taken up by each component of a serialized proof.
+pub struct ProofSizeBreakdown {
+    pub total: usize,
+    pub context: usize,
+    pub commitments: usize,
+    pub trace_queries: usize,
+    pub constraint_queries: usize,
+    pub ood_frame: usize,
+    pub ldt_proof: usize,
+}
+
+impl ProofSizeBreakdown {
+    /// Computes the size breakdown for the specified proof.
+    pub fn new(proof: &StarkProof) -> Self {
+        ProofSizeBreakdown {
+            total: proof.to_bytes().len(),
+            context: proof.context.to_bytes().len(),
+            commitments: proof.commitments.to_bytes().len(),
+            trace_queries: proof.trace_queries.iter().map(|q| q.to_bytes().len()).sum(),
+            constraint_queries: proof.constraint_queries.to_bytes().len(),
+            ood_frame: proof.ood_frame.to_bytes().len(),
+            ldt_proof: proof.ldt_proof.to_bytes().len(),
+        }
+    }
+
+    /// Returns a JSON representation of this breakdown.
+    fn to_json(&self) -> String {
+        format!(
+            concat!(
+                "{{ \"total\": {}, \"context\": {}, \"commitments\": {}, \"trace_queries\": {}, ",
+                "\"constraint_queries\": {}, \"ood_frame\": {}, \"ldt_proof\": {} }}"
+            ),
+            self.total,
+            self.context,
+            self.commitments,
+            self.trace_queries,
+            self.constraint_queries,
+            self.ood_frame,
+            self.ldt_proof,
+        )
+    }
+}
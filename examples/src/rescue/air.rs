@@ -6,8 +6,8 @@
 use super::{rescue, BaseElement, FieldElement, ProofOptions, CYCLE_LENGTH, TRACE_WIDTH};
 use crate::utils::{are_equal, is_zero, not, EvaluationResult};
 use winterfell::{
-    math::ToElements, Air, AirContext, Assertion, EvaluationFrame, TraceInfo,
-    TransitionConstraintDegree,
+    math::ToElements, Air, AirContext, Assertion, ByteReader, ByteWriter, Deserializable,
+    DeserializationError, EvaluationFrame, Serializable, TraceInfo, TransitionConstraintDegree,
 };
 
 // CONSTANTS
@@ -49,6 +49,21 @@ impl ToElements<BaseElement> for PublicInputs {
     }
 }
 
+impl Serializable for PublicInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.seed.write_into(target);
+        self.result.write_into(target);
+    }
+}
+
+impl Deserializable for PublicInputs {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let seed = Deserializable::read_from(source)?;
+        let result = Deserializable::read_from(source)?;
+        Ok(PublicInputs { seed, result })
+    }
+}
+
 pub struct RescueAir {
     context: AirContext<BaseElement>,
     seed: [BaseElement; 2],
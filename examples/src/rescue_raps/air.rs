@@ -10,7 +10,8 @@ use super::{
 use crate::utils::{are_equal, not, EvaluationResult};
 use core_utils::flatten_slice_elements;
 use winterfell::{
-    math::ToElements, Air, AirContext, Assertion, AuxTraceRandElements, EvaluationFrame, TraceInfo,
+    math::ToElements, Air, AirContext, Assertion, AuxTraceRandElements, ByteReader, ByteWriter,
+    Deserializable, DeserializationError, EvaluationFrame, Serializable, TraceInfo,
     TransitionConstraintDegree,
 };
 
@@ -50,6 +51,19 @@ impl ToElements<BaseElement> for PublicInputs {
     }
 }
 
+impl Serializable for PublicInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.result.write_into(target);
+    }
+}
+
+impl Deserializable for PublicInputs {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let result = Deserializable::read_from(source)?;
+        Ok(PublicInputs { result })
+    }
+}
+
 pub struct RescueRapsAir {
     context: AirContext<BaseElement>,
     result: [[BaseElement; 2]; 2],
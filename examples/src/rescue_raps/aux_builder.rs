@@ -0,0 +1,69 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core_utils::uninit_vector;
+use winterfell::{
+    math::{FieldElement, StarkField},
+    matrix::ColMatrix,
+    AuxTraceBuilder,
+};
+
+// RAP AUXILIARY TRACE BUILDER
+// ================================================================================================
+/// Builds the auxiliary trace segment for the Rescue RAPs example.
+///
+/// The segment consists of 3 columns: the first two columns contain random linear combinations
+/// of the values copied between hash cycles in the original and permuted chains, and the last
+/// column contains the running product of the permutation argument.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RapsAuxTraceBuilder;
+
+impl<B: StarkField> AuxTraceBuilder<B> for RapsAuxTraceBuilder {
+    fn build_aux_segment<E>(
+        &self,
+        main_segment: &ColMatrix<B>,
+        _aux_segments: &[ColMatrix<E>],
+        rand_elements: &[E],
+    ) -> ColMatrix<E>
+    where
+        E: FieldElement<BaseField = B>,
+    {
+        let trace_length = main_segment.num_rows();
+        let mut current_row = unsafe { uninit_vector(main_segment.num_cols()) };
+        let mut next_row = unsafe { uninit_vector(main_segment.num_cols()) };
+        main_segment.read_row_into(0, &mut current_row);
+        let mut aux_columns = vec![vec![E::ZERO; trace_length]; 3];
+
+        // Columns storing the copied values for the permutation argument are not necessary, but
+        // help understanding the construction of RAPs and are kept for illustrative purposes.
+        aux_columns[0][0] =
+            rand_elements[0] * current_row[0].into() + rand_elements[1] * current_row[1].into();
+        aux_columns[1][0] =
+            rand_elements[0] * current_row[4].into() + rand_elements[1] * current_row[5].into();
+
+        // Permutation argument column
+        aux_columns[2][0] = E::ONE;
+
+        for index in 1..trace_length {
+            // At every last step before a new hash iteration,
+            // copy the permuted values into the auxiliary columns
+            if (index % super::CYCLE_LENGTH) == super::NUM_HASH_ROUNDS {
+                main_segment.read_row_into(index, &mut current_row);
+                main_segment.read_row_into(index + 1, &mut next_row);
+
+                aux_columns[0][index] = rand_elements[0] * (next_row[0] - current_row[0]).into()
+                    + rand_elements[1] * (next_row[1] - current_row[1]).into();
+                aux_columns[1][index] = rand_elements[0] * (next_row[4] - current_row[4]).into()
+                    + rand_elements[1] * (next_row[5] - current_row[5]).into();
+            }
+
+            let num = aux_columns[0][index - 1] + rand_elements[2];
+            let denom = aux_columns[1][index - 1] + rand_elements[2];
+            aux_columns[2][index] = aux_columns[2][index - 1] * num * denom.inv();
+        }
+
+        ColMatrix::new(aux_columns)
+    }
+}
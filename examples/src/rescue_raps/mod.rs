@@ -3,19 +3,22 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::{Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256};
+use crate::{
+    parse_pub_inputs, Blake3_192, Blake3_256, Blake3_384, Example, ExampleOptions, HashFunction,
+    Keccak256, Sha3_256, Sha3_512,
+};
 use core::marker::PhantomData;
 use rand_utils::rand_array;
 use std::time::Instant;
 use tracing::{field, info_span};
 use winterfell::{
-    crypto::{DefaultRandomCoin, ElementHasher},
+    crypto::{DefaultRandomCoin, ElementHasher, MerkleTree},
     math::{fields::f128::BaseElement, ExtensionOf, FieldElement},
-    ProofOptions, Prover, StarkProof, Trace, VerifierError,
+    ProofOptions, Prover, Serializable, StarkProof, Trace, VerifierError,
 };
 
-mod custom_trace_table;
-pub use custom_trace_table::RapTraceTable;
+mod aux_builder;
+pub use aux_builder::RapsAuxTraceBuilder;
 
 use super::rescue::rescue::{self, STATE_WIDTH};
 
@@ -54,6 +57,15 @@ pub fn get_example(
         HashFunction::Sha3_256 => {
             Ok(Box::new(RescueRapsExample::<Sha3_256>::new(chain_length, options)))
         }
+        HashFunction::Keccak256 => {
+            Ok(Box::new(RescueRapsExample::<Keccak256>::new(chain_length, options)))
+        }
+        HashFunction::Blake3_384 => {
+            Ok(Box::new(RescueRapsExample::<Blake3_384>::new(chain_length, options)))
+        }
+        HashFunction::Sha3_512 => {
+            Ok(Box::new(RescueRapsExample::<Sha3_512>::new(chain_length, options)))
+        }
         _ => Err("The specified hash function cannot be used with this example.".to_string()),
     }
 }
@@ -83,7 +95,7 @@ impl<H: ElementHasher> RescueRapsExample<H> {
         // compute the sequence of hashes using external implementation of Rescue hash
         let now = Instant::now();
         let result = compute_permuted_hash_chains(&seeds, &permuted_seeds);
-        println!(
+        eprintln!(
             "Computed two permuted chains of {} Rescue hashes in {} ms",
             chain_length,
             now.elapsed().as_millis(),
@@ -109,7 +121,7 @@ where
 {
     fn prove(&self) -> StarkProof {
         // generate the execution trace
-        println!("Generating proof for computing a chain of {} Rescue hashes", self.chain_length);
+        eprintln!("Generating proof for computing a chain of {} Rescue hashes", self.chain_length);
 
         // create a prover
         let prover = RescueRapsProver::<H>::new(self.options.clone());
@@ -127,13 +139,22 @@ where
         prover.prove(trace).unwrap()
     }
 
-    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+    fn get_pub_inputs(&self) -> Vec<u8> {
         let pub_inputs = PublicInputs {
             result: self.result,
         };
+        pub_inputs.to_bytes()
+    }
+
+    fn verify_with_pub_inputs(
+        &self,
+        proof: StarkProof,
+        pub_inputs: &[u8],
+    ) -> Result<(), VerifierError> {
+        let pub_inputs = parse_pub_inputs(pub_inputs)?;
         let acceptable_options =
             winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
-        winterfell::verify::<RescueRapsAir, H, DefaultRandomCoin<H>>(
+        winterfell::verify::<RescueRapsAir, H, DefaultRandomCoin<H>, MerkleTree<H>>(
             proof,
             pub_inputs,
             &acceptable_options,
@@ -146,7 +167,7 @@ where
         };
         let acceptable_options =
             winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
-        winterfell::verify::<RescueRapsAir, H, DefaultRandomCoin<H>>(
+        winterfell::verify::<RescueRapsAir, H, DefaultRandomCoin<H>, MerkleTree<H>>(
             proof,
             pub_inputs,
             &acceptable_options,
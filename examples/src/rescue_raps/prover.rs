@@ -5,12 +5,13 @@
 
 use super::{
     apply_rescue_round_parallel, rescue::STATE_WIDTH, BaseElement, DefaultRandomCoin,
-    ElementHasher, FieldElement, PhantomData, ProofOptions, Prover, PublicInputs, RapTraceTable,
-    RescueRapsAir, CYCLE_LENGTH, NUM_HASH_ROUNDS,
+    ElementHasher, FieldElement, MerkleTree, PhantomData, ProofOptions, Prover, PublicInputs,
+    RapsAuxTraceBuilder, RescueRapsAir, CYCLE_LENGTH, NUM_HASH_ROUNDS,
 };
 use winterfell::{
     matrix::ColMatrix, AuxTraceRandElements, ConstraintCompositionCoefficients,
-    DefaultConstraintEvaluator, DefaultTraceLde, StarkDomain, Trace, TraceInfo, TracePolyTable,
+    DefaultConstraintEvaluator, DefaultTraceLde, StarkDomain, Trace, TraceInfo, TraceLayout,
+    TracePolyTable, TraceTable,
 };
 
 // RESCUE PROVER
@@ -37,11 +38,11 @@ impl<H: ElementHasher> RescueRapsProver<H> {
         seeds: &[[BaseElement; 2]],
         permuted_seeds: &[[BaseElement; 2]],
         result: [[BaseElement; 2]; 2],
-    ) -> RapTraceTable<BaseElement> {
+    ) -> TraceTable<BaseElement, RapsAuxTraceBuilder> {
         debug_assert_eq!(seeds.len(), permuted_seeds.len());
         // allocate memory to hold the trace table
         let trace_length = seeds.len() * CYCLE_LENGTH;
-        let mut trace = RapTraceTable::new(2 * STATE_WIDTH, trace_length);
+        let mut trace = TraceTable::new(2 * STATE_WIDTH, trace_length);
         const END_INCLUSIVE_RANGE: usize = NUM_HASH_ROUNDS - 1;
 
         trace.fill(
@@ -89,7 +90,9 @@ impl<H: ElementHasher> RescueRapsProver<H> {
         debug_assert_eq!(trace.get(4, trace_length - 1), result[1][0]);
         debug_assert_eq!(trace.get(5, trace_length - 1), result[1][1]);
 
-        trace
+        // the auxiliary segment consists of 3 columns and requires 3 random elements
+        let layout = TraceLayout::new(2 * STATE_WIDTH, [3], [3]);
+        trace.with_aux_builder(layout, RapsAuxTraceBuilder)
     }
 }
 
@@ -99,9 +102,10 @@ where
 {
     type BaseField = BaseElement;
     type Air = RescueRapsAir;
-    type Trace = RapTraceTable<BaseElement>;
+    type Trace = TraceTable<BaseElement, RapsAuxTraceBuilder>;
     type HashFn = H;
     type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type VC = MerkleTree<Self::HashFn>;
     type TraceLde<E: FieldElement<BaseField = Self::BaseField>> = DefaultTraceLde<E, Self::HashFn>;
     type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
         DefaultConstraintEvaluator<'a, Self::Air, E>;
@@ -3,8 +3,11 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::Blake3_256;
-use winterfell::{FieldExtension, ProofOptions};
+use super::{Blake3_256, Example};
+use winterfell::{
+    crypto::Hasher, ByteWriter, Deserializable, FieldExtension, LowDegreeTest, ProofOptions,
+    VerifierError,
+};
 
 #[test]
 fn rescue_test_basic_proof_verification() {
@@ -26,6 +29,101 @@ fn rescue_test_basic_proof_verification_fail() {
     crate::tests::test_basic_proof_verification_fail(rescue_eg);
 }
 
+#[test]
+fn rescue_test_basic_proof_verification_merkle_cap() {
+    let options = build_options(false).with_merkle_cap_height(4);
+    let rescue_eg = Box::new(super::RescueRapsExample::<Blake3_256>::new(128, options));
+    crate::tests::test_basic_proof_verification(rescue_eg);
+}
+
+#[test]
+fn rescue_test_merkle_cap_size_mismatch() {
+    let options = build_options(false).with_merkle_cap_height(4);
+    let rescue_eg = super::RescueRapsExample::<Blake3_256>::new(128, options);
+    let proof = rescue_eg.prove();
+
+    let num_fri_layers = proof.options().to_fri_options().num_fri_layers(proof.lde_domain_size());
+    let (trace_caps, constraint_cap, fri_caps) =
+        proof.commitments.clone().parse::<Blake3_256>(2, num_fri_layers).unwrap();
+    assert_eq!(16, trace_caps[0].len());
+
+    // a trace commitment with fewer digests than implied by the cap height should be rejected
+    let mut caps = trace_caps.clone();
+    caps.push(constraint_cap.clone());
+    caps.extend(fri_caps.iter().cloned());
+    caps[0].truncate(8);
+    let mut tampered = proof.clone();
+    tampered.commitments = build_commitments(&caps);
+    assert!(matches!(
+        rescue_eg.verify(tampered),
+        Err(VerifierError::ProofDeserializationError(_))
+    ));
+
+    // and so should be an FRI layer commitment
+    let mut caps = trace_caps;
+    caps.push(constraint_cap);
+    caps.extend(fri_caps.iter().cloned());
+    caps[3].truncate(8);
+    let mut tampered = proof;
+    tampered.commitments = build_commitments(&caps);
+    let err = rescue_eg.verify(tampered).unwrap_err();
+    assert!(matches!(err, VerifierError::FriVerificationFailed(_)));
+    assert!(err
+        .to_string()
+        .contains(&format!("consist of {} digests, but was 8", fri_caps[0].len())));
+}
+
+#[test]
+fn rescue_test_basic_proof_verification_merkle_arity() {
+    let options = build_options(false).with_merkle_cap_height(3).with_merkle_arities(4, 8, 16);
+    let rescue_eg = Box::new(super::RescueRapsExample::<Blake3_256>::new(128, options));
+    crate::tests::test_basic_proof_verification(rescue_eg);
+}
+
+#[test]
+fn rescue_test_basic_proof_verification_partitions() {
+    let options = build_options(true).with_partition_sizes(3, 1);
+    let rescue_eg = Box::new(super::RescueRapsExample::<Blake3_256>::new(128, options));
+    crate::tests::test_basic_proof_verification(rescue_eg);
+}
+
+#[test]
+fn rescue_test_basic_proof_verification_folding_schedule() {
+    let options = build_options(false).with_fri_folding_schedule(&[16, 8]);
+    let rescue_eg = Box::new(super::RescueRapsExample::<Blake3_256>::new(128, options));
+    crate::tests::test_basic_proof_verification(rescue_eg);
+}
+
+#[test]
+fn rescue_test_basic_proof_verification_stir() {
+    let options = build_options(true).with_low_degree_test(LowDegreeTest::Stir);
+    let rescue_eg = Box::new(super::RescueRapsExample::<Blake3_256>::new(128, options));
+    crate::tests::test_basic_proof_verification(rescue_eg);
+}
+
+#[test]
+fn rescue_test_basic_proof_verification_stir_fail() {
+    let options = build_options(false).with_low_degree_test(LowDegreeTest::Stir);
+    let rescue_eg = Box::new(super::RescueRapsExample::<Blake3_256>::new(128, options));
+    crate::tests::test_basic_proof_verification_fail(rescue_eg);
+}
+
+#[test]
+fn rescue_test_basic_proof_verification_commit_grinding() {
+    let options = build_options(true).with_commit_grinding_factor(8);
+    let rescue_eg = Box::new(super::RescueRapsExample::<Blake3_256>::new(128, options));
+    crate::tests::test_basic_proof_verification(rescue_eg);
+}
+
+#[test]
+fn rescue_test_basic_proof_verification_commit_grinding_stir() {
+    let options = build_options(false)
+        .with_commit_grinding_factor(8)
+        .with_low_degree_test(LowDegreeTest::Stir);
+    let rescue_eg = Box::new(super::RescueRapsExample::<Blake3_256>::new(128, options));
+    crate::tests::test_basic_proof_verification(rescue_eg);
+}
+
 fn build_options(use_extension_field: bool) -> ProofOptions {
     let extension = if use_extension_field {
         FieldExtension::Quadratic
@@ -34,3 +132,16 @@ fn build_options(use_extension_field: bool) -> ProofOptions {
     };
     ProofOptions::new(28, 8, 0, extension, 4, 31)
 }
+
+/// Serializes the specified Merkle caps in the same way as they are serialized in a proof.
+fn build_commitments<T: Deserializable>(caps: &[Vec<<Blake3_256 as Hasher>::Digest>]) -> T {
+    let mut commitments = Vec::new();
+    for cap in caps {
+        commitments.write_u8(cap.len().ilog2() as u8);
+        commitments.write_many(cap);
+    }
+    let mut bytes = Vec::new();
+    bytes.write_u32(commitments.len() as u32);
+    bytes.write_bytes(&commitments);
+    T::read_from_bytes(&bytes).unwrap()
+}
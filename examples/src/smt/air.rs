@@ -0,0 +1,206 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    rescue, BaseElement, FieldElement, BIT_COL, HASH_CYCLE_LEN, HASH_STATE_WIDTH, NEW_PATH_COL,
+    OLD_PATH_COL, TRACE_WIDTH,
+};
+use crate::utils::{are_equal, is_binary, is_zero, not, EvaluationResult};
+use winterfell::{
+    math::ToElements, Air, AirContext, Assertion, ByteReader, ByteWriter, Deserializable,
+    DeserializationError, EvaluationFrame, ProofOptions, Serializable, TraceInfo,
+    TransitionConstraintDegree,
+};
+
+// SPARSE MERKLE TREE UPDATE AIR
+// ================================================================================================
+
+pub struct PublicInputs {
+    pub old_root: [BaseElement; 2],
+    pub new_root: [BaseElement; 2],
+    pub key: u64,
+    pub old_value: [BaseElement; 2],
+    pub new_value: [BaseElement; 2],
+}
+
+impl ToElements<BaseElement> for PublicInputs {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        let mut result = self.old_root.to_vec();
+        result.extend_from_slice(&self.new_root);
+        result.push(BaseElement::from(self.key));
+        result.extend_from_slice(&self.old_value);
+        result.extend_from_slice(&self.new_value);
+        result
+    }
+}
+
+impl Serializable for PublicInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.old_root.write_into(target);
+        self.new_root.write_into(target);
+        target.write_u64(self.key);
+        self.old_value.write_into(target);
+        self.new_value.write_into(target);
+    }
+}
+
+impl Deserializable for PublicInputs {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let old_root = Deserializable::read_from(source)?;
+        let new_root = Deserializable::read_from(source)?;
+        let key = source.read_u64()?;
+        let old_value = Deserializable::read_from(source)?;
+        let new_value = Deserializable::read_from(source)?;
+        Ok(PublicInputs {
+            old_root,
+            new_root,
+            key,
+            old_value,
+            new_value,
+        })
+    }
+}
+
+pub struct SmtUpdateAir {
+    context: AirContext<BaseElement>,
+    pub_inputs: PublicInputs,
+}
+
+impl Air for SmtUpdateAir {
+    type BaseField = BaseElement;
+    type PublicInputs = PublicInputs;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
+        let mut degrees = Vec::new();
+        for _ in 0..2 * HASH_STATE_WIDTH {
+            degrees.push(TransitionConstraintDegree::with_cycles(5, vec![HASH_CYCLE_LEN]));
+        }
+        degrees.push(TransitionConstraintDegree::new(2));
+        degrees.push(TransitionConstraintDegree::with_cycles(2, vec![HASH_CYCLE_LEN]));
+        degrees.push(TransitionConstraintDegree::with_cycles(2, vec![HASH_CYCLE_LEN]));
+        assert_eq!(TRACE_WIDTH, trace_info.width());
+        SmtUpdateAir {
+            context: AirContext::new(trace_info, degrees, 13, options),
+            pub_inputs,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        debug_assert_eq!(TRACE_WIDTH, current.len());
+        debug_assert_eq!(TRACE_WIDTH, next.len());
+
+        // split periodic values into masks and Rescue round constants
+        let hash_flag = periodic_values[0];
+        let ark = &periodic_values[1..];
+        let hash_init_flag = not(hash_flag);
+        let bit = next[BIT_COL];
+        let not_bit = not(bit);
+
+        // the old and the new paths are computed in the same way: when hash_flag = 1, constraints
+        // for Rescue round are enforced; when hash_flag = 0, the accumulated hash must be placed
+        // in registers [0, 1] of the hash state if the index bit is zero, and into registers
+        // [2, 3] if the index bit is one
+        for col in [OLD_PATH_COL, NEW_PATH_COL] {
+            let current = &current[col..col + HASH_STATE_WIDTH];
+            let next = &next[col..col + HASH_STATE_WIDTH];
+            let result = &mut result[col..col + HASH_STATE_WIDTH];
+
+            rescue::enforce_round(result, current, next, ark, hash_flag);
+
+            result.agg_constraint(0, hash_init_flag, not_bit * are_equal(current[0], next[0]));
+            result.agg_constraint(1, hash_init_flag, not_bit * are_equal(current[1], next[1]));
+            result.agg_constraint(2, hash_init_flag, bit * are_equal(current[0], next[2]));
+            result.agg_constraint(3, hash_init_flag, bit * are_equal(current[1], next[3]));
+
+            // make sure capacity registers of the hash state are reset to zeros
+            result.agg_constraint(4, hash_init_flag, is_zero(next[4]));
+            result.agg_constraint(5, hash_init_flag, is_zero(next[5]));
+        }
+
+        // values in the bit register must always be binary
+        result[12] = is_binary(current[BIT_COL]);
+
+        // the same sibling must be inserted into both hash states; when the index bit is zero,
+        // the sibling goes into registers [2, 3], and when it is one, into registers [0, 1]
+        let (old, new) = (&next[OLD_PATH_COL..], &next[NEW_PATH_COL..]);
+        result.agg_constraint(
+            13,
+            hash_init_flag,
+            not_bit * are_equal(old[2], new[2]) + bit * are_equal(old[0], new[0]),
+        );
+        result.agg_constraint(
+            14,
+            hash_init_flag,
+            not_bit * are_equal(old[3], new[3]) + bit * are_equal(old[1], new[1]),
+        );
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let last_step = self.trace_length() - 1;
+        let pub_inputs = &self.pub_inputs;
+
+        // the bit register contains bits of the key at the first step of every hash cycle after
+        // the first one, and a ZERO at the first step of the first hash cycle
+        let num_cycles = self.trace_length() / HASH_CYCLE_LEN;
+        let key_bits = (0..num_cycles)
+            .map(|i| match i {
+                0 => BaseElement::ZERO,
+                i => BaseElement::from((pub_inputs.key >> (i - 1)) & 1),
+            })
+            .collect();
+
+        vec![
+            // the old and the new values are hashed into the leaves
+            Assertion::single(OLD_PATH_COL, 0, pub_inputs.old_value[0]),
+            Assertion::single(OLD_PATH_COL + 1, 0, pub_inputs.old_value[1]),
+            Assertion::single(NEW_PATH_COL, 0, pub_inputs.new_value[0]),
+            Assertion::single(NEW_PATH_COL + 1, 0, pub_inputs.new_value[1]),
+            // the paths resolve to the old and the new roots
+            Assertion::single(OLD_PATH_COL, last_step, pub_inputs.old_root[0]),
+            Assertion::single(OLD_PATH_COL + 1, last_step, pub_inputs.old_root[1]),
+            Assertion::single(NEW_PATH_COL, last_step, pub_inputs.new_root[0]),
+            Assertion::single(NEW_PATH_COL + 1, last_step, pub_inputs.new_root[1]),
+            // capacity registers of both hash states are reset to ZERO every 8 steps
+            Assertion::periodic(OLD_PATH_COL + 4, 0, HASH_CYCLE_LEN, BaseElement::ZERO),
+            Assertion::periodic(OLD_PATH_COL + 5, 0, HASH_CYCLE_LEN, BaseElement::ZERO),
+            Assertion::periodic(NEW_PATH_COL + 4, 0, HASH_CYCLE_LEN, BaseElement::ZERO),
+            Assertion::periodic(NEW_PATH_COL + 5, 0, HASH_CYCLE_LEN, BaseElement::ZERO),
+            // the paths are defined by the bits of the key
+            Assertion::sequence(BIT_COL, 0, HASH_CYCLE_LEN, key_bits),
+        ]
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        let mut result = vec![HASH_CYCLE_MASK.to_vec()];
+        result.append(&mut rescue::get_round_constants());
+        result
+    }
+}
+
+// MASKS
+// ================================================================================================
+const HASH_CYCLE_MASK: [BaseElement; HASH_CYCLE_LEN] = [
+    BaseElement::ONE,
+    BaseElement::ONE,
+    BaseElement::ONE,
+    BaseElement::ONE,
+    BaseElement::ONE,
+    BaseElement::ONE,
+    BaseElement::ONE,
+    BaseElement::ZERO,
+];
@@ -0,0 +1,229 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::utils::rescue::{
+    self, CYCLE_LENGTH as HASH_CYCLE_LEN, NUM_ROUNDS as NUM_HASH_ROUNDS,
+    STATE_WIDTH as HASH_STATE_WIDTH,
+};
+use crate::{
+    parse_pub_inputs,
+    utils::rescue::{Hash, Rescue128},
+    Blake3_192, Blake3_256, Blake3_384, Example, ExampleOptions, HashFunction, Keccak256, Sha3_256,
+    Sha3_512,
+};
+use core::marker::PhantomData;
+use rand_utils::{rand_array, rand_value};
+use std::time::Instant;
+use tracing::{field, info_span};
+use winterfell::{
+    crypto::{DefaultRandomCoin, Digest, ElementHasher, MerkleTree, SmtKey, SparseMerkleTree},
+    math::{fields::f128::BaseElement, FieldElement},
+    ProofOptions, Prover, Serializable, StarkProof, Trace, VerifierError,
+};
+
+mod air;
+use air::{PublicInputs, SmtUpdateAir};
+
+mod prover;
+use prover::SmtUpdateProver;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+const TRACE_WIDTH: usize = 13;
+
+/// Index of the first column of the hash state used to compute the old root of the tree.
+const OLD_PATH_COL: usize = 0;
+
+/// Index of the first column of the hash state used to compute the new root of the tree.
+const NEW_PATH_COL: usize = HASH_STATE_WIDTH;
+
+/// Index of the column containing bits of the key.
+const BIT_COL: usize = 2 * HASH_STATE_WIDTH;
+
+/// Number of non-empty leaves in the sparse Merkle tree built for the example.
+const NUM_LEAVES: usize = 64;
+
+// SPARSE MERKLE TREE UPDATE EXAMPLE
+// ================================================================================================
+pub fn get_example(
+    options: &ExampleOptions,
+    tree_depth: usize,
+) -> Result<Box<dyn Example>, String> {
+    let (options, hash_fn) = options.to_proof_options(28, 8);
+
+    match hash_fn {
+        HashFunction::Blake3_192 => {
+            Ok(Box::new(SmtUpdateExample::<Blake3_192>::new(tree_depth, options)))
+        }
+        HashFunction::Blake3_256 => {
+            Ok(Box::new(SmtUpdateExample::<Blake3_256>::new(tree_depth, options)))
+        }
+        HashFunction::Sha3_256 => {
+            Ok(Box::new(SmtUpdateExample::<Sha3_256>::new(tree_depth, options)))
+        }
+        HashFunction::Keccak256 => {
+            Ok(Box::new(SmtUpdateExample::<Keccak256>::new(tree_depth, options)))
+        }
+        HashFunction::Blake3_384 => {
+            Ok(Box::new(SmtUpdateExample::<Blake3_384>::new(tree_depth, options)))
+        }
+        HashFunction::Sha3_512 => {
+            Ok(Box::new(SmtUpdateExample::<Sha3_512>::new(tree_depth, options)))
+        }
+        _ => Err("The specified hash function cannot be used with this example.".to_string()),
+    }
+}
+
+pub struct SmtUpdateExample<H: ElementHasher> {
+    options: ProofOptions,
+    old_root: Hash,
+    new_root: Hash,
+    key: u64,
+    old_value: [BaseElement; 2],
+    new_value: [BaseElement; 2],
+    path: Vec<Hash>,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> SmtUpdateExample<H> {
+    pub fn new(tree_depth: usize, options: ProofOptions) -> Self {
+        assert!(
+            (tree_depth + 1).is_power_of_two(),
+            "tree depth must be one less than a power of 2"
+        );
+        assert!(tree_depth < 64, "tree depth must be smaller than 64");
+
+        // build a sparse Merkle tree of the specified depth with a few random leaves
+        let now = Instant::now();
+        let mut tree = build_sparse_merkle_tree(tree_depth);
+        eprintln!(
+            "Built sparse Merkle tree of depth {} with {} leaves in {} ms",
+            tree_depth,
+            tree.num_leaves(),
+            now.elapsed().as_millis(),
+        );
+
+        // pick one of the leaves and update its value
+        let index = rand_value::<u64>() as usize % tree.num_leaves();
+        let (&key, _) = tree.leaves().nth(index).expect("leaf not found");
+        let old_value = [BaseElement::new(42), BaseElement::new(43)];
+        let new_value = rand_array::<BaseElement, 2>();
+        tree.insert(key, Rescue128::digest(&old_value)).unwrap();
+        let old_root = tree.root();
+
+        // compute the path from the leaf to the root; since siblings on the path do not change
+        // when the leaf is updated, this path is valid for both the old and the new roots
+        let now = Instant::now();
+        let path = tree.prove(&key).unwrap().path().to_vec();
+        tree.insert(key, Rescue128::digest(&new_value)).unwrap();
+        let key = u64::from_be_bytes(key.as_bytes()[24..].try_into().unwrap());
+        eprintln!(
+            "Updated leaf {} changing tree root from {} to {} in {} ms",
+            key,
+            hex::encode(old_root.as_bytes()),
+            hex::encode(tree.root().as_bytes()),
+            now.elapsed().as_millis(),
+        );
+
+        SmtUpdateExample {
+            options,
+            old_root,
+            new_root: tree.root(),
+            key,
+            old_value,
+            new_value,
+            path,
+            _hasher: PhantomData,
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: ElementHasher> Example for SmtUpdateExample<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    fn prove(&self) -> StarkProof {
+        // generate the execution trace
+        eprintln!(
+            "Generating proof for updating a leaf in a sparse Merkle tree of depth {}",
+            self.path.len()
+        );
+        // create the prover
+        let prover = SmtUpdateProver::<H>::new(self.options.clone(), self.key);
+
+        // generate execution trace
+        let trace =
+            info_span!("generate_execution_trace", num_cols = TRACE_WIDTH, steps = field::Empty)
+                .in_scope(|| {
+                    let trace = prover.build_trace(self.old_value, self.new_value, &self.path);
+                    tracing::Span::current().record("steps", trace.length());
+                    trace
+                });
+
+        // generate the proof
+        prover.prove(trace).unwrap()
+    }
+
+    fn get_pub_inputs(&self) -> Vec<u8> {
+        let pub_inputs = PublicInputs {
+            old_root: self.old_root.to_elements(),
+            new_root: self.new_root.to_elements(),
+            key: self.key,
+            old_value: self.old_value,
+            new_value: self.new_value,
+        };
+        pub_inputs.to_bytes()
+    }
+
+    fn verify_with_pub_inputs(
+        &self,
+        proof: StarkProof,
+        pub_inputs: &[u8],
+    ) -> Result<(), VerifierError> {
+        let pub_inputs = parse_pub_inputs(pub_inputs)?;
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<SmtUpdateAir, H, DefaultRandomCoin<H>, MerkleTree<H>>(
+            proof,
+            pub_inputs,
+            &acceptable_options,
+        )
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = PublicInputs {
+            old_root: self.new_root.to_elements(),
+            new_root: self.old_root.to_elements(),
+            key: self.key,
+            old_value: self.old_value,
+            new_value: self.new_value,
+        };
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<SmtUpdateAir, H, DefaultRandomCoin<H>, MerkleTree<H>>(
+            proof,
+            pub_inputs,
+            &acceptable_options,
+        )
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+fn build_sparse_merkle_tree(depth: usize) -> SparseMerkleTree<Rescue128> {
+    let leaves = (0..NUM_LEAVES).map(|_| {
+        let key = SmtKey::from_u64(rand_value::<u64>() >> (64 - depth));
+        let value = rand_array::<BaseElement, 2>();
+        (key, Rescue128::digest(&value))
+    });
+    SparseMerkleTree::with_leaves(depth, leaves).unwrap()
+}
@@ -0,0 +1,145 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    rescue, BaseElement, DefaultRandomCoin, ElementHasher, FieldElement, MerkleTree, PhantomData,
+    ProofOptions, Prover, PublicInputs, SmtUpdateAir, BIT_COL, HASH_CYCLE_LEN, HASH_STATE_WIDTH,
+    NEW_PATH_COL, NUM_HASH_ROUNDS, OLD_PATH_COL, TRACE_WIDTH,
+};
+use winterfell::{
+    matrix::ColMatrix, AuxTraceRandElements, ConstraintCompositionCoefficients,
+    DefaultConstraintEvaluator, DefaultTraceLde, StarkDomain, Trace, TraceInfo, TracePolyTable,
+    TraceTable,
+};
+
+// SPARSE MERKLE TREE UPDATE PROVER
+// ================================================================================================
+
+pub struct SmtUpdateProver<H: ElementHasher> {
+    options: ProofOptions,
+    key: u64,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> SmtUpdateProver<H> {
+    pub fn new(options: ProofOptions, key: u64) -> Self {
+        Self {
+            options,
+            key,
+            _hasher: PhantomData,
+        }
+    }
+
+    pub fn build_trace(
+        &self,
+        old_value: [BaseElement; 2],
+        new_value: [BaseElement; 2],
+        path: &[rescue::Hash],
+    ) -> TraceTable<BaseElement> {
+        // allocate memory to hold the trace table; the first hash cycle is used to hash the
+        // values into leaves, and every subsequent cycle merges the result with the next sibling
+        let trace_length = (path.len() + 1) * HASH_CYCLE_LEN;
+        let mut trace = TraceTable::new(TRACE_WIDTH, trace_length);
+
+        trace.fill(
+            |state| {
+                // initialize first state of the computation
+                state.fill(BaseElement::ZERO);
+                state[OLD_PATH_COL..OLD_PATH_COL + 2].copy_from_slice(&old_value);
+                state[NEW_PATH_COL..NEW_PATH_COL + 2].copy_from_slice(&new_value);
+            },
+            |step, state| {
+                // execute the transition function for all steps
+                //
+                // For the first 7 steps of each 8-step cycle, compute a single round of Rescue
+                // hash for both paths. On the 8th step, insert the next sibling into both hash
+                // states in the positions defined by the next bit of the key, in the same way as
+                // in the Merkle authentication path example.
+
+                let cycle_num = step / HASH_CYCLE_LEN;
+                let cycle_pos = step % HASH_CYCLE_LEN;
+
+                if cycle_pos < NUM_HASH_ROUNDS {
+                    for col in [OLD_PATH_COL, NEW_PATH_COL] {
+                        rescue::apply_round(&mut state[col..col + HASH_STATE_WIDTH], step);
+                    }
+                } else {
+                    let sibling = path[cycle_num].to_elements();
+                    let key_bit = (self.key >> cycle_num) & 1;
+                    for col in [OLD_PATH_COL, NEW_PATH_COL] {
+                        let state = &mut state[col..col + HASH_STATE_WIDTH];
+                        if key_bit == 0 {
+                            state[2] = sibling[0];
+                            state[3] = sibling[1];
+                        } else {
+                            state[2] = state[0];
+                            state[3] = state[1];
+                            state[0] = sibling[0];
+                            state[1] = sibling[1];
+                        }
+                        // reset the capacity registers of the state to ZERO
+                        state[4] = BaseElement::ZERO;
+                        state[5] = BaseElement::ZERO;
+                    }
+                    state[BIT_COL] = BaseElement::from(key_bit);
+                }
+            },
+        );
+
+        // set the bit at the second step to one to make sure there are no repeating patterns in
+        // the bit register; see the Merkle authentication path example for details
+        trace.set(BIT_COL, 1, FieldElement::ONE);
+
+        trace
+    }
+}
+
+impl<H: ElementHasher> Prover for SmtUpdateProver<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    type BaseField = BaseElement;
+    type Air = SmtUpdateAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = H;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type VC = MerkleTree<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = Self::BaseField>> = DefaultTraceLde<E, Self::HashFn>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintEvaluator<'a, Self::Air, E>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> PublicInputs {
+        let last_step = trace.length() - 1;
+        PublicInputs {
+            old_root: [trace.get(OLD_PATH_COL, last_step), trace.get(OLD_PATH_COL + 1, last_step)],
+            new_root: [trace.get(NEW_PATH_COL, last_step), trace.get(NEW_PATH_COL + 1, last_step)],
+            key: self.key,
+            old_value: [trace.get(OLD_PATH_COL, 0), trace.get(OLD_PATH_COL + 1, 0)],
+            new_value: [trace.get(NEW_PATH_COL, 0), trace.get(NEW_PATH_COL + 1, 0)],
+        }
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn new_trace_lde<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>) {
+        DefaultTraceLde::new(trace_info, main_trace, domain)
+    }
+
+    fn new_evaluator<'a, E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: AuxTraceRandElements<E>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E> {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+}
@@ -0,0 +1,34 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::Blake3_256;
+use winterfell::{FieldExtension, ProofOptions};
+
+#[test]
+fn smt_update_test_basic_proof_verification() {
+    let smt = Box::new(super::SmtUpdateExample::<Blake3_256>::new(15, build_options(false)));
+    crate::tests::test_basic_proof_verification(smt);
+}
+
+#[test]
+fn smt_update_test_basic_proof_verification_extension() {
+    let smt = Box::new(super::SmtUpdateExample::<Blake3_256>::new(15, build_options(true)));
+    crate::tests::test_basic_proof_verification(smt);
+}
+
+#[test]
+fn smt_update_test_basic_proof_verification_fail() {
+    let smt = Box::new(super::SmtUpdateExample::<Blake3_256>::new(15, build_options(false)));
+    crate::tests::test_basic_proof_verification_fail(smt);
+}
+
+fn build_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(28, 8, 0, extension, 4, 31)
+}
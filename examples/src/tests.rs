@@ -15,3 +15,131 @@ pub fn test_basic_proof_verification_fail(e: Box<dyn Example>) {
     let verified = e.verify_with_wrong_inputs(proof);
     assert!(verified.is_err());
 }
+
+// PROOF FILES
+// ================================================================================================
+
+#[cfg(feature = "std")]
+mod proof_files {
+    use structopt::StructOpt;
+    use winterfell::{ByteWriter, Deserializable, DeserializationError, Serializable};
+
+    use crate::{
+        report::{ProofFile, ProofReport, ProofSizeBreakdown},
+        Command, ExampleOptions, ExampleType,
+    };
+
+    fn parse_options(args: &[&str]) -> ExampleOptions {
+        ExampleOptions::from_iter_safe(core::iter::once("winterfell").chain(args.iter().copied()))
+            .unwrap()
+    }
+
+    #[test]
+    fn parse_commands() {
+        let options = parse_options(&["fib", "-n", "64"]);
+        assert!(matches!(
+            options.command,
+            Command::Run(ExampleType::Fib {
+                sequence_length: 64
+            })
+        ));
+
+        let options = parse_options(&["-h", "rpo64_256", "prove", "-o", "proof.bin", "vdf"]);
+        assert_eq!(options.hash_fn(), "rpo64_256");
+        match options.command {
+            Command::Prove { output, example } => {
+                assert_eq!(output.to_str(), Some("proof.bin"));
+                assert_eq!(example, ExampleType::Vdf { num_steps: 1048576 });
+            }
+            command => panic!("unexpected command: {command:?}"),
+        }
+
+        let options = parse_options(&["report", "proof.bin"]);
+        assert!(matches!(options.command, Command::Report { output: None, .. }));
+    }
+
+    #[test]
+    fn example_names() {
+        let examples = [
+            ExampleType::Fib {
+                sequence_length: 64,
+            },
+            ExampleType::Vdf { num_steps: 64 },
+            ExampleType::Smt { tree_depth: 7 },
+            ExampleType::LamportT { num_signers: 3 },
+        ];
+        for example in examples {
+            let options = parse_options(&[example.name(), "-n", &example.size().to_string()]);
+            assert!(matches!(&options.command, Command::Run(parsed) if *parsed == example));
+            assert_eq!(
+                Some(example.clone()),
+                ExampleType::from_name(example.name(), example.size())
+            );
+        }
+        assert_eq!(None, ExampleType::from_name("fibonacci", 64));
+    }
+
+    #[test]
+    fn proof_file_round_trip() {
+        let options = parse_options(&["-h", "sha3_256", "fib", "-n", "64"]);
+        let example_type = ExampleType::Fib {
+            sequence_length: 64,
+        };
+        let example = example_type.get_example(&options).unwrap();
+
+        let proof_file = ProofFile {
+            example: example_type.clone(),
+            hash_fn: options.hash_fn().to_string(),
+            prove_time_ms: 42,
+            pub_inputs: example.get_pub_inputs(),
+            proof: example.prove(),
+        };
+        let proof_file = ProofFile::read_from_bytes(&proof_file.to_bytes()).unwrap();
+        assert_eq!(example_type, proof_file.example);
+        assert_eq!("sha3_256", proof_file.hash_fn);
+        assert_eq!(42, proof_file.prove_time_ms);
+
+        // the example is rebuilt from the proof file only, as the verify command does it
+        let options = parse_options(&["fib"]).with_hash_fn(&proof_file.hash_fn);
+        let example = proof_file.example.get_example(&options).unwrap();
+        let proof_size = ProofSizeBreakdown::new(&proof_file.proof);
+        assert!(example.verify_with_pub_inputs(proof_file.proof, &proof_file.pub_inputs).is_ok());
+
+        // the report includes both the recorded proving time and the verification time
+        let report = ProofReport {
+            example: proof_file.example,
+            hash_fn: proof_file.hash_fn,
+            conjectured_security: 99,
+            proven_security: 55,
+            proof_size,
+            prove_time_ms: proof_file.prove_time_ms,
+            verify_time_ms: 1.5,
+            verified: true,
+        };
+        let json = report.to_json();
+        assert!(json.starts_with("{\n  \"example\": { \"name\": \"fib\", \"size\": 64 },\n"));
+        assert!(json.contains("\"timings_ms\": { \"prove\": 42, \"verify\": 1.5 },\n"));
+    }
+
+    #[test]
+    fn proof_file_invalid_metadata() {
+        let mut bytes = Vec::new();
+        "fibonacci".write_into(&mut bytes);
+        bytes.write_usize(64);
+        let err = ProofFile::read_from_bytes(&bytes).err();
+        assert_eq!(
+            Some(DeserializationError::InvalidValue("unknown example 'fibonacci'".to_string())),
+            err
+        );
+
+        let mut bytes = Vec::new();
+        "fib".write_into(&mut bytes);
+        bytes.write_usize(64);
+        "md5".write_into(&mut bytes);
+        let err = ProofFile::read_from_bytes(&bytes).err();
+        assert_eq!(
+            Some(DeserializationError::InvalidValue("unknown hash function 'md5'".to_string())),
+            err
+        );
+    }
+}
@@ -5,8 +5,8 @@
 
 use super::{BaseElement, FieldElement, ProofOptions, ALPHA, FORTY_TWO, TRACE_WIDTH};
 use winterfell::{
-    math::ToElements, Air, AirContext, Assertion, EvaluationFrame, TraceInfo,
-    TransitionConstraintDegree,
+    math::ToElements, Air, AirContext, Assertion, ByteReader, ByteWriter, Deserializable,
+    DeserializationError, EvaluationFrame, Serializable, TraceInfo, TransitionConstraintDegree,
 };
 
 // PUBLIC INPUTS
@@ -24,6 +24,21 @@ impl ToElements<BaseElement> for VdfInputs {
     }
 }
 
+impl Serializable for VdfInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.seed.write_into(target);
+        self.result.write_into(target);
+    }
+}
+
+impl Deserializable for VdfInputs {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let seed = Deserializable::read_from(source)?;
+        let result = Deserializable::read_from(source)?;
+        Ok(VdfInputs { seed, result })
+    }
+}
+
 // VDF AIR
 // ================================================================================================
 
@@ -0,0 +1,372 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    get_trace_length, BaseElement, ExtensionOf, FieldElement, Instruction, Opcode, ProofOptions,
+    ACC_COL, ADD_FLAG_COL, CLK_COL, LOAD_FLAG_COL, MEM_ADDR_COL, MEM_CLK_COL, MEM_SAME_ADDR_COL,
+    MEM_USED_COL, MEM_VALUE_COL, MEM_WRITE_COL, NUM_AUX_RAND_ELEMENTS, PUSH_FLAG_COL,
+    RANGE_BITS_COL, RANGE_CHECK_BITS, STORE_FLAG_COL, TRACE_WIDTH,
+};
+use crate::utils::{are_equal, is_binary, is_zero, not, EvaluationResult};
+use winterfell::{
+    math::ToElements, Air, AirContext, Assertion, AuxTraceRandElements, ByteReader, ByteWriter,
+    Deserializable, DeserializationError, EvaluationFrame, Serializable, TraceInfo,
+    TransitionConstraintDegree,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of constraints describing execution of instructions.
+const NUM_EXEC_CONSTRAINTS: usize = 8;
+
+/// Number of constraints describing the sorted memory table, not counting range check bits.
+const NUM_MEMORY_CONSTRAINTS: usize = 8;
+
+// PUBLIC INPUTS
+// ================================================================================================
+
+#[derive(Clone)]
+pub struct PublicInputs {
+    pub program: Vec<Instruction>,
+    pub result: BaseElement,
+}
+
+impl ToElements<BaseElement> for PublicInputs {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        let mut result = Vec::with_capacity(self.program.len() * 2 + 1);
+        for instruction in self.program.iter() {
+            result.push(BaseElement::from(instruction.opcode as u8));
+            result.push(BaseElement::from(instruction.operand));
+        }
+        result.push(self.result);
+        result
+    }
+}
+
+impl Serializable for PublicInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_usize(self.program.len());
+        for instruction in self.program.iter() {
+            target.write_u8(instruction.opcode as u8);
+            target.write_u32(instruction.operand);
+        }
+        self.result.write_into(target);
+    }
+}
+
+impl Deserializable for PublicInputs {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let num_instructions = source.read_usize()?;
+        let mut program = Vec::with_capacity(num_instructions);
+        for _ in 0..num_instructions {
+            let opcode =
+                Opcode::try_from(source.read_u8()?).map_err(DeserializationError::InvalidValue)?;
+            let operand = source.read_u32()?;
+            program.push(Instruction::new(opcode, operand));
+        }
+        let result = BaseElement::read_from(source)?;
+        Ok(PublicInputs { program, result })
+    }
+}
+
+// VM AIR
+// ================================================================================================
+
+/// AIR for the execution of a program by a simple accumulator-based VM.
+///
+/// The main trace segment consists of three parts:
+/// * Execution columns: clock, accumulator and one-hot encoded opcode flags. The program is
+///   a public input and is supplied to the constraints via two periodic columns (opcodes and
+///   operands) whose period is equal to the length of the trace.
+/// * Memory columns: all memory accesses sorted by address and then by clock. The first row of
+///   the table is a sentinel write of zero into address zero, which cannot be accessed by the
+///   program. This makes sure that the first access to every address is handled uniformly.
+/// * Range check columns: a bit decomposition of the difference between consecutive rows of the
+///   memory table. This guarantees that the table is sorted.
+///
+/// The auxiliary segment contains a single column with a running product which enforces that
+/// the memory accesses made during execution are a permutation of the rows in the memory table.
+///
+/// The last two rows of the trace are exempt from transition constraints; the last row may
+/// contain arbitrary values.
+pub struct VmAir {
+    context: AirContext<BaseElement>,
+    program: Vec<Instruction>,
+    result: BaseElement,
+}
+
+impl Air for VmAir {
+    type BaseField = BaseElement;
+    type PublicInputs = PublicInputs;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
+        assert_eq!(TRACE_WIDTH + 1, trace_info.width());
+        assert!(
+            get_trace_length(pub_inputs.program.len()) <= trace_info.length(),
+            "trace is too short for a program of {} instructions",
+            pub_inputs.program.len()
+        );
+        assert!(
+            trace_info.length() <= 1 << RANGE_CHECK_BITS,
+            "trace length cannot exceed 2^{RANGE_CHECK_BITS}"
+        );
+        for instruction in pub_inputs.program.iter() {
+            if instruction.is_memory_access() {
+                assert!(
+                    instruction.operand > 0 && instruction.operand < 1 << RANGE_CHECK_BITS,
+                    "memory address must be between 1 and 2^{RANGE_CHECK_BITS}, but was {}",
+                    instruction.operand
+                );
+            }
+        }
+
+        let mut main_degrees = vec![
+            // opcode flags and their sum must be binary
+            TransitionConstraintDegree::new(2),
+            TransitionConstraintDegree::new(2),
+            TransitionConstraintDegree::new(2),
+            TransitionConstraintDegree::new(2),
+            TransitionConstraintDegree::new(2),
+            // opcode flags must decode into the opcode from the program
+            TransitionConstraintDegree::new(1),
+            // accumulator and clock updates
+            TransitionConstraintDegree::new(2),
+            TransitionConstraintDegree::new(1),
+            // memory table
+            TransitionConstraintDegree::new(2),
+            TransitionConstraintDegree::new(2),
+            TransitionConstraintDegree::new(2),
+            TransitionConstraintDegree::new(2),
+            TransitionConstraintDegree::new(3),
+            TransitionConstraintDegree::new(3),
+            TransitionConstraintDegree::new(4),
+            TransitionConstraintDegree::new(4),
+        ];
+        main_degrees.resize(
+            NUM_EXEC_CONSTRAINTS + NUM_MEMORY_CONSTRAINTS + RANGE_CHECK_BITS,
+            TransitionConstraintDegree::new(2),
+        );
+        let aux_degrees = vec![TransitionConstraintDegree::new(3)];
+
+        let context =
+            AirContext::new_multi_segment(trace_info, main_degrees, aux_degrees, 8, 2, options)
+                .set_num_transition_exemptions(2);
+
+        VmAir {
+            context,
+            program: pub_inputs.program,
+            result: pub_inputs.result,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        debug_assert_eq!(TRACE_WIDTH, current.len());
+        debug_assert_eq!(TRACE_WIDTH, next.len());
+
+        let opcode = periodic_values[0];
+        let operand = periodic_values[1];
+
+        // --- instruction decoding -------------------------------------------------------------
+        let push_flag = current[PUSH_FLAG_COL];
+        let add_flag = current[ADD_FLAG_COL];
+        let load_flag = current[LOAD_FLAG_COL];
+        let store_flag = current[STORE_FLAG_COL];
+
+        // all flags are binary and at most one of them is set; when none of the flags are set,
+        // the instruction is a NOP
+        result[0] = is_binary(push_flag);
+        result[1] = is_binary(add_flag);
+        result[2] = is_binary(load_flag);
+        result[3] = is_binary(store_flag);
+        result[4] = is_binary(push_flag + add_flag + load_flag + store_flag);
+
+        // flags must encode the opcode of the instruction executed at this step
+        let decoded_opcode = push_flag * E::from(Opcode::Push as u8)
+            + add_flag * E::from(Opcode::Add as u8)
+            + load_flag * E::from(Opcode::Load as u8)
+            + store_flag * E::from(Opcode::Store as u8);
+        result[5] = are_equal(decoded_opcode, opcode);
+
+        // --- instruction execution ------------------------------------------------------------
+        // the value loaded by LOAD instruction is not constrained here; instead, it is
+        // constrained by the memory consistency argument in the auxiliary segment
+        let acc = current[ACC_COL];
+        let keep_flag = not(push_flag + add_flag + load_flag);
+        let expected_acc = push_flag * operand + add_flag * (acc + operand) + keep_flag * acc;
+        result[6] = are_equal(next[ACC_COL] * not(load_flag), expected_acc);
+
+        result[7] = are_equal(next[CLK_COL], current[CLK_COL] + E::ONE);
+
+        // --- memory table ---------------------------------------------------------------------
+        let used = current[MEM_USED_COL];
+        let next_used = next[MEM_USED_COL];
+        let same_addr = current[MEM_SAME_ADDR_COL];
+        let next_write = next[MEM_WRITE_COL];
+
+        // used rows must form a contiguous block at the start of the table
+        result[8] = is_binary(used);
+        result[9] = next_used * not(used);
+
+        result[10] = is_binary(same_addr);
+        result[11] = is_binary(current[MEM_WRITE_COL]);
+
+        // when the next row has the same address, the address must not change; otherwise,
+        // the address must increase and when it is the same, the clock must increase; both
+        // are enforced by making sure the difference fits into RANGE_CHECK_BITS bits
+        result.agg_constraint(
+            12,
+            next_used * same_addr,
+            are_equal(next[MEM_ADDR_COL], current[MEM_ADDR_COL]),
+        );
+
+        let delta = same_addr * (next[MEM_CLK_COL] - current[MEM_CLK_COL] - E::ONE)
+            + not(same_addr) * (next[MEM_ADDR_COL] - current[MEM_ADDR_COL] - E::ONE);
+        result.agg_constraint(13, next_used, are_equal(delta, combine_bits(current)));
+
+        // reads must return the most recently written value; reading an address which has
+        // not been written to returns zero
+        result.agg_constraint(
+            14,
+            next_used * same_addr * not(next_write),
+            are_equal(next[MEM_VALUE_COL], current[MEM_VALUE_COL]),
+        );
+        result.agg_constraint(
+            15,
+            next_used * not(same_addr) * not(next_write),
+            is_zero(next[MEM_VALUE_COL]),
+        );
+
+        // --- range checks ---------------------------------------------------------------------
+        for i in 0..RANGE_CHECK_BITS {
+            result[NUM_EXEC_CONSTRAINTS + NUM_MEMORY_CONSTRAINTS + i] =
+                is_binary(current[RANGE_BITS_COL + i]);
+        }
+    }
+
+    fn evaluate_aux_transition<F, E>(
+        &self,
+        main_frame: &EvaluationFrame<F>,
+        aux_frame: &EvaluationFrame<E>,
+        periodic_values: &[F],
+        aux_rand_elements: &AuxTraceRandElements<E>,
+        result: &mut [E],
+    ) where
+        F: FieldElement<BaseField = Self::BaseField>,
+        E: FieldElement<BaseField = Self::BaseField> + ExtensionOf<F>,
+    {
+        let current = main_frame.current();
+        let next = main_frame.next();
+        let rand_elements = aux_rand_elements.get_segment_elements(0);
+
+        // memory access made by the instruction executed at this step (if any)
+        let operand = periodic_values[1];
+        let load_flag = current[LOAD_FLAG_COL];
+        let store_flag = current[STORE_FLAG_COL];
+        let access_flag = load_flag + store_flag;
+        let value = load_flag * next[ACC_COL] + store_flag * current[ACC_COL];
+        let exec_access = rand_elements[0] * access_flag.into()
+            + rand_elements[1] * (access_flag * operand).into()
+            + rand_elements[2] * (access_flag * current[CLK_COL]).into()
+            + rand_elements[3] * value.into()
+            + rand_elements[4] * store_flag.into()
+            + not(access_flag).into();
+
+        // memory access recorded in the current row of the memory table (if any)
+        let used = current[MEM_USED_COL];
+        let table_access = compress_access(
+            rand_elements,
+            current[MEM_ADDR_COL],
+            current[MEM_CLK_COL],
+            current[MEM_VALUE_COL],
+            current[MEM_WRITE_COL],
+        ) * used.into()
+            + not(used).into();
+
+        // p' * table_access = p * exec_access
+        result[0] =
+            are_equal(aux_frame.next()[0] * table_access, aux_frame.current()[0] * exec_access);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        // the last row may contain arbitrary values, so the result is read from the row before it
+        let result_step = self.trace_length() - 2;
+        vec![
+            // the VM starts with zero in the accumulator
+            Assertion::single(CLK_COL, 0, BaseElement::ZERO),
+            Assertion::single(ACC_COL, 0, BaseElement::ZERO),
+            // the first row of the memory table is a sentinel write of zero into address zero
+            Assertion::single(MEM_ADDR_COL, 0, BaseElement::ZERO),
+            Assertion::single(MEM_CLK_COL, 0, BaseElement::ZERO),
+            Assertion::single(MEM_VALUE_COL, 0, BaseElement::ZERO),
+            Assertion::single(MEM_WRITE_COL, 0, BaseElement::ONE),
+            Assertion::single(MEM_USED_COL, 0, BaseElement::ONE),
+            // the final value of the accumulator is the result of the program
+            Assertion::single(ACC_COL, result_step, self.result),
+        ]
+    }
+
+    fn get_aux_assertions<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        aux_rand_elements: &AuxTraceRandElements<E>,
+    ) -> Vec<Assertion<E>> {
+        // the memory table contains the sentinel access in addition to the accesses made during
+        // execution, and thus, the running product must end with the inverse of that access
+        let rand_elements = aux_rand_elements.get_segment_elements(0);
+        let sentinel = compress_access(rand_elements, E::ZERO, E::ZERO, E::ZERO, E::ONE);
+        let last_step = self.trace_length() - 2;
+        vec![Assertion::single(0, 0, E::ONE), Assertion::single(0, last_step, sentinel.inv())]
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        // the program is padded with NOPs to the length of the trace
+        let trace_length = self.trace_length();
+        let mut opcodes = vec![BaseElement::ZERO; trace_length];
+        let mut operands = vec![BaseElement::ZERO; trace_length];
+        for (i, instruction) in self.program.iter().enumerate() {
+            opcodes[i] = BaseElement::from(instruction.opcode as u8);
+            operands[i] = BaseElement::from(instruction.operand);
+        }
+        vec![opcodes, operands]
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Reduces a memory access to a single value using the provided random elements.
+pub fn compress_access<F, E>(rand_elements: &[E], address: F, clk: F, value: F, is_write: F) -> E
+where
+    F: FieldElement,
+    E: FieldElement + From<F>,
+{
+    debug_assert_eq!(NUM_AUX_RAND_ELEMENTS, rand_elements.len());
+    rand_elements[0]
+        + rand_elements[1] * address.into()
+        + rand_elements[2] * clk.into()
+        + rand_elements[3] * value.into()
+        + rand_elements[4] * is_write.into()
+}
+
+/// Combines range check bits of the specified row into a single value.
+fn combine_bits<E: FieldElement>(row: &[E]) -> E {
+    row[RANGE_BITS_COL..RANGE_BITS_COL + RANGE_CHECK_BITS]
+        .iter()
+        .rev()
+        .fold(E::ZERO, |acc, &bit| acc.double() + bit)
+}
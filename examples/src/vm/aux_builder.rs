@@ -0,0 +1,92 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    air::compress_access, Instruction, Opcode, ACC_COL, CLK_COL, MEM_ADDR_COL, MEM_CLK_COL,
+    MEM_USED_COL, MEM_VALUE_COL, MEM_WRITE_COL, NUM_AUX_RAND_ELEMENTS,
+};
+use winterfell::{
+    math::{FieldElement, StarkField},
+    matrix::ColMatrix,
+    AuxTraceBuilder,
+};
+
+// MEMORY AUXILIARY TRACE BUILDER
+// ================================================================================================
+/// Builds the auxiliary trace segment for the VM example.
+///
+/// The segment consists of a single column containing the running product of the memory
+/// consistency argument: at every step, the product is multiplied by the (compressed) memory
+/// access made by the executed instruction, and divided by the (compressed) access recorded in
+/// the sorted memory table.
+///
+/// Memory addresses are not part of the main trace (they are operands of the program), and
+/// thus, the builder keeps a copy of the program being executed.
+#[derive(Debug, Clone)]
+pub struct MemoryAuxTraceBuilder {
+    program: Vec<Instruction>,
+}
+
+impl MemoryAuxTraceBuilder {
+    pub fn new(program: Vec<Instruction>) -> Self {
+        Self { program }
+    }
+}
+
+impl<B: StarkField> AuxTraceBuilder<B> for MemoryAuxTraceBuilder {
+    fn build_aux_segment<E>(
+        &self,
+        main_segment: &ColMatrix<B>,
+        _aux_segments: &[ColMatrix<E>],
+        rand_elements: &[E],
+    ) -> ColMatrix<E>
+    where
+        E: FieldElement<BaseField = B>,
+    {
+        debug_assert_eq!(NUM_AUX_RAND_ELEMENTS, rand_elements.len());
+        let trace_length = main_segment.num_rows();
+
+        // compute the numerators and the denominators of all steps first, so that the
+        // denominators can be inverted in a single batch
+        let mut numerators = vec![E::ONE; trace_length - 1];
+        let mut denominators = vec![E::ONE; trace_length - 1];
+        for (step, instruction) in self.program.iter().enumerate() {
+            if instruction.is_memory_access() {
+                let (value, is_write) = match instruction.opcode {
+                    Opcode::Load => (main_segment.get(ACC_COL, step + 1), B::ZERO),
+                    _ => (main_segment.get(ACC_COL, step), B::ONE),
+                };
+                numerators[step] = compress_access(
+                    rand_elements,
+                    B::from(instruction.operand),
+                    main_segment.get(CLK_COL, step),
+                    value,
+                    is_write,
+                );
+            }
+        }
+        for (step, denominator) in denominators.iter_mut().enumerate() {
+            if main_segment.get(MEM_USED_COL, step) == B::ONE {
+                *denominator = compress_access(
+                    rand_elements,
+                    main_segment.get(MEM_ADDR_COL, step),
+                    main_segment.get(MEM_CLK_COL, step),
+                    main_segment.get(MEM_VALUE_COL, step),
+                    main_segment.get(MEM_WRITE_COL, step),
+                );
+            }
+        }
+        let denominators = winterfell::math::batch_inversion(&denominators);
+
+        let mut running_product = Vec::with_capacity(trace_length);
+        running_product.push(E::ONE);
+        for (&numerator, &inv_denominator) in numerators.iter().zip(denominators.iter()) {
+            let last = *running_product.last().expect("running product is not empty");
+            running_product.push(last * numerator * inv_denominator);
+        }
+
+        ColMatrix::new(vec![running_product])
+    }
+}
@@ -0,0 +1,333 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{
+    parse_pub_inputs, Blake3_192, Blake3_256, Blake3_384, Example, ExampleOptions, HashFunction,
+    Keccak256, Sha3_256, Sha3_512,
+};
+use core::marker::PhantomData;
+use rand_utils::rand_value;
+use std::{collections::BTreeMap, time::Instant};
+use tracing::{field, info_span};
+use winterfell::{
+    crypto::{DefaultRandomCoin, ElementHasher, MerkleTree},
+    math::{fields::f128::BaseElement, ExtensionOf, FieldElement},
+    ProofOptions, Prover, Serializable, StarkProof, Trace, VerifierError,
+};
+
+mod air;
+pub use air::{PublicInputs, VmAir};
+
+mod aux_builder;
+pub use aux_builder::MemoryAuxTraceBuilder;
+
+mod prover;
+use prover::VmProver;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of columns describing the state of the VM at a given step: clock, accumulator and
+/// one flag for each of the opcodes other than NOP.
+const NUM_EXEC_COLS: usize = 6;
+
+/// Number of columns describing a memory access in the sorted memory table: address, clock,
+/// value, write flag, "row is used" flag and "next row has the same address" flag.
+const NUM_MEMORY_COLS: usize = 6;
+
+/// Number of bits into which differences between consecutive rows of the sorted memory table
+/// are decomposed; this also bounds the range of valid memory addresses.
+const RANGE_CHECK_BITS: usize = 16;
+
+const TRACE_WIDTH: usize = NUM_EXEC_COLS + NUM_MEMORY_COLS + RANGE_CHECK_BITS;
+
+// indexes of columns in the main trace segment
+const CLK_COL: usize = 0;
+const ACC_COL: usize = 1;
+const PUSH_FLAG_COL: usize = 2;
+const ADD_FLAG_COL: usize = 3;
+const LOAD_FLAG_COL: usize = 4;
+const STORE_FLAG_COL: usize = 5;
+const MEM_ADDR_COL: usize = 6;
+const MEM_CLK_COL: usize = 7;
+const MEM_VALUE_COL: usize = 8;
+const MEM_WRITE_COL: usize = 9;
+const MEM_USED_COL: usize = 10;
+const MEM_SAME_ADDR_COL: usize = 11;
+const RANGE_BITS_COL: usize = 12;
+
+/// Number of random elements needed to build the auxiliary trace segment.
+const NUM_AUX_RAND_ELEMENTS: usize = 5;
+
+/// Number of distinct memory addresses used by randomly generated programs.
+const NUM_RANDOM_ADDRESSES: u32 = 16;
+
+// INSTRUCTIONS
+// ================================================================================================
+
+/// Operations supported by the VM.
+///
+/// The VM has a single accumulator register which is initialized to zero, and a read/write
+/// memory in which every cell is initialized to zero.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    /// Leaves the accumulator unchanged.
+    Nop = 0,
+    /// Sets the accumulator to the operand.
+    Push = 1,
+    /// Adds the operand to the accumulator.
+    Add = 2,
+    /// Sets the accumulator to the value stored in memory at the address specified by the
+    /// operand.
+    Load = 3,
+    /// Writes the value of the accumulator into memory at the address specified by the operand.
+    Store = 4,
+}
+
+impl TryFrom<u8> for Opcode {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Nop),
+            1 => Ok(Self::Push),
+            2 => Ok(Self::Add),
+            3 => Ok(Self::Load),
+            4 => Ok(Self::Store),
+            _ => Err(format!("{value} is not a valid opcode")),
+        }
+    }
+}
+
+/// A single instruction of a VM program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instruction {
+    pub opcode: Opcode,
+    pub operand: u32,
+}
+
+impl Instruction {
+    pub fn new(opcode: Opcode, operand: u32) -> Self {
+        Self { opcode, operand }
+    }
+
+    /// Returns true if this instruction reads from or writes into memory.
+    pub fn is_memory_access(&self) -> bool {
+        matches!(self.opcode, Opcode::Load | Opcode::Store)
+    }
+}
+
+// VM EXAMPLE
+// ================================================================================================
+
+pub fn get_example(
+    options: &ExampleOptions,
+    num_instructions: usize,
+) -> Result<Box<dyn Example>, String> {
+    let (options, hash_fn) = options.to_proof_options(28, 8);
+
+    match hash_fn {
+        HashFunction::Blake3_192 => {
+            Ok(Box::new(VmExample::<Blake3_192>::new(num_instructions, options)))
+        }
+        HashFunction::Blake3_256 => {
+            Ok(Box::new(VmExample::<Blake3_256>::new(num_instructions, options)))
+        }
+        HashFunction::Sha3_256 => {
+            Ok(Box::new(VmExample::<Sha3_256>::new(num_instructions, options)))
+        }
+        HashFunction::Keccak256 => {
+            Ok(Box::new(VmExample::<Keccak256>::new(num_instructions, options)))
+        }
+        HashFunction::Blake3_384 => {
+            Ok(Box::new(VmExample::<Blake3_384>::new(num_instructions, options)))
+        }
+        HashFunction::Sha3_512 => {
+            Ok(Box::new(VmExample::<Sha3_512>::new(num_instructions, options)))
+        }
+        _ => Err("The specified hash function cannot be used with this example.".to_string()),
+    }
+}
+
+pub struct VmExample<H: ElementHasher> {
+    options: ProofOptions,
+    program: Vec<Instruction>,
+    result: BaseElement,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> VmExample<H> {
+    pub fn new(num_instructions: usize, options: ProofOptions) -> Self {
+        assert!(num_instructions >= 4, "program must contain at least 4 instructions");
+        assert!(
+            get_trace_length(num_instructions) <= 1 << RANGE_CHECK_BITS,
+            "program cannot contain more than {} instructions",
+            (1 << RANGE_CHECK_BITS) - 3
+        );
+
+        let program = build_random_program(num_instructions);
+
+        // execute the program outside of the VM to get the expected result
+        let now = Instant::now();
+        let result = execute(&program);
+        eprintln!(
+            "Executed a program of {} instructions in {} ms",
+            num_instructions,
+            now.elapsed().as_millis()
+        );
+
+        VmExample {
+            options,
+            program,
+            result,
+            _hasher: PhantomData,
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: ElementHasher> Example for VmExample<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    fn prove(&self) -> StarkProof {
+        eprintln!(
+            "Generating proof for executing a program of {} instructions",
+            self.program.len()
+        );
+
+        // create a prover
+        let pub_inputs = PublicInputs {
+            program: self.program.clone(),
+            result: self.result,
+        };
+        let prover = VmProver::<H>::new(pub_inputs, self.options.clone());
+
+        // generate execution trace
+        let trace =
+            info_span!("generate_execution_trace", num_cols = TRACE_WIDTH, steps = field::Empty)
+                .in_scope(|| {
+                    let trace = prover.build_trace();
+                    tracing::Span::current().record("steps", trace.length());
+                    trace
+                });
+
+        // generate the proof
+        prover.prove(trace).unwrap()
+    }
+
+    fn get_pub_inputs(&self) -> Vec<u8> {
+        let pub_inputs = PublicInputs {
+            program: self.program.clone(),
+            result: self.result,
+        };
+        pub_inputs.to_bytes()
+    }
+
+    fn verify_with_pub_inputs(
+        &self,
+        proof: StarkProof,
+        pub_inputs: &[u8],
+    ) -> Result<(), VerifierError> {
+        let pub_inputs = parse_pub_inputs(pub_inputs)?;
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<VmAir, H, DefaultRandomCoin<H>, MerkleTree<H>>(
+            proof,
+            pub_inputs,
+            &acceptable_options,
+        )
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = PublicInputs {
+            program: self.program.clone(),
+            result: self.result + BaseElement::ONE,
+        };
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<VmAir, H, DefaultRandomCoin<H>, MerkleTree<H>>(
+            proof,
+            pub_inputs,
+            &acceptable_options,
+        )
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the length of the execution trace for a program with the specified number of
+/// instructions.
+///
+/// The trace must have room for the program, the sentinel row of the memory table, and the two
+/// final rows which are exempt from transition constraints.
+pub fn get_trace_length(num_instructions: usize) -> usize {
+    (num_instructions + 3).next_power_of_two().max(8)
+}
+
+/// Executes the program and returns the final value of the accumulator.
+pub fn execute(program: &[Instruction]) -> BaseElement {
+    let mut acc = BaseElement::ZERO;
+    let mut memory = BTreeMap::new();
+    for instruction in program {
+        acc = apply_instruction(instruction, acc, &mut memory);
+    }
+    acc
+}
+
+/// Applies a single instruction to the state of the VM and returns the new value of the
+/// accumulator.
+fn apply_instruction(
+    instruction: &Instruction,
+    acc: BaseElement,
+    memory: &mut BTreeMap<u32, BaseElement>,
+) -> BaseElement {
+    let operand = BaseElement::from(instruction.operand);
+    match instruction.opcode {
+        Opcode::Nop => acc,
+        Opcode::Push => operand,
+        Opcode::Add => acc + operand,
+        Opcode::Load => memory.get(&instruction.operand).copied().unwrap_or(BaseElement::ZERO),
+        Opcode::Store => {
+            memory.insert(instruction.operand, acc);
+            acc
+        }
+    }
+}
+
+/// Builds a random program with the specified number of instructions.
+///
+/// Memory accesses are restricted to a small set of addresses so that the same memory cells are
+/// read and written multiple times.
+fn build_random_program(num_instructions: usize) -> Vec<Instruction> {
+    // make sure every opcode appears in the program at least once
+    let mut program = vec![
+        Instruction::new(Opcode::Push, rand_value::<u32>()),
+        Instruction::new(Opcode::Store, 1),
+        Instruction::new(Opcode::Add, rand_value::<u32>()),
+        Instruction::new(Opcode::Load, 1),
+    ];
+
+    while program.len() < num_instructions {
+        let address = rand_value::<u32>() % NUM_RANDOM_ADDRESSES + 1;
+        let instruction = match rand_value::<u32>() % 5 {
+            0 => Instruction::new(Opcode::Nop, 0),
+            1 => Instruction::new(Opcode::Push, rand_value::<u32>()),
+            2 => Instruction::new(Opcode::Add, rand_value::<u32>()),
+            3 => Instruction::new(Opcode::Load, address),
+            _ => Instruction::new(Opcode::Store, address),
+        };
+        program.push(instruction);
+    }
+
+    program
+}
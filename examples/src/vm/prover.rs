@@ -0,0 +1,154 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    apply_instruction, get_trace_length, rand_value, BTreeMap, BaseElement, DefaultRandomCoin,
+    ElementHasher, FieldElement, MemoryAuxTraceBuilder, MerkleTree, Opcode, PhantomData,
+    ProofOptions, Prover, PublicInputs, VmAir, ACC_COL, ADD_FLAG_COL, CLK_COL, LOAD_FLAG_COL,
+    MEM_ADDR_COL, MEM_CLK_COL, MEM_SAME_ADDR_COL, MEM_USED_COL, MEM_VALUE_COL, MEM_WRITE_COL,
+    NUM_AUX_RAND_ELEMENTS, PUSH_FLAG_COL, RANGE_BITS_COL, RANGE_CHECK_BITS, STORE_FLAG_COL,
+    TRACE_WIDTH,
+};
+use winterfell::{
+    matrix::ColMatrix, AuxTraceRandElements, ConstraintCompositionCoefficients,
+    DefaultConstraintEvaluator, DefaultTraceLde, StarkDomain, TraceInfo, TraceLayout,
+    TracePolyTable, TraceTable,
+};
+
+// VM PROVER
+// ================================================================================================
+
+pub struct VmProver<H: ElementHasher> {
+    pub_inputs: PublicInputs,
+    options: ProofOptions,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> VmProver<H> {
+    pub fn new(pub_inputs: PublicInputs, options: ProofOptions) -> Self {
+        Self {
+            pub_inputs,
+            options,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Builds an execution trace for the program specified in the public inputs.
+    ///
+    /// Every row of the trace is built in full and the trace table is then created from the
+    /// resulting row-major data.
+    pub fn build_trace(&self) -> TraceTable<BaseElement, MemoryAuxTraceBuilder> {
+        let program = &self.pub_inputs.program;
+        let trace_length = get_trace_length(program.len());
+        let mut rows = vec![BaseElement::ZERO; trace_length * TRACE_WIDTH];
+
+        // execute the program recording all memory accesses as (address, clk, value, is_write)
+        // tuples; the first access is the sentinel write into address zero
+        let mut accesses = vec![(0, 0, BaseElement::ZERO, true)];
+        let mut acc = BaseElement::ZERO;
+        let mut memory = BTreeMap::new();
+        for (step, row) in rows.chunks_mut(TRACE_WIDTH).take(trace_length - 1).enumerate() {
+            row[CLK_COL] = BaseElement::from(step as u32);
+            row[ACC_COL] = acc;
+
+            if let Some(instruction) = program.get(step) {
+                let flag_col = match instruction.opcode {
+                    Opcode::Nop => None,
+                    Opcode::Push => Some(PUSH_FLAG_COL),
+                    Opcode::Add => Some(ADD_FLAG_COL),
+                    Opcode::Load => Some(LOAD_FLAG_COL),
+                    Opcode::Store => Some(STORE_FLAG_COL),
+                };
+                if let Some(flag_col) = flag_col {
+                    row[flag_col] = BaseElement::ONE;
+                }
+
+                acc = apply_instruction(instruction, acc, &mut memory);
+                match instruction.opcode {
+                    Opcode::Load => accesses.push((instruction.operand, step, acc, false)),
+                    Opcode::Store => accesses.push((instruction.operand, step, acc, true)),
+                    _ => {}
+                }
+            }
+        }
+        debug_assert_eq!(acc, self.pub_inputs.result);
+
+        // build the memory table by sorting the accesses by address and then by clock
+        accesses.sort_by_key(|&(address, clk, ..)| (address, clk));
+        for (i, &(address, clk, value, is_write)) in accesses.iter().enumerate() {
+            let row = &mut rows[i * TRACE_WIDTH..(i + 1) * TRACE_WIDTH];
+            row[MEM_ADDR_COL] = BaseElement::from(address);
+            row[MEM_CLK_COL] = BaseElement::from(clk as u32);
+            row[MEM_VALUE_COL] = value;
+            row[MEM_WRITE_COL] = BaseElement::from(is_write as u8);
+            row[MEM_USED_COL] = BaseElement::ONE;
+
+            // decompose the difference between this and the next access into bits
+            if let Some(&(next_address, next_clk, ..)) = accesses.get(i + 1) {
+                let delta = if next_address == address {
+                    row[MEM_SAME_ADDR_COL] = BaseElement::ONE;
+                    next_clk - clk - 1
+                } else {
+                    (next_address - address - 1) as usize
+                };
+                debug_assert!(delta < 1 << RANGE_CHECK_BITS, "delta {delta} is out of range");
+                for (j, bit) in row[RANGE_BITS_COL..].iter_mut().enumerate() {
+                    *bit = BaseElement::from(((delta >> j) & 1) as u8);
+                }
+            }
+        }
+
+        // put garbage values into the last row
+        for value in rows[(trace_length - 1) * TRACE_WIDTH..].iter_mut() {
+            *value = rand_value();
+        }
+
+        // the auxiliary segment consists of a single column and requires 5 random elements
+        let layout = TraceLayout::new(TRACE_WIDTH, [1], [NUM_AUX_RAND_ELEMENTS]);
+        TraceTable::from_row_major(&rows, TRACE_WIDTH)
+            .with_aux_builder(layout, MemoryAuxTraceBuilder::new(program.clone()))
+    }
+}
+
+impl<H: ElementHasher> Prover for VmProver<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    type BaseField = BaseElement;
+    type Air = VmAir;
+    type Trace = TraceTable<BaseElement, MemoryAuxTraceBuilder>;
+    type HashFn = H;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type VC = MerkleTree<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = Self::BaseField>> = DefaultTraceLde<E, Self::HashFn>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintEvaluator<'a, Self::Air, E>;
+
+    fn get_pub_inputs(&self, _trace: &Self::Trace) -> PublicInputs {
+        self.pub_inputs.clone()
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn new_trace_lde<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>) {
+        DefaultTraceLde::new(trace_info, main_trace, domain)
+    }
+
+    fn new_evaluator<'a, E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: AuxTraceRandElements<E>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E> {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+}
@@ -0,0 +1,34 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::Blake3_256;
+use winterfell::{FieldExtension, ProofOptions};
+
+#[test]
+fn vm_test_basic_proof_verification() {
+    let vm_eg = Box::new(super::VmExample::<Blake3_256>::new(100, build_options(false)));
+    crate::tests::test_basic_proof_verification(vm_eg);
+}
+
+#[test]
+fn vm_test_basic_proof_verification_extension() {
+    let vm_eg = Box::new(super::VmExample::<Blake3_256>::new(100, build_options(true)));
+    crate::tests::test_basic_proof_verification(vm_eg);
+}
+
+#[test]
+fn vm_test_basic_proof_verification_fail() {
+    let vm_eg = Box::new(super::VmExample::<Blake3_256>::new(100, build_options(false)));
+    crate::tests::test_basic_proof_verification_fail(vm_eg);
+}
+
+fn build_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(28, 8, 0, extension, 4, 31)
+}
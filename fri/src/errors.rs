@@ -3,6 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use alloc::string::String;
 use core::fmt;
 
 use crypto::RandomCoinError;
@@ -32,8 +33,21 @@ pub enum VerifierError {
     RemainderDegreeNotValid,
     /// FRI remainder degree is greater than the polynomial degree expected for the last layer.
     RemainderDegreeMismatch(usize),
+    /// Number of FRI remainder evaluations does not match the size of the last layer domain.
+    RemainderSizeMismatch(usize, usize),
     /// Polynomial degree at one of the FRI layers could not be divided evenly by the folding factor.
     DegreeTruncation(usize, usize, usize),
+    /// Number of batched evaluation vectors does not match the number of domain sizes.
+    NumBatchedEvaluationsMismatch(usize, usize),
+    /// Domain size of a batched evaluation vector does not match the domain of any FRI layer.
+    InvalidBatchDomainSize(usize),
+    /// Number of Proof-of-Work nonces does not match the number of FRI layers.
+    NumPowNoncesMismatch(usize, usize),
+    /// Proof-of-Work nonce for one of the layers did not satisfy the grinding factor.
+    LayerProofOfWorkVerificationFailed(usize),
+    /// Number of digests in the commitment to one of the layers is inconsistent with the Merkle
+    /// cap height specified in the options.
+    LayerCommitmentSizeMismatch(usize, usize, usize),
 }
 
 impl fmt::Display for VerifierError {
@@ -67,9 +81,101 @@ impl fmt::Display for VerifierError {
             Self::RemainderDegreeMismatch(degree) => {
                 write!(f, "FRI remainder is not a valid degree {degree} polynomial")
             }
+            Self::RemainderSizeMismatch(expected, actual) => {
+                write!(f, "expected {expected} FRI remainder evaluations, but {actual} were provided")
+            }
             Self::DegreeTruncation(degree, folding, layer) => {
                 write!(f, "degree reduction from {degree} by {folding} at layer {layer} results in degree truncation")
             }
+            Self::NumBatchedEvaluationsMismatch(num_domains, num_evaluations) => write!(f,
+                "the number of batched evaluation vectors must be the same as the number of domain sizes, but {num_domains} and {num_evaluations} were provided"
+            ),
+            Self::InvalidBatchDomainSize(size) => {
+                write!(f, "domain of size {size} does not match the domain of any FRI layer")
+            }
+            Self::NumPowNoncesMismatch(expected, actual) => {
+                write!(f, "expected {expected} FRI layer proof-of-work nonces, but {actual} were provided")
+            }
+            Self::LayerProofOfWorkVerificationFailed(layer) => {
+                write!(f, "proof-of-work verification for FRI layer {layer} failed")
+            }
+            Self::LayerCommitmentSizeMismatch(layer, expected, actual) => {
+                write!(f, "expected commitment to FRI layer {layer} to consist of {expected} digests, but was {actual}")
+            }
+        }
+    }
+}
+
+// POLYNOMIAL COMMITMENT ERROR
+// ================================================================================================
+
+/// Defines errors which can occur when committing to, opening, or verifying openings of
+/// polynomials via [FriPcs](crate::FriPcs).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PcsError {
+    /// Attempt to commit to an empty batch of polynomials.
+    EmptyPolynomialBatch,
+    /// Evaluation vectors in the batch have different lengths or their length is not a power of
+    /// two.
+    InvalidEvaluationsLength(usize),
+    /// Polynomials in the batch are too small to be committed to with the specified FRI options.
+    PolynomialTooSmall(usize),
+    /// Polynomials in the batch are too large for their low-degree extension domain to fit into
+    /// the two-adic subgroup of the base field.
+    PolynomialTooLarge(usize),
+    /// Opening point at the specified index lies in the low-degree extension domain.
+    PointInEvaluationDomain(usize),
+    /// Number of claimed openings does not match the number of opening points and polynomials.
+    NumOpeningsMismatch(usize, usize),
+    /// Vector commitment to polynomial evaluations could not be built.
+    CommitmentFailed(String),
+    /// Evaluations at queried positions did not match the polynomial commitment.
+    RowsCommitmentMismatch,
+    /// Opening proof could not be parsed.
+    ProofDeserializationError(String),
+    /// Attempt to draw a random value from a public coin failed.
+    RandomCoinError(RandomCoinError),
+    /// FRI proof for the DEEP quotient could not be verified.
+    FriVerificationFailed(VerifierError),
+}
+
+impl fmt::Display for PcsError {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyPolynomialBatch => {
+                write!(f, "batch of polynomials cannot be empty")
+            }
+            Self::InvalidEvaluationsLength(len) => {
+                write!(f, "all evaluation vectors must have the same power of two length, but a vector of length {len} was provided")
+            }
+            Self::PolynomialTooSmall(size) => {
+                write!(f, "polynomials with {size} coefficients are too small to be committed to")
+            }
+            Self::PolynomialTooLarge(size) => {
+                write!(f, "polynomials with {size} coefficients are too large to be committed to")
+            }
+            Self::PointInEvaluationDomain(index) => {
+                write!(f, "opening point {index} lies in the low-degree extension domain")
+            }
+            Self::NumOpeningsMismatch(expected, actual) => {
+                write!(f, "expected {expected} openings, but {actual} were provided")
+            }
+            Self::CommitmentFailed(err) => {
+                write!(f, "failed to commit to polynomial evaluations: {err}")
+            }
+            Self::RowsCommitmentMismatch => {
+                write!(f, "queried evaluations did not match the polynomial commitment")
+            }
+            Self::ProofDeserializationError(msg) => {
+                write!(f, "opening proof deserialization failed: {msg}")
+            }
+            Self::RandomCoinError(err) => {
+                write!(f, "failed to draw a random value from the public coin: {err}")
+            }
+            Self::FriVerificationFailed(err) => {
+                write!(f, "verification of the FRI proof failed: {err}")
+            }
         }
     }
 }
@@ -21,6 +21,9 @@
 //!    `max_remainder_size` parameter. While performing the reduction, the prover writes a set of
 //!    layer commitments into the [ProverChannel]. These commitments should be recorded and sent
 //!    to the verifier as they will be needed during the proof verification procedure.
+//!    Polynomials of different degrees can be tested together via
+//!    [build_layers_batched()](prover::FriProver::build_layers_batched()) function, which folds
+//!    evaluations over smaller domains into the FRI layers of matching sizes.
 //! 2. Then, the query phase of the protocol is executed via
 //!    [build_proof()](prover::FriProver::build_proof()) function. The output of this function is
 //!    an instance of the [FriProof] struct. When FRI is executed as a part of the STARK protocol,
@@ -42,7 +45,16 @@
 //! 3. Finally, the query phase of the FRI protocol should be executed via
 //!    [verify()](FriVerifier::verify()) function. Note that query values at the first FRI layer
 //!    are provided to the [verify()](FriVerifier::verify()) function directly. The values at
-//!    remaining layers, the verifier reads from the specified verifier channel.
+//!    remaining layers, the verifier reads from the specified verifier channel. Proofs for
+//!    batches of polynomials of different degrees are verified via
+//!    [verify_batched()](FriVerifier::verify_batched()) function.
+//!
+//! # Polynomial commitments
+//! In addition to low-degree testing, this crate provides a polynomial commitment scheme built on
+//! top of the FRI protocol via [FriPcs]. The scheme can be used to commit to a batch of
+//! polynomials (given either by their coefficients or by their evaluations), to open them at
+//! arbitrary points in an extension field, and to verify such openings without implementing DEEP
+//! quotients, channels, or query sampling manually.
 //!
 //! # Protocol parameters
 //! The current implementation supports executing FRI protocol with dynamically configurable
@@ -53,7 +65,8 @@
 //! * Domain blowup factor,
 //! * Hash function (used for Merkle tree commitments),
 //! * Folding factor (used for degree reduction for each FRI layer),
-//! * Maximum size of the last FRI layer.
+//! * Maximum size of the last FRI layer,
+//! * Format in which the last FRI layer is sent to the verifier (see [RemainderFormat]).
 //!
 //! # References
 //! * StarkWare's blog post on [Low Degree Testing](https://medium.com/starkware/low-degree-testing-f7614f5172db)
@@ -75,12 +88,15 @@ mod verifier;
 pub use verifier::{DefaultVerifierChannel, FriVerifier, VerifierChannel};
 
 mod options;
-pub use options::FriOptions;
+pub use options::{FriOptions, RemainderFormat};
 
 mod proof;
 pub use proof::FriProof;
 
 mod errors;
-pub use errors::VerifierError;
+pub use errors::{PcsError, VerifierError};
+
+pub mod pcs;
+pub use pcs::{FriPcs, PcsCommitment, PcsOpeningProof, PcsProverData};
 
 pub mod utils;
@@ -3,7 +3,9 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use alloc::vec::Vec;
 use math::StarkField;
+use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
 
 // FRI OPTIONS
 // ================================================================================================
@@ -12,8 +14,30 @@ use math::StarkField;
 #[derive(Clone, PartialEq, Eq)]
 pub struct FriOptions {
     folding_factor: usize,
+    folding_schedule: Vec<usize>,
     remainder_max_degree: usize,
     blowup_factor: usize,
+    merkle_cap_height: usize,
+    merkle_arity: usize,
+    grinding_factor: u32,
+    remainder_format: RemainderFormat,
+}
+
+/// Defines how the last FRI layer (the remainder) is sent by the prover to the verifier.
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum RemainderFormat {
+    /// The remainder layer is interpolated into a polynomial, and the coefficients of this
+    /// polynomial are sent to the verifier. The remainder is committed to by hashing the
+    /// coefficients, and the verifier checks query values by evaluating the polynomial at the
+    /// queried points.
+    Coefficients = 1,
+    /// Evaluations of the remainder layer over its entire domain are sent to the verifier. The
+    /// remainder is committed to using the same vector commitment scheme as all other layers,
+    /// the verifier checks the degree of the remainder by interpolating the evaluations, and
+    /// checks query values by looking them up directly. This avoids polynomial evaluations at
+    /// queried points, which may be expensive when the proof is verified recursively.
+    Evaluations = 2,
 }
 
 impl FriOptions {
@@ -29,20 +53,81 @@ impl FriOptions {
             blowup_factor.is_power_of_two(),
             "blowup factor must be a power of two, but was {blowup_factor}"
         );
-        assert!(
-            folding_factor == 2
-                || folding_factor == 4
-                || folding_factor == 8
-                || folding_factor == 16,
-            "folding factor {folding_factor} is not supported"
-        );
+        assert_folding_factor_supported(folding_factor);
         FriOptions {
             folding_factor,
+            folding_schedule: Vec::new(),
             remainder_max_degree,
             blowup_factor,
+            merkle_cap_height: 0,
+            merkle_arity: 2,
+            grinding_factor: 0,
+            remainder_format: RemainderFormat::Coefficients,
         }
     }
 
+    /// Updates the provided [FriOptions] instance to fold FRI layers using the specified folding
+    /// schedule.
+    ///
+    /// The i-th entry of the schedule specifies the folding factor for the i-th FRI layer. Layers
+    /// beyond the end of the schedule are folded using the `folding_factor` specified when the
+    /// options were created. For example, a schedule of `[16, 16]` together with folding factor
+    /// 4 folds the first two layers by 16 and all subsequent layers by 4.
+    ///
+    /// # Panics
+    /// Panics if any of the factors in the schedule is not 2, 4, 8, or 16.
+    pub fn with_folding_schedule(mut self, folding_schedule: Vec<usize>) -> Self {
+        for &folding_factor in folding_schedule.iter() {
+            assert_folding_factor_supported(folding_factor);
+        }
+        self.folding_schedule = folding_schedule;
+        self
+    }
+
+    /// Updates the provided [FriOptions] instance to commit to FRI layers using Merkle tree caps
+    /// of the specified height instead of Merkle tree roots.
+    ///
+    /// Layers which are too small to have a cap at the specified height are committed to using
+    /// caps one level below the root of the layer tree.
+    pub fn with_merkle_cap_height(mut self, merkle_cap_height: usize) -> Self {
+        self.merkle_cap_height = merkle_cap_height;
+        self
+    }
+
+    /// Updates the provided [FriOptions] instance to commit to FRI layers using Merkle trees of
+    /// the specified arity.
+    ///
+    /// # Panics
+    /// Panics if `merkle_arity` is not a power of two between 2 and 16.
+    pub fn with_merkle_arity(mut self, merkle_arity: usize) -> Self {
+        assert!(
+            merkle_arity.is_power_of_two() && (2..=16).contains(&merkle_arity),
+            "Merkle tree arity {merkle_arity} is not supported"
+        );
+        self.merkle_arity = merkle_arity;
+        self
+    }
+
+    /// Updates the provided [FriOptions] instance to require Proof-of-Work of the specified
+    /// number of bits before each layer folding challenge is drawn.
+    ///
+    /// # Panics
+    /// Panics if `grinding_factor` is greater than 32.
+    pub fn with_grinding_factor(mut self, grinding_factor: u32) -> Self {
+        assert!(grinding_factor <= 32, "grinding factor cannot be greater than 32");
+        self.grinding_factor = grinding_factor;
+        self
+    }
+
+    /// Updates the provided [FriOptions] instance to send the remainder layer to the verifier in
+    /// the specified format.
+    ///
+    /// By default, the remainder is sent as coefficients of the remainder polynomial.
+    pub fn with_remainder_format(mut self, remainder_format: RemainderFormat) -> Self {
+        self.remainder_format = remainder_format;
+        self
+    }
+
     /// Returns the offset by which the evaluation domain is shifted.
     ///
     /// The domain is shifted by multiplying every element in the domain by this offset.
@@ -53,7 +138,8 @@ impl FriOptions {
         B::GENERATOR
     }
 
-    /// Returns the factor by which the degree of a polynomial is reduced with each FRI layer.
+    /// Returns the factor by which the degree of a polynomial is reduced with each FRI layer
+    /// which is not covered by the folding schedule.
     ///
     /// In combination with `remainder_max_degree_plus_1` this property defines how many FRI layers are
     /// needed for an evaluation domain of a given size.
@@ -61,6 +147,22 @@ impl FriOptions {
         self.folding_factor
     }
 
+    /// Returns the folding factors used for the first FRI layers.
+    ///
+    /// The schedule is empty if all layers are folded using the same `folding_factor`.
+    pub fn folding_schedule(&self) -> &[usize] {
+        &self.folding_schedule
+    }
+
+    /// Returns the factor by which the degree of a polynomial is reduced at the specified FRI
+    /// layer.
+    ///
+    /// This is the entry of the folding schedule at the specified `layer_idx`, or the
+    /// `folding_factor` if the schedule does not cover this layer.
+    pub fn folding_factor_at(&self, layer_idx: usize) -> usize {
+        self.folding_schedule.get(layer_idx).copied().unwrap_or(self.folding_factor)
+    }
+
     /// Returns maximum allowed remainder polynomial degree.
     ///
     /// In combination with `folding_factor` this property defines how many FRI layers are needed
@@ -78,17 +180,81 @@ impl FriOptions {
         self.blowup_factor
     }
 
+    /// Returns the height of Merkle tree caps used to commit to FRI layers.
+    ///
+    /// The cap height used for a specific layer is reduced for layers with fewer than
+    /// 2^(merkle_cap_height + 1) leaves.
+    pub fn merkle_cap_height(&self) -> usize {
+        self.merkle_cap_height
+    }
+
+    /// Returns the arity of Merkle trees used to commit to FRI layers.
+    pub fn merkle_arity(&self) -> usize {
+        self.merkle_arity
+    }
+
+    /// Returns the number of bits of Proof-of-Work which the prover must perform before each
+    /// layer folding challenge is drawn.
+    ///
+    /// Grinding factor of zero means that folding challenges are drawn without any Proof-of-Work.
+    pub fn grinding_factor(&self) -> u32 {
+        self.grinding_factor
+    }
+
+    /// Returns the format in which the remainder layer is sent to the verifier.
+    pub fn remainder_format(&self) -> RemainderFormat {
+        self.remainder_format
+    }
+
     /// Computes and return the number of FRI layers required for a domain of the specified size.
     ///
-    /// The number of layers for a given domain size is defined by the `folding_factor` and
-    /// `remainder_max_degree` and `blowup_factor` settings.
+    /// The number of layers for a given domain size is defined by the `folding_factor`,
+    /// `folding_schedule`, `remainder_max_degree` and `blowup_factor` settings.
     pub fn num_fri_layers(&self, mut domain_size: usize) -> usize {
         let mut result = 0;
         let max_remainder_size = (self.remainder_max_degree + 1) * self.blowup_factor;
         while domain_size > max_remainder_size {
-            domain_size /= self.folding_factor;
+            domain_size /= self.folding_factor_at(result);
             result += 1;
         }
         result
     }
 }
+
+// REMAINDER FORMAT SERIALIZATION
+// ================================================================================================
+
+impl Serializable for RemainderFormat {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(*self as u8);
+    }
+
+    /// Returns an estimate of how many bytes are needed to represent self.
+    fn get_size_hint(&self) -> usize {
+        1
+    }
+}
+
+impl Deserializable for RemainderFormat {
+    /// Reads a remainder format enum from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            1 => Ok(RemainderFormat::Coefficients),
+            2 => Ok(RemainderFormat::Evaluations),
+            value => Err(DeserializationError::InvalidValue(format!(
+                "value {value} cannot be deserialized as RemainderFormat enum"
+            ))),
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn assert_folding_factor_supported(folding_factor: usize) {
+    assert!(
+        folding_factor == 2 || folding_factor == 4 || folding_factor == 8 || folding_factor == 16,
+        "folding factor {folding_factor} is not supported"
+    );
+}
@@ -0,0 +1,607 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Contains an implementation of a FRI-based polynomial commitment scheme.
+
+use crate::{DefaultVerifierChannel, FriOptions, FriProver, FriVerifier, PcsError, ProverChannel};
+use alloc::{string::ToString, vec::Vec};
+use core::marker::PhantomData;
+use crypto::{
+    ElementHasher, HashDomain, MerkleTree, RandomCoin, VectorCommitment, VectorCommitmentOptions,
+};
+use math::{
+    batch_inversion, fft, get_power_series, get_power_series_with_offset, polynom, FieldElement,
+    StarkField,
+};
+use utils::SliceReader;
+
+mod proof;
+pub use proof::{PcsCommitment, PcsOpeningProof};
+
+#[cfg(test)]
+mod tests;
+
+// FRI POLYNOMIAL COMMITMENT SCHEME
+// ================================================================================================
+/// Implements a polynomial commitment scheme on top of the FRI protocol.
+///
+/// The scheme commits to a batch of polynomials with coefficients in the base field, and can
+/// later open all polynomials in the batch at a set of arbitrary points in the field `E` (which
+/// can be an extension of the base field). The scheme works as follows:
+///
+/// * To commit to a batch of polynomials, the polynomials are evaluated over a low-degree
+///   extension (LDE) domain defined by the `blowup_factor` from the FRI options, and the
+///   evaluations of all polynomials at each point of the domain are hashed into a single item of
+///   a vector commitment (by default, a Merkle tree).
+/// * To open the polynomials at points z_0, ..., z_k, the prover evaluates all polynomials at
+///   these points, and then builds a DEEP quotient as a random linear combination of
+///   (p_j(x) - p_j(z_i)) / (x - z_i) for all polynomials p_j and all points z_i. The prover then
+///   proves that the quotient is of low degree using the FRI protocol, and opens rows of
+///   evaluations of the committed polynomials at the positions queried by FRI.
+/// * To verify an opening, the verifier checks the opened rows against the commitment, computes
+///   the values of the DEEP quotient at the queried positions from these rows and the claimed
+///   evaluations, and verifies the FRI proof against these values.
+///
+/// The scheme is parametrized by the following types:
+///
+/// * `E` specifies the field in which the polynomials are opened. This can be the same as the
+///   base field, but it can also be an extension of the base field in cases when the base field
+///   is too small to provide desired security level.
+/// * `H` specifies the hash function used to build vector commitments.
+/// * `R` specifies the public coin used to derive randomness in a non-interactive way.
+/// * `V` specifies the vector commitment scheme used to commit to polynomial evaluations and FRI
+///   layers. By default, this is a [MerkleTree].
+///
+/// All randomness used by the scheme is drawn from a public coin supplied by the caller. Before
+/// drawing any randomness, the coin is reseeded with the commitment to the polynomials, the
+/// opening points, and the claimed evaluations. The prover and the verifier must supply coins in
+/// the same state for an opening proof to be verified successfully.
+pub struct FriPcs<E, H, R, V = MerkleTree<H>>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+    V: VectorCommitment<H>,
+{
+    options: FriOptions,
+    num_queries: usize,
+    _field_element: PhantomData<E>,
+    _public_coin: PhantomData<R>,
+    _vector_commitment: PhantomData<V>,
+}
+
+/// Data retained by the prover after committing to a batch of polynomials.
+///
+/// This data is required to open the committed polynomials via [FriPcs::open()].
+pub struct PcsProverData<B: StarkField, H: ElementHasher<BaseField = B>, V: VectorCommitment<H>> {
+    polys: Vec<Vec<B>>,
+    lde_rows: Vec<B>,
+    vector_commitment: V,
+    commitment: PcsCommitment<H>,
+}
+
+impl<B: StarkField, H: ElementHasher<BaseField = B>, V: VectorCommitment<H>>
+    PcsProverData<B, H, V>
+{
+    /// Returns the commitment to the batch of polynomials.
+    pub fn commitment(&self) -> &PcsCommitment<H> {
+        &self.commitment
+    }
+
+    /// Returns the committed polynomials in coefficient form.
+    pub fn polys(&self) -> &[Vec<B>] {
+        &self.polys
+    }
+}
+
+impl<E, H, R, V> FriPcs<E, H, R, V>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+    V: VectorCommitment<H>,
+{
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new polynomial commitment scheme instantiated with the specified FRI `options`
+    /// and the number of FRI queries used in opening proofs.
+    ///
+    /// # Panics
+    /// Panics if `num_queries` is zero.
+    pub fn new(options: FriOptions, num_queries: usize) -> Self {
+        assert!(num_queries > 0, "number of queries must be greater than zero");
+        FriPcs {
+            options,
+            num_queries,
+            _field_element: PhantomData,
+            _public_coin: PhantomData,
+            _vector_commitment: PhantomData,
+        }
+    }
+
+    // ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns FRI options used by this scheme.
+    pub fn options(&self) -> &FriOptions {
+        &self.options
+    }
+
+    /// Returns the number of FRI queries used in opening proofs.
+    pub fn num_queries(&self) -> usize {
+        self.num_queries
+    }
+
+    // COMMITMENT
+    // --------------------------------------------------------------------------------------------
+
+    /// Commits to a batch of polynomials specified by their coefficients.
+    ///
+    /// Polynomials in the batch may have different numbers of coefficients; all polynomials are
+    /// padded with zeros to the smallest power of two greater than or equal to the number of
+    /// coefficients of the largest polynomial.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The batch is empty.
+    /// * The polynomials are too small for the low-degree extension domain to accommodate the
+    ///   number of queries specified for this scheme.
+    /// * The vector commitment to the polynomial evaluations could not be built.
+    pub fn commit_coefficients(
+        &self,
+        mut polys: Vec<Vec<E::BaseField>>,
+    ) -> Result<PcsProverData<E::BaseField, H, V>, PcsError> {
+        if polys.is_empty() {
+            return Err(PcsError::EmptyPolynomialBatch);
+        }
+        let poly_size = polys.iter().map(|p| p.len()).max().unwrap().max(1).next_power_of_two();
+        for poly in polys.iter_mut() {
+            poly.resize(poly_size, E::BaseField::ZERO);
+        }
+        self.commit_polys(polys)
+    }
+
+    /// Commits to a batch of polynomials specified by their evaluations over a multiplicative
+    /// subgroup of the base field.
+    ///
+    /// All evaluation vectors must have the same length n, which must be a power of two; the
+    /// i-th evaluation of each vector is assumed to be the evaluation of the corresponding
+    /// polynomial at g^i, where g is the generator of the subgroup of size n.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The batch is empty.
+    /// * The evaluation vectors have different lengths or their length is not a power of two.
+    /// * The polynomials are too small for the low-degree extension domain to accommodate the
+    ///   number of queries specified for this scheme.
+    /// * The vector commitment to the polynomial evaluations could not be built.
+    pub fn commit_evaluations(
+        &self,
+        mut evaluations: Vec<Vec<E::BaseField>>,
+    ) -> Result<PcsProverData<E::BaseField, H, V>, PcsError> {
+        if evaluations.is_empty() {
+            return Err(PcsError::EmptyPolynomialBatch);
+        }
+        let poly_size = evaluations[0].len();
+        for column in evaluations.iter() {
+            if column.len() != poly_size || !poly_size.is_power_of_two() {
+                return Err(PcsError::InvalidEvaluationsLength(column.len()));
+            }
+        }
+
+        let inv_twiddles = fft::get_inv_twiddles::<E::BaseField>(poly_size);
+        for column in evaluations.iter_mut() {
+            fft::interpolate_poly(column, &inv_twiddles);
+        }
+        self.commit_polys(evaluations)
+    }
+
+    // OPENING
+    // --------------------------------------------------------------------------------------------
+
+    /// Opens the polynomials committed to in `prover_data` at the specified `points`.
+    ///
+    /// Returns evaluations of all committed polynomials at each of the points (the i-th vector
+    /// contains evaluations of all polynomials at the i-th point), together with a proof of
+    /// correctness of these evaluations.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * Any of the points lies in the low-degree extension domain.
+    /// * An error was encountered while drawing random values from the `public_coin`.
+    pub fn open(
+        &self,
+        prover_data: &PcsProverData<E::BaseField, H, V>,
+        points: &[E],
+        public_coin: &mut R,
+    ) -> Result<(Vec<Vec<E>>, PcsOpeningProof<H>), PcsError> {
+        let commitment = prover_data.commitment();
+        let lde_domain_size = commitment.poly_size() * self.options.blowup_factor();
+        self.check_points(points, lde_domain_size)?;
+
+        // evaluate all polynomials at all opening points
+        let values = points
+            .iter()
+            .map(|&z| prover_data.polys.iter().map(|p| polynom::eval(p, z)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        // draw the coefficients for the DEEP quotient after the openings have been fixed
+        let alphas =
+            draw_deep_coefficients::<E, H, R, V>(commitment, points, &values, public_coin)?;
+        let deep_offsets = get_deep_offsets(&values, &alphas);
+
+        // evaluate the DEEP quotient over the LDE domain
+        let domain = get_lde_domain::<E::BaseField>(lde_domain_size, self.options.domain_offset());
+        let inv_denominators = points
+            .iter()
+            .map(|&z| {
+                let denominators = domain.iter().map(|&x| E::from(x) - z).collect::<Vec<_>>();
+                batch_inversion(&denominators)
+            })
+            .collect::<Vec<_>>();
+        let num_polys = commitment.num_polys();
+        let quotient_evaluations = prover_data
+            .lde_rows
+            .chunks(num_polys)
+            .enumerate()
+            .map(|(i, row)| {
+                let inv_denominators = inv_denominators.iter().map(|inv| inv[i]);
+                evaluate_deep_quotient(row, inv_denominators, &alphas, &deep_offsets)
+            })
+            .collect::<Vec<_>>();
+
+        // prove that the DEEP quotient is of low degree
+        let mut channel = PcsProverChannel::<E, H, R, V> {
+            public_coin,
+            commitments: Vec::new(),
+            _field_element: PhantomData,
+            _vector_commitment: PhantomData,
+        };
+        let mut fri_prover = FriProver::<E::BaseField, E, _, H, V>::new(self.options.clone());
+        fri_prover.build_layers(&mut channel, quotient_evaluations);
+        let PcsProverChannel {
+            public_coin,
+            commitments: fri_commitments,
+            ..
+        } = channel;
+
+        let positions = draw_query_positions(public_coin, self.num_queries, lde_domain_size)?;
+        let fri_proof = fri_prover.build_proof(&positions);
+
+        // open rows of the committed evaluations at the queried positions
+        let mut queried_rows = Vec::with_capacity(positions.len() * num_polys);
+        for &position in positions.iter() {
+            let row_start = position * num_polys;
+            queried_rows.extend_from_slice(&prover_data.lde_rows[row_start..row_start + num_polys]);
+        }
+        let rows_proof = prover_data
+            .vector_commitment
+            .open_many(&positions)
+            .map_err(|err| PcsError::CommitmentFailed(err.to_string()))?;
+
+        let proof = PcsOpeningProof::new(
+            queried_rows,
+            V::serialize_multiproof(&rows_proof),
+            fri_commitments,
+            fri_proof,
+        );
+        Ok((values, proof))
+    }
+
+    // VERIFICATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Verifies that `values` are evaluations of the polynomials committed to via `commitment`
+    /// at the specified `points`.
+    ///
+    /// `values` must be laid out in the same way as the evaluations returned by
+    /// [open()](FriPcs::open()), and the `public_coin` must be in the same state as the coin
+    /// supplied to [open()](FriPcs::open()) was.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The commitment is to an empty batch of polynomials, or the low-degree extension domain
+    ///   of the committed polynomials does not fit into the two-adic subgroup of the base field.
+    /// * The number of claimed evaluations is inconsistent with the number of points and the
+    ///   number of committed polynomials.
+    /// * Any of the points lies in the low-degree extension domain.
+    /// * The opening proof could not be parsed.
+    /// * Evaluations at queried positions do not match the commitment.
+    /// * The FRI proof for the DEEP quotient is not valid.
+    pub fn verify(
+        &self,
+        commitment: &PcsCommitment<H>,
+        points: &[E],
+        values: &[Vec<E>],
+        proof: PcsOpeningProof<H>,
+        public_coin: &mut R,
+    ) -> Result<(), PcsError> {
+        // the commitment may come from an untrusted source, and so its parameters need to be
+        // validated before they are used to size the verification
+        let num_polys = commitment.num_polys();
+        if num_polys == 0 {
+            return Err(PcsError::EmptyPolynomialBatch);
+        }
+        let poly_size = commitment.poly_size();
+        if poly_size.ilog2() + self.options.blowup_factor().ilog2() > E::BaseField::TWO_ADICITY {
+            return Err(PcsError::PolynomialTooLarge(poly_size));
+        }
+
+        let expected_num_values = points.len() * num_polys;
+        let num_values = values.iter().map(|v| v.len()).sum();
+        if values.len() != points.len() || values.iter().any(|v| v.len() != num_polys) {
+            return Err(PcsError::NumOpeningsMismatch(expected_num_values, num_values));
+        }
+
+        let lde_domain_size = poly_size * self.options.blowup_factor();
+        self.check_points(points, lde_domain_size)?;
+
+        let alphas = draw_deep_coefficients::<E, H, R, V>(commitment, points, values, public_coin)?;
+        let deep_offsets = get_deep_offsets(values, &alphas);
+
+        // execute the commit phase of FRI
+        let (queried_rows, rows_proof, fri_commitments, fri_proof) = proof.into_parts();
+        let num_fri_layers = self.options.num_fri_layers(lde_domain_size);
+        if fri_commitments.len() != num_fri_layers + 1 || fri_proof.num_layers() != num_fri_layers {
+            return Err(PcsError::ProofDeserializationError(format!(
+                "expected {num_fri_layers} FRI layers, but the proof contains {}",
+                fri_proof.num_layers()
+            )));
+        }
+        let fri_commitment_options = VectorCommitmentOptions::new(
+            self.options.merkle_arity(),
+            self.options.merkle_cap_height(),
+        );
+        let mut channel = DefaultVerifierChannel::<E, H, V>::new(
+            fri_proof,
+            fri_commitments,
+            lde_domain_size,
+            &self.options,
+            fri_commitment_options,
+        )
+        .map_err(|err| PcsError::ProofDeserializationError(err.to_string()))?;
+        let fri_verifier = FriVerifier::new(
+            &mut channel,
+            public_coin,
+            self.options.clone(),
+            commitment.poly_size() - 1,
+        )
+        .map_err(PcsError::FriVerificationFailed)?;
+
+        // check the queried rows against the commitment
+        let positions = draw_query_positions(public_coin, self.num_queries, lde_domain_size)?;
+        if queried_rows.len() != positions.len() * num_polys {
+            return Err(PcsError::ProofDeserializationError(format!(
+                "expected {} queried values, but the proof contains {}",
+                positions.len() * num_polys,
+                queried_rows.len()
+            )));
+        }
+        let items = queried_rows
+            .chunks(num_polys)
+            .map(|row| H::hash_elements_in_domain(row, HashDomain::Leaf))
+            .collect::<Vec<_>>();
+        let rows_proof = V::deserialize_multiproof(
+            &mut SliceReader::new(&rows_proof),
+            items,
+            lde_domain_size,
+            self.rows_commitment_options(lde_domain_size),
+        )
+        .map_err(|err| PcsError::ProofDeserializationError(err.to_string()))?;
+        V::verify_many(commitment.commitment(), &positions, &rows_proof)
+            .map_err(|_| PcsError::RowsCommitmentMismatch)?;
+
+        // compute the values of the DEEP quotient at the queried positions and verify the FRI
+        // proof against them
+        let domain_generator = E::BaseField::get_root_of_unity(lde_domain_size.ilog2());
+        let domain_offset: E::BaseField = self.options.domain_offset();
+        let quotient_evaluations = positions
+            .iter()
+            .zip(queried_rows.chunks(num_polys))
+            .map(|(&position, row)| {
+                let x = domain_offset * domain_generator.exp_vartime((position as u64).into());
+                let inv_denominators = points.iter().map(|&z| (E::from(x) - z).inv());
+                evaluate_deep_quotient(row, inv_denominators, &alphas, &deep_offsets)
+            })
+            .collect::<Vec<_>>();
+
+        fri_verifier
+            .verify(&mut channel, &quotient_evaluations, &positions)
+            .map_err(PcsError::FriVerificationFailed)
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Evaluates the specified polynomials over the LDE domain and commits to the evaluations.
+    fn commit_polys(
+        &self,
+        polys: Vec<Vec<E::BaseField>>,
+    ) -> Result<PcsProverData<E::BaseField, H, V>, PcsError> {
+        let num_polys = polys.len();
+        let poly_size = polys[0].len();
+        let blowup_factor = self.options.blowup_factor();
+        let lde_domain_size = poly_size * blowup_factor;
+        if lde_domain_size <= self.num_queries.max(1) {
+            return Err(PcsError::PolynomialTooSmall(poly_size));
+        }
+        if lde_domain_size.ilog2() > E::BaseField::TWO_ADICITY {
+            return Err(PcsError::PolynomialTooLarge(poly_size));
+        }
+
+        // evaluate polynomials over the LDE domain and arrange the evaluations into rows
+        let twiddles = fft::get_twiddles::<E::BaseField>(poly_size);
+        let domain_offset = self.options.domain_offset();
+        let mut lde_rows = vec![E::BaseField::ZERO; lde_domain_size * num_polys];
+        for (j, poly) in polys.iter().enumerate() {
+            let evaluations =
+                fft::evaluate_poly_with_offset(poly, &twiddles, domain_offset, blowup_factor);
+            for (i, value) in evaluations.into_iter().enumerate() {
+                lde_rows[i * num_polys + j] = value;
+            }
+        }
+
+        // commit to the hashed rows
+        let row_hashes = lde_rows
+            .chunks(num_polys)
+            .map(|row| H::hash_elements_in_domain(row, HashDomain::Leaf))
+            .collect::<Vec<_>>();
+        let vector_commitment =
+            V::with_options(row_hashes, self.rows_commitment_options(lde_domain_size))
+                .map_err(|err| PcsError::CommitmentFailed(err.to_string()))?;
+        let commitment =
+            PcsCommitment::new(vector_commitment.commitment().to_vec(), num_polys, poly_size);
+
+        Ok(PcsProverData {
+            polys,
+            lde_rows,
+            vector_commitment,
+            commitment,
+        })
+    }
+
+    /// Returns options for the vector commitment to the rows of the LDE; the cap height is
+    /// reduced for small domains in the same way as for FRI layers.
+    fn rows_commitment_options(&self, lde_domain_size: usize) -> VectorCommitmentOptions {
+        let cap_height = self.options.merkle_cap_height().min(lde_domain_size.ilog2() as usize - 1);
+        VectorCommitmentOptions::new(self.options.merkle_arity(), cap_height)
+    }
+
+    /// Makes sure none of the specified points lies in the LDE domain of the specified size.
+    fn check_points(&self, points: &[E], lde_domain_size: usize) -> Result<(), PcsError> {
+        // all x in the LDE domain satisfy x^n = offset^n, where n is the size of the domain
+        let domain_offset: E::BaseField = self.options.domain_offset();
+        let domain_value = E::from(domain_offset.exp_vartime((lde_domain_size as u64).into()));
+        for (i, &z) in points.iter().enumerate() {
+            if z.exp_vartime((lde_domain_size as u64).into()) == domain_value {
+                return Err(PcsError::PointInEvaluationDomain(i));
+            }
+        }
+        Ok(())
+    }
+}
+
+// PROVER CHANNEL
+// ================================================================================================
+
+/// FRI prover channel which draws randomness from the public coin supplied by the caller.
+struct PcsProverChannel<'a, E, H, R, V>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+    V: VectorCommitment<H>,
+{
+    public_coin: &'a mut R,
+    commitments: Vec<Vec<H::Digest>>,
+    _field_element: PhantomData<E>,
+    _vector_commitment: PhantomData<V>,
+}
+
+impl<'a, E, H, R, V> ProverChannel<E> for PcsProverChannel<'a, E, H, R, V>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+    V: VectorCommitment<H>,
+{
+    type Hasher = H;
+
+    fn commit_fri_layer(&mut self, layer_cap: &[H::Digest]) {
+        self.commitments.push(layer_cap.to_vec());
+        self.public_coin.reseed(V::commitment_digest(layer_cap));
+    }
+
+    fn draw_fri_alpha(&mut self) -> E {
+        self.public_coin.draw().expect("failed to draw FRI alpha")
+    }
+
+    fn grind_fri_layer(&mut self, grinding_factor: u32) -> u64 {
+        let nonce = (1..u64::MAX)
+            .find(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor)
+            .expect("nonce not found");
+        self.public_coin.reseed_with_int(nonce);
+        nonce
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Reseeds the public coin with the commitment, opening points and claimed evaluations, and
+/// draws the coefficients of the random linear combination used to build the DEEP quotient.
+fn draw_deep_coefficients<E, H, R, V>(
+    commitment: &PcsCommitment<H>,
+    points: &[E],
+    values: &[Vec<E>],
+    public_coin: &mut R,
+) -> Result<Vec<E>, PcsError>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+    V: VectorCommitment<H>,
+{
+    public_coin.reseed(V::commitment_digest(commitment.commitment()));
+    public_coin.reseed(H::hash_elements_in_domain(points, HashDomain::Transcript));
+    let values = values.iter().flatten().copied().collect::<Vec<_>>();
+    public_coin.reseed(H::hash_elements_in_domain(&values, HashDomain::Transcript));
+
+    let alpha: E = public_coin.draw().map_err(PcsError::RandomCoinError)?;
+    Ok(get_power_series(alpha, values.len()))
+}
+
+/// Returns sum_j alpha_{i, j} * p_j(z_i) for each opening point z_i.
+fn get_deep_offsets<E: FieldElement>(values: &[Vec<E>], alphas: &[E]) -> Vec<E> {
+    let num_polys = values.first().map(|v| v.len()).unwrap_or(0);
+    values
+        .iter()
+        .zip(alphas.chunks(num_polys.max(1)))
+        .map(|(values, alphas)| {
+            values.iter().zip(alphas).fold(E::ZERO, |acc, (&v, &a)| acc + v * a)
+        })
+        .collect()
+}
+
+/// Evaluates the DEEP quotient at x given evaluations of the committed polynomials at x (`row`)
+/// and values 1 / (x - z_i) for all opening points z_i.
+fn evaluate_deep_quotient<E: FieldElement>(
+    row: &[E::BaseField],
+    inv_denominators: impl Iterator<Item = E>,
+    alphas: &[E],
+    deep_offsets: &[E],
+) -> E {
+    inv_denominators.zip(alphas.chunks(row.len())).zip(deep_offsets).fold(
+        E::ZERO,
+        |acc, ((inv_denominator, alphas), &offset)| {
+            let numerator = row
+                .iter()
+                .zip(alphas)
+                .fold(E::ZERO, |acc, (&value, &alpha)| acc + alpha.mul_base(value));
+            acc + (numerator - offset) * inv_denominator
+        },
+    )
+}
+
+/// Returns the points of the LDE domain of the specified size in natural order.
+fn get_lde_domain<B: StarkField>(domain_size: usize, domain_offset: B) -> Vec<B> {
+    let domain_generator = B::get_root_of_unity(domain_size.ilog2());
+    get_power_series_with_offset(domain_generator, domain_offset, domain_size)
+}
+
+/// Draws query positions in the LDE domain from the public coin; the returned positions are
+/// sorted and contain no duplicates.
+fn draw_query_positions<R: RandomCoin>(
+    public_coin: &mut R,
+    num_queries: usize,
+    lde_domain_size: usize,
+) -> Result<Vec<usize>, PcsError> {
+    let mut positions = public_coin
+        .draw_integers(num_queries, lde_domain_size, 0)
+        .map_err(PcsError::RandomCoinError)?;
+    positions.sort_unstable();
+    positions.dedup();
+    Ok(positions)
+}
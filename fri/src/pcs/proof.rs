@@ -0,0 +1,247 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::FriProof;
+use alloc::{string::ToString, vec::Vec};
+use crypto::{ElementHasher, Hasher};
+use math::StarkField;
+use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+// POLYNOMIAL COMMITMENT
+// ================================================================================================
+
+/// Commitment to a batch of polynomials made by a [FriPcs](super::FriPcs).
+///
+/// The commitment consists of the vector commitment to the evaluations of all polynomials in the
+/// batch over the low-degree extension domain (for Merkle trees, the root or the cap of the tree),
+/// together with the number of polynomials in the batch and the number of coefficients in each
+/// polynomial.
+#[derive(Debug)]
+pub struct PcsCommitment<H: Hasher> {
+    commitment: Vec<H::Digest>,
+    num_polys: usize,
+    poly_size: usize,
+}
+
+impl<H: Hasher> PcsCommitment<H> {
+    /// Returns a new commitment instantiated from the specified parameters.
+    pub(super) fn new(commitment: Vec<H::Digest>, num_polys: usize, poly_size: usize) -> Self {
+        PcsCommitment {
+            commitment,
+            num_polys,
+            poly_size,
+        }
+    }
+
+    /// Returns the vector commitment to the evaluations of the committed polynomials.
+    pub fn commitment(&self) -> &[H::Digest] {
+        &self.commitment
+    }
+
+    /// Returns the number of polynomials in the committed batch.
+    pub fn num_polys(&self) -> usize {
+        self.num_polys
+    }
+
+    /// Returns the number of coefficients in each of the committed polynomials.
+    ///
+    /// This is always a power of two, and the degree of all committed polynomials is smaller than
+    /// this value.
+    pub fn poly_size(&self) -> usize {
+        self.poly_size
+    }
+}
+
+// the traits below are implemented manually because deriving them would require the hasher
+// type parameter to implement them as well
+
+impl<H: Hasher> Clone for PcsCommitment<H> {
+    fn clone(&self) -> Self {
+        PcsCommitment {
+            commitment: self.commitment.clone(),
+            num_polys: self.num_polys,
+            poly_size: self.poly_size,
+        }
+    }
+}
+
+impl<H: Hasher> PartialEq for PcsCommitment<H> {
+    fn eq(&self, other: &Self) -> bool {
+        self.commitment == other.commitment
+            && self.num_polys == other.num_polys
+            && self.poly_size == other.poly_size
+    }
+}
+
+impl<H: Hasher> Eq for PcsCommitment<H> {}
+
+impl<H: Hasher> Serializable for PcsCommitment<H> {
+    /// Serializes `self` and writes the resulting bytes into the `target` writer.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u16(self.commitment.len() as u16);
+        target.write_many(&self.commitment);
+        target.write_u32(self.num_polys as u32);
+        target.write_u8(self.poly_size.ilog2() as u8);
+    }
+}
+
+impl<H: ElementHasher> Deserializable for PcsCommitment<H> {
+    /// Reads a polynomial commitment from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid commitment could not be read from the source, or if the
+    /// commitment is to an empty batch of polynomials, or if the polynomials are too large for
+    /// their evaluation domain to fit into the two-adic subgroup of the base field.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let num_digests = source.read_u16()? as usize;
+        let commitment = source.read_many(num_digests)?;
+        let num_polys = source.read_u32()? as usize;
+        if num_polys == 0 {
+            return Err(DeserializationError::InvalidValue(
+                "number of committed polynomials must be greater than zero".to_string(),
+            ));
+        }
+        let log_poly_size = source.read_u8()? as u32;
+        if log_poly_size > <H::BaseField as StarkField>::TWO_ADICITY {
+            return Err(DeserializationError::InvalidValue(format!(
+                "polynomial size 2^{log_poly_size} is too large"
+            )));
+        }
+        Ok(PcsCommitment {
+            commitment,
+            num_polys,
+            poly_size: 1 << log_poly_size,
+        })
+    }
+}
+
+// OPENING PROOF
+// ================================================================================================
+
+/// Proof of opening of a batch of committed polynomials at a set of points.
+///
+/// The proof contains evaluations of all committed polynomials at the positions of the
+/// low-degree extension domain queried by the verifier together with a proof of their opening
+/// against the polynomial commitment, as well as a FRI proof attesting to the low degree of the
+/// DEEP quotient built from the committed polynomials and the claimed openings.
+#[derive(Debug)]
+pub struct PcsOpeningProof<H: ElementHasher> {
+    queried_rows: Vec<H::BaseField>,
+    rows_proof: Vec<u8>,
+    fri_commitments: Vec<Vec<H::Digest>>,
+    fri_proof: FriProof,
+}
+
+impl<H: ElementHasher> PcsOpeningProof<H> {
+    /// Returns a new opening proof instantiated from the specified parameters.
+    pub(super) fn new(
+        queried_rows: Vec<H::BaseField>,
+        rows_proof: Vec<u8>,
+        fri_commitments: Vec<Vec<H::Digest>>,
+        fri_proof: FriProof,
+    ) -> Self {
+        PcsOpeningProof {
+            queried_rows,
+            rows_proof,
+            fri_commitments,
+            fri_proof,
+        }
+    }
+
+    /// Returns evaluations of the committed polynomials at the queried positions of the
+    /// low-degree extension domain, one row of evaluations per queried position.
+    pub fn queried_rows(&self) -> &[H::BaseField] {
+        &self.queried_rows
+    }
+
+    /// Returns the serialized proof of opening of the queried rows against the polynomial
+    /// commitment.
+    pub fn rows_proof(&self) -> &[u8] {
+        &self.rows_proof
+    }
+
+    /// Returns the commitments to the FRI layers of the DEEP quotient.
+    pub fn fri_commitments(&self) -> &[Vec<H::Digest>] {
+        &self.fri_commitments
+    }
+
+    /// Returns the FRI proof for the DEEP quotient.
+    pub fn fri_proof(&self) -> &FriProof {
+        &self.fri_proof
+    }
+
+    /// Consumes this proof and returns its parts.
+    #[allow(clippy::type_complexity)]
+    pub(super) fn into_parts(self) -> (Vec<H::BaseField>, Vec<u8>, Vec<Vec<H::Digest>>, FriProof) {
+        (self.queried_rows, self.rows_proof, self.fri_commitments, self.fri_proof)
+    }
+}
+
+impl<H: ElementHasher> Clone for PcsOpeningProof<H> {
+    fn clone(&self) -> Self {
+        PcsOpeningProof {
+            queried_rows: self.queried_rows.clone(),
+            rows_proof: self.rows_proof.clone(),
+            fri_commitments: self.fri_commitments.clone(),
+            fri_proof: self.fri_proof.clone(),
+        }
+    }
+}
+
+impl<H: ElementHasher> PartialEq for PcsOpeningProof<H> {
+    fn eq(&self, other: &Self) -> bool {
+        self.queried_rows == other.queried_rows
+            && self.rows_proof == other.rows_proof
+            && self.fri_commitments == other.fri_commitments
+            && self.fri_proof == other.fri_proof
+    }
+}
+
+impl<H: ElementHasher> Eq for PcsOpeningProof<H> {}
+
+impl<H: ElementHasher> Serializable for PcsOpeningProof<H> {
+    /// Serializes `self` and writes the resulting bytes into the `target` writer.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u32(self.queried_rows.len() as u32);
+        target.write_many(&self.queried_rows);
+        target.write_u32(self.rows_proof.len() as u32);
+        target.write_bytes(&self.rows_proof);
+
+        target.write_u8(self.fri_commitments.len() as u8);
+        for commitment in self.fri_commitments.iter() {
+            target.write_u16(commitment.len() as u16);
+            target.write_many(commitment);
+        }
+        self.fri_proof.write_into(target);
+    }
+}
+
+impl<H: ElementHasher> Deserializable for PcsOpeningProof<H> {
+    /// Reads an opening proof from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid proof could not be read from the source.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let num_row_elements = source.read_u32()? as usize;
+        let queried_rows = source.read_many(num_row_elements)?;
+        let num_proof_bytes = source.read_u32()? as usize;
+        let rows_proof = source.read_vec(num_proof_bytes)?;
+
+        let num_layers = source.read_u8()? as usize;
+        let mut fri_commitments = Vec::with_capacity(num_layers);
+        for _ in 0..num_layers {
+            let num_digests = source.read_u16()? as usize;
+            fri_commitments.push(source.read_many(num_digests)?);
+        }
+        let fri_proof = FriProof::read_from(source)?;
+
+        Ok(PcsOpeningProof {
+            queried_rows,
+            rows_proof,
+            fri_commitments,
+            fri_proof,
+        })
+    }
+}
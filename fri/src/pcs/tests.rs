@@ -0,0 +1,143 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{FriPcs, PcsCommitment, PcsOpeningProof};
+use crate::{FriOptions, PcsError};
+use alloc::vec::Vec;
+use crypto::{hashers::Blake3_256, DefaultRandomCoin, MerkleTree, RandomCoin};
+use math::{
+    fft,
+    fields::{f64::BaseElement, QuadExtension},
+    polynom, FieldElement, StarkField,
+};
+use rand_utils::rand_vector;
+use utils::{Deserializable, Serializable};
+
+type Blake3 = Blake3_256<BaseElement>;
+type E = QuadExtension<BaseElement>;
+type Pcs = FriPcs<E, Blake3, DefaultRandomCoin<Blake3>, MerkleTree<Blake3>>;
+
+// OPEN/VERIFY TESTS
+// ================================================================================================
+
+#[test]
+fn pcs_open_verify_coefficients() {
+    let pcs = Pcs::new(FriOptions::new(8, 4, 7), 32);
+    let polys = (0..5).map(|_| rand_vector::<BaseElement>(1024)).collect::<Vec<_>>();
+    let prover_data = pcs.commit_coefficients(polys.clone()).unwrap();
+    let points = rand_vector::<E>(3);
+
+    let (values, proof) = pcs.open(&prover_data, &points, &mut build_coin()).unwrap();
+    for (z, values) in points.iter().zip(values.iter()) {
+        for (poly, &value) in polys.iter().zip(values.iter()) {
+            assert_eq!(polynom::eval(poly, *z), value);
+        }
+    }
+
+    // verify the proof after serializing and deserializing commitment and proof
+    let commitment = prover_data.commitment().to_bytes();
+    let commitment = PcsCommitment::<Blake3>::read_from_bytes(&commitment).unwrap();
+    let proof = PcsOpeningProof::<Blake3>::read_from_bytes(&proof.to_bytes()).unwrap();
+    assert!(pcs.verify(&commitment, &points, &values, proof, &mut build_coin()).is_ok());
+}
+
+#[test]
+fn pcs_open_verify_evaluations() {
+    let options = FriOptions::new(4, 8, 15).with_merkle_cap_height(2).with_merkle_arity(4);
+    let pcs = Pcs::new(options, 20);
+
+    // commit to evaluations of random polynomials of different degrees
+    let polys = [rand_vector::<BaseElement>(512), rand_vector::<BaseElement>(100)];
+    let evaluations = polys
+        .iter()
+        .map(|poly| {
+            let mut evaluations = poly.clone();
+            evaluations.resize(512, BaseElement::ZERO);
+            fft::evaluate_poly(&mut evaluations, &fft::get_twiddles(512));
+            evaluations
+        })
+        .collect::<Vec<_>>();
+    let prover_data = pcs.commit_evaluations(evaluations).unwrap();
+    let points = rand_vector::<E>(2);
+
+    let (values, proof) = pcs.open(&prover_data, &points, &mut build_coin()).unwrap();
+    assert_eq!(polynom::eval(&polys[1], points[0]), values[0][1]);
+
+    let commitment = prover_data.commitment();
+    assert!(pcs.verify(commitment, &points, &values, proof, &mut build_coin()).is_ok());
+}
+
+#[test]
+fn pcs_verify_fail() {
+    let pcs = Pcs::new(FriOptions::new(8, 4, 7), 32);
+    let polys = (0..3).map(|_| rand_vector::<BaseElement>(256)).collect::<Vec<_>>();
+    let prover_data = pcs.commit_coefficients(polys).unwrap();
+    let commitment = prover_data.commitment();
+    let points = rand_vector::<E>(2);
+    let (values, proof) = pcs.open(&prover_data, &points, &mut build_coin()).unwrap();
+
+    // wrong evaluations
+    let mut wrong_values = values.clone();
+    wrong_values[1][2] += E::ONE;
+    assert!(pcs
+        .verify(commitment, &points, &wrong_values, proof.clone(), &mut build_coin())
+        .is_err());
+
+    // wrong number of evaluations
+    let result = pcs.verify(commitment, &points, &values[..1], proof.clone(), &mut build_coin());
+    assert_eq!(Err(PcsError::NumOpeningsMismatch(6, 3)), result);
+
+    // public coin in a different state
+    let mut coin = DefaultRandomCoin::<Blake3>::new(&[BaseElement::ONE]);
+    assert!(pcs.verify(commitment, &points, &values, proof, &mut coin).is_err());
+}
+
+#[test]
+fn pcs_point_in_domain() {
+    let pcs = Pcs::new(FriOptions::new(8, 4, 7), 32);
+    let polys = vec![rand_vector::<BaseElement>(256)];
+    let prover_data = pcs.commit_coefficients(polys).unwrap();
+
+    let point = E::from(BaseElement::GENERATOR * BaseElement::get_root_of_unity(11).exp(5));
+    let result = pcs.open(&prover_data, &[E::ONE, point], &mut build_coin());
+    assert_eq!(Some(PcsError::PointInEvaluationDomain(1)), result.err());
+}
+
+#[test]
+fn pcs_invalid_commitment() {
+    let pcs = Pcs::new(FriOptions::new(8, 4, 7), 32);
+    let polys = (0..2).map(|_| rand_vector::<BaseElement>(256)).collect::<Vec<_>>();
+    let prover_data = pcs.commit_coefficients(polys).unwrap();
+    let points = rand_vector::<E>(1);
+    let (values, proof) = pcs.open(&prover_data, &points, &mut build_coin()).unwrap();
+    let digests = prover_data.commitment().commitment().to_vec();
+
+    // commitments to empty batches or to oversized polynomials cannot be deserialized
+    let mut bytes = prover_data.commitment().to_bytes();
+    let num_polys_idx = bytes.len() - 5;
+    bytes[num_polys_idx..num_polys_idx + 4].copy_from_slice(&0u32.to_le_bytes());
+    assert!(PcsCommitment::<Blake3>::read_from_bytes(&bytes).is_err());
+
+    let mut bytes = prover_data.commitment().to_bytes();
+    *bytes.last_mut().unwrap() = BaseElement::TWO_ADICITY as u8 + 1;
+    assert!(PcsCommitment::<Blake3>::read_from_bytes(&bytes).is_err());
+
+    // and are rejected by the verifier
+    let commitment = PcsCommitment::<Blake3>::new(digests.clone(), 0, 256);
+    let result = pcs.verify(&commitment, &points, &values, proof.clone(), &mut build_coin());
+    assert_eq!(Err(PcsError::EmptyPolynomialBatch), result);
+
+    let poly_size = 1 << (BaseElement::TWO_ADICITY - 2);
+    let commitment = PcsCommitment::<Blake3>::new(digests, 2, poly_size);
+    let result = pcs.verify(&commitment, &points, &values, proof, &mut build_coin());
+    assert_eq!(Err(PcsError::PolynomialTooLarge(poly_size)), result);
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn build_coin() -> DefaultRandomCoin<Blake3> {
+    DefaultRandomCoin::new(&[])
+}
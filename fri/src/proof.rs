@@ -3,8 +3,9 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use crate::{FriOptions, RemainderFormat};
 use alloc::{string::ToString, vec::Vec};
-use crypto::{BatchMerkleProof, ElementHasher, Hasher};
+use crypto::{ElementHasher, HashDomain, Hasher, VectorCommitment, VectorCommitmentOptions};
 use math::FieldElement;
 use utils::{
     ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader,
@@ -23,7 +24,18 @@ use utils::{
 /// A proof consists of zero or more layers and a remainder polynomial. Each layer contains a set of
 /// polynomial evaluations at positions queried by the verifier as well as Merkle authentication
 /// paths for these evaluations (the Merkle paths are compressed into a batch Merkle proof). The
-/// remainder polynomial is given by its list of coefficients i.e. field elements.
+/// remainder polynomial is given either by its list of coefficients or by its evaluations over the
+/// domain of the last layer, depending on the [RemainderFormat] used to generate the proof.
+///
+/// Proofs for batches of functions evaluated over domains of different sizes (see
+/// [FriProver::build_layers_batched()](crate::FriProver::build_layers_batched())) have the same
+/// structure: the layers contain evaluations of the combined functions, while evaluations of the
+/// batched functions at the queried positions are not included into the proof, and must be
+/// supplied to the verifier together with the evaluations of the first function.
+///
+/// If the proof was generated with a non-zero grinding factor, the proof also contains one
+/// Proof-of-Work nonce for every layer (excluding the remainder); the verifier absorbs each nonce
+/// into its public coin before drawing the folding challenge for the corresponding layer.
 ///
 /// All values in a proof are stored as vectors of bytes. Thus, the values must be parsed before
 /// they can be returned to the user. To do this, [parse_layers()](FriProof::parse_layers())
@@ -32,22 +44,28 @@ use utils::{
 pub struct FriProof {
     layers: Vec<FriProofLayer>,
     remainder: Vec<u8>,
+    remainder_format: RemainderFormat,
     num_partitions: u8, // stored as power of 2
+    pow_nonces: Vec<u64>,
 }
 
 impl FriProof {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
-    /// Creates a new FRI proof from the provided layers and remainder polynomial.
+    /// Creates a new FRI proof from the provided layers, remainder (in the specified format), and
+    /// layer Proof-of-Work nonces.
     ///
     /// # Panics
     /// Panics if:
     /// * Number of remainder elements zero or is not a power of two.
     /// * `num_partitions` is zero or is not a power of two.
+    /// * `pow_nonces` is not empty and the number of nonces is not equal to the number of layers.
     pub(crate) fn new<E: FieldElement>(
         layers: Vec<FriProofLayer>,
         remainder: Vec<E>,
+        remainder_format: RemainderFormat,
         num_partitions: usize,
+        pow_nonces: Vec<u64>,
     ) -> Self {
         assert!(!remainder.is_empty(), "number of remainder elements must be greater than zero");
         assert!(
@@ -61,13 +79,21 @@ impl FriProof {
             "number of partitions must be a power of two, but was {num_partitions}"
         );
 
+        assert!(
+            pow_nonces.is_empty() || pow_nonces.len() == layers.len(),
+            "number of PoW nonces must be equal to the number of layers, but was {}",
+            pow_nonces.len()
+        );
+
         let mut remainder_bytes = Vec::with_capacity(E::ELEMENT_BYTES * remainder.len());
         remainder_bytes.write_many(&remainder);
 
         FriProof {
             layers,
             remainder: remainder_bytes,
+            remainder_format,
             num_partitions: num_partitions.trailing_zeros() as u8,
+            pow_nonces,
         }
     }
 
@@ -76,7 +102,9 @@ impl FriProof {
         Self {
             layers: Vec::new(),
             remainder: Vec::new(),
+            remainder_format: RemainderFormat::Coefficients,
             num_partitions: 0,
+            pow_nonces: Vec::new(),
         }
     }
 
@@ -96,57 +124,88 @@ impl FriProof {
         self.remainder.len() / E::ELEMENT_BYTES
     }
 
+    /// Returns the format in which the remainder is stored in this proof.
+    pub fn remainder_format(&self) -> RemainderFormat {
+        self.remainder_format
+    }
+
     /// Returns the number of partitions used during proof generation.
     pub fn num_partitions(&self) -> usize {
         2usize.pow(self.num_partitions as u32)
     }
 
+    /// Returns Proof-of-Work nonces for FRI layers.
+    ///
+    /// The vector is empty if the proof was generated without layer grinding; otherwise, it
+    /// contains one nonce per layer.
+    pub fn pow_nonces(&self) -> &[u64] {
+        &self.pow_nonces
+    }
+
     /// Returns the size of this proof in bytes.
     pub fn size(&self) -> usize {
-        // +1 for number of layers, +1 for remainder length, +1 for number of partitions
+        // +1 for number of layers, +2 for remainder length, +1 for remainder format, +1 for
+        // number of partitions, +1 for number of PoW nonces
         self.layers
             .iter()
-            .fold(self.remainder.len() + 3, |acc, layer| acc + layer.size())
+            .fold(self.remainder.len() + self.pow_nonces.len() * 8 + 6, |acc, layer| {
+                acc + layer.size()
+            })
     }
 
     // PARSING
     // --------------------------------------------------------------------------------------------
 
-    /// Decomposes this proof into vectors of query values for each layer and corresponding Merkle
-    /// authentication paths for each query (grouped into batch Merkle proofs).
+    /// Decomposes this proof into vectors of query values for each layer and corresponding
+    /// opening proofs for the vector commitment scheme `V` built with the specified
+    /// `commitment_options` (for Merkle trees, batch Merkle proofs).
+    ///
+    /// The folding factor of each layer is determined by the folding schedule and the folding
+    /// factor specified in the `options`.
     ///
     /// # Panics
-    /// Panics if:
-    /// * `domain_size` is not a power of two.
-    /// * `folding_factor` is smaller than two or is not a power of two.
+    /// Panics if `domain_size` is not a power of two.
     ///
     /// # Errors
     /// Returns an error if:
-    /// * This proof is not consistent with the specified `domain_size` and `folding_factor`.
+    /// * This proof is not consistent with the specified `domain_size`, FRI `options`, and
+    ///   `commitment_options`.
     /// * Any of the layers could not be parsed successfully.
     #[allow(clippy::type_complexity)]
-    pub fn parse_layers<H, E>(
+    pub fn parse_layers<H, E, V>(
         self,
         mut domain_size: usize,
-        folding_factor: usize,
-    ) -> Result<(Vec<Vec<E>>, Vec<BatchMerkleProof<H>>), DeserializationError>
+        options: &FriOptions,
+        commitment_options: VectorCommitmentOptions,
+    ) -> Result<(Vec<Vec<E>>, Vec<V::MultiProof>), DeserializationError>
     where
         E: FieldElement,
         H: ElementHasher<BaseField = E::BaseField>,
+        V: VectorCommitment<H>,
     {
         assert!(domain_size.is_power_of_two(), "domain size must be a power of two");
-        assert!(folding_factor.is_power_of_two(), "folding factor must be a power of two");
-        assert!(folding_factor > 1, "folding factor must be greater than 1");
 
         let mut layer_proofs = Vec::new();
         let mut layer_queries = Vec::new();
 
         // parse all layers
         for (i, layer) in self.layers.into_iter().enumerate() {
+            let folding_factor = options.folding_factor_at(i);
             domain_size /= folding_factor;
-            let (qv, mp) = layer.parse(domain_size, folding_factor).map_err(|err| {
-                DeserializationError::InvalidValue(format!("failed to parse FRI layer {i}: {err}"))
-            })?;
+            // the cap height is reduced for layers which are too small for a cap of the
+            // specified height, in the same way as it is done by the prover
+            let cap_height = commitment_options
+                .cap_height()
+                .min((domain_size.ilog2() as usize).saturating_sub(1));
+            let layer_options =
+                VectorCommitmentOptions::new(commitment_options.arity(), cap_height);
+            let (qv, mp) = layer
+                .parse::<H, E, V>(domain_size, folding_factor, layer_options)
+                .map_err(|err| {
+                    DeserializationError::InvalidValue(format!(
+                        "failed to parse FRI layer {i}: {err}"
+                    ))
+                })?;
             layer_proofs.push(mp);
             layer_queries.push(qv);
         }
@@ -156,6 +215,9 @@ impl FriProof {
 
     /// Returns a vector of remainder values (last FRI layer) parsed from this proof.
     ///
+    /// The values are either coefficients of the remainder polynomial or evaluations of the
+    /// remainder over its domain, depending on the [RemainderFormat] of this proof.
+    ///
     /// # Errors
     /// Returns an error if:
     /// * The number of remainder values implied by a combination of `E` type parameter and
@@ -192,12 +254,18 @@ impl Serializable for FriProof {
             layer.write_into(target);
         }
 
-        // write remainder
-        target.write_u16(self.remainder.len() as u16);
+        // write remainder; when sent as evaluations, the remainder may be too large for its
+        // length to fit into 2 bytes
+        target.write_usize(self.remainder.len());
         target.write_bytes(&self.remainder);
+        self.remainder_format.write_into(target);
 
         // write number of partitions
         target.write_u8(self.num_partitions);
+
+        // write PoW nonces
+        target.write_u8(self.pow_nonces.len() as u8);
+        target.write_many(&self.pow_nonces);
     }
 }
 
@@ -212,16 +280,23 @@ impl Deserializable for FriProof {
         let layers = source.read_many(num_layers)?;
 
         // read remainder
-        let num_remainder_bytes = source.read_u16()? as usize;
+        let num_remainder_bytes = source.read_usize()?;
         let remainder = source.read_vec(num_remainder_bytes)?;
+        let remainder_format = RemainderFormat::read_from(source)?;
 
         // read number of partitions
         let num_partitions = source.read_u8()?;
 
+        // read PoW nonces
+        let num_pow_nonces = source.read_u8()? as usize;
+        let pow_nonces = source.read_many(num_pow_nonces)?;
+
         Ok(FriProof {
             layers,
             remainder,
+            remainder_format,
             num_partitions,
+            pow_nonces,
         })
     }
 }
@@ -238,14 +313,14 @@ pub struct FriProofLayer {
 impl FriProofLayer {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
-    /// Creates a new proof layer from the specified query values and the corresponding Merkle
-    /// paths aggregated into a single batch Merkle proof.
+    /// Creates a new proof layer from the specified query values and the corresponding opening
+    /// proof (e.g., Merkle paths aggregated into a single batch Merkle proof).
     ///
     /// # Panics
     /// Panics if `query_values` is an empty slice.
-    pub(crate) fn new<H: Hasher, E: FieldElement, const N: usize>(
+    pub(crate) fn new<H: Hasher, E: FieldElement, V: VectorCommitment<H>, const N: usize>(
         query_values: Vec<[E; N]>,
-        merkle_proof: BatchMerkleProof<H>,
+        proof: V::MultiProof,
     ) -> Self {
         assert!(!query_values.is_empty(), "query values cannot be empty");
 
@@ -254,12 +329,11 @@ impl FriProofLayer {
         let mut value_bytes = Vec::with_capacity(E::ELEMENT_BYTES * N * query_values.len());
         value_bytes.write_many(&query_values);
 
-        // concatenate all query values and all internal Merkle proof nodes into vectors of bytes;
-        // we care about internal nodes only because leaf nodes can be reconstructed from hashes
-        // of query values
+        // concatenate all query values and the opening proof into vectors of bytes; the opened
+        // items are not serialized because they can be reconstructed from hashes of query values
         FriProofLayer {
             values: value_bytes,
-            paths: merkle_proof.serialize_nodes(),
+            paths: V::serialize_multiproof(&proof),
         }
     }
 
@@ -274,22 +348,26 @@ impl FriProofLayer {
 
     // PARSING
     // --------------------------------------------------------------------------------------------
-    /// Decomposes this layer into a combination of query values and corresponding Merkle
-    /// authentication paths (grouped together into a single batch Merkle proof).
+    /// Decomposes this layer into a combination of query values and the corresponding opening
+    /// proof for the vector commitment scheme `V` built with the specified `commitment_options`
+    /// (for Merkle trees, authentication paths grouped together into a single batch Merkle
+    /// proof).
     ///
     /// # Errors
     /// Returns an error if:
     /// * This layer does not contain at least one query.
-    /// * Parsing of any of the query values or the corresponding Merkle paths fails.
+    /// * Parsing of any of the query values or the corresponding opening proof fails.
     /// * Not all bytes have been consumed while parsing this layer.
-    pub fn parse<H, E>(
+    pub fn parse<H, E, V>(
         self,
         domain_size: usize,
         folding_factor: usize,
-    ) -> Result<(Vec<E>, BatchMerkleProof<H>), DeserializationError>
+        commitment_options: VectorCommitmentOptions,
+    ) -> Result<(Vec<E>, V::MultiProof), DeserializationError>
     where
         E: FieldElement,
         H: ElementHasher<BaseField = E::BaseField>,
+        V: VectorCommitment<H>,
     {
         // make sure the number of value bytes can be parsed into a whole number of queries
         let num_query_bytes = E::ELEMENT_BYTES * folding_factor;
@@ -310,26 +388,30 @@ impl FriProofLayer {
         let mut query_values = Vec::with_capacity(num_queries * folding_factor);
 
         // read bytes corresponding to each query, convert them into field elements,
-        // and also hash them to build leaf nodes of the batch Merkle proof
+        // and also hash them to build the opened items of the opening proof
         let mut reader = SliceReader::new(&self.values);
         for query_hash in hashed_queries.iter_mut() {
             let mut qe = reader.read_many(folding_factor)?;
-            *query_hash = H::hash_elements(&qe);
+            *query_hash = H::hash_elements_in_domain(&qe, HashDomain::Leaf);
             query_values.append(&mut qe);
         }
         if reader.has_more_bytes() {
             return Err(DeserializationError::UnconsumedBytes);
         }
 
-        // build batch Merkle proof
+        // build the opening proof
         let mut reader = SliceReader::new(&self.paths);
-        let tree_depth = domain_size.ilog2() as u8;
-        let merkle_proof = BatchMerkleProof::deserialize(&mut reader, hashed_queries, tree_depth)?;
+        let proof = V::deserialize_multiproof(
+            &mut reader,
+            hashed_queries,
+            domain_size,
+            commitment_options,
+        )?;
         if reader.has_more_bytes() {
             return Err(DeserializationError::UnconsumedBytes);
         }
 
-        Ok((query_values, merkle_proof))
+        Ok((query_values, proof))
     }
 }
 
@@ -5,7 +5,7 @@
 
 use alloc::vec::Vec;
 use core::marker::PhantomData;
-use crypto::{ElementHasher, Hasher, RandomCoin};
+use crypto::{ElementHasher, Hasher, MerkleTree, RandomCoin, VectorCommitment};
 use math::FieldElement;
 
 // PROVER CHANNEL TRAIT
@@ -26,15 +26,15 @@ pub trait ProverChannel<E: FieldElement> {
 
     /// Sends a layer commitment to the verifier.
     ///
-    /// A layer commitment is a root of a Merkle tree built from evaluations of a polynomial
-    /// at a given layer. The Merkle tree is built by first transposing evaluations into a
-    /// two-dimensional matrix where each row contains values needed to compute a single
-    /// value of the next FRI layer, and then putting each row of the matrix into a single
-    /// leaf of the Merkle tree. Thus, the number of elements grouped into a single leaf is
-    /// equal to the `folding_factor` used for FRI layer construction.
+    /// A layer commitment is a vector commitment (by default, a cap of a Merkle tree) built from
+    /// evaluations of a polynomial at a given layer. The vector commitment is built by first
+    /// transposing evaluations into a two-dimensional matrix where each row contains values
+    /// needed to compute a single value of the next FRI layer, and then hashing each row of the
+    /// matrix into a single item of the committed vector. Thus, the number of elements grouped
+    /// into a single item is equal to the `folding_factor` used for FRI layer construction.
     fn commit_fri_layer(
         &mut self,
-        layer_root: <<Self as ProverChannel<E>>::Hasher as Hasher>::Digest,
+        layer_cap: &[<<Self as ProverChannel<E>>::Hasher as Hasher>::Digest],
     );
 
     /// Returns a random α drawn uniformly at random from the entire field.
@@ -45,6 +45,14 @@ pub trait ProverChannel<E: FieldElement> {
     /// prover, in the non-interactive version, the α is pseudo-randomly generated based on the
     /// values the prover previously wrote into the channel.
     fn draw_fri_alpha(&mut self) -> E;
+
+    /// Finds a nonce such that hashing it together with the current state of the channel
+    /// results in a value with at least `grinding_factor` leading zeros, absorbs the nonce into
+    /// the channel, and returns it.
+    ///
+    /// The prover calls this method before drawing α for a layer when the FRI protocol is
+    /// executed with a non-zero grinding factor.
+    fn grind_fri_layer(&mut self, grinding_factor: u32) -> u64;
 }
 
 // DEFAULT PROVER CHANNEL IMPLEMENTATION
@@ -52,26 +60,32 @@ pub trait ProverChannel<E: FieldElement> {
 
 /// Provides a default implementation of the [ProverChannel] trait.
 ///
+/// Layer commitments are absorbed into the public coin using the digest computed by the vector
+/// commitment scheme `V` (by default, a Merkle tree).
+///
 /// Though this implementation is intended primarily for testing purposes, it can be used in
 /// production use cases as well.
-pub struct DefaultProverChannel<E, H, R>
+pub struct DefaultProverChannel<E, H, R, V = MerkleTree<H>>
 where
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
     R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+    V: VectorCommitment<H>,
 {
     public_coin: R,
-    commitments: Vec<H::Digest>,
+    commitments: Vec<Vec<H::Digest>>,
     domain_size: usize,
     num_queries: usize,
     _field_element: PhantomData<E>,
+    _vector_commitment: PhantomData<V>,
 }
 
-impl<E, H, R> DefaultProverChannel<E, H, R>
+impl<E, H, R, V> DefaultProverChannel<E, H, R, V>
 where
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
     R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+    V: VectorCommitment<H>,
 {
     /// Returns a new prover channel instantiated from the specified parameters.
     ///
@@ -92,6 +106,7 @@ where
             domain_size,
             num_queries,
             _field_element: PhantomData,
+            _vector_commitment: PhantomData,
         }
     }
 
@@ -112,25 +127,34 @@ where
     }
 
     /// Returns a list of FRI layer commitments written by the prover into this channel.
-    pub fn layer_commitments(&self) -> &[H::Digest] {
+    pub fn layer_commitments(&self) -> &[Vec<H::Digest>] {
         &self.commitments
     }
 }
 
-impl<E, H, R> ProverChannel<E> for DefaultProverChannel<E, H, R>
+impl<E, H, R, V> ProverChannel<E> for DefaultProverChannel<E, H, R, V>
 where
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
     R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+    V: VectorCommitment<H>,
 {
     type Hasher = H;
 
-    fn commit_fri_layer(&mut self, layer_root: H::Digest) {
-        self.commitments.push(layer_root);
-        self.public_coin.reseed(layer_root);
+    fn commit_fri_layer(&mut self, layer_cap: &[H::Digest]) {
+        self.commitments.push(layer_cap.to_vec());
+        self.public_coin.reseed(V::commitment_digest(layer_cap));
     }
 
     fn draw_fri_alpha(&mut self) -> E {
         self.public_coin.draw().expect("failed to draw FRI alpha")
     }
+
+    fn grind_fri_layer(&mut self, grinding_factor: u32) -> u64 {
+        let nonce = (1..u64::MAX)
+            .find(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor)
+            .expect("nonce not found");
+        self.public_coin.reseed_with_int(nonce);
+        nonce
+    }
 }
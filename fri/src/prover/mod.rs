@@ -7,11 +7,13 @@ use crate::{
     folding::{apply_drp, fold_positions},
     proof::{FriProof, FriProofLayer},
     utils::hash_values,
-    FriOptions,
+    FriOptions, RemainderFormat,
 };
 use alloc::vec::Vec;
 use core::marker::PhantomData;
-use crypto::{ElementHasher, Hasher, MerkleTree};
+use crypto::{
+    ElementHasher, HashDomain, Hasher, MerkleTree, VectorCommitment, VectorCommitmentOptions,
+};
 use math::{fft, FieldElement, StarkField};
 use utils::{flatten_vector_elements, group_slice_elements, transpose_slice};
 
@@ -40,6 +42,8 @@ mod tests;
 /// * `C` specifies the type used to simulate prover-verifier interaction.
 /// * `H` specifies the hash function used to build layer Merkle trees. The same hash function
 ///   must be used in the prover channel to generate pseudo random values.
+/// * `V` specifies the vector commitment scheme used to commit to FRI layers. By default, this
+///   is a [MerkleTree].
 ///
 /// Proof generation is performed in two phases: commit phase and query phase.
 ///
@@ -47,17 +51,18 @@ mod tests;
 /// During the commit phase, which is executed via [build_layers()](FriProver::build_layers())
 /// function, the prover repeatedly applies a degree-respecting projection (DRP) to `evaluations`
 /// (see [folding](crate::folding)). With every application of the DRP, the degree of the function
-/// *f* (and size of the domain over which it is evaluated) is reduced by the `folding_factor`
-/// until the remaining evaluations correspond to a polynomial, called remainder polynomial, with
-/// a number of coefficients less than or equal to `remainder_max_degree_plus_1`.
+/// *f* (and size of the domain over which it is evaluated) is reduced by the folding factor of
+/// the current layer (see [FriOptions::folding_factor_at()]) until the remaining evaluations
+/// correspond to a polynomial, called remainder polynomial, with a number of coefficients less
+/// than or equal to `remainder_max_degree_plus_1`.
 ///
 /// At each layer of reduction, the prover commits to the current set of evaluations. This is done
 /// by building a Merkle tree from the evaluations and sending the root of the tree to the verifier
 /// (via [ProverChannel]). The Merkle tree is build in such a way that all evaluations needed to
 /// compute a single value in the next FRI layer are grouped into the same leaf (the number of
 /// evaluations needed to compute a single element in the next FRI layer is equal to the
-/// `folding_factor`). This allows us to decommit all these values using a single Merkle
-/// authentication path.
+/// folding factor of the layer). This allows us to decommit all these values using a single
+/// Merkle authentication path.
 ///
 /// After committing to the set of evaluations at the current layer, the prover draws a random
 /// field element α from the channel, and uses it to build the next FRI layer. In the interactive
@@ -87,36 +92,42 @@ mod tests;
 ///
 /// Calling [build_layers()](FriProver::build_layers()) when the internal state is dirty, or
 /// calling [build_proof()](FriProver::build_proof()) on a clean state will result in a panic.
-pub struct FriProver<B, E, C, H>
+pub struct FriProver<B, E, C, H, V = MerkleTree<H>>
 where
     B: StarkField,
     E: FieldElement<BaseField = B>,
     C: ProverChannel<E, Hasher = H>,
     H: ElementHasher<BaseField = B>,
+    V: VectorCommitment<H>,
 {
     options: FriOptions,
-    layers: Vec<FriLayer<B, E, H>>,
+    layers: Vec<FriLayer<B, E, H, V>>,
     remainder_poly: FriRemainder<E>,
+    pow_nonces: Vec<u64>,
     _channel: PhantomData<C>,
 }
 
-struct FriLayer<B: StarkField, E: FieldElement<BaseField = B>, H: Hasher> {
-    tree: MerkleTree<H>,
+struct FriLayer<B: StarkField, E: FieldElement<BaseField = B>, H: Hasher, V: VectorCommitment<H>> {
+    commitment: V,
     evaluations: Vec<E>,
     _base_field: PhantomData<B>,
+    _hasher: PhantomData<H>,
 }
 
+/// Coefficients of the remainder polynomial or evaluations of the remainder layer, depending on
+/// the remainder format specified in the FRI options.
 struct FriRemainder<E: FieldElement>(Vec<E>);
 
 // PROVER IMPLEMENTATION
 // ================================================================================================
 
-impl<B, E, C, H> FriProver<B, E, C, H>
+impl<B, E, C, H, V> FriProver<B, E, C, H, V>
 where
     B: StarkField,
     E: FieldElement<BaseField = B>,
     C: ProverChannel<E, Hasher = H>,
     H: ElementHasher<BaseField = B>,
+    V: VectorCommitment<H>,
 {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
@@ -126,6 +137,7 @@ where
             options,
             layers: Vec::new(),
             remainder_poly: FriRemainder(vec![]),
+            pow_nonces: Vec::new(),
             _channel: PhantomData,
         }
     }
@@ -134,10 +146,19 @@ where
     // --------------------------------------------------------------------------------------------
 
     /// Returns folding factor for this prover.
+    ///
+    /// This is the folding factor used for the layers which are not covered by the folding
+    /// schedule; the factor used for a specific layer can be obtained via
+    /// [folding_factor_at()](FriProver::folding_factor_at()).
     pub fn folding_factor(&self) -> usize {
         self.options.folding_factor()
     }
 
+    /// Returns the folding factor used for the FRI layer at the specified depth.
+    pub fn folding_factor_at(&self, layer_idx: usize) -> usize {
+        self.options.folding_factor_at(layer_idx)
+    }
+
     /// Returns offset of the domain over which FRI protocol is executed by this prover.
     pub fn domain_offset(&self) -> B {
         self.options.domain_offset()
@@ -153,6 +174,7 @@ where
     pub fn reset(&mut self) {
         self.layers.clear();
         self.remainder_poly.0.clear();
+        self.pow_nonces.clear();
     }
 
     // COMMIT PHASE
@@ -164,66 +186,159 @@ where
     /// application of the DRP the degree of the function (and size of the domain) is reduced by
     /// `folding_factor` until the remaining evaluations can be represented by a remainder polynomial
     /// with at most `remainder_max_degree_plus_1` number of coefficients.
-    /// At each layer of reduction the current evaluations are committed to using a Merkle tree,
-    /// and the root of this tree is written into the channel. After this the prover draws a random
-    /// field element α from the channel, and uses it in the next application of the DRP.
+    /// At each layer of reduction the current evaluations are committed to using a vector
+    /// commitment (by default, a Merkle tree), and the commitment (for Merkle trees, the cap of
+    /// the tree) is written into the channel. After this the prover draws a random
+    /// field element α from the channel, and uses it in the next application of the DRP. If the
+    /// FRI options specify a non-zero grinding factor, the prover performs Proof-of-Work before
+    /// drawing each α.
     ///
     /// # Panics
     /// Panics if the prover state is dirty (the vector of layers is not empty).
-    pub fn build_layers(&mut self, channel: &mut C, mut evaluations: Vec<E>) {
+    pub fn build_layers(&mut self, channel: &mut C, evaluations: Vec<E>) {
+        self.build_layers_batched(channel, vec![evaluations]);
+    }
+
+    /// Executes the commit phase of the FRI protocol for a batch of functions evaluated over
+    /// domains of different sizes.
+    ///
+    /// The first vector in `evaluations` defines the domain *D* over which the protocol is
+    /// executed, and is processed in the same way as in [build_layers()](FriProver::build_layers()).
+    /// Every other vector must contain evaluations of a function over a domain which has the same
+    /// size as the domain of one of the subsequent FRI layers (including the remainder layer),
+    /// and which is shifted by the same domain offset as *D*. Such a vector is folded into the
+    /// FRI layer with the matching domain size: after the DRP with the random value α is applied
+    /// to the previous layer, the vector multiplied by α^(N + j) is added to the result, where N
+    /// is the folding factor of the previous layer and j is the index of the vector among the
+    /// vectors folded into the same layer.
+    ///
+    /// As the degree of a function is reduced together with the size of its domain, the degree
+    /// bound for each vector is implied by the length of the vector and the blowup factor.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The prover state is dirty (the vector of layers is not empty).
+    /// * `evaluations` is empty.
+    /// * The length of any of the vectors but the first one does not match the domain size of
+    ///   any of the FRI layers following the first layer.
+    pub fn build_layers_batched(&mut self, channel: &mut C, mut evaluations: Vec<Vec<E>>) {
         assert!(
             self.layers.is_empty(),
             "a prior proof generation request has not been completed yet"
         );
-
-        // reduce the degree by folding_factor at each iteration until the remaining polynomial
-        // has small enough degree
-        for _ in 0..self.options.num_fri_layers(evaluations.len()) {
-            match self.folding_factor() {
+        assert!(!evaluations.is_empty(), "at least one evaluation vector must be provided");
+        let mut batched_evaluations = evaluations.split_off(1);
+        let mut evaluations = evaluations.remove(0);
+
+        // reduce the degree by the folding factor of the current layer at each iteration until
+        // the remaining polynomial has small enough degree
+        for depth in 0..self.options.num_fri_layers(evaluations.len()) {
+            let folding_factor = self.options.folding_factor_at(depth);
+            let alpha = match folding_factor {
                 2 => self.build_layer::<2>(channel, &mut evaluations),
                 4 => self.build_layer::<4>(channel, &mut evaluations),
                 8 => self.build_layer::<8>(channel, &mut evaluations),
                 16 => self.build_layer::<16>(channel, &mut evaluations),
-                _ => unimplemented!("folding factor {} is not supported", self.folding_factor()),
+                _ => unimplemented!("folding factor {} is not supported", folding_factor),
+            };
+
+            // fold in the evaluations over the domain of the next layer
+            let domain_size = evaluations.len();
+            let mut coefficient = alpha.exp_vartime((folding_factor as u32).into());
+            for batched in batched_evaluations.iter().filter(|e| e.len() == domain_size) {
+                for (value, &batched_value) in evaluations.iter_mut().zip(batched.iter()) {
+                    *value += coefficient * batched_value;
+                }
+                coefficient *= alpha;
             }
+            batched_evaluations.retain(|e| e.len() != domain_size);
         }
+        assert!(
+            batched_evaluations.is_empty(),
+            "evaluation vector of length {} does not match the domain of any FRI layer",
+            batched_evaluations.first().map(|e| e.len()).unwrap_or_default()
+        );
 
         self.set_remainder(channel, &mut evaluations);
     }
 
     /// Builds a single FRI layer by first committing to the `evaluations`, then drawing a random
     /// alpha from the channel and use it to perform degree-respecting projection.
-    fn build_layer<const N: usize>(&mut self, channel: &mut C, evaluations: &mut Vec<E>) {
+    ///
+    /// Returns the alpha used in the degree-respecting projection.
+    fn build_layer<const N: usize>(&mut self, channel: &mut C, evaluations: &mut Vec<E>) -> E {
         // commit to the evaluations at the current layer; we do this by first transposing the
-        // evaluations into a matrix of N columns, and then building a Merkle tree from the
-        // rows of this matrix; we do this so that we could de-commit to N values with a single
-        // Merkle authentication path.
+        // evaluations into a matrix of N columns, and then committing to the hashed rows of
+        // this matrix; we do this so that we could de-commit to N values with a single opening
+        // proof.
         let transposed_evaluations = transpose_slice(evaluations);
         let hashed_evaluations = hash_values::<H, E, N>(&transposed_evaluations);
-        let evaluation_tree =
-            MerkleTree::<H>::new(hashed_evaluations).expect("failed to construct FRI layer tree");
-        channel.commit_fri_layer(*evaluation_tree.root());
+        let evaluation_commitment = self.build_layer_commitment(hashed_evaluations);
+        channel.commit_fri_layer(evaluation_commitment.commitment());
+
+        // if required, perform Proof-of-Work before drawing the coefficient
+        let grinding_factor = self.options.grinding_factor();
+        if grinding_factor > 0 {
+            self.pow_nonces.push(channel.grind_fri_layer(grinding_factor));
+        }
 
         // draw a pseudo-random coefficient from the channel, and use it in degree-respecting
         // projection to reduce the degree of evaluations by N
         let alpha = channel.draw_fri_alpha();
         *evaluations = apply_drp(&transposed_evaluations, self.domain_offset(), alpha);
         self.layers.push(FriLayer {
-            tree: evaluation_tree,
+            commitment: evaluation_commitment,
             evaluations: flatten_vector_elements(transposed_evaluations),
             _base_field: PhantomData,
+            _hasher: PhantomData,
         });
+
+        alpha
     }
 
-    /// Creates remainder polynomial in coefficient form from a vector of `evaluations` over a domain.
+    /// Creates the remainder from a vector of `evaluations` over a domain and commits to it.
+    ///
+    /// Depending on the remainder format specified in the options, the remainder is either the
+    /// remainder polynomial in coefficient form (committed to by hashing the coefficients), or the
+    /// `evaluations` themselves (committed to using a vector commitment with a single evaluation
+    /// per leaf).
     fn set_remainder(&mut self, channel: &mut C, evaluations: &mut [E]) {
-        let inv_twiddles = fft::get_inv_twiddles(evaluations.len());
-        fft::interpolate_poly_with_offset(evaluations, &inv_twiddles, self.options.domain_offset());
-        let remainder_poly_size = evaluations.len() / self.options.blowup_factor();
-        let remainder_poly = evaluations[..remainder_poly_size].to_vec();
-        let commitment = <H as ElementHasher>::hash_elements(&remainder_poly);
-        channel.commit_fri_layer(commitment);
-        self.remainder_poly = FriRemainder(remainder_poly);
+        let remainder = match self.options.remainder_format() {
+            RemainderFormat::Coefficients => {
+                let inv_twiddles = fft::get_inv_twiddles(evaluations.len());
+                let domain_offset = self.options.domain_offset();
+                fft::interpolate_poly_with_offset(evaluations, &inv_twiddles, domain_offset);
+                let remainder_poly_size = evaluations.len() / self.options.blowup_factor();
+                let remainder_poly = evaluations[..remainder_poly_size].to_vec();
+                let commitment = <H as ElementHasher>::hash_elements_in_domain(
+                    &remainder_poly,
+                    HashDomain::Transcript,
+                );
+                channel.commit_fri_layer(&[commitment]);
+                remainder_poly
+            }
+            RemainderFormat::Evaluations => {
+                let hashed_evaluations =
+                    hash_values::<H, E, 1>(&transpose_slice::<_, 1>(evaluations));
+                let commitment = self.build_layer_commitment(hashed_evaluations);
+                channel.commit_fri_layer(commitment.commitment());
+                evaluations.to_vec()
+            }
+        };
+        self.remainder_poly = FriRemainder(remainder);
+    }
+
+    /// Builds a vector commitment to the specified hashed layer values.
+    ///
+    /// The cap height is reduced for layers which are too small for a cap of the height
+    /// specified in the options.
+    fn build_layer_commitment(&self, hashed_values: Vec<H::Digest>) -> V {
+        let tree_depth = hashed_values.len().ilog2() as usize;
+        let cap_height = self.options.merkle_cap_height().min(tree_depth.saturating_sub(1));
+        let commitment_options =
+            VectorCommitmentOptions::new(self.options.merkle_arity(), cap_height);
+        V::with_options(hashed_values, commitment_options)
+            .expect("failed to construct FRI layer commitment")
     }
 
     // QUERY PHASE
@@ -231,9 +346,11 @@ where
     /// Executes query phase of FRI protocol.
     ///
     /// For each of the provided `positions`, corresponding evaluations from each of the layers
-    /// (excluding the remainder layer) are recorded into the proof together with Merkle
-    /// authentication paths from the root of layer commitment trees. For the remainder, we send
-    /// the whole remainder polynomial resulting from interpolating the remainder layer.
+    /// (excluding the remainder layer) are recorded into the proof together with opening proofs
+    /// against layer commitments (for Merkle trees, batch authentication paths to the tree caps).
+    /// For the remainder, we send either the whole remainder polynomial resulting from
+    /// interpolating the remainder layer, or all evaluations of the remainder layer, depending on
+    /// the remainder format specified in the options.
     ///
     /// # Panics
     /// Panics is the prover state is clean (no FRI layers have been build yet).
@@ -245,19 +362,19 @@ where
         if !self.layers.is_empty() {
             let mut positions = positions.to_vec();
             let mut domain_size = self.layers[0].evaluations.len();
-            let folding_factor = self.options.folding_factor();
 
             // for all FRI layers, except the last one, record tree root, determine a set of query
             // positions, and query the layer at these positions.
             for i in 0..self.layers.len() {
+                let folding_factor = self.options.folding_factor_at(i);
                 positions = fold_positions(&positions, domain_size, folding_factor);
 
                 // sort of a static dispatch for folding_factor parameter
                 let proof_layer = match folding_factor {
-                    2 => query_layer::<B, E, H, 2>(&self.layers[i], &positions),
-                    4 => query_layer::<B, E, H, 4>(&self.layers[i], &positions),
-                    8 => query_layer::<B, E, H, 8>(&self.layers[i], &positions),
-                    16 => query_layer::<B, E, H, 16>(&self.layers[i], &positions),
+                    2 => query_layer::<B, E, H, V, 2>(&self.layers[i], &positions),
+                    4 => query_layer::<B, E, H, V, 4>(&self.layers[i], &positions),
+                    8 => query_layer::<B, E, H, V, 8>(&self.layers[i], &positions),
+                    16 => query_layer::<B, E, H, V, 16>(&self.layers[i], &positions),
                     _ => unimplemented!("folding factor {} is not supported", folding_factor),
                 };
 
@@ -268,11 +385,12 @@ where
 
         // use the remaining polynomial values directly as proof
         let remainder = self.remainder_poly.0.clone();
+        let pow_nonces = self.pow_nonces.clone();
 
         // clear layers so that another proof can be generated
         self.reset();
 
-        FriProof::new(layers, remainder, 1)
+        FriProof::new(layers, remainder, self.options.remainder_format(), 1, pow_nonces)
     }
 }
 
@@ -281,15 +399,21 @@ where
 
 /// Builds a single proof layer by querying the evaluations of the passed in FRI layer at the
 /// specified positions.
-fn query_layer<B: StarkField, E: FieldElement<BaseField = B>, H: Hasher, const N: usize>(
-    layer: &FriLayer<B, E, H>,
+fn query_layer<B, E, H, V, const N: usize>(
+    layer: &FriLayer<B, E, H, V>,
     positions: &[usize],
-) -> FriProofLayer {
-    // build Merkle authentication paths for all query positions
+) -> FriProofLayer
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+    H: Hasher,
+    V: VectorCommitment<H>,
+{
+    // build an opening proof (e.g., Merkle authentication paths) for all query positions
     let proof = layer
-        .tree
-        .prove_batch(positions)
-        .expect("failed to generate a Merkle proof for FRI layer queries");
+        .commitment
+        .open_many(positions)
+        .expect("failed to generate an opening proof for FRI layer queries");
 
     // build a list of polynomial evaluations at each position; since evaluations in FRI layers
     // are stored in transposed form, a position refers to N evaluations which are committed
@@ -300,5 +424,5 @@ fn query_layer<B: StarkField, E: FieldElement<BaseField = B>, H: Hasher, const N
         queried_values.push(evaluations[position]);
     }
 
-    FriProofLayer::new(queried_values, proof)
+    FriProofLayer::new::<H, E, V, N>(queried_values, proof)
 }
@@ -6,10 +6,12 @@
 use super::{DefaultProverChannel, FriProver};
 use crate::{
     verifier::{DefaultVerifierChannel, FriVerifier},
-    FriOptions, FriProof, VerifierError,
+    FriOptions, FriProof, RemainderFormat, VerifierError,
 };
 use alloc::vec::Vec;
-use crypto::{hashers::Blake3_256, DefaultRandomCoin, Hasher, RandomCoin};
+use crypto::{
+    hashers::Blake3_256, DefaultRandomCoin, Hasher, MerkleTree, RandomCoin, VectorCommitmentOptions,
+};
 use math::{fft, fields::f128::BaseElement, FieldElement};
 use utils::{Deserializable, Serializable, SliceReader};
 
@@ -24,7 +26,16 @@ fn fri_folding_2() {
     let lde_blowup_e = 3;
     let folding_factor_e = 1;
     let max_remainder_degree = 7;
-    fri_prove_verify(trace_length_e, lde_blowup_e, folding_factor_e, max_remainder_degree)
+    let merkle_cap_height = 0;
+    fri_prove_verify(
+        trace_length_e,
+        lde_blowup_e,
+        folding_factor_e,
+        max_remainder_degree,
+        merkle_cap_height,
+        2,
+        &[],
+    )
 }
 
 #[test]
@@ -33,7 +44,231 @@ fn fri_folding_4() {
     let lde_blowup_e = 3;
     let folding_factor_e = 2;
     let max_remainder_degree = 255;
-    fri_prove_verify(trace_length_e, lde_blowup_e, folding_factor_e, max_remainder_degree)
+    let merkle_cap_height = 0;
+    fri_prove_verify(
+        trace_length_e,
+        lde_blowup_e,
+        folding_factor_e,
+        max_remainder_degree,
+        merkle_cap_height,
+        2,
+        &[],
+    )
+}
+
+#[test]
+fn fri_folding_4_with_cap() {
+    let trace_length_e = 12;
+    let lde_blowup_e = 3;
+    let folding_factor_e = 2;
+    let max_remainder_degree = 7;
+    let merkle_cap_height = 4;
+    fri_prove_verify(
+        trace_length_e,
+        lde_blowup_e,
+        folding_factor_e,
+        max_remainder_degree,
+        merkle_cap_height,
+        2,
+        &[],
+    )
+}
+
+#[test]
+fn fri_folding_4_with_arity() {
+    let trace_length_e = 12;
+    let lde_blowup_e = 3;
+    let folding_factor_e = 2;
+    let max_remainder_degree = 7;
+    let merkle_cap_height = 3;
+    let merkle_arity = 4;
+    fri_prove_verify(
+        trace_length_e,
+        lde_blowup_e,
+        folding_factor_e,
+        max_remainder_degree,
+        merkle_cap_height,
+        merkle_arity,
+        &[],
+    )
+}
+
+#[test]
+fn fri_folding_schedule() {
+    let trace_length_e = 12;
+    let lde_blowup_e = 3;
+    let folding_factor_e = 2;
+    let max_remainder_degree = 7;
+    let merkle_cap_height = 0;
+    fri_prove_verify(
+        trace_length_e,
+        lde_blowup_e,
+        folding_factor_e,
+        max_remainder_degree,
+        merkle_cap_height,
+        2,
+        &[16, 8],
+    )
+}
+
+#[test]
+fn fri_batched() {
+    let trace_length = 1 << 12;
+    let lde_blowup = 8;
+    let options = FriOptions::new(lde_blowup, 4, 7);
+    let domain_size = trace_length * lde_blowup;
+
+    // fold in evaluations over the domains of the second and third FRI layers, as well as over
+    // the domain of the remainder layer
+    let evaluations = vec![
+        build_evaluations(trace_length, lde_blowup),
+        build_evaluations(trace_length / 4, lde_blowup),
+        build_evaluations(trace_length / 16, lde_blowup),
+        build_evaluations(trace_length / 16, lde_blowup),
+        build_evaluations(4, lde_blowup),
+    ];
+    let domain_sizes = evaluations.iter().map(|e| e.len()).collect::<Vec<_>>();
+
+    let mut channel = build_prover_channel(trace_length, &options);
+    let mut prover = FriProver::<_, _, _, _, MerkleTree<Blake3>>::new(options.clone());
+    prover.build_layers_batched(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions(0);
+    let proof = prover.build_proof(&positions);
+    let commitments = channel.layer_commitments().to_vec();
+
+    let verify = |evaluations: &[Vec<BaseElement>], domain_sizes: &[usize]| {
+        let mut channel = DefaultVerifierChannel::<BaseElement, Blake3, MerkleTree<Blake3>>::new(
+            proof.clone(),
+            commitments.clone(),
+            domain_size,
+            &options,
+            VectorCommitmentOptions::default(),
+        )
+        .unwrap();
+        let mut coin = DefaultRandomCoin::<Blake3>::new(&[]);
+        let verifier =
+            FriVerifier::new(&mut channel, &mut coin, options.clone(), trace_length - 1)?;
+        let queried_evaluations = evaluations
+            .iter()
+            .map(|e| positions.iter().map(|&p| e[p % e.len()]).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        verifier.verify_batched(&mut channel, &queried_evaluations, domain_sizes, &positions)
+    };
+
+    assert_eq!(Ok(()), verify(&evaluations, &domain_sizes));
+
+    // verification fails if one of the batched functions is omitted
+    assert!(verify(&evaluations[..4], &domain_sizes[..4]).is_err());
+
+    // verification fails if a domain size does not match any layer
+    let mut invalid_domain_sizes = domain_sizes.clone();
+    invalid_domain_sizes[1] = domain_size / 2;
+    assert_eq!(
+        Err(VerifierError::InvalidBatchDomainSize(domain_size / 2)),
+        verify(&evaluations, &invalid_domain_sizes)
+    );
+}
+
+#[test]
+fn fri_layer_grinding() {
+    let trace_length = 1 << 10;
+    let lde_blowup = 8;
+    let grinding_factor = 8;
+    let options = FriOptions::new(lde_blowup, 4, 7).with_grinding_factor(grinding_factor);
+    let domain_size = trace_length * lde_blowup;
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    let mut channel = build_prover_channel(trace_length, &options);
+    let mut prover = FriProver::<_, _, _, _, MerkleTree<Blake3>>::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions(0);
+    let proof = prover.build_proof(&positions);
+    let commitments = channel.layer_commitments().to_vec();
+
+    // the proof contains one nonce per layer
+    assert_eq!(proof.num_layers(), proof.pow_nonces().len());
+
+    let verify = |proof: FriProof, options: &FriOptions| {
+        verify_proof(
+            proof,
+            commitments.clone(),
+            &evaluations,
+            trace_length - 1,
+            domain_size,
+            &positions,
+            options,
+        )
+    };
+    assert_eq!(Ok(()), verify(proof.clone(), &options));
+
+    // verification fails if the verifier does not expect layer grinding
+    let num_nonces = proof.pow_nonces().len();
+    assert_eq!(
+        Err(VerifierError::NumPowNoncesMismatch(0, num_nonces)),
+        verify(proof.clone(), &FriOptions::new(lde_blowup, 4, 7))
+    );
+
+    // verification fails if any of the nonces is modified; the nonces are serialized at the
+    // very end of the proof
+    let mut proof_bytes = proof.to_bytes();
+    let last_byte = proof_bytes.len() - 8;
+    proof_bytes[last_byte] ^= 1;
+    let tampered_proof = FriProof::read_from_bytes(&proof_bytes).unwrap();
+    assert!(verify(tampered_proof, &options).is_err());
+}
+
+#[test]
+fn fri_remainder_evaluations() {
+    let trace_length = 1 << 10;
+    let lde_blowup = 8;
+    let options = FriOptions::new(lde_blowup, 4, 15)
+        .with_merkle_cap_height(2)
+        .with_remainder_format(RemainderFormat::Evaluations);
+    let domain_size = trace_length * lde_blowup;
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    let mut channel = build_prover_channel(trace_length, &options);
+    let mut prover = FriProver::<_, _, _, _, MerkleTree<Blake3>>::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions(0);
+    let proof = prover.build_proof(&positions);
+    let commitments = channel.layer_commitments().to_vec();
+
+    // the remainder is sent as evaluations over the entire domain of the last layer
+    assert_eq!(RemainderFormat::Evaluations, proof.remainder_format());
+    assert_eq!(16 * lde_blowup, proof.num_remainder_elements::<BaseElement>());
+
+    let verify = |proof: FriProof, max_degree: usize, options: &FriOptions| {
+        verify_proof(
+            proof,
+            commitments.clone(),
+            &evaluations,
+            max_degree,
+            domain_size,
+            &positions,
+            options,
+        )
+    };
+    assert_eq!(Ok(()), verify(proof.clone(), trace_length - 1, &options));
+
+    // verification fails if the degree of the remainder is too high; the degree bound is
+    // reduced from 767 to 11 over three layers
+    let max_degree = trace_length * 3 / 4 - 1;
+    assert_eq!(
+        Err(VerifierError::RemainderDegreeMismatch(11)),
+        verify(proof.clone(), max_degree, &options)
+    );
+
+    // verification fails if the remainder does not match its commitment; the remainder is
+    // serialized right before its format, number of partitions and the number of PoW nonces
+    let mut proof_bytes = proof.to_bytes();
+    let remainder_byte = proof_bytes.len() - 4;
+    proof_bytes[remainder_byte] ^= 1;
+    let tampered_proof = FriProof::read_from_bytes(&proof_bytes).unwrap();
+    assert_eq!(
+        Err(VerifierError::RemainderCommitmentMismatch),
+        verify(tampered_proof, trace_length - 1, &options)
+    );
 }
 
 // TEST UTILS
@@ -59,7 +294,7 @@ pub fn build_evaluations(trace_length: usize, lde_blowup: usize) -> Vec<BaseElem
 
 pub fn verify_proof(
     proof: FriProof,
-    commitments: Vec<<Blake3 as Hasher>::Digest>,
+    commitments: Vec<Vec<<Blake3 as Hasher>::Digest>>,
     evaluations: &[BaseElement],
     max_degree: usize,
     domain_size: usize,
@@ -74,11 +309,12 @@ pub fn verify_proof(
     let proof = FriProof::read_from(&mut reader).unwrap();
 
     // verify the proof
-    let mut channel = DefaultVerifierChannel::<BaseElement, Blake3>::new(
+    let mut channel = DefaultVerifierChannel::<BaseElement, Blake3, MerkleTree<Blake3>>::new(
         proof,
         commitments,
         domain_size,
-        options.folding_factor(),
+        options,
+        VectorCommitmentOptions::new(options.merkle_arity(), options.merkle_cap_height()),
     )
     .unwrap();
     let mut coin = DefaultRandomCoin::<Blake3>::new(&[]);
@@ -92,17 +328,23 @@ fn fri_prove_verify(
     lde_blowup_e: usize,
     folding_factor_e: usize,
     max_remainder_degree: usize,
+    merkle_cap_height: usize,
+    merkle_arity: usize,
+    folding_schedule: &[usize],
 ) {
     let trace_length = 1 << trace_length_e;
     let lde_blowup = 1 << lde_blowup_e;
     let folding_factor = 1 << folding_factor_e;
 
-    let options = FriOptions::new(lde_blowup, folding_factor, max_remainder_degree);
+    let options = FriOptions::new(lde_blowup, folding_factor, max_remainder_degree)
+        .with_folding_schedule(folding_schedule.to_vec())
+        .with_merkle_cap_height(merkle_cap_height)
+        .with_merkle_arity(merkle_arity);
     let mut channel = build_prover_channel(trace_length, &options);
     let evaluations = build_evaluations(trace_length, lde_blowup);
 
     // instantiate the prover and generate the proof
-    let mut prover = FriProver::new(options.clone());
+    let mut prover = FriProver::<_, _, _, _, MerkleTree<Blake3>>::new(options.clone());
     prover.build_layers(&mut channel, evaluations.clone());
     let positions = channel.draw_query_positions(0);
     let proof = prover.build_proof(&positions);
@@ -4,7 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use alloc::vec::Vec;
-use crypto::ElementHasher;
+use crypto::{ElementHasher, HashDomain};
 use math::FieldElement;
 use utils::{iter_mut, uninit_vector};
 
@@ -38,7 +38,8 @@ pub fn map_positions_to_indexes(
     result
 }
 
-/// Hashes each of the arrays in the provided slice and returns a vector of resulting hashes.
+/// Hashes each of the arrays in the provided slice in the [HashDomain::Leaf] domain and returns a
+/// vector of resulting hashes.
 pub fn hash_values<H, E, const N: usize>(values: &[[E; N]]) -> Vec<H::Digest>
 where
     E: FieldElement,
@@ -46,7 +47,7 @@ where
 {
     let mut result: Vec<H::Digest> = unsafe { uninit_vector(values.len()) };
     iter_mut!(result, 1024).zip(values).for_each(|(r, v)| {
-        *r = H::hash_elements(v);
+        *r = H::hash_elements_in_domain(v, HashDomain::Leaf);
     });
     result
 }
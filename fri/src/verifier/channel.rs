@@ -3,9 +3,9 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::{FriProof, VerifierError};
-use alloc::vec::Vec;
-use crypto::{BatchMerkleProof, ElementHasher, Hasher, MerkleTree};
+use crate::{FriOptions, FriProof, VerifierError};
+use alloc::{string::ToString, vec::Vec};
+use crypto::{ElementHasher, Hasher, MerkleTree, VectorCommitment, VectorCommitmentOptions};
 use math::FieldElement;
 use utils::{group_vector_elements, DeserializationError};
 
@@ -24,6 +24,9 @@ pub trait VerifierChannel<E: FieldElement> {
     /// Hash function used by the prover to commit to polynomial evaluations.
     type Hasher: ElementHasher<BaseField = E::BaseField>;
 
+    /// Vector commitment scheme used by the prover to commit to polynomial evaluations.
+    type VectorCommitment: VectorCommitment<Self::Hasher>;
+
     // REQUIRED METHODS
     // --------------------------------------------------------------------------------------------
 
@@ -37,9 +40,20 @@ pub trait VerifierChannel<E: FieldElement> {
     /// from the entire field after each layer commitment is received. In the non-interactive
     /// version, the verifier can read all layer commitments at once, and then generate α values
     /// locally.
+    ///
+    /// Each layer commitment is a commitment of the vector commitment scheme used by the prover
+    /// (for Merkle trees, the tree cap; when the cap height is zero, the cap consists of the tree
+    /// root only).
     fn read_fri_layer_commitments(
         &mut self,
-    ) -> Vec<<<Self as VerifierChannel<E>>::Hasher as Hasher>::Digest>;
+    ) -> Vec<Vec<<<Self as VerifierChannel<E>>::Hasher as Hasher>::Digest>>;
+
+    /// Reads and removes from the channel Proof-of-Work nonces for FRI layers.
+    ///
+    /// If FRI protocol was executed with a non-zero grinding factor, the prover sends one nonce
+    /// per layer before the verifier responds with α for that layer; otherwise, the returned
+    /// vector is empty.
+    fn read_fri_pow_nonces(&mut self) -> Vec<u64>;
 
     /// Reads and removes from the channel evaluations of the polynomial at the queried positions
     /// for the next FRI layer.
@@ -48,20 +62,20 @@ pub trait VerifierChannel<E: FieldElement> {
     /// the verifier during the query phase of the FRI protocol.
     ///
     /// It is expected that layer queries and layer proofs at the same FRI layer are consistent.
-    /// That is, query values hash into the leaf nodes of corresponding Merkle authentication
-    /// paths.
+    /// That is, query values hash into the items opened by the corresponding opening proof.
     fn take_next_fri_layer_queries(&mut self) -> Vec<E>;
 
-    /// Reads and removes from the channel Merkle authentication paths for queried evaluations for
-    /// the next FRI layer.
+    /// Reads and removes from the channel the opening proof (e.g., Merkle authentication paths)
+    /// for queried evaluations for the next FRI layer.
     ///
-    /// In the interactive version of the protocol, these authentication paths are sent from the
-    /// prover to the verifier during the query phase of the FRI protocol.
+    /// In the interactive version of the protocol, these proofs are sent from the prover to the
+    /// verifier during the query phase of the FRI protocol.
     ///
     /// It is expected that layer proofs and layer queries at the same FRI layer are consistent.
-    /// That is, query values hash into the leaf nodes of corresponding Merkle authentication
-    /// paths.
-    fn take_next_fri_layer_proof(&mut self) -> BatchMerkleProof<Self::Hasher>;
+    /// That is, query values hash into the items opened by the corresponding opening proof.
+    fn take_next_fri_layer_proof(
+        &mut self,
+    ) -> <Self::VectorCommitment as VectorCommitment<Self::Hasher>>::MultiProof;
 
     /// Reads and removes the remainder polynomial from the channel.
     fn take_fri_remainder(&mut self) -> Vec<E>;
@@ -79,11 +93,15 @@ pub trait VerifierChannel<E: FieldElement> {
     fn read_layer_queries<const N: usize>(
         &mut self,
         positions: &[usize],
-        commitment: &<<Self as VerifierChannel<E>>::Hasher as Hasher>::Digest,
+        commitment: &[<<Self as VerifierChannel<E>>::Hasher as Hasher>::Digest],
     ) -> Result<Vec<[E; N]>, VerifierError> {
         let layer_proof = self.take_next_fri_layer_proof();
-        MerkleTree::<Self::Hasher>::verify_batch(commitment, positions, &layer_proof)
-            .map_err(|_| VerifierError::LayerCommitmentMismatch)?;
+        <Self::VectorCommitment as VectorCommitment<Self::Hasher>>::verify_many(
+            commitment,
+            positions,
+            &layer_proof,
+        )
+        .map_err(|_| VerifierError::LayerCommitmentMismatch)?;
 
         // TODO: make sure layer queries hash into leaves of layer proof
 
@@ -104,38 +122,56 @@ pub trait VerifierChannel<E: FieldElement> {
 
 /// Provides a default implementation of the [VerifierChannel] trait.
 ///
-/// Default verifier channel can be instantiated directly from a [FriProof] struct.
+/// Default verifier channel can be instantiated directly from a [FriProof] struct. Layer
+/// commitments are expected to be built using the vector commitment scheme `V` (by default, a
+/// Merkle tree).
 ///
 /// Though this implementation is primarily intended for testing purposes, it can be used in
 /// production use cases as well.
-pub struct DefaultVerifierChannel<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
-    layer_commitments: Vec<H::Digest>,
-    layer_proofs: Vec<BatchMerkleProof<H>>,
+pub struct DefaultVerifierChannel<
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H> = MerkleTree<H>,
+> {
+    layer_commitments: Vec<Vec<H::Digest>>,
+    layer_proofs: Vec<V::MultiProof>,
     layer_queries: Vec<Vec<E>>,
     remainder: Vec<E>,
     num_partitions: usize,
+    pow_nonces: Vec<u64>,
 }
 
-impl<E, H> DefaultVerifierChannel<E, H>
+impl<E, H, V> DefaultVerifierChannel<E, H, V>
 where
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H>,
 {
     /// Builds a new verifier channel from the specified [FriProof].
     ///
+    /// `options` must be the FRI options with which the proof was generated, and
+    /// `commitment_options` must be the options with which the prover built layer commitments.
+    ///
     /// # Errors
     /// Returns an error if the specified `proof` could not be parsed correctly.
     pub fn new(
         proof: FriProof,
-        layer_commitments: Vec<H::Digest>,
+        layer_commitments: Vec<Vec<H::Digest>>,
         domain_size: usize,
-        folding_factor: usize,
+        options: &FriOptions,
+        commitment_options: VectorCommitmentOptions,
     ) -> Result<Self, DeserializationError> {
+        if proof.remainder_format() != options.remainder_format() {
+            return Err(DeserializationError::InvalidValue(
+                "FRI remainder format is inconsistent with FRI options".to_string(),
+            ));
+        }
         let num_partitions = proof.num_partitions();
+        let pow_nonces = proof.pow_nonces().to_vec();
 
         let remainder = proof.parse_remainder()?;
         let (layer_queries, layer_proofs) =
-            proof.parse_layers::<H, E>(domain_size, folding_factor)?;
+            proof.parse_layers::<H, E, V>(domain_size, options, commitment_options)?;
 
         Ok(DefaultVerifierChannel {
             layer_commitments,
@@ -143,26 +179,33 @@ where
             layer_queries,
             remainder,
             num_partitions,
+            pow_nonces,
         })
     }
 }
 
-impl<E, H> VerifierChannel<E> for DefaultVerifierChannel<E, H>
+impl<E, H, V> VerifierChannel<E> for DefaultVerifierChannel<E, H, V>
 where
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H>,
 {
     type Hasher = H;
+    type VectorCommitment = V;
 
     fn read_fri_num_partitions(&self) -> usize {
         self.num_partitions
     }
 
-    fn read_fri_layer_commitments(&mut self) -> Vec<H::Digest> {
+    fn read_fri_layer_commitments(&mut self) -> Vec<Vec<H::Digest>> {
         self.layer_commitments.drain(..).collect()
     }
 
-    fn take_next_fri_layer_proof(&mut self) -> BatchMerkleProof<H> {
+    fn read_fri_pow_nonces(&mut self) -> Vec<u64> {
+        self.pow_nonces.drain(..).collect()
+    }
+
+    fn take_next_fri_layer_proof(&mut self) -> V::MultiProof {
         self.layer_proofs.remove(0)
     }
 
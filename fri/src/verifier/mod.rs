@@ -5,11 +5,16 @@
 
 //! Contains an implementation of FRI verifier and associated components.
 
-use crate::{folding::fold_positions, utils::map_positions_to_indexes, FriOptions, VerifierError};
+use crate::{
+    folding::fold_positions,
+    utils::{hash_values, map_positions_to_indexes},
+    FriOptions, RemainderFormat, VerifierError,
+};
 use alloc::vec::Vec;
-use core::{marker::PhantomData, mem};
-use crypto::{ElementHasher, RandomCoin};
-use math::{polynom, FieldElement, StarkField};
+use core::marker::PhantomData;
+use crypto::{ElementHasher, HashDomain, RandomCoin, VectorCommitment, VectorCommitmentOptions};
+use math::{fft, polynom, FieldElement, StarkField};
+use utils::transpose_slice;
 
 mod channel;
 pub use channel::{DefaultVerifierChannel, VerifierChannel};
@@ -53,7 +58,7 @@ pub use channel::{DefaultVerifierChannel, VerifierChannel};
 /// * The evaluations are consistent across FRI layers (i.e., the degree-respecting projection
 ///   was applied correctly).
 /// * The degree of the polynomial implied by evaluations at the last FRI layer (the remainder)
-///   is smaller than the degree resulting from reducing degree *d* by `folding_factor` at each
+///   is smaller than the degree resulting from reducing degree *d* by the folding factor of each
 ///   FRI layer.
 pub struct FriVerifier<E, C, H, R>
 where
@@ -65,7 +70,7 @@ where
     max_poly_degree: usize,
     domain_size: usize,
     domain_generator: E::BaseField,
-    layer_commitments: Vec<H::Digest>,
+    layer_commitments: Vec<Vec<H::Digest>>,
     layer_alphas: Vec<E>,
     options: FriOptions,
     num_partitions: usize,
@@ -88,8 +93,10 @@ where
     ///
     /// Creating a FRI verifier executes the commit phase of the FRI protocol from the verifier's
     /// perspective. Specifically, the verifier reads FRI layer commitments from the `channel`,
-    /// and for each commitment, updates the `public_coin` with this commitment and then draws
-    /// a random value α from the coin.
+    /// and for each commitment, updates the `public_coin` with the root of the Merkle tree
+    /// implied by this commitment and then draws a random value α from the coin. If the `options`
+    /// specify a non-zero grinding factor, the verifier also checks the Proof-of-Work nonce sent
+    /// by the prover for each layer, and updates the `public_coin` with it before drawing α.
     ///
     /// The verifier stores layer commitments and corresponding α values in its internal state,
     /// and, thus, an instance of FRI verifier can be used to verify only a single proof.
@@ -97,8 +104,13 @@ where
     /// # Errors
     /// Returns an error if:
     /// * `max_poly_degree` is inconsistent with the number of FRI layers read from the channel
-    ///   and `folding_factor` specified in the `options` parameter.
+    ///   and the folding factors specified in the `options` parameter.
     /// * An error was encountered while drawing a random α value from the coin.
+    /// * The number of Proof-of-Work nonces read from the channel is inconsistent with the
+    ///   number of FRI layers and the grinding factor specified in the `options` parameter.
+    /// * Any of the Proof-of-Work nonces does not satisfy the grinding factor.
+    /// * The number of digests in any of the layer commitments is inconsistent with the Merkle
+    ///   cap height specified in the `options` parameter.
     pub fn new(
         channel: &mut C,
         public_coin: &mut R,
@@ -113,25 +125,64 @@ where
 
         // read layer commitments from the channel and use them to build a list of alphas
         let layer_commitments = channel.read_fri_layer_commitments();
+        let pow_nonces = channel.read_fri_pow_nonces();
+        let grinding_factor = options.grinding_factor();
+        let num_layers = layer_commitments.len().saturating_sub(1);
+        let expected_num_nonces = if grinding_factor > 0 { num_layers } else { 0 };
+        if pow_nonces.len() != expected_num_nonces {
+            return Err(VerifierError::NumPowNoncesMismatch(expected_num_nonces, pow_nonces.len()));
+        }
+
         let mut layer_alphas = Vec::with_capacity(layer_commitments.len());
         let mut max_degree_plus_1 = max_poly_degree + 1;
+        let mut layer_domain_size = domain_size;
         for (depth, commitment) in layer_commitments.iter().enumerate() {
-            public_coin.reseed(*commitment);
+            // make sure the commitment consists of as many digests as implied by the cap height;
+            // a remainder sent as coefficients is committed to via a single hash
+            let expected_len = if depth < num_layers {
+                let num_leaves = layer_domain_size / options.folding_factor_at(depth);
+                layer_domain_size = num_leaves;
+                layer_commitment_len::<H, C::VectorCommitment>(num_leaves, &options)
+            } else {
+                match options.remainder_format() {
+                    RemainderFormat::Coefficients => 1,
+                    RemainderFormat::Evaluations => {
+                        layer_commitment_len::<H, C::VectorCommitment>(layer_domain_size, &options)
+                    }
+                }
+            };
+            if commitment.len() != expected_len {
+                return Err(VerifierError::LayerCommitmentSizeMismatch(
+                    depth,
+                    expected_len,
+                    commitment.len(),
+                ));
+            }
+
+            public_coin.reseed(C::VectorCommitment::commitment_digest(commitment));
+
+            // check Proof-of-Work for all layers but the remainder layer
+            if let Some(&nonce) = pow_nonces.get(depth) {
+                if public_coin.check_leading_zeros(nonce) < grinding_factor {
+                    return Err(VerifierError::LayerProofOfWorkVerificationFailed(depth));
+                }
+                public_coin.reseed_with_int(nonce);
+            }
+
             let alpha = public_coin.draw().map_err(VerifierError::RandomCoinError)?;
             layer_alphas.push(alpha);
 
             // make sure the degree can be reduced by the folding factor at all layers
             // but the remainder layer
-            if depth != layer_commitments.len() - 1
-                && max_degree_plus_1 % options.folding_factor() != 0
-            {
+            let folding_factor = options.folding_factor_at(depth);
+            if depth != layer_commitments.len() - 1 && max_degree_plus_1 % folding_factor != 0 {
                 return Err(VerifierError::DegreeTruncation(
                     max_degree_plus_1 - 1,
-                    options.folding_factor(),
+                    folding_factor,
                     depth,
                 ));
             }
-            max_degree_plus_1 /= options.folding_factor();
+            max_degree_plus_1 /= folding_factor;
         }
 
         Ok(FriVerifier {
@@ -201,117 +252,166 @@ where
     ///   at any of the FRI layers.
     /// * The degree of the remainder at the last FRI layer is greater than the degree implied by
     ///   `max_poly_degree` reduced by the folding factor at each FRI layer.
+    /// * The remainder was sent as evaluations, and the evaluations do not match the remainder
+    ///   commitment or do not cover the entire domain of the remainder layer.
     pub fn verify(
         &self,
         channel: &mut C,
         evaluations: &[E],
         positions: &[usize],
     ) -> Result<(), VerifierError> {
-        if evaluations.len() != positions.len() {
-            return Err(VerifierError::NumPositionEvaluationMismatch(
-                positions.len(),
-                evaluations.len(),
-            ));
-        }
-
-        // static dispatch for folding factor parameter
-        let folding_factor = self.options.folding_factor();
-        match folding_factor {
-            2 => self.verify_generic::<2>(channel, evaluations, positions),
-            4 => self.verify_generic::<4>(channel, evaluations, positions),
-            8 => self.verify_generic::<8>(channel, evaluations, positions),
-            16 => self.verify_generic::<16>(channel, evaluations, positions),
-            _ => Err(VerifierError::UnsupportedFoldingFactor(folding_factor)),
-        }
+        self.verify_batched(channel, &[evaluations.to_vec()], &[self.domain_size], positions)
     }
 
-    /// This is the actual implementation of the verification procedure described above, but it
-    /// also takes folding factor as a generic parameter N.
-    fn verify_generic<const N: usize>(
+    /// Executes the query phase of the FRI protocol for a batch of functions evaluated over
+    /// domains of different sizes.
+    ///
+    /// This is the verifier counterpart of
+    /// [FriProver::build_layers_batched()](crate::FriProver::build_layers_batched()). The k-th
+    /// vector in `evaluations` must contain evaluations of the k-th function of the batch over
+    /// a domain of size `domain_sizes[k]`: the i-th value in the vector is the evaluation at
+    /// position `positions[i] % domain_sizes[k]`. The first domain size must be equal to the
+    /// size of the domain of this verifier, and the remaining domain sizes must be equal to the
+    /// domain sizes of subsequent FRI layers. Each function is folded into the FRI layer with
+    /// the matching domain size in the same way as it is done by the prover.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The number of evaluation vectors is different from the number of domain sizes.
+    /// * The length of any of the evaluation vectors is not equal to the length of `positions`.
+    /// * Any of the domain sizes does not match the domain of this verifier (for the first
+    ///   domain size) or the domain of any of the subsequent FRI layers (for all other domain
+    ///   sizes).
+    /// * Any of the conditions described for [verify()](FriVerifier::verify()) holds.
+    pub fn verify_batched(
         &self,
         channel: &mut C,
-        evaluations: &[E],
+        evaluations: &[Vec<E>],
+        domain_sizes: &[usize],
         positions: &[usize],
     ) -> Result<(), VerifierError> {
-        // pre-compute roots of unity used in computing x coordinates in the folded domain
-        let folding_roots = (0..N)
-            .map(|i| self.domain_generator.exp_vartime(((self.domain_size / N * i) as u64).into()))
-            .collect::<Vec<_>>();
+        if evaluations.is_empty() || evaluations.len() != domain_sizes.len() {
+            return Err(VerifierError::NumBatchedEvaluationsMismatch(
+                domain_sizes.len(),
+                evaluations.len(),
+            ));
+        }
+        if let Some(batched) = evaluations.iter().find(|e| e.len() != positions.len()) {
+            return Err(VerifierError::NumPositionEvaluationMismatch(
+                positions.len(),
+                batched.len(),
+            ));
+        }
+        if domain_sizes[0] != self.domain_size {
+            return Err(VerifierError::InvalidBatchDomainSize(domain_sizes[0]));
+        }
+        if let Some(&size) = domain_sizes[1..].iter().find(|&&s| !self.is_layer_domain_size(s)) {
+            return Err(VerifierError::InvalidBatchDomainSize(size));
+        }
 
         // 1 ----- verify the recursive components of the FRI proof -----------------------------------
-        let mut domain_generator = self.domain_generator;
-        let mut domain_size = self.domain_size;
+        let mut layer = LayerState {
+            positions: positions.to_vec(),
+            evaluations: evaluations[0].clone(),
+            domain_generator: self.domain_generator,
+            domain_size: self.domain_size,
+        };
         let mut max_degree_plus_1 = self.max_poly_degree + 1;
-        let mut positions = positions.to_vec();
-        let mut evaluations = evaluations.to_vec();
 
         for depth in 0..self.options.num_fri_layers(self.domain_size) {
-            // determine which evaluations were queried in the folded layer
-            let mut folded_positions =
-                fold_positions(&positions, domain_size, self.options.folding_factor());
-            // determine where these evaluations are in the commitment Merkle tree
-            let position_indexes = map_positions_to_indexes(
-                &folded_positions,
-                domain_size,
-                self.options.folding_factor(),
-                self.num_partitions,
-            );
-            // read query values from the specified indexes in the Merkle tree
-            let layer_commitment = self.layer_commitments[depth];
-            // TODO: add layer depth to the potential error message
-            let layer_values = channel.read_layer_queries(&position_indexes, &layer_commitment)?;
-            let query_values =
-                get_query_values::<E, N>(&layer_values, &positions, &folded_positions, domain_size);
-            if evaluations != query_values {
-                return Err(VerifierError::InvalidLayerFolding(depth));
+            // static dispatch for folding factor parameter
+            let folding_factor = self.options.folding_factor_at(depth);
+            match folding_factor {
+                2 => self.verify_layer::<2>(channel, depth, &mut layer)?,
+                4 => self.verify_layer::<4>(channel, depth, &mut layer)?,
+                8 => self.verify_layer::<8>(channel, depth, &mut layer)?,
+                16 => self.verify_layer::<16>(channel, depth, &mut layer)?,
+                _ => return Err(VerifierError::UnsupportedFoldingFactor(folding_factor)),
             }
 
-            // build a set of x coordinates for each row polynomial
-            #[rustfmt::skip]
-            let xs = folded_positions.iter().map(|&i| {
-                let xe = domain_generator.exp_vartime((i as u64).into()) * self.options.domain_offset();
-                folding_roots.iter()
-                    .map(|&r| E::from(xe * r))
-                    .collect::<Vec<_>>().try_into().unwrap()
-            })
-            .collect::<Vec<_>>();
-
-            // interpolate x and y values into row polynomials
-            let row_polys = polynom::interpolate_batch(&xs, &layer_values);
-
-            // calculate the pseudo-random value used for linear combination in layer folding
+            // fold in the evaluations over the domain of the next layer
             let alpha = self.layer_alphas[depth];
-
-            // check that when the polynomials are evaluated at alpha, the result is equal to
-            // the corresponding column value
-            evaluations = row_polys.iter().map(|p| polynom::eval(p, alpha)).collect();
+            let mut coefficient = alpha.exp_vartime((folding_factor as u32).into());
+            for (batched, _) in evaluations
+                .iter()
+                .zip(domain_sizes)
+                .skip(1)
+                .filter(|(_, &domain_size)| domain_size == layer.domain_size)
+            {
+                for (evaluation, &position) in layer.evaluations.iter_mut().zip(&layer.positions) {
+                    let idx = positions
+                        .iter()
+                        .position(|&p| p % layer.domain_size == position)
+                        .expect("folded position must be implied by a queried position");
+                    *evaluation += coefficient * batched[idx];
+                }
+                coefficient *= alpha;
+            }
 
             // make sure next degree reduction does not result in degree truncation
-            if max_degree_plus_1 % N != 0 {
-                return Err(VerifierError::DegreeTruncation(max_degree_plus_1 - 1, N, depth));
+            if max_degree_plus_1 % folding_factor != 0 {
+                return Err(VerifierError::DegreeTruncation(
+                    max_degree_plus_1 - 1,
+                    folding_factor,
+                    depth,
+                ));
             }
+            max_degree_plus_1 /= folding_factor;
+        }
 
-            // update variables for the next iteration of the loop
-            domain_generator = domain_generator.exp_vartime((N as u32).into());
-            max_degree_plus_1 /= N;
-            domain_size /= N;
-            mem::swap(&mut positions, &mut folded_positions);
+        // 2 ----- verify the remainder of the FRI proof ------------------------------------------
+        let remainder = channel.read_remainder()?;
+        match self.options.remainder_format() {
+            RemainderFormat::Coefficients => {
+                self.verify_remainder_poly(&remainder, max_degree_plus_1, layer)
+            }
+            RemainderFormat::Evaluations => {
+                self.verify_remainder_evaluations(remainder, max_degree_plus_1, layer)
+            }
         }
+    }
 
-        // 2 ----- verify the remainder polynomial of the FRI proof -------------------------------
+    /// Returns true if the specified domain size is equal to the domain size of one of the FRI
+    /// layers following the first layer (including the remainder layer).
+    fn is_layer_domain_size(&self, domain_size: usize) -> bool {
+        let mut layer_domain_size = self.domain_size;
+        for depth in 0..self.options.num_fri_layers(self.domain_size) {
+            layer_domain_size /= self.options.folding_factor_at(depth);
+            if layer_domain_size == domain_size {
+                return true;
+            }
+        }
+        false
+    }
 
-        // read the remainder polynomial from the channel and make sure it agrees with the evaluations
-        // from the previous layer.
-        let remainder_poly = channel.read_remainder()?;
+    /// Verifies the remainder sent as coefficients of the remainder polynomial.
+    ///
+    /// The coefficients are checked against the remainder commitment, the degree of the polynomial
+    /// is implied by the number of coefficients, and the evaluations from the previous layer are
+    /// checked by evaluating the polynomial at the queried points.
+    fn verify_remainder_poly(
+        &self,
+        remainder_poly: &[E],
+        max_degree_plus_1: usize,
+        layer: LayerState<E>,
+    ) -> Result<(), VerifierError> {
         if remainder_poly.len() > max_degree_plus_1 {
             return Err(VerifierError::RemainderDegreeMismatch(max_degree_plus_1 - 1));
         }
+
+        // make sure the coefficients match the commitment; the prover commits to the remainder
+        // polynomial by hashing its coefficients in the transcript domain
+        let commitment = H::hash_elements_in_domain(remainder_poly, HashDomain::Transcript);
+        let remainder_commitment = self.layer_commitments.last().expect("no remainder commitment");
+        if remainder_commitment.as_slice() != [commitment] {
+            return Err(VerifierError::RemainderCommitmentMismatch);
+        }
         let offset: E::BaseField = self.options().domain_offset();
 
-        for (&position, evaluation) in positions.iter().zip(evaluations) {
+        for (&position, evaluation) in layer.positions.iter().zip(layer.evaluations) {
             let comp_eval = eval_horner::<E>(
-                &remainder_poly,
-                offset * domain_generator.exp_vartime((position as u64).into()),
+                remainder_poly,
+                offset * layer.domain_generator.exp_vartime((position as u64).into()),
             );
             if comp_eval != evaluation {
                 return Err(VerifierError::InvalidRemainderFolding);
@@ -320,6 +420,135 @@ where
 
         Ok(())
     }
+
+    /// Verifies the remainder sent as evaluations over the domain of the remainder layer.
+    ///
+    /// The evaluations are checked against the remainder commitment, the degree of the remainder
+    /// is checked by interpolating the evaluations, and the evaluations from the previous layer
+    /// are checked by looking up the remainder evaluations at the queried positions.
+    fn verify_remainder_evaluations(
+        &self,
+        remainder: Vec<E>,
+        max_degree_plus_1: usize,
+        layer: LayerState<E>,
+    ) -> Result<(), VerifierError> {
+        if remainder.len() != layer.domain_size {
+            return Err(VerifierError::RemainderSizeMismatch(layer.domain_size, remainder.len()));
+        }
+
+        // make sure the evaluations match the commitment; the commitment is built in the same
+        // way as it is done by the prover
+        let hashed_evaluations = hash_values::<H, E, 1>(&transpose_slice::<_, 1>(&remainder));
+        let cap_height = self
+            .options
+            .merkle_cap_height()
+            .min((remainder.len().ilog2() as usize).saturating_sub(1));
+        let commitment_options =
+            VectorCommitmentOptions::new(self.options.merkle_arity(), cap_height);
+        let commitment = C::VectorCommitment::with_options(hashed_evaluations, commitment_options)
+            .map_err(|_| VerifierError::RemainderCommitmentMismatch)?;
+        let remainder_commitment = self.layer_commitments.last().expect("no remainder commitment");
+        if commitment.commitment() != remainder_commitment.as_slice() {
+            return Err(VerifierError::RemainderCommitmentMismatch);
+        }
+
+        // interpolate the evaluations and make sure the degree of the resulting polynomial is
+        // within the bound implied by the degree of the previous layer
+        let mut remainder_poly = remainder.clone();
+        let inv_twiddles = fft::get_inv_twiddles::<E::BaseField>(remainder_poly.len());
+        let offset: E::BaseField = self.options().domain_offset();
+        fft::interpolate_poly_with_offset(&mut remainder_poly, &inv_twiddles, offset);
+        if remainder_poly.iter().skip(max_degree_plus_1).any(|&c| c != E::ZERO) {
+            return Err(VerifierError::RemainderDegreeMismatch(max_degree_plus_1 - 1));
+        }
+
+        // make sure the remainder agrees with the evaluations from the previous layer
+        for (&position, evaluation) in layer.positions.iter().zip(layer.evaluations) {
+            if remainder[position] != evaluation {
+                return Err(VerifierError::InvalidRemainderFolding);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Verifies the FRI layer at the specified `depth` assuming it was folded with folding
+    /// factor N.
+    ///
+    /// If the layer is valid, the `layer` state is updated to describe queried positions and
+    /// evaluations in the folded domain of the next layer.
+    fn verify_layer<const N: usize>(
+        &self,
+        channel: &mut C,
+        depth: usize,
+        layer: &mut LayerState<E>,
+    ) -> Result<(), VerifierError> {
+        let domain_generator = layer.domain_generator;
+        let domain_size = layer.domain_size;
+
+        // pre-compute roots of unity used in computing x coordinates in the folded domain
+        let folding_roots = (0..N)
+            .map(|i| domain_generator.exp_vartime(((domain_size / N * i) as u64).into()))
+            .collect::<Vec<_>>();
+
+        // determine which evaluations were queried in the folded layer
+        let folded_positions = fold_positions(&layer.positions, domain_size, N);
+        // determine where these evaluations are in the commitment Merkle tree
+        let position_indexes =
+            map_positions_to_indexes(&folded_positions, domain_size, N, self.num_partitions);
+        // read query values from the specified indexes in the Merkle tree
+        let layer_commitment = &self.layer_commitments[depth];
+        // TODO: add layer depth to the potential error message
+        let layer_values = channel.read_layer_queries(&position_indexes, layer_commitment)?;
+        let query_values = get_query_values::<E, N>(
+            &layer_values,
+            &layer.positions,
+            &folded_positions,
+            domain_size,
+        );
+        if layer.evaluations != query_values {
+            return Err(VerifierError::InvalidLayerFolding(depth));
+        }
+
+        // build a set of x coordinates for each row polynomial
+        #[rustfmt::skip]
+        let xs = folded_positions.iter().map(|&i| {
+            let xe = domain_generator.exp_vartime((i as u64).into()) * self.options.domain_offset();
+            folding_roots.iter()
+                .map(|&r| E::from(xe * r))
+                .collect::<Vec<_>>().try_into().unwrap()
+        })
+        .collect::<Vec<_>>();
+
+        // interpolate x and y values into row polynomials
+        let row_polys = polynom::interpolate_batch(&xs, &layer_values);
+
+        // calculate the pseudo-random value used for linear combination in layer folding
+        let alpha = self.layer_alphas[depth];
+
+        // check that when the polynomials are evaluated at alpha, the result is equal to
+        // the corresponding column value
+        layer.evaluations = row_polys.iter().map(|p| polynom::eval(p, alpha)).collect();
+
+        // update the layer state for the next iteration
+        layer.positions = folded_positions;
+        layer.domain_generator = domain_generator.exp_vartime((N as u32).into());
+        layer.domain_size /= N;
+
+        Ok(())
+    }
+}
+
+// LAYER STATE
+// ================================================================================================
+
+/// Queried positions and evaluations at the FRI layer currently being verified, together with
+/// the description of the domain of this layer.
+struct LayerState<E: FieldElement> {
+    positions: Vec<usize>,
+    evaluations: Vec<E>,
+    domain_generator: E::BaseField,
+    domain_size: usize,
 }
 
 // HELPER FUNCTIONS
@@ -342,6 +571,17 @@ fn get_query_values<E: FieldElement, const N: usize>(
     result
 }
 
+/// Returns the number of digests in a commitment to a FRI layer with the specified number of
+/// leaves; the cap height is reduced for small layers in the same way as it is done by the prover.
+fn layer_commitment_len<H: ElementHasher, V: VectorCommitment<H>>(
+    num_leaves: usize,
+    options: &FriOptions,
+) -> usize {
+    let tree_depth = num_leaves.checked_ilog2().unwrap_or(0) as usize;
+    let cap_height = options.merkle_cap_height().min(tree_depth.saturating_sub(1));
+    V::commitment_len(num_leaves, VectorCommitmentOptions::new(options.merkle_arity(), cap_height))
+}
+
 // Evaluates a polynomial with coefficients in an extension field at a point in the base field.
 pub fn eval_horner<E>(p: &[E], x: E::BaseField) -> E
 where
@@ -4,13 +4,12 @@
 // LICENSE file in the root directory of this source tree.
 
 use air::{
-    proof::{Commitments, Context, OodFrame, Queries, StarkProof},
+    proof::{Commitments, Context, LowDegreeProof, OodFrame, Queries, StarkProof},
     Air, ConstraintCompositionCoefficients, DeepCompositionCoefficients,
 };
 use alloc::vec::Vec;
 use core::marker::PhantomData;
-use crypto::{ElementHasher, RandomCoin};
-use fri::FriProof;
+use crypto::{ElementHasher, HashDomain, RandomCoin, VectorCommitment};
 use math::{FieldElement, ToElements};
 
 #[cfg(feature = "concurrent")]
@@ -19,12 +18,13 @@ use utils::iterators::*;
 // TYPES AND INTERFACES
 // ================================================================================================
 
-pub struct ProverChannel<'a, A, E, H, R>
+pub struct ProverChannel<'a, A, E, H, R, V>
 where
     A: Air,
     E: FieldElement<BaseField = A::BaseField>,
     H: ElementHasher<BaseField = A::BaseField>,
     R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+    V: VectorCommitment<H>,
 {
     air: &'a A,
     public_coin: R,
@@ -32,18 +32,21 @@ where
     commitments: Commitments,
     ood_frame: OodFrame,
     pow_nonce: u64,
+    deep_pow_nonce: u64,
     _field_element: PhantomData<E>,
+    _vector_commitment: PhantomData<V>,
 }
 
 // PROVER CHANNEL IMPLEMENTATION
 // ================================================================================================
 
-impl<'a, A, E, H, R> ProverChannel<'a, A, E, H, R>
+impl<'a, A, E, H, R, V> ProverChannel<'a, A, E, H, R, V>
 where
     A: Air,
     E: FieldElement<BaseField = A::BaseField>,
     H: ElementHasher<BaseField = A::BaseField>,
     R: RandomCoin<BaseField = A::BaseField, Hasher = H>,
+    V: VectorCommitment<H>,
 {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
@@ -64,7 +67,9 @@ where
             commitments: Commitments::default(),
             ood_frame: OodFrame::default(),
             pow_nonce: 0,
+            deep_pow_nonce: 0,
             _field_element: PhantomData,
+            _vector_commitment: PhantomData,
         }
     }
 
@@ -72,29 +77,39 @@ where
     // --------------------------------------------------------------------------------------------
 
     /// Commits the prover the extended execution trace.
-    pub fn commit_trace(&mut self, trace_root: H::Digest) {
-        self.commitments.add::<H>(&trace_root);
-        self.public_coin.reseed(trace_root);
+    ///
+    /// The commitment is built using the vector commitment scheme `V` (e.g., it is a Merkle tree
+    /// cap); the public coin is reseeded with the digest which binds the commitment.
+    pub fn commit_trace(&mut self, trace_cap: &[H::Digest]) {
+        self.commitments.add::<H>(trace_cap);
+        self.public_coin.reseed(V::commitment_digest(trace_cap));
     }
 
     /// Commits the prover to the evaluations of the constraint composition polynomial.
-    pub fn commit_constraints(&mut self, constraint_root: H::Digest) {
-        self.commitments.add::<H>(&constraint_root);
-        self.public_coin.reseed(constraint_root);
+    ///
+    /// The commitment is built using the vector commitment scheme `V` (e.g., it is a Merkle tree
+    /// cap); the public coin is reseeded with the digest which binds the commitment.
+    pub fn commit_constraints(&mut self, constraint_cap: &[H::Digest]) {
+        self.commitments.add::<H>(constraint_cap);
+        self.public_coin.reseed(V::commitment_digest(constraint_cap));
     }
 
     /// Saves the evaluations of trace polynomials over the out-of-domain evaluation frame. This
-    /// also reseeds the public coin with the hashes of the evaluation frame states.
+    /// also reseeds the public coin with the hash of the evaluation frame states computed in the
+    /// [HashDomain::Transcript] domain.
     pub fn send_ood_trace_states(&mut self, trace_states: &[Vec<E>]) {
         let result = self.ood_frame.set_trace_states(trace_states);
-        self.public_coin.reseed(H::hash_elements(&result));
+        self.public_coin
+            .reseed(H::hash_elements_in_domain(&result, HashDomain::Transcript));
     }
 
     /// Saves the evaluations of constraint composition polynomial columns at the out-of-domain
-    /// point. This also reseeds the public coin wit the hash of the evaluations.
+    /// point. This also reseeds the public coin with the hash of the evaluations computed in the
+    /// [HashDomain::Transcript] domain.
     pub fn send_ood_constraint_evaluations(&mut self, evaluations: &[E]) {
         self.ood_frame.set_constraint_evaluations(evaluations);
-        self.public_coin.reseed(H::hash_elements(evaluations));
+        self.public_coin
+            .reseed(H::hash_elements_in_domain(evaluations, HashDomain::Transcript));
     }
 
     // PUBLIC COIN METHODS
@@ -126,8 +141,16 @@ where
 
     /// Returns a set of coefficients for constructing a DEEP composition polynomial.
     ///
-    /// The coefficients are drawn from the public coin uniformly at random.
+    /// The coefficients are drawn from the public coin uniformly at random. If the proof options
+    /// specify a non-zero commit grinding factor, Proof-of-Work is performed and the resulting
+    /// nonce is absorbed into the public coin before the coefficients are drawn.
     pub fn get_deep_composition_coeffs(&mut self) -> DeepCompositionCoefficients<E> {
+        let grinding_factor = self.context.options().commit_grinding_factor();
+        if grinding_factor > 0 {
+            self.deep_pow_nonce = self.find_pow_nonce(grinding_factor);
+            self.public_coin.reseed_with_int(self.deep_pow_nonce);
+        }
+
         self.air
             .get_deep_composition_coefficients(&mut self.public_coin)
             .expect("failed to draw DEEP composition coefficients")
@@ -158,67 +181,138 @@ where
     /// in the proof options.
     pub fn grind_query_seed(&mut self) {
         let grinding_factor = self.context.options().grinding_factor();
+        self.pow_nonce = self.find_pow_nonce(grinding_factor);
+    }
+
+    /// Returns a nonce, which when hashed with the current seed of the public coin results in a
+    /// value with at least the specified number of leading zeros.
+    fn find_pow_nonce(&self, grinding_factor: u32) -> u64 {
+        // only the coin is captured by the search closure since the channel itself may not be
+        // shared between threads
+        let coin = &self.public_coin;
 
         #[cfg(not(feature = "concurrent"))]
         let nonce = (1..u64::MAX)
-            .find(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor)
+            .find(|&nonce| coin.check_leading_zeros(nonce) >= grinding_factor)
             .expect("nonce not found");
 
         #[cfg(feature = "concurrent")]
         let nonce = (1..u64::MAX)
             .into_par_iter()
-            .find_any(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor)
+            .find_any(|&nonce| coin.check_leading_zeros(nonce) >= grinding_factor)
             .expect("nonce not found");
 
-        self.pow_nonce = nonce;
+        nonce
     }
 
     // PROOF BUILDER
     // --------------------------------------------------------------------------------------------
     /// Builds a proof from the previously committed values as well as values passed into
     /// this method.
+    ///
+    /// The public coin is returned together with the proof so that the transcript of the
+    /// interaction can be inspected when the coin records it.
     pub fn build_proof(
         self,
         trace_queries: Vec<Queries>,
         constraint_queries: Queries,
-        fri_proof: FriProof,
+        ldt_proof: LowDegreeProof,
         num_query_positions: usize,
-    ) -> StarkProof {
+    ) -> (StarkProof, R) {
         assert!(num_query_positions <= u8::MAX as usize, "num_query_positions too big");
 
-        StarkProof {
+        let proof = StarkProof {
             context: self.context,
             commitments: self.commitments,
             ood_frame: self.ood_frame,
             trace_queries,
             constraint_queries,
-            fri_proof,
+            ldt_proof,
             pow_nonce: self.pow_nonce,
+            deep_pow_nonce: self.deep_pow_nonce,
             num_unique_queries: num_query_positions as u8,
-        }
+        };
+        (proof, self.public_coin)
     }
 }
 
 // FRI PROVER CHANNEL IMPLEMENTATION
 // ================================================================================================
 
-impl<'a, A, E, H, R> fri::ProverChannel<E> for ProverChannel<'a, A, E, H, R>
+impl<'a, A, E, H, R, V> fri::ProverChannel<E> for ProverChannel<'a, A, E, H, R, V>
 where
     A: Air,
     E: FieldElement<BaseField = A::BaseField>,
     H: ElementHasher<BaseField = A::BaseField>,
     R: RandomCoin<BaseField = A::BaseField, Hasher = H>,
+    V: VectorCommitment<H>,
 {
     type Hasher = H;
 
     /// Commits the prover to a FRI layer.
-    fn commit_fri_layer(&mut self, layer_root: H::Digest) {
-        self.commitments.add::<H>(&layer_root);
-        self.public_coin.reseed(layer_root);
+    fn commit_fri_layer(&mut self, layer_cap: &[H::Digest]) {
+        self.commitments.add::<H>(layer_cap);
+        self.public_coin.reseed(V::commitment_digest(layer_cap));
     }
 
     /// Returns a new alpha drawn from the public coin.
     fn draw_fri_alpha(&mut self) -> E {
         self.public_coin.draw().expect("failed to draw FRI alpha")
     }
+
+    /// Performs Proof-of-Work for a FRI layer and absorbs the resulting nonce into the public
+    /// coin.
+    fn grind_fri_layer(&mut self, grinding_factor: u32) -> u64 {
+        let nonce = self.find_pow_nonce(grinding_factor);
+        self.public_coin.reseed_with_int(nonce);
+        nonce
+    }
+}
+
+// STIR PROVER CHANNEL IMPLEMENTATION
+// ================================================================================================
+
+impl<'a, A, E, H, R, V> stir::ProverChannel<E> for ProverChannel<'a, A, E, H, R, V>
+where
+    A: Air,
+    E: FieldElement<BaseField = A::BaseField>,
+    H: ElementHasher<BaseField = A::BaseField>,
+    R: RandomCoin<BaseField = A::BaseField, Hasher = H>,
+    V: VectorCommitment<H>,
+{
+    type Hasher = H;
+
+    /// Commits the prover to the function of a STIR round.
+    fn commit_stir_layer(&mut self, layer_cap: &[H::Digest]) {
+        self.commitments.add::<H>(layer_cap);
+        self.public_coin.reseed(V::commitment_digest(layer_cap));
+    }
+
+    /// Reseeds the public coin with the out-of-domain evaluation of a STIR round function.
+    ///
+    /// The evaluation itself is included into the STIR proof.
+    fn send_stir_ood_evaluation(&mut self, value: E) {
+        self.public_coin
+            .reseed(H::hash_elements_in_domain(&[value], HashDomain::Transcript));
+    }
+
+    /// Reseeds the public coin with the coefficients of the final STIR polynomial.
+    ///
+    /// The coefficients themselves are included into the STIR proof.
+    fn send_stir_final_poly(&mut self, poly: &[E]) {
+        self.public_coin
+            .reseed(H::hash_elements_in_domain(poly, HashDomain::Transcript));
+    }
+
+    /// Returns a new STIR challenge drawn from the public coin.
+    fn draw_stir_challenge(&mut self) -> E {
+        self.public_coin.draw().expect("failed to draw STIR challenge")
+    }
+
+    /// Returns a list of STIR query indexes drawn from the public coin.
+    fn draw_stir_query_indexes(&mut self, num_queries: usize, domain_size: usize) -> Vec<usize> {
+        self.public_coin
+            .draw_integers(num_queries, domain_size, 0)
+            .expect("failed to draw STIR query indexes")
+    }
 }
@@ -6,7 +6,8 @@
 use super::RowMatrix;
 use air::proof::Queries;
 use alloc::vec::Vec;
-use crypto::{ElementHasher, MerkleTree};
+use core::marker::PhantomData;
+use crypto::{ElementHasher, MerkleTree, VectorCommitment};
 use math::FieldElement;
 
 // CONSTRAINT COMMITMENT
@@ -16,47 +17,58 @@ use math::FieldElement;
 ///
 /// The commitment consists of two components:
 /// * Evaluations of composition polynomial columns over the LDE domain.
-/// * Merkle tree where each leaf in the tree corresponds to a row in the composition polynomial
-///   evaluation matrix.
-pub struct ConstraintCommitment<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
+/// * Vector commitment (by default, a Merkle tree) where each committed item corresponds to a row
+///   in the composition polynomial evaluation matrix.
+pub struct ConstraintCommitment<
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H> = MerkleTree<H>,
+> {
     evaluations: RowMatrix<E>,
-    commitment: MerkleTree<H>,
+    commitment: V,
+    _hasher: PhantomData<H>,
 }
 
-impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> ConstraintCommitment<E, H> {
+impl<E, H, V> ConstraintCommitment<E, H, V>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H>,
+{
     /// Creates a new constraint evaluation commitment from the provided composition polynomial
-    /// evaluations and the corresponding Merkle tree commitment.
-    pub fn new(evaluations: RowMatrix<E>, commitment: MerkleTree<H>) -> ConstraintCommitment<E, H> {
+    /// evaluations and the corresponding vector commitment.
+    pub fn new(evaluations: RowMatrix<E>, commitment: V) -> ConstraintCommitment<E, H, V> {
         assert_eq!(
             evaluations.num_rows(),
-            commitment.leaves().len(),
-            "number of rows in constraint evaluation matrix must be the same as number of leaves in constraint commitment"
+            commitment.domain_len(),
+            "number of rows in constraint evaluation matrix must be the same as number of items in constraint commitment"
         );
         ConstraintCommitment {
             evaluations,
             commitment,
+            _hasher: PhantomData,
         }
     }
 
-    /// Returns the root of the commitment Merkle tree.
-    pub fn root(&self) -> H::Digest {
-        *self.commitment.root()
+    /// Returns the commitment to the constraint evaluations.
+    pub fn commitment(&self) -> &[H::Digest] {
+        self.commitment.commitment()
     }
 
-    /// Returns the depth of the commitment Merkle tree.
+    /// Returns the number of items in the committed vector.
     #[allow(unused)]
-    pub fn tree_depth(&self) -> usize {
-        self.commitment.depth()
+    pub fn domain_len(&self) -> usize {
+        self.commitment.domain_len()
     }
 
-    /// Returns constraint evaluations at the specified positions along with Merkle authentication
-    /// paths from the root of the commitment to these evaluations.
+    /// Returns constraint evaluations at the specified positions along with an opening proof
+    /// for these evaluations against the commitment.
     pub fn query(self, positions: &[usize]) -> Queries {
-        // build Merkle authentication paths to the leaves specified by positions
-        let merkle_proof = self
+        // build an opening proof for the items specified by positions
+        let opening_proof = self
             .commitment
-            .prove_batch(positions)
-            .expect("failed to generate a Merkle proof for constraint queries");
+            .open_many(positions)
+            .expect("failed to generate an opening proof for constraint queries");
 
         // determine a set of evaluations corresponding to each position
         let mut evaluations = Vec::new();
@@ -65,6 +77,6 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> ConstraintComm
             evaluations.push(row);
         }
 
-        Queries::new(merkle_proof, evaluations)
+        Queries::new::<H, E, V>(opening_proof, evaluations)
     }
 }
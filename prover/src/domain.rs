@@ -5,6 +5,7 @@
 
 use air::Air;
 use alloc::vec::Vec;
+use crypto::VectorCommitmentOptions;
 use math::{fft, get_power_series, StarkField};
 
 // TYPES AND INTERFACES
@@ -29,6 +30,21 @@ pub struct StarkDomain<B: StarkField> {
 
     /// Offset of the low-degree extension domain.
     domain_offset: B,
+
+    /// Height of the caps of Merkle trees built over the low-degree extension domain.
+    merkle_cap_height: usize,
+
+    /// Arity of Merkle trees used to commit to the execution trace.
+    trace_merkle_arity: usize,
+
+    /// Arity of the Merkle tree used to commit to the constraint evaluations.
+    constraint_merkle_arity: usize,
+
+    /// Size of partitions in which rows of the extended execution trace are hashed.
+    trace_partition_size: usize,
+
+    /// Size of partitions in which rows of the constraint evaluation matrix are hashed.
+    constraint_partition_size: usize,
 }
 
 // STARK DOMAIN IMPLEMENTATION
@@ -49,6 +65,14 @@ impl<B: StarkField> StarkDomain<B> {
             ce_to_lde_blowup: air.lde_domain_size() / air.ce_domain_size(),
             ce_domain_mod_mask: air.ce_domain_size() - 1,
             domain_offset: air.domain_offset(),
+            merkle_cap_height: air
+                .options()
+                .merkle_cap_height()
+                .min(air.lde_domain_size().ilog2() as usize - 1),
+            trace_merkle_arity: air.options().trace_merkle_arity(),
+            constraint_merkle_arity: air.options().constraint_merkle_arity(),
+            trace_partition_size: air.options().trace_partition_size(),
+            constraint_partition_size: air.options().constraint_partition_size(),
         }
     }
 
@@ -71,6 +95,11 @@ impl<B: StarkField> StarkDomain<B> {
             ce_to_lde_blowup: 1,
             ce_domain_mod_mask: ce_domain_size - 1,
             domain_offset,
+            merkle_cap_height: 0,
+            trace_merkle_arity: 2,
+            constraint_merkle_arity: 2,
+            trace_partition_size: 0,
+            constraint_partition_size: 0,
         }
     }
 
@@ -151,4 +180,52 @@ impl<B: StarkField> StarkDomain<B> {
     pub fn offset(&self) -> B {
         self.domain_offset
     }
+
+    /// Returns the height of the caps of Merkle trees which commit to evaluations over the LDE
+    /// domain.
+    ///
+    /// This is the Merkle cap height specified in the proof options, reduced to one less than the
+    /// depth of the trees when the LDE domain is too small for a cap of the specified height.
+    pub fn merkle_cap_height(&self) -> usize {
+        self.merkle_cap_height
+    }
+
+    /// Returns the arity of Merkle trees which commit to execution trace evaluations over the LDE
+    /// domain.
+    pub fn trace_merkle_arity(&self) -> usize {
+        self.trace_merkle_arity
+    }
+
+    /// Returns the arity of the Merkle tree which commits to constraint evaluations over the LDE
+    /// domain.
+    pub fn constraint_merkle_arity(&self) -> usize {
+        self.constraint_merkle_arity
+    }
+
+    /// Returns the size of partitions in which rows of the extended execution trace are hashed.
+    ///
+    /// A partition size of zero means that the rows are not partitioned.
+    pub fn trace_partition_size(&self) -> usize {
+        self.trace_partition_size
+    }
+
+    /// Returns the size of partitions in which rows of the constraint evaluation matrix are
+    /// hashed.
+    ///
+    /// A partition size of zero means that the rows are not partitioned.
+    pub fn constraint_partition_size(&self) -> usize {
+        self.constraint_partition_size
+    }
+
+    /// Returns the options for building vector commitments to execution trace evaluations over
+    /// the LDE domain.
+    pub fn trace_commitment_options(&self) -> VectorCommitmentOptions {
+        VectorCommitmentOptions::new(self.trace_merkle_arity, self.merkle_cap_height)
+    }
+
+    /// Returns the options for building the vector commitment to constraint evaluations over the
+    /// LDE domain.
+    pub fn constraint_commitment_options(&self) -> VectorCommitmentOptions {
+        VectorCommitmentOptions::new(self.constraint_merkle_arity, self.merkle_cap_height)
+    }
 }
@@ -45,8 +45,8 @@ extern crate alloc;
 pub use air::{
     proof, proof::StarkProof, Air, AirContext, Assertion, AuxTraceRandElements, BoundaryConstraint,
     BoundaryConstraintGroup, ConstraintCompositionCoefficients, ConstraintDivisor,
-    DeepCompositionCoefficients, EvaluationFrame, FieldExtension, ProofOptions, TraceInfo,
-    TraceLayout, TransitionConstraintDegree,
+    DeepCompositionCoefficients, EvaluationFrame, FieldExtension, LowDegreeTest, ProofOptions,
+    TraceInfo, TraceLayout, TransitionConstraintDegree,
 };
 use tracing::{event, info_span, Level};
 pub use utils::{
@@ -54,8 +54,10 @@ pub use utils::{
     SliceReader,
 };
 
+use air::proof::LowDegreeProof;
 use alloc::vec::Vec;
 use fri::FriProver;
+use stir::StirProver;
 
 pub use math;
 use math::{
@@ -65,7 +67,7 @@ use math::{
 };
 
 pub use crypto;
-use crypto::{ElementHasher, RandomCoin};
+use crypto::{ElementHasher, RandomCoin, VectorCommitment};
 
 mod domain;
 pub use domain::StarkDomain;
@@ -83,7 +85,11 @@ mod composer;
 use composer::DeepCompositionPoly;
 
 mod trace;
-pub use trace::{DefaultTraceLde, Trace, TraceLde, TracePolyTable, TraceTable, TraceTableFragment};
+pub use trace::{
+    segment_fragments, AuxTraceBuilder, DefaultTraceLde, FnAuxTraceBuilder, NoAuxTraceBuilder,
+    Trace, TraceColumnGroup, TraceLde, TracePolyTable, TraceTable, TraceTableBuilder,
+    TraceTableFragment,
+};
 
 mod channel;
 use channel::ProverChannel;
@@ -138,8 +144,12 @@ pub trait Prover {
     /// PRNG to be used for generating random field elements.
     type RandomCoin: RandomCoin<BaseField = Self::BaseField, Hasher = Self::HashFn>;
 
+    /// Vector commitment scheme to be used for committing to the execution trace, constraint
+    /// evaluations, and FRI layers (e.g., [MerkleTree](crypto::MerkleTree)).
+    type VC: VectorCommitment<Self::HashFn> + Sync;
+
     /// Trace low-degree extension for building the LDEs of trace segments and their commitments.
-    type TraceLde<E>: TraceLde<E, HashFn = Self::HashFn>
+    type TraceLde<E>: TraceLde<E, HashFn = Self::HashFn, VC = Self::VC>
     where
         E: FieldElement<BaseField = Self::BaseField>;
 
@@ -200,6 +210,22 @@ pub trait Prover {
     /// secret and public inputs. Public inputs must match the value returned from
     /// [Self::get_pub_inputs()](Prover::get_pub_inputs) for the provided trace.
     fn prove(&self, trace: Self::Trace) -> Result<StarkProof, ProverError> {
+        self.prove_with_coin(trace).map(|(proof, _)| proof)
+    }
+
+    /// Returns a STARK proof attesting to a correct execution of a computation defined by the
+    /// provided trace together with the public coin used to generate the proof.
+    ///
+    /// The proof is the same as the one returned from [Self::prove()](Prover::prove). The coin is
+    /// returned in the state it was in after the query positions were drawn. This is useful when
+    /// [Self::RandomCoin](Prover::RandomCoin) is a
+    /// [RecordingRandomCoin](crypto::RecordingRandomCoin): the transcript of all interactions
+    /// with the coin can be compared against the transcript recorded by the verifier, or exported
+    /// as a test vector.
+    fn prove_with_coin(
+        &self,
+        trace: Self::Trace,
+    ) -> Result<(StarkProof, Self::RandomCoin), ProverError> {
         // figure out which version of the generic proof generation procedure to run. this is a sort
         // of static dispatch for selecting two generic parameter: extension field and hash
         // function.
@@ -224,10 +250,14 @@ pub trait Prover {
     // --------------------------------------------------------------------------------------------
 
     /// Performs the actual proof generation procedure, generating the proof that the provided
-    /// execution `trace` is valid against this prover's AIR.
+    /// execution `trace` is valid against this prover's AIR; the proof is returned together with
+    /// the public coin used to generate it.
     /// TODO: make this function un-callable externally?
     #[doc(hidden)]
-    fn generate_proof<E>(&self, mut trace: Self::Trace) -> Result<StarkProof, ProverError>
+    fn generate_proof<E>(
+        &self,
+        mut trace: Self::Trace,
+    ) -> Result<(StarkProof, Self::RandomCoin), ProverError>
     where
         E: FieldElement<BaseField = Self::BaseField>,
     {
@@ -245,10 +275,11 @@ pub trait Prover {
         // create a channel which is used to simulate interaction between the prover and the
         // verifier; the channel will be used to commit to values and to draw randomness that
         // should come from the verifier.
-        let mut channel = ProverChannel::<Self::Air, E, Self::HashFn, Self::RandomCoin>::new(
-            &air,
-            pub_inputs_elements,
-        );
+        let mut channel =
+            ProverChannel::<Self::Air, E, Self::HashFn, Self::RandomCoin, Self::VC>::new(
+                &air,
+                pub_inputs_elements,
+            );
 
         // 1 ----- Commit to the execution trace --------------------------------------------------
 
@@ -268,11 +299,11 @@ pub trait Prover {
                 self.new_trace_lde(&trace.get_info(), trace.main_segment(), &domain);
 
             // get the commitment to the main trace segment LDE
-            let main_trace_root = trace_lde.get_main_trace_commitment();
+            let main_trace_cap = trace_lde.get_main_trace_commitment();
 
-            // commit to the LDE of the main trace by writing the root of its Merkle tree into
+            // commit to the LDE of the main trace by writing the cap of its Merkle tree into
             // the channel
-            channel.commit_trace(main_trace_root);
+            channel.commit_trace(&main_trace_cap);
 
             drop(span);
             (trace_lde, trace_polys)
@@ -306,12 +337,12 @@ pub trait Prover {
                 // extend the auxiliary trace segment and build a Merkle tree from the extended
                 // trace
                 let span = info_span!("commit_to_aux_trace_segment").entered();
-                let (aux_segment_polys, aux_segment_root) =
+                let (aux_segment_polys, aux_segment_cap) =
                     trace_lde.add_aux_segment(&aux_segment, &domain);
 
-                // commit to the LDE of the extended auxiliary trace segment by writing the root of
+                // commit to the LDE of the extended auxiliary trace segment by writing the cap of
                 // its Merkle tree into the channel
-                channel.commit_trace(aux_segment_root);
+                channel.commit_trace(&aux_segment_cap);
 
                 drop(span);
                 aux_segment_polys
@@ -360,9 +391,9 @@ pub trait Prover {
                 &domain,
             );
 
-            // then, commit to the evaluations of constraints by writing the root of the constraint
+            // then, commit to the evaluations of constraints by writing the cap of the constraint
             // Merkle tree into the channel
-            channel.commit_constraints(constraint_commitment.root());
+            channel.commit_constraints(constraint_commitment.commitment());
 
             drop(span);
             (constraint_commitment, composition_poly)
@@ -424,12 +455,25 @@ pub trait Prover {
             deep_evaluations
         };
 
-        // 6 ----- compute FRI layers for the composition polynomial ------------------------------
-        let fri_options = air.options().to_fri_options();
-        let num_layers = fri_options.num_fri_layers(lde_domain_size);
-        let mut fri_prover = FriProver::new(fri_options);
-        info_span!("compute_fri_layers", num_layers)
-            .in_scope(|| fri_prover.build_layers(&mut channel, deep_evaluations));
+        // 6 ----- compute low-degree test layers for the composition polynomial -----------------
+        let mut ldt_prover = match air.options().low_degree_test() {
+            LowDegreeTest::Fri => {
+                let fri_options = air.options().to_fri_options();
+                let num_layers = fri_options.num_fri_layers(lde_domain_size);
+                let mut fri_prover = FriProver::<_, _, _, _, Self::VC>::new(fri_options);
+                info_span!("compute_fri_layers", num_layers)
+                    .in_scope(|| fri_prover.build_layers(&mut channel, deep_evaluations));
+                LowDegreeProver::Fri(fri_prover)
+            }
+            LowDegreeTest::Stir => {
+                let stir_options = air.options().to_stir_options();
+                let num_rounds = stir_options.num_rounds(lde_domain_size);
+                let mut stir_prover = StirProver::<_, _, _, _, Self::VC>::new(stir_options);
+                info_span!("compute_stir_layers", num_rounds)
+                    .in_scope(|| stir_prover.build_layers(&mut channel, deep_evaluations));
+                LowDegreeProver::Stir(stir_prover)
+            }
+        };
 
         // 7 ----- determine query positions ------------------------------------------------------
         let query_positions = {
@@ -452,8 +496,15 @@ pub trait Prover {
         // 8 ----- build proof object -------------------------------------------------------------
         let proof = {
             let span = info_span!("build_proof_object").entered();
-            // generate FRI proof
-            let fri_proof = fri_prover.build_proof(&query_positions);
+            // generate low-degree proof
+            let ldt_proof = match &mut ldt_prover {
+                LowDegreeProver::Fri(prover) => {
+                    LowDegreeProof::Fri(prover.build_proof(&query_positions))
+                }
+                LowDegreeProver::Stir(prover) => {
+                    LowDegreeProof::Stir(prover.build_proof(&query_positions))
+                }
+            };
 
             // query the execution trace at the selected position; for each query, we need the
             // state of the trace at that position + Merkle authentication path
@@ -466,15 +517,15 @@ pub trait Prover {
             let constraint_queries = constraint_commitment.query(&query_positions);
 
             // build the proof object
-            let proof = channel.build_proof(
+            let result = channel.build_proof(
                 trace_queries,
                 constraint_queries,
-                fri_proof,
+                ldt_proof,
                 query_positions.len(),
             );
 
             drop(span);
-            proof
+            result
         };
 
         Ok(proof)
@@ -489,13 +540,13 @@ pub trait Prover {
     /// polynomial column over the LDE domain.
     ///
     /// The commitment is computed by hashing each row in the evaluation matrix, and then building
-    /// a Merkle tree from the resulting hashes.
+    /// a vector commitment (e.g., a Merkle tree) from the resulting hashes.
     fn build_constraint_commitment<E>(
         &self,
         composition_poly_trace: CompositionPolyTrace<E>,
         num_constraint_composition_columns: usize,
         domain: &StarkDomain<Self::BaseField>,
-    ) -> (ConstraintCommitment<E, Self::HashFn>, CompositionPoly<E>)
+    ) -> (ConstraintCommitment<E, Self::HashFn, Self::VC>, CompositionPoly<E>)
     where
         E: FieldElement<BaseField = Self::BaseField>,
     {
@@ -527,11 +578,30 @@ pub trait Prover {
             tree_depth = domain_size.ilog2()
         )
         .in_scope(|| {
-            let commitment = composed_evaluations.commit_to_rows();
+            let commitment = composed_evaluations.commit_to_rows::<Self::HashFn, Self::VC>(
+                domain.constraint_partition_size(),
+                domain.constraint_commitment_options(),
+            );
             ConstraintCommitment::new(composed_evaluations, commitment)
         });
-        assert_eq!(constraint_commitment.tree_depth(), domain_size.ilog2() as usize);
+        assert_eq!(constraint_commitment.domain_len(), domain_size);
 
         (constraint_commitment, composition_poly)
     }
 }
+
+// LOW-DEGREE PROVER
+// ================================================================================================
+
+/// Prover for the protocol used to test the DEEP composition polynomial for low degree.
+enum LowDegreeProver<B, E, C, H, V>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+    C: fri::ProverChannel<E, Hasher = H> + stir::ProverChannel<E, Hasher = H>,
+    H: ElementHasher<BaseField = B>,
+    V: VectorCommitment<H>,
+{
+    Fri(FriProver<B, E, C, H, V>),
+    Stir(StirProver<B, E, C, H, V>),
+}
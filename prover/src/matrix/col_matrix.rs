@@ -3,10 +3,11 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use super::ROW_HASH_BATCH_SIZE;
 use crate::StarkDomain;
 use alloc::vec::Vec;
 use core::{iter::FusedIterator, slice};
-use crypto::{ElementHasher, MerkleTree};
+use crypto::{ElementHasher, VectorCommitment};
 use math::{fft, polynom, FieldElement};
 use utils::{batch_iter_mut, iter, iter_mut, uninit_vector};
 
@@ -246,34 +247,45 @@ impl<E: FieldElement> ColMatrix<E> {
     ///
     /// The commitment is built as follows:
     /// * Each row of the matrix is hashed into a single digest of the specified hash function.
-    /// * The resulting values are used to built a binary Merkle tree such that each row digest
-    ///   becomes a leaf in the tree. Thus, the number of leaves in the tree is equal to the
-    ///   number of rows in the matrix.
-    /// * The resulting Merkle tree is return as the commitment to the entire matrix.
-    pub fn commit_to_rows<H>(&self) -> MerkleTree<H>
+    ///   Rows wider than `partition_size` elements are hashed in partitions of `partition_size`
+    ///   elements, and the partition digests are merged into a single digest; a partition size of
+    ///   zero means that rows are not partitioned.
+    /// * The resulting values are used to build a vector commitment `V` with default options
+    ///   such that each row digest becomes an item of the committed vector (e.g., a leaf in a
+    ///   binary Merkle tree). Thus, the length of the committed vector is equal to the number of
+    ///   rows in the matrix.
+    /// * The resulting vector commitment is returned as the commitment to the entire matrix.
+    pub fn commit_to_rows<H, V>(&self, partition_size: usize) -> V
     where
         H: ElementHasher<BaseField = E::BaseField>,
+        V: VectorCommitment<H>,
     {
         // allocate vector to store row hashes
         let mut row_hashes = unsafe { uninit_vector::<H::Digest>(self.num_rows()) };
 
-        // iterate though matrix rows, hashing each row; the hashing is done by first copying a
-        // row into row_buf to avoid heap allocations, and then by applying the hash function to
-        // the buffer.
+        // iterate though matrix rows, hashing ROW_HASH_BATCH_SIZE rows at a time; the hashing is
+        // done by first copying the rows into row_buf to avoid heap allocations, and then by
+        // applying the hash function to the rows in the buffer.
         batch_iter_mut!(
             &mut row_hashes,
             128, // min batch size
             |batch: &mut [H::Digest], batch_offset: usize| {
-                let mut row_buf = vec![E::ZERO; self.num_cols()];
-                for (i, row_hash) in batch.iter_mut().enumerate() {
-                    self.read_row_into(i + batch_offset, &mut row_buf);
-                    *row_hash = H::hash_elements(&row_buf);
+                let num_cols = self.num_cols();
+                let mut row_buf = vec![E::ZERO; num_cols * ROW_HASH_BATCH_SIZE];
+                for (i, hashes) in batch.chunks_mut(ROW_HASH_BATCH_SIZE).enumerate() {
+                    let row_offset = batch_offset + i * ROW_HASH_BATCH_SIZE;
+                    for (j, row) in row_buf.chunks_mut(num_cols).take(hashes.len()).enumerate() {
+                        self.read_row_into(row_offset + j, row);
+                    }
+                    let rows: [&[E]; ROW_HASH_BATCH_SIZE] =
+                        core::array::from_fn(|j| &row_buf[j * num_cols..(j + 1) * num_cols]);
+                    H::hash_rows_in_partitions(&rows[..hashes.len()], partition_size, hashes);
                 }
             }
         );
 
-        // build Merkle tree out of hashed rows
-        MerkleTree::new(row_hashes).expect("failed to construct trace Merkle tree")
+        // build vector commitment out of hashed rows
+        V::new(row_hashes).expect("failed to construct trace vector commitment")
     }
 
     // CONVERSIONS
@@ -5,12 +5,23 @@
 
 //! Two-dimensional data structures used to represent polynomials and polynomial evaluations.
 
+// CONSTANTS
+// ================================================================================================
+
+/// Number of rows passed to a hash function at once when committing to rows of a matrix; this
+/// allows hash functions which can hash many rows at once (e.g., using SIMD instructions) to do so.
+const ROW_HASH_BATCH_SIZE: usize = 16;
+
 mod row_matrix;
 pub use row_matrix::{build_segments, get_evaluation_offsets, RowMatrix};
 
 mod col_matrix;
 pub use col_matrix::{ColMatrix, ColumnIter, MultiColumnIter};
 
+mod serialization;
+pub use serialization::ElementEncoding;
+pub(crate) use serialization::{decode_hex, encode_hex};
+
 mod segments;
 pub use segments::Segment;
 
@@ -3,10 +3,10 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{ColMatrix, Segment};
+use super::{ColMatrix, Segment, ROW_HASH_BATCH_SIZE};
 use crate::StarkDomain;
 use alloc::vec::Vec;
-use crypto::{ElementHasher, MerkleTree};
+use crypto::{ElementHasher, VectorCommitment, VectorCommitmentOptions};
 use math::{fft, FieldElement, StarkField};
 use utils::{batch_iter_mut, flatten_vector_elements, uninit_vector};
 
@@ -175,30 +175,46 @@ impl<E: FieldElement> RowMatrix<E> {
     ///
     /// The commitment is built as follows:
     /// * Each row of the matrix is hashed into a single digest of the specified hash function.
-    /// * The resulting values are used to build a binary Merkle tree such that each row digest
-    ///   becomes a leaf in the tree. Thus, the number of leaves in the tree is equal to the
-    ///   number of rows in the matrix.
-    /// * The resulting Merkle tree is returned as the commitment to the entire matrix.
-    pub fn commit_to_rows<H>(&self) -> MerkleTree<H>
+    ///   Rows wider than `partition_size` elements are hashed in partitions of `partition_size`
+    ///   elements, and the partition digests are merged into a single digest; a partition size of
+    ///   zero means that rows are not partitioned.
+    /// * The resulting values are used to build a vector commitment `V` with the specified
+    ///   `options` such that each row digest becomes an item of the committed vector. For a
+    ///   Merkle tree, this means that each row digest becomes a leaf in a tree of the specified
+    ///   arity, and the tree commits to its cap at the specified cap height (rounded down to the
+    ///   nearest level of the tree).
+    /// * The resulting vector commitment is returned as the commitment to the entire matrix.
+    ///
+    /// # Panics
+    /// Panics if the vector commitment could not be built with the specified `options` (e.g.,
+    /// for a Merkle tree, if the arity is not a power of two between 2 and 16, or if the cap
+    /// height is greater than or equal to the depth of the resulting tree).
+    pub fn commit_to_rows<H, V>(&self, partition_size: usize, options: VectorCommitmentOptions) -> V
     where
         H: ElementHasher<BaseField = E::BaseField>,
+        V: VectorCommitment<H>,
     {
         // allocate vector to store row hashes
         let mut row_hashes = unsafe { uninit_vector::<H::Digest>(self.num_rows()) };
 
-        // iterate though matrix rows, hashing each row
+        // iterate though matrix rows, hashing ROW_HASH_BATCH_SIZE rows at a time
         batch_iter_mut!(
             &mut row_hashes,
             128, // min batch size
             |batch: &mut [H::Digest], batch_offset: usize| {
-                for (i, row_hash) in batch.iter_mut().enumerate() {
-                    *row_hash = H::hash_elements(self.row(batch_offset + i));
+                let mut rows: [&[E]; ROW_HASH_BATCH_SIZE] = [&[]; ROW_HASH_BATCH_SIZE];
+                for (i, hashes) in batch.chunks_mut(ROW_HASH_BATCH_SIZE).enumerate() {
+                    let row_offset = batch_offset + i * ROW_HASH_BATCH_SIZE;
+                    for (j, row) in rows.iter_mut().take(hashes.len()).enumerate() {
+                        *row = self.row(row_offset + j);
+                    }
+                    H::hash_rows_in_partitions(&rows[..hashes.len()], partition_size, hashes);
                 }
             }
         );
 
-        // build Merkle tree out of hashed rows
-        MerkleTree::new(row_hashes).expect("failed to construct trace Merkle tree")
+        // build vector commitment out of hashed rows
+        V::with_options(row_hashes, options).expect("failed to construct trace vector commitment")
     }
 }
 
@@ -0,0 +1,308 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::ColMatrix;
+use air::TraceInfo;
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Write;
+use math::{FieldElement, StarkField};
+use utils::{
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader,
+};
+
+// ELEMENT ENCODING
+// ================================================================================================
+
+/// Defines how field elements are encoded in text-based (e.g., CSV) representations of a matrix.
+///
+/// Elements of extension fields are encoded as a sequence of their base field components, with
+/// each component occupying a separate value in the output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ElementEncoding {
+    /// Each base field element is written as a decimal number in its canonical integer
+    /// representation.
+    #[default]
+    Decimal,
+    /// Each base field element is written as a `0x`-prefixed hexadecimal string of the bytes
+    /// produced by the field's [Serializable] implementation.
+    Hex,
+}
+
+impl ElementEncoding {
+    /// Writes the specified base field element into the `target` string using this encoding.
+    pub(crate) fn write_element<E: FieldElement>(&self, element: E, target: &mut String) {
+        match self {
+            Self::Decimal => write!(target, "{element}").expect("failed to write element"),
+            Self::Hex => {
+                target.push_str("0x");
+                target.push_str(&encode_hex(&element.to_bytes()));
+            }
+        }
+    }
+
+    /// Parses a base field element from the specified string using this encoding.
+    pub(crate) fn read_element<E: FieldElement>(
+        &self,
+        source: &str,
+    ) -> Result<E, DeserializationError> {
+        let source = source.trim();
+        match self {
+            Self::Decimal => {
+                let value = source.parse::<u128>().map_err(|_| {
+                    DeserializationError::InvalidValue(format!(
+                        "'{source}' is not a valid decimal field element"
+                    ))
+                })?;
+                E::try_from(value).map_err(|_| {
+                    DeserializationError::InvalidValue(format!(
+                        "value {value} is not a valid field element"
+                    ))
+                })
+            }
+            Self::Hex => {
+                let bytes = decode_hex(source.strip_prefix("0x").unwrap_or(source))?;
+                let mut reader = SliceReader::new(&bytes);
+                let element = E::read_from(&mut reader)?;
+                if reader.has_more_bytes() {
+                    return Err(DeserializationError::InvalidValue(format!(
+                        "'{source}' contains more bytes than a single field element"
+                    )));
+                }
+                Ok(element)
+            }
+        }
+    }
+}
+
+// CSV SERIALIZATION
+// ================================================================================================
+
+impl<E: FieldElement> ColMatrix<E> {
+    /// Returns a CSV representation of this matrix with values encoded using the specified
+    /// encoding.
+    ///
+    /// The first line of the output is a header containing column names: for matrices over prime
+    /// fields these are `c0`, `c1` etc.; for matrices over extension fields, each column is split
+    /// into its base field components named `c0.0`, `c0.1` etc. Each subsequent line contains a
+    /// single row of the matrix.
+    pub fn to_csv(&self, encoding: ElementEncoding) -> String {
+        let mut result = String::new();
+        result.push_str(&csv_header::<E>(self.num_cols()));
+        result.push('\n');
+
+        let mut row = vec![E::ZERO; self.num_cols()];
+        for row_idx in 0..self.num_rows() {
+            self.read_row_into(row_idx, &mut row);
+            for (i, &value) in E::slice_as_base_elements(&row).iter().enumerate() {
+                if i > 0 {
+                    result.push(',');
+                }
+                encoding.write_element(value, &mut result);
+            }
+            result.push('\n');
+        }
+
+        result
+    }
+
+    /// Parses a matrix from a CSV representation with values encoded using the specified encoding.
+    ///
+    /// The input is expected to have the same format as the output of [ColMatrix::to_csv()]:
+    /// the first line (not counting empty lines and lines starting with `#`) is treated as a
+    /// header, and is used only to determine the number of columns in the matrix.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The input does not contain a header, or the number of values in the header is not
+    ///   divisible by the extension degree of `E`.
+    /// * Any of the rows contains a different number of values than the header.
+    /// * Any of the values is not a valid field element for the specified encoding.
+    /// * The number of columns is greater than [TraceInfo::MAX_TRACE_WIDTH].
+    /// * The number of rows is smaller than 2, is not a power of two, or is greater than
+    ///   2^TWO_ADICITY of the base field.
+    pub fn from_csv(source: &str, encoding: ElementEncoding) -> Result<Self, DeserializationError> {
+        let mut lines = source
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'));
+
+        let header = lines.next().ok_or_else(|| {
+            DeserializationError::InvalidValue("CSV input does not contain a header".to_string())
+        })?;
+        let num_values = header.split(',').count();
+        if num_values % E::EXTENSION_DEGREE != 0 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of values in a row must be divisible by {}, but was {}",
+                E::EXTENSION_DEGREE,
+                num_values
+            )));
+        }
+        let num_cols = num_values / E::EXTENSION_DEGREE;
+        if num_cols > TraceInfo::MAX_TRACE_WIDTH {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of columns in a matrix cannot be greater than {}, but was {}",
+                TraceInfo::MAX_TRACE_WIDTH,
+                num_cols
+            )));
+        }
+
+        let mut columns: Vec<Vec<E>> = (0..num_cols).map(|_| Vec::new()).collect();
+        let mut base_row = Vec::with_capacity(num_values);
+        for (row_idx, line) in lines.enumerate() {
+            base_row.clear();
+            for value in line.split(',') {
+                base_row.push(encoding.read_element::<E::BaseField>(value)?);
+            }
+            if base_row.len() != num_values {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "row {} must contain {} values, but contained {}",
+                    row_idx,
+                    num_values,
+                    base_row.len()
+                )));
+            }
+            for (column, &value) in columns.iter_mut().zip(E::slice_from_base_elements(&base_row)) {
+                column.push(value);
+            }
+        }
+
+        validate_columns(&columns)?;
+        Ok(Self::new(columns))
+    }
+}
+
+// BINARY SERIALIZATION
+// ================================================================================================
+
+impl<E: FieldElement> Serializable for ColMatrix<E> {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    ///
+    /// The matrix is written as the number of columns and rows followed by all elements of the
+    /// matrix in column-major order; elements are encoded using their [Serializable]
+    /// implementation.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_usize(self.num_cols());
+        target.write_usize(self.num_rows());
+        for column in self.columns() {
+            target.write_many(column);
+        }
+    }
+}
+
+impl<E: FieldElement> Deserializable for ColMatrix<E> {
+    /// Reads a column-major matrix from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid matrix could not be read from the specified `source`. Matrix
+    /// dimensions are validated against the same bounds as in [ColMatrix::from_csv()], and
+    /// against the amount of data remaining in the `source`, before any memory is allocated.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let num_cols = source.read_usize()?;
+        let num_rows = source.read_usize()?;
+        validate_dimensions::<E>(num_cols, num_rows)?;
+
+        // the bounds above guarantee that this cannot overflow
+        source.check_eor(num_cols * num_rows * E::ELEMENT_BYTES)?;
+
+        let mut columns = Vec::with_capacity(num_cols);
+        for _ in 0..num_cols {
+            columns.push(source.read_many(num_rows)?);
+        }
+        Ok(Self::new(columns))
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns a CSV header for a matrix with the specified number of columns.
+fn csv_header<E: FieldElement>(num_cols: usize) -> String {
+    let mut header = String::new();
+    for col_idx in 0..num_cols {
+        if col_idx > 0 {
+            header.push(',');
+        }
+        if E::EXTENSION_DEGREE == 1 {
+            write!(header, "c{col_idx}").expect("failed to write header");
+        } else {
+            for i in 0..E::EXTENSION_DEGREE {
+                if i > 0 {
+                    header.push(',');
+                }
+                write!(header, "c{col_idx}.{i}").expect("failed to write header");
+            }
+        }
+    }
+    header
+}
+
+/// Makes sure the provided columns can be used to instantiate a [ColMatrix].
+fn validate_columns<E: FieldElement>(columns: &[Vec<E>]) -> Result<(), DeserializationError> {
+    let num_rows = columns.first().map_or(0, |column| column.len());
+    validate_dimensions::<E>(columns.len(), num_rows)
+}
+
+/// Makes sure a [ColMatrix] with the specified dimensions can be built from untrusted input.
+fn validate_dimensions<E: FieldElement>(
+    num_cols: usize,
+    num_rows: usize,
+) -> Result<(), DeserializationError> {
+    if num_cols == 0 {
+        return Err(DeserializationError::InvalidValue(
+            "a matrix must contain at least one column".to_string(),
+        ));
+    }
+    if num_cols > TraceInfo::MAX_TRACE_WIDTH {
+        return Err(DeserializationError::InvalidValue(format!(
+            "number of columns in a matrix cannot be greater than {}, but was {}",
+            TraceInfo::MAX_TRACE_WIDTH,
+            num_cols
+        )));
+    }
+    if num_rows <= 1 || !num_rows.is_power_of_two() {
+        return Err(DeserializationError::InvalidValue(format!(
+            "number of rows in a matrix must be a power of two greater than one, but was {num_rows}"
+        )));
+    }
+    if num_rows.ilog2() > E::BaseField::TWO_ADICITY {
+        return Err(DeserializationError::InvalidValue(format!(
+            "number of rows in a matrix cannot be greater than 2^{}, but was {}",
+            E::BaseField::TWO_ADICITY,
+            num_rows
+        )));
+    }
+    Ok(())
+}
+
+/// Decodes a string of hexadecimal digits into a vector of bytes.
+pub(crate) fn decode_hex(source: &str) -> Result<Vec<u8>, DeserializationError> {
+    if source.len() % 2 != 0 || !source.is_ascii() {
+        return Err(DeserializationError::InvalidValue(format!(
+            "'{source}' is not a valid hexadecimal string"
+        )));
+    }
+    (0..source.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&source[i..i + 2], 16).map_err(|_| {
+                DeserializationError::InvalidValue(format!(
+                    "'{source}' is not a valid hexadecimal string"
+                ))
+            })
+        })
+        .collect()
+}
+
+/// Encodes a slice of bytes as a string of hexadecimal digits.
+pub(crate) fn encode_hex(bytes: &[u8]) -> String {
+    let mut result = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        write!(result, "{byte:02x}").expect("failed to write byte");
+    }
+    result
+}
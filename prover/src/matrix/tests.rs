@@ -4,8 +4,12 @@
 // LICENSE file in the root directory of this source tree.
 
 use crate::{
-    math::{fields::f64::BaseElement, get_power_series, polynom, StarkField},
-    ColMatrix, RowMatrix,
+    math::{
+        fields::{f64::BaseElement, QuadExtension},
+        get_power_series, polynom, StarkField,
+    },
+    matrix::ElementEncoding,
+    ByteWriter, ColMatrix, Deserializable, RowMatrix, Serializable, TraceInfo,
 };
 use alloc::vec::Vec;
 use rand_utils::rand_vector;
@@ -39,6 +43,57 @@ fn test_eval_poly_with_offset_matrix() {
     }
 }
 
+#[test]
+fn col_matrix_csv_serialization() {
+    type QuadElement = QuadExtension<BaseElement>;
+    let columns: Vec<Vec<QuadElement>> = (0..3).map(|_| rand_vector(16)).collect();
+    let matrix = ColMatrix::new(columns.clone());
+
+    for encoding in [ElementEncoding::Decimal, ElementEncoding::Hex] {
+        let csv = matrix.to_csv(encoding);
+        assert!(csv.starts_with("c0.0,c0.1,c1.0,c1.1,c2.0,c2.1\n"));
+        assert_eq!(17, csv.lines().count());
+
+        let parsed = ColMatrix::<QuadElement>::from_csv(&csv, encoding).unwrap();
+        assert_eq!(columns, parsed.into_columns());
+    }
+
+    // rows with a wrong number of values are rejected
+    let csv = "c0,c1\n1,2\n3\n";
+    assert!(ColMatrix::<BaseElement>::from_csv(csv, ElementEncoding::Decimal).is_err());
+
+    // values which are not valid field elements are rejected
+    let csv = "c0\n1\n18446744073709551615\n";
+    assert!(ColMatrix::<BaseElement>::from_csv(csv, ElementEncoding::Decimal).is_err());
+}
+
+#[test]
+fn col_matrix_binary_serialization() {
+    let columns: Vec<Vec<BaseElement>> = (0..4).map(|_| rand_vector(32)).collect();
+    let matrix = ColMatrix::new(columns.clone());
+
+    let bytes = matrix.to_bytes();
+    let parsed = ColMatrix::<BaseElement>::read_from_bytes(&bytes).unwrap();
+    assert_eq!(columns, parsed.into_columns());
+
+    // dimensions which are too large, or are not backed by enough data, are rejected
+    let mut bytes = Vec::new();
+    bytes.write_usize(TraceInfo::MAX_TRACE_WIDTH + 1);
+    bytes.write_usize(32);
+    assert!(ColMatrix::<BaseElement>::read_from_bytes(&bytes).is_err());
+
+    let mut bytes = Vec::new();
+    bytes.write_usize(1);
+    bytes.write_usize(1 << (BaseElement::TWO_ADICITY + 1));
+    assert!(ColMatrix::<BaseElement>::read_from_bytes(&bytes).is_err());
+
+    let mut bytes = Vec::new();
+    bytes.write_usize(255);
+    bytes.write_usize(1 << 30);
+    bytes.write_many(&columns[0]);
+    assert!(ColMatrix::<BaseElement>::read_from_bytes(&bytes).is_err());
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
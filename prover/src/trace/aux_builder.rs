@@ -0,0 +1,169 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::ColMatrix;
+use alloc::vec::Vec;
+use core::{fmt, marker::PhantomData};
+use math::{FieldElement, StarkField};
+
+// AUXILIARY TRACE BUILDER
+// ================================================================================================
+/// Defines a procedure for building auxiliary segments of a [TraceTable](super::TraceTable).
+///
+/// Auxiliary trace segments are built over the extension field selected for proof generation,
+/// and thus, the builder must be able to work with any extension of the base field `B`. For this
+/// reason, the builder is defined as a trait with a generic method rather than as a closure.
+/// Since this makes the trait unusable as a trait object, builders which only need to work with
+/// a single extension field can instead be defined by a closure via [FnAuxTraceBuilder].
+///
+/// Auxiliary segments can be built in parallel by breaking them into fragments via
+/// [segment_fragments()](super::segment_fragments) function.
+pub trait AuxTraceBuilder<B: StarkField> {
+    /// Builds and returns the next auxiliary trace segment.
+    ///
+    /// The parameters are as follows:
+    /// - `main_segment` is the main segment of the execution trace.
+    /// - `aux_segments` contains a list of auxiliary trace segments built as a result of prior
+    ///   invocations of this method. Thus, on the first invocation, `aux_segments` will be empty;
+    ///   on the second invocation, it will contain a single matrix etc.
+    /// - `rand_elements` contains random elements drawn for the segment being built.
+    ///
+    /// The returned segment must have the same number of rows as the main segment, and the
+    /// number of columns specified for this segment in the trace layout.
+    fn build_aux_segment<E>(
+        &self,
+        main_segment: &ColMatrix<B>,
+        aux_segments: &[ColMatrix<E>],
+        rand_elements: &[E],
+    ) -> ColMatrix<E>
+    where
+        E: FieldElement<BaseField = B>;
+}
+
+/// An [AuxTraceBuilder] for execution traces which do not have auxiliary segments.
+///
+/// This is the default auxiliary trace builder of [TraceTable](super::TraceTable). It is never
+/// invoked, since the layout of such traces does not contain any auxiliary segments.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoAuxTraceBuilder;
+
+impl<B: StarkField> AuxTraceBuilder<B> for NoAuxTraceBuilder {
+    fn build_aux_segment<E>(
+        &self,
+        _main_segment: &ColMatrix<B>,
+        _aux_segments: &[ColMatrix<E>],
+        _rand_elements: &[E],
+    ) -> ColMatrix<E>
+    where
+        E: FieldElement<BaseField = B>,
+    {
+        unreachable!("execution trace does not have auxiliary segments")
+    }
+}
+
+// CLOSURE-BASED BUILDER
+// ================================================================================================
+
+/// An [AuxTraceBuilder] which builds auxiliary segments over a fixed extension field `E` by
+/// invoking a closure.
+///
+/// The closure receives the same parameters as [AuxTraceBuilder::build_aux_segment()]. Unlike
+/// implementations of [AuxTraceBuilder], the closure can be stored as a trait object (e.g., as
+/// `Box<dyn Fn(&ColMatrix<B>, &[ColMatrix<E>], &[E]) -> ColMatrix<E>>`), which allows choosing
+/// the builder at runtime and using traces with different builders as the same type.
+///
+/// The prover must generate proofs using the field extension of the same degree as `E`. Since
+/// the prover may instantiate the builder with a different type for the same extension, segments
+/// and random elements are copied between the two types via their base field representations.
+///
+/// # Panics
+/// Building a segment panics if the extension degree of the field selected for proof generation
+/// is different from the extension degree of `E`.
+pub struct FnAuxTraceBuilder<E, F> {
+    build_fn: F,
+    _field: PhantomData<E>,
+}
+
+impl<E, F> FnAuxTraceBuilder<E, F>
+where
+    E: FieldElement,
+    F: Fn(&ColMatrix<E::BaseField>, &[ColMatrix<E>], &[E]) -> ColMatrix<E>,
+{
+    /// Returns a new auxiliary trace builder which builds segments by invoking `build_fn`.
+    pub fn new(build_fn: F) -> Self {
+        Self {
+            build_fn,
+            _field: PhantomData,
+        }
+    }
+}
+
+impl<B, E, F> AuxTraceBuilder<B> for FnAuxTraceBuilder<E, F>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+    F: Fn(&ColMatrix<B>, &[ColMatrix<E>], &[E]) -> ColMatrix<E>,
+{
+    fn build_aux_segment<P>(
+        &self,
+        main_segment: &ColMatrix<B>,
+        aux_segments: &[ColMatrix<P>],
+        rand_elements: &[P],
+    ) -> ColMatrix<P>
+    where
+        P: FieldElement<BaseField = B>,
+    {
+        assert_eq!(
+            P::EXTENSION_DEGREE,
+            E::EXTENSION_DEGREE,
+            "auxiliary trace builder expects field extension of degree {}, but was {}",
+            E::EXTENSION_DEGREE,
+            P::EXTENSION_DEGREE
+        );
+
+        let aux_segments = aux_segments.iter().map(convert_matrix).collect::<Vec<_>>();
+        let rand_elements = convert_elements(rand_elements);
+        let aux_segment = (self.build_fn)(main_segment, &aux_segments, &rand_elements);
+        convert_matrix(&aux_segment)
+    }
+}
+
+impl<E, F: Clone> Clone for FnAuxTraceBuilder<E, F> {
+    fn clone(&self) -> Self {
+        Self {
+            build_fn: self.build_fn.clone(),
+            _field: PhantomData,
+        }
+    }
+}
+
+impl<E, F> fmt::Debug for FnAuxTraceBuilder<E, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FnAuxTraceBuilder").finish_non_exhaustive()
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Converts elements of field `S` into elements of field `T`, where both fields are extensions of
+/// the same degree over the same base field.
+fn convert_elements<S, T>(elements: &[S]) -> Vec<T>
+where
+    S: FieldElement,
+    T: FieldElement<BaseField = S::BaseField>,
+{
+    T::slice_from_base_elements(S::slice_as_base_elements(elements)).to_vec()
+}
+
+/// Converts a matrix over field `S` into a matrix over field `T`, where both fields are
+/// extensions of the same degree over the same base field.
+fn convert_matrix<S, T>(matrix: &ColMatrix<S>) -> ColMatrix<T>
+where
+    S: FieldElement,
+    T: FieldElement<BaseField = S::BaseField>,
+{
+    ColMatrix::new(matrix.columns().map(convert_elements).collect())
+}
@@ -13,8 +13,13 @@ pub use trace_lde::{DefaultTraceLde, TraceLde};
 mod poly_table;
 pub use poly_table::TracePolyTable;
 
+mod aux_builder;
+pub use aux_builder::{AuxTraceBuilder, FnAuxTraceBuilder, NoAuxTraceBuilder};
+
 mod trace_table;
-pub use trace_table::{TraceTable, TraceTableFragment};
+pub use trace_table::{
+    segment_fragments, TraceColumnGroup, TraceTable, TraceTableBuilder, TraceTableFragment,
+};
 
 #[cfg(test)]
 mod tests;
@@ -3,9 +3,18 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::{tests::build_fib_trace, Trace};
-use alloc::vec::Vec;
-use math::fields::f128::BaseElement;
+use crate::{
+    matrix::{ColMatrix, ElementEncoding},
+    segment_fragments,
+    tests::build_fib_trace,
+    AuxTraceBuilder, Deserializable, FnAuxTraceBuilder, Serializable, Trace, TraceLayout,
+    TraceTable, TraceTableBuilder,
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+use math::{fields::f128::BaseElement, fields::QuadExtension, FieldElement, StarkField};
+
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
 
 #[test]
 fn new_trace_table() {
@@ -27,3 +36,211 @@ fn new_trace_table() {
         .collect();
     assert_eq!(expected, trace.get_column(1));
 }
+
+#[test]
+fn fill_trace_table_rows() {
+    let mut trace = TraceTable::new(2, 16);
+    trace.fill_rows(|i, row| {
+        row[0] = BaseElement::from(i as u32);
+        row[1] = BaseElement::from((i * i) as u32);
+    });
+
+    let expected: Vec<BaseElement> = (0..16u32).map(BaseElement::from).collect();
+    assert_eq!(expected, trace.get_column(0));
+    let expected: Vec<BaseElement> = (0..16u32).map(|i| BaseElement::from(i * i)).collect();
+    assert_eq!(expected, trace.get_column(1));
+}
+
+#[test]
+fn fill_trace_table_chunks() {
+    let mut trace = TraceTable::new(1, 16);
+    trace.fill_chunks(
+        4,
+        |chunk_idx, state| state[0] = BaseElement::from(chunk_idx as u32 * 10),
+        |_, _, state| state[0] += BaseElement::ONE,
+    );
+
+    let expected: Vec<BaseElement> =
+        (0..16u32).map(|i| BaseElement::from((i / 4) * 10 + i % 4)).collect();
+    assert_eq!(expected, trace.get_column(0));
+}
+
+#[test]
+fn fill_trace_table_column_groups() {
+    let mut trace = TraceTable::<BaseElement>::new(3, 8);
+    trace.column_groups(&[1, 2]).for_each(|mut group| {
+        let offset = group.offset() as u32;
+        group.fill(
+            |state| state.iter_mut().for_each(|v| *v = BaseElement::from(offset)),
+            |_, state| state.iter_mut().for_each(|v| *v += BaseElement::ONE),
+        );
+    });
+
+    let expected: Vec<BaseElement> = (0..8u32).map(BaseElement::from).collect();
+    assert_eq!(expected, trace.get_column(0));
+    let expected: Vec<BaseElement> = (1..9u32).map(BaseElement::from).collect();
+    assert_eq!(expected, trace.get_column(1));
+    assert_eq!(expected, trace.get_column(2));
+}
+
+#[test]
+fn trace_table_from_row_major() {
+    let data: Vec<BaseElement> = (0..24u32).map(BaseElement::from).collect();
+    let trace = TraceTable::from_row_major(&data, 3);
+
+    assert_eq!(3, trace.main_trace_width());
+    assert_eq!(8, trace.length());
+    for col_idx in 0..3 {
+        let expected: Vec<BaseElement> =
+            (0..8u32).map(|i| BaseElement::from(i * 3 + col_idx as u32)).collect();
+        assert_eq!(expected, trace.get_column(col_idx));
+    }
+}
+
+#[test]
+fn build_trace_table_incrementally() {
+    let mut builder = TraceTableBuilder::new(2);
+    for i in 0..10u32 {
+        builder.push_row(&[BaseElement::from(i), BaseElement::from(2 * i)]);
+    }
+    assert_eq!(10, builder.num_rows());
+
+    // padding with copies of the last row
+    let trace = builder.clone().build();
+    assert_eq!(16, trace.length());
+    let mut expected: Vec<BaseElement> = (0..10u32).map(BaseElement::from).collect();
+    expected.resize(16, BaseElement::from(9u32));
+    assert_eq!(expected, trace.get_column(0));
+
+    // padding with a custom closure
+    let trace = builder.build_with_padding(|_, state| state[1] += BaseElement::ONE);
+    assert_eq!(16, trace.length());
+    let expected: Vec<BaseElement> = (0..10u32)
+        .map(|i| BaseElement::from(2 * i))
+        .chain((19..25u32).map(BaseElement::from))
+        .collect();
+    assert_eq!(expected, trace.get_column(1));
+
+    // short traces are padded to the minimum trace length
+    let mut builder = TraceTableBuilder::new(1);
+    builder.push_row(&[BaseElement::ONE]);
+    assert_eq!(8, builder.build().length());
+}
+
+#[test]
+fn build_trace_table_aux_segment() {
+    let trace = build_fib_trace(16);
+    let layout = TraceLayout::new(2, [1], [1]);
+    let mut trace = trace.with_aux_builder(layout, SumAuxTraceBuilder);
+    assert_eq!(1, trace.aux_trace_width());
+
+    let rand_elements = vec![QuadExtension::<BaseElement>::from(3u32)];
+    let aux_segment = trace.build_aux_segment(&[], &rand_elements).unwrap();
+    for i in 0..trace.length() {
+        let expected = rand_elements[0] * (trace.get(0, i) + trace.get(1, i)).into();
+        assert_eq!(expected, aux_segment.get(0, i));
+    }
+
+    // there is only one auxiliary segment in the layout
+    assert!(trace.build_aux_segment(&[aux_segment], &rand_elements).is_none());
+}
+
+#[test]
+fn build_trace_table_aux_segment_with_closure() {
+    type E = QuadExtension<BaseElement>;
+    type BuildFn = dyn Fn(&ColMatrix<BaseElement>, &[ColMatrix<E>], &[E]) -> ColMatrix<E>;
+
+    // the builder is fixed over the quadratic extension, and thus, can be boxed
+    let build_fn: Box<BuildFn> = Box::new(|main_segment, _aux_segments, rand_elements| {
+        let column = (0..main_segment.num_rows())
+            .map(|i| rand_elements[0] * (main_segment.get(0, i) + main_segment.get(1, i)).into())
+            .collect();
+        ColMatrix::new(vec![column])
+    });
+    let layout = TraceLayout::new(2, [1], [1]);
+    let mut trace = build_fib_trace(16).with_aux_builder(layout, FnAuxTraceBuilder::new(build_fn));
+
+    // segments built by the closure are the same as segments built by an equivalent builder
+    let rand_elements = vec![E::from(3u32)];
+    let aux_segment = trace.build_aux_segment(&[], &rand_elements).unwrap();
+    let expected = SumAuxTraceBuilder.build_aux_segment(trace.main_segment(), &[], &rand_elements);
+    assert_eq!(expected.get_column(0), aux_segment.get_column(0));
+}
+
+#[test]
+#[should_panic(expected = "auxiliary trace builder expects field extension of degree 2, but was 1")]
+fn build_trace_table_aux_segment_with_closure_wrong_extension() {
+    let builder = FnAuxTraceBuilder::new(
+        |main_segment: &ColMatrix<BaseElement>,
+         _: &[ColMatrix<QuadExtension<BaseElement>>],
+         _: &[_]| {
+            ColMatrix::new(vec![vec![QuadExtension::ZERO; main_segment.num_rows()]])
+        },
+    );
+    let layout = TraceLayout::new(2, [1], [1]);
+    let mut trace = build_fib_trace(16).with_aux_builder(layout, builder);
+    trace.build_aux_segment(&[], &[BaseElement::ONE]);
+}
+
+#[test]
+fn trace_table_serialization() {
+    let mut trace = build_fib_trace(16);
+    trace.set_meta(vec![1, 2, 3]);
+
+    // CSV serialization
+    for encoding in [ElementEncoding::Decimal, ElementEncoding::Hex] {
+        let csv = trace.to_csv(encoding);
+        assert!(csv.starts_with("# meta: 0x010203\nc0,c1\n"));
+
+        let parsed = TraceTable::<BaseElement>::from_csv(&csv, encoding).unwrap();
+        assert_eq!(trace.meta(), parsed.meta());
+        assert_eq!(trace.get_column(0), parsed.get_column(0));
+        assert_eq!(trace.get_column(1), parsed.get_column(1));
+    }
+
+    // traces produced by external tools may not have metadata
+    let csv = "c0\n1\n2\n3\n4\n5\n6\n7\n8\n";
+    let parsed = TraceTable::<BaseElement>::from_csv(csv, ElementEncoding::Decimal).unwrap();
+    assert!(parsed.meta().is_empty());
+    assert_eq!(8, parsed.length());
+
+    // traces which are too short are rejected
+    let csv = "c0\n1\n2\n3\n4\n";
+    assert!(TraceTable::<BaseElement>::from_csv(csv, ElementEncoding::Decimal).is_err());
+
+    // binary serialization
+    let bytes = trace.to_bytes();
+    let parsed = TraceTable::<BaseElement>::read_from_bytes(&bytes).unwrap();
+    assert_eq!(trace.meta(), parsed.meta());
+    assert_eq!(trace.get_column(0), parsed.get_column(0));
+    assert_eq!(trace.get_column(1), parsed.get_column(1));
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Builds a single auxiliary column containing sums of main trace columns multiplied by the
+/// first random element; the column is built in fragments of 4 rows.
+struct SumAuxTraceBuilder;
+
+impl<B: StarkField> AuxTraceBuilder<B> for SumAuxTraceBuilder {
+    fn build_aux_segment<E>(
+        &self,
+        main_segment: &ColMatrix<B>,
+        _aux_segments: &[ColMatrix<E>],
+        rand_elements: &[E],
+    ) -> ColMatrix<E>
+    where
+        E: FieldElement<BaseField = B>,
+    {
+        let mut aux_segment = ColMatrix::new(vec![vec![E::ZERO; main_segment.num_rows()]]);
+        segment_fragments(&mut aux_segment, 4).for_each(|mut fragment| {
+            let offset = fragment.offset();
+            fragment.fill_rows(|i, row| {
+                let sum = main_segment.get(0, offset + i) + main_segment.get(1, offset + i);
+                row[0] = rand_elements[0].mul_base(sum);
+            });
+        });
+        aux_segment
+    }
+}
@@ -9,7 +9,8 @@ use super::{
 };
 use crate::{RowMatrix, DEFAULT_SEGMENT_WIDTH};
 use alloc::vec::Vec;
-use crypto::MerkleTree;
+use core::marker::PhantomData;
+use crypto::{MerkleTree, VectorCommitment};
 use tracing::info_span;
 
 #[cfg(test)]
@@ -25,20 +26,35 @@ mod tests;
 ///   will always be elements in the base field (even when an extension field is used).
 /// - Auxiliary segments: a list of 0 or more segments for traces generated after the prover
 ///   commits to the first trace segment. Currently, at most 1 auxiliary segment is possible.
-pub struct DefaultTraceLde<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
+///
+/// Commitments to the segments are built using the vector commitment scheme `V`, which defaults
+/// to [MerkleTree].
+pub struct DefaultTraceLde<
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H> = MerkleTree<H>,
+> {
     // low-degree extension of the main segment of the trace
     main_segment_lde: RowMatrix<E::BaseField>,
     // commitment to the main segment of the trace
-    main_segment_tree: MerkleTree<H>,
+    main_segment_tree: V,
     // low-degree extensions of the auxiliary segments of the trace
     aux_segment_ldes: Vec<RowMatrix<E>>,
     // commitment to the auxiliary segments of the trace
-    aux_segment_trees: Vec<MerkleTree<H>>,
+    aux_segment_trees: Vec<V>,
     blowup: usize,
     trace_info: TraceInfo,
+    // the hasher is used only through the vector commitment; a function pointer marker keeps the
+    // struct `Sync` regardless of the hasher type
+    _hasher: PhantomData<fn() -> H>,
 }
 
-impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> DefaultTraceLde<E, H> {
+impl<E, H, V> DefaultTraceLde<E, H, V>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H>,
+{
     /// Takes the main trace segment columns as input, interpolates them into polynomials in
     /// coefficient form, evaluates the polynomials over the LDE domain, commits to the
     /// polynomial evaluations, and creates a new [DefaultTraceLde] with the LDE of the main trace
@@ -51,9 +67,9 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> DefaultTraceLd
         main_trace: &ColMatrix<E::BaseField>,
         domain: &StarkDomain<E::BaseField>,
     ) -> (Self, TracePolyTable<E>) {
-        // extend the main execution trace and build a Merkle tree from the extended trace
+        // extend the main execution trace and build a commitment to the extended trace
         let (main_segment_lde, main_segment_tree, main_segment_polys) =
-            build_trace_commitment::<E, E::BaseField, H>(main_trace, domain);
+            build_trace_commitment::<E, E::BaseField, H, V>(main_trace, domain);
 
         let trace_poly_table = TracePolyTable::new(main_segment_polys);
         let trace_lde = DefaultTraceLde {
@@ -63,6 +79,7 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> DefaultTraceLd
             aux_segment_trees: Vec::new(),
             blowup: domain.trace_to_lde_blowup(),
             trace_info: trace_info.clone(),
+            _hasher: PhantomData,
         };
 
         (trace_lde, trace_poly_table)
@@ -92,17 +109,18 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> DefaultTraceLd
     }
 }
 
-impl<E, H> TraceLde<E> for DefaultTraceLde<E, H>
+impl<E, H, V> TraceLde<E> for DefaultTraceLde<E, H, V>
 where
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H> + Sync,
 {
     type HashFn = H;
+    type VC = V;
 
     /// Returns the commitment to the low-degree extension of the main trace segment.
-    fn get_main_trace_commitment(&self) -> <Self::HashFn as Hasher>::Digest {
-        let root_hash = self.main_segment_tree.root();
-        *root_hash
+    fn get_main_trace_commitment(&self) -> Vec<<Self::HashFn as Hasher>::Digest> {
+        self.main_segment_tree.commitment().to_vec()
     }
 
     /// Takes auxiliary trace segment columns as input, interpolates them into polynomials in
@@ -121,10 +139,10 @@ where
         &mut self,
         aux_trace: &ColMatrix<E>,
         domain: &StarkDomain<E::BaseField>,
-    ) -> (ColMatrix<E>, <Self::HashFn as Hasher>::Digest) {
-        // extend the auxiliary trace segment and build a Merkle tree from the extended trace
+    ) -> (ColMatrix<E>, Vec<<Self::HashFn as Hasher>::Digest>) {
+        // extend the auxiliary trace segment and build a commitment to the extended trace
         let (aux_segment_lde, aux_segment_tree, aux_segment_polys) =
-            build_trace_commitment::<E, E, H>(aux_trace, domain);
+            build_trace_commitment::<E, E, H, V>(aux_trace, domain);
 
         // check errors
         assert!(
@@ -139,10 +157,10 @@ where
 
         // save the lde and commitment
         self.aux_segment_ldes.push(aux_segment_lde);
-        let root_hash = *aux_segment_tree.root();
+        let commitment = aux_segment_tree.commitment().to_vec();
         self.aux_segment_trees.push(aux_segment_tree);
 
-        (aux_segment_polys, root_hash)
+        (aux_segment_polys, commitment)
     }
 
     /// Reads current and next rows from the main trace segment into the specified frame.
@@ -174,8 +192,8 @@ where
         frame.next_mut().copy_from_slice(segment.row(next_lde_step));
     }
 
-    /// Returns trace table rows at the specified positions along with Merkle authentication paths
-    /// from the commitment root to these rows.
+    /// Returns trace table rows at the specified positions along with opening proofs for these
+    /// rows against the segment commitments.
     fn query(&self, positions: &[usize]) -> Vec<Queries> {
         // build queries for the main trace segment
         let mut result = vec![build_segment_queries(
@@ -219,16 +237,19 @@ where
 /// polynomial of degree = trace_length - 1, and then evaluating the polynomial over the LDE
 /// domain.
 ///
-/// The trace commitment is computed by hashing each row of the extended execution trace, then
-/// building a Merkle tree from the resulting hashes.
-fn build_trace_commitment<E, F, H>(
+/// The trace commitment is computed by hashing each row of the extended execution trace (in
+/// partitions of the trace partition size specified by the domain), then building a vector
+/// commitment (e.g., a Merkle tree) from the resulting hashes using the trace commitment options
+/// specified by the domain.
+fn build_trace_commitment<E, F, H, V>(
     trace: &ColMatrix<F>,
     domain: &StarkDomain<E::BaseField>,
-) -> (RowMatrix<F>, MerkleTree<H>, ColMatrix<F>)
+) -> (RowMatrix<F>, V, ColMatrix<F>)
 where
     E: FieldElement,
     F: FieldElement<BaseField = E::BaseField>,
     H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H>,
 {
     // extend the execution trace
     let (trace_lde, trace_polys) = {
@@ -251,31 +272,36 @@ where
 
     // build trace commitment
     let tree_depth = trace_lde.num_rows().ilog2() as usize;
-    let trace_tree = info_span!("compute_execution_trace_commitment", tree_depth)
-        .in_scope(|| trace_lde.commit_to_rows());
-    assert_eq!(trace_tree.depth(), tree_depth);
+    let trace_tree = info_span!("compute_execution_trace_commitment", tree_depth).in_scope(|| {
+        trace_lde.commit_to_rows::<H, V>(
+            domain.trace_partition_size(),
+            domain.trace_commitment_options(),
+        )
+    });
+    assert_eq!(trace_tree.domain_len(), trace_lde.num_rows());
 
     (trace_lde, trace_tree, trace_polys)
 }
 
-fn build_segment_queries<E, H>(
+fn build_segment_queries<E, H, V>(
     segment_lde: &RowMatrix<E>,
-    segment_tree: &MerkleTree<H>,
+    segment_tree: &V,
     positions: &[usize],
 ) -> Queries
 where
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H>,
 {
     // for each position, get the corresponding row from the trace segment LDE and put all these
     // rows into a single vector
     let trace_states =
         positions.iter().map(|&pos| segment_lde.row(pos).to_vec()).collect::<Vec<_>>();
 
-    // build Merkle authentication paths to the leaves specified by positions
+    // build an opening proof for the rows specified by positions
     let trace_proof = segment_tree
-        .prove_batch(positions)
-        .expect("failed to generate a Merkle proof for trace queries");
+        .open_many(positions)
+        .expect("failed to generate an opening proof for trace queries");
 
-    Queries::new(trace_proof, trace_states)
+    Queries::new::<H, E, V>(trace_proof, trace_states)
 }
@@ -95,7 +95,7 @@ fn commit_trace_table() {
     let expected_tree = MerkleTree::<Blake3>::new(hashed_states).unwrap();
 
     // compare the result
-    assert_eq!(*expected_tree.root(), trace_lde.get_main_trace_commitment())
+    assert_eq!(expected_tree.cap(), trace_lde.get_main_trace_commitment())
 }
 
 // HELPER FUNCTIONS
@@ -7,7 +7,7 @@ use super::{ColMatrix, EvaluationFrame, FieldElement, TracePolyTable};
 use crate::StarkDomain;
 use air::{proof::Queries, TraceInfo, TraceLayout};
 use alloc::vec::Vec;
-use crypto::{ElementHasher, Hasher};
+use crypto::{ElementHasher, Hasher, VectorCommitment};
 
 mod default;
 pub use default::DefaultTraceLde;
@@ -22,11 +22,18 @@ pub use default::DefaultTraceLde;
 /// - Auxiliary segments: a list of 0 or more segments for traces generated after the prover
 ///   commits to the first trace segment. Currently, at most 1 auxiliary segment is possible.
 pub trait TraceLde<E: FieldElement>: Sync {
-    /// The hash function used for building the Merkle tree commitments to trace segment LDEs.
+    /// The hash function used for building the vector commitments to trace segment LDEs.
     type HashFn: ElementHasher<BaseField = E::BaseField>;
 
+    /// The vector commitment scheme used for committing to trace segment LDEs.
+    type VC: VectorCommitment<Self::HashFn>;
+
     /// Returns the commitment to the low-degree extension of the main trace segment.
-    fn get_main_trace_commitment(&self) -> <Self::HashFn as Hasher>::Digest;
+    ///
+    /// The commitment is built from the rows of the extended trace segment (e.g., it is the cap
+    /// of a Merkle tree built from the hashed rows); the commitment options are expected to be
+    /// derived from [StarkDomain::trace_commitment_options()].
+    fn get_main_trace_commitment(&self) -> Vec<<Self::HashFn as Hasher>::Digest>;
 
     /// Takes auxiliary trace segment columns as input, interpolates them into polynomials in
     /// coefficient form, evaluates the polynomials over the LDE domain, and commits to the
@@ -44,7 +51,7 @@ pub trait TraceLde<E: FieldElement>: Sync {
         &mut self,
         aux_trace: &ColMatrix<E>,
         domain: &StarkDomain<E::BaseField>,
-    ) -> (ColMatrix<E>, <Self::HashFn as Hasher>::Digest);
+    ) -> (ColMatrix<E>, Vec<<Self::HashFn as Hasher>::Digest>);
 
     /// Reads current and next rows from the main trace segment into the specified frame.
     fn read_main_trace_frame_into(
@@ -56,8 +63,8 @@ pub trait TraceLde<E: FieldElement>: Sync {
     /// Reads current and next rows from the auxiliary trace segment into the specified frame.
     fn read_aux_trace_frame_into(&self, lde_step: usize, frame: &mut EvaluationFrame<E>);
 
-    /// Returns trace table rows at the specified positions along with Merkle authentication paths
-    /// from the commitment root to these rows.
+    /// Returns trace table rows at the specified positions along with opening proofs for these
+    /// rows against the segment commitments.
     fn query(&self, positions: &[usize]) -> Vec<Queries>;
 
     /// Returns the number of rows in the execution trace.
@@ -3,11 +3,15 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{ColMatrix, Trace};
+use super::{AuxTraceBuilder, ColMatrix, NoAuxTraceBuilder, Trace};
+use crate::matrix::{decode_hex, encode_hex, ElementEncoding};
 use air::{EvaluationFrame, TraceInfo, TraceLayout};
-use alloc::vec::Vec;
+use alloc::{string::String, vec::Vec};
 use math::{FieldElement, StarkField};
-use utils::uninit_vector;
+use utils::{
+    iter_mut, uninit_vector, ByteReader, ByteWriter, Deserializable, DeserializationError,
+    Serializable,
+};
 
 #[cfg(feature = "concurrent")]
 use utils::{iterators::*, rayon};
@@ -17,6 +21,13 @@ use utils::{iterators::*, rayon};
 
 const MIN_FRAGMENT_LENGTH: usize = 2;
 
+/// Number of rows processed by a single thread when filling the trace row-by-row via
+/// [TraceTable::fill_rows()].
+const ROW_FILL_FRAGMENT_LENGTH: usize = 1024;
+
+/// Prefix of the comment line which carries trace metadata in CSV representation of a trace.
+const CSV_META_PREFIX: &str = "# meta: 0x";
+
 // TRACE TABLE
 // ================================================================================================
 /// A concrete implementation of the [Trace] trait.
@@ -57,11 +68,37 @@ const MIN_FRAGMENT_LENGTH: usize = 2;
 /// [fill()](TraceTableFragment::fill) method to fill all fragments with data in parallel.
 /// The semantics of the fragment's [TraceTableFragment::fill()] method are identical to the
 /// semantics of the [TraceTable::fill()] method.
+///
+/// For traces in which each row can be computed independently of all other rows, the
+/// [fill_rows()](TraceTable::fill_rows) method can be used instead. Similarly, for traces which
+/// consist of independent chunks with known initial states, the
+/// [fill_chunks()](TraceTable::fill_chunks) method can be used. Lastly, groups of columns which
+/// do not depend on each other can be filled in parallel using the
+/// [column_groups()](TraceTable::column_groups) method.
+///
+/// # Other ways to build a trace
+/// A trace table can also be built from a row-major buffer via [TraceTable::from_row_major()],
+/// or incrementally, one row at a time, via [TraceTableBuilder].
+///
+/// # Import and export
+/// The main segment of a trace table can be exported to CSV via [TraceTable::to_csv()] method
+/// and imported back via [TraceTable::from_csv()] function. Trace tables also implement
+/// [Serializable] and [Deserializable] traits which provide a compact binary representation.
+/// Both formats include trace metadata, and can be used to prove traces produced by external
+/// tools. Auxiliary segments can be exported via [ColMatrix::to_csv()] method.
+///
+/// # Auxiliary trace segments
+/// By default, a trace table consists of a single main segment. To add auxiliary segments to
+/// the trace, use [TraceTable::with_aux_builder()] method, which takes a trace layout describing
+/// the auxiliary segments and an [AuxTraceBuilder] responsible for building them. The builder
+/// is invoked by the prover once for every auxiliary segment, after the random elements for the
+/// segment have been drawn.
 #[derive(Debug, Clone)]
-pub struct TraceTable<B: StarkField> {
+pub struct TraceTable<B: StarkField, A: AuxTraceBuilder<B> = NoAuxTraceBuilder> {
     layout: TraceLayout,
     trace: ColMatrix<B>,
     meta: Vec<u8>,
+    aux_builder: A,
 }
 
 impl<B: StarkField> TraceTable<B> {
@@ -127,6 +164,7 @@ impl<B: StarkField> TraceTable<B> {
             layout: TraceLayout::new(width, [0], [0]),
             trace: ColMatrix::new(columns),
             meta,
+            aux_builder: NoAuxTraceBuilder,
         }
     }
 
@@ -168,9 +206,98 @@ impl<B: StarkField> TraceTable<B> {
             layout: TraceLayout::new(columns.len(), [0], [0]),
             trace: ColMatrix::new(columns),
             meta: vec![],
+            aux_builder: NoAuxTraceBuilder,
         }
     }
 
+    /// Creates a new execution trace from a buffer of values stored in row-major order.
+    ///
+    /// The values are written directly into the columns of the trace, and thus, no intermediate
+    /// row-major matrix is allocated. When `concurrent` feature is enabled, columns are populated
+    /// in multiple threads.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `width` is zero or greater than 255.
+    /// * Length of `data` is not divisible by `width`.
+    /// * The resulting number of rows is smaller than 8, greater than the biggest multiplicative
+    ///   subgroup in the field `B`, or is not a power of two.
+    pub fn from_row_major(data: &[B], width: usize) -> Self {
+        assert!(width > 0, "execution trace must consist of at least one column");
+        assert!(
+            width <= TraceInfo::MAX_TRACE_WIDTH,
+            "execution trace width cannot be greater than {}, but was {}",
+            TraceInfo::MAX_TRACE_WIDTH,
+            width
+        );
+        assert!(
+            data.len() % width == 0,
+            "number of values must be a multiple of trace width {}, but was {}",
+            width,
+            data.len()
+        );
+        let length = data.len() / width;
+
+        let mut columns: Vec<Vec<B>> =
+            unsafe { (0..width).map(|_| uninit_vector(length)).collect() };
+        iter_mut!(columns).enumerate().for_each(|(col_idx, column)| {
+            for (value, row) in column.iter_mut().zip(data.chunks_exact(width)) {
+                *value = row[col_idx];
+            }
+        });
+
+        Self::init(columns)
+    }
+
+    /// Parses an execution trace from a CSV representation with values encoded using the
+    /// specified encoding.
+    ///
+    /// The input is expected to have the same format as the output of [TraceTable::to_csv()]:
+    /// an optional `# meta: 0x...` line containing hex-encoded trace metadata, followed by a
+    /// header line and one line per trace row.
+    ///
+    /// # Errors
+    /// Returns an error if the input is not a valid CSV representation of an execution trace.
+    pub fn from_csv(source: &str, encoding: ElementEncoding) -> Result<Self, DeserializationError> {
+        let meta =
+            match source.lines().map(str::trim).find(|line| line.starts_with(CSV_META_PREFIX)) {
+                Some(line) => decode_hex(&line[CSV_META_PREFIX.len()..])?,
+                None => Vec::new(),
+            };
+        let trace = ColMatrix::from_csv(source, encoding)?;
+        Self::from_parts(trace, meta)
+    }
+
+    /// Converts this execution trace into a trace with auxiliary segments described by the
+    /// provided `layout`.
+    ///
+    /// The auxiliary segments will be built by the provided `aux_builder` during proof
+    /// generation.
+    ///
+    /// # Panics
+    /// Panics if the width of the main segment in `layout` is not equal to the width of this
+    /// execution trace.
+    pub fn with_aux_builder<A>(self, layout: TraceLayout, aux_builder: A) -> TraceTable<B, A>
+    where
+        A: AuxTraceBuilder<B>,
+    {
+        assert_eq!(
+            layout.main_trace_width(),
+            self.layout.main_trace_width(),
+            "main trace segment width must be {}, but was {}",
+            self.layout.main_trace_width(),
+            layout.main_trace_width()
+        );
+        TraceTable {
+            layout,
+            trace: self.trace,
+            meta: self.meta,
+            aux_builder,
+        }
+    }
+}
+
+impl<B: StarkField, A: AuxTraceBuilder<B>> TraceTable<B, A> {
     // DATA MUTATORS
     // --------------------------------------------------------------------------------------------
 
@@ -225,6 +352,53 @@ impl<B: StarkField> TraceTable<B> {
         }
     }
 
+    /// Fills all rows in the execution trace using the provided row closure.
+    ///
+    /// The closure receives two parameters:
+    /// - index of the row to be filled.
+    /// - a mutable reference to the state of the row initialized to all zeros; the contents of
+    ///   the state are copied into the row of the trace after the closure returns.
+    ///
+    /// Rows are assumed to be independent of each other, and thus, when `concurrent` feature is
+    /// enabled, the rows are filled in multiple threads.
+    pub fn fill_rows<F>(&mut self, fill_row: F)
+    where
+        F: Fn(usize, &mut [B]) + Send + Sync,
+    {
+        let fragment_length = ROW_FILL_FRAGMENT_LENGTH.min(self.length());
+        self.fragments(fragment_length).for_each(|mut fragment| {
+            let offset = fragment.offset();
+            fragment.fill_rows(|row_idx, state| fill_row(offset + row_idx, state));
+        });
+    }
+
+    /// Fills all rows in the execution trace by breaking it into independent chunks of
+    /// `chunk_length` rows each.
+    ///
+    /// The chunks are filled by executing the provided closures as follows:
+    /// - `init` closure is used to initialize the first row of each chunk; it receives the index
+    ///   of the chunk and a mutable reference to the first state of the chunk initialized to all
+    ///   zeros.
+    /// - `update` closure is used to populate all subsequent rows of each chunk; it receives the
+    ///   index of the chunk, the index of the last updated row in the context of the chunk
+    ///   (starting with 0), and a mutable reference to the last updated state.
+    ///
+    /// When `concurrent` feature is enabled, the chunks are filled in multiple threads.
+    ///
+    /// # Panics
+    /// Panics if `chunk_length` is smaller than 2, greater than the length of the trace, or is
+    /// not a power of two.
+    pub fn fill_chunks<I, U>(&mut self, chunk_length: usize, init: I, update: U)
+    where
+        I: Fn(usize, &mut [B]) + Send + Sync,
+        U: Fn(usize, usize, &mut [B]) + Send + Sync,
+    {
+        self.fragments(chunk_length).for_each(|mut fragment| {
+            let index = fragment.index();
+            fragment.fill(|state| init(index, state), |step, state| update(index, step, state));
+        });
+    }
+
     /// Updates a single row in the execution trace with provided data.
     pub fn update_row(&mut self, step: usize, state: &[B]) {
         self.trace.update_row(step, state);
@@ -246,7 +420,7 @@ impl<B: StarkField> TraceTable<B> {
         &mut self,
         fragment_length: usize,
     ) -> alloc::vec::IntoIter<TraceTableFragment<B>> {
-        self.build_fragments(fragment_length).into_iter()
+        build_fragments(&mut self.trace, fragment_length).into_iter()
     }
 
     /// Breaks the execution trace into mutable fragments.
@@ -262,43 +436,98 @@ impl<B: StarkField> TraceTable<B> {
         &mut self,
         fragment_length: usize,
     ) -> rayon::vec::IntoIter<TraceTableFragment<B>> {
-        self.build_fragments(fragment_length).into_par_iter()
+        build_fragments(&mut self.trace, fragment_length).into_par_iter()
+    }
+
+    // COLUMN GROUPS
+    // --------------------------------------------------------------------------------------------
+
+    /// Breaks the execution trace into mutable groups of columns.
+    ///
+    /// The number of columns in each group is specified by the corresponding entry in
+    /// `group_widths`. The returned groups can be used to fill independent sets of columns from
+    /// multiple threads.
+    ///
+    /// # Panics
+    /// Panics if any of the group widths is zero, or if the sum of all group widths is not equal
+    /// to the width of the trace.
+    #[cfg(not(feature = "concurrent"))]
+    pub fn column_groups(
+        &mut self,
+        group_widths: &[usize],
+    ) -> alloc::vec::IntoIter<TraceColumnGroup<'_, B>> {
+        self.build_column_groups(group_widths).into_iter()
+    }
+
+    /// Breaks the execution trace into mutable groups of columns.
+    ///
+    /// The number of columns in each group is specified by the corresponding entry in
+    /// `group_widths`. The returned groups can be used to fill independent sets of columns from
+    /// multiple threads.
+    ///
+    /// # Panics
+    /// Panics if any of the group widths is zero, or if the sum of all group widths is not equal
+    /// to the width of the trace.
+    #[cfg(feature = "concurrent")]
+    pub fn column_groups(
+        &mut self,
+        group_widths: &[usize],
+    ) -> rayon::vec::IntoIter<TraceColumnGroup<'_, B>> {
+        self.build_column_groups(group_widths).into_par_iter()
     }
 
-    /// Returns a vector of trace fragments each covering the number of steps specified by the
-    /// `fragment_length` parameter.
-    fn build_fragments(&mut self, fragment_length: usize) -> Vec<TraceTableFragment<B>> {
+    /// Returns a vector of column groups with widths specified by the `group_widths` parameter.
+    fn build_column_groups(&mut self, group_widths: &[usize]) -> Vec<TraceColumnGroup<'_, B>> {
         assert!(
-            fragment_length >= MIN_FRAGMENT_LENGTH,
-            "fragment length must be at least {MIN_FRAGMENT_LENGTH}, but was {fragment_length}"
+            group_widths.iter().all(|&width| width > 0),
+            "column group must consist of at least one column"
         );
-        assert!(
-            fragment_length <= self.length(),
-            "length of a fragment cannot exceed {}, but was {}",
-            self.length(),
-            fragment_length
+        let total_width: usize = group_widths.iter().sum();
+        assert_eq!(
+            total_width,
+            self.width(),
+            "column groups must cover all {} columns of the trace, but covered {}",
+            self.width(),
+            total_width
         );
-        assert!(fragment_length.is_power_of_two(), "fragment length must be a power of 2");
-        let num_fragments = self.length() / fragment_length;
-
-        let mut fragment_data = (0..num_fragments).map(|_| Vec::new()).collect::<Vec<_>>();
-        self.trace.columns_mut().for_each(|column| {
-            for (i, fragment) in column.chunks_mut(fragment_length).enumerate() {
-                fragment_data[i].push(fragment);
-            }
-        });
 
-        fragment_data
-            .into_iter()
+        let mut columns = self.trace.columns_mut();
+        let mut offset = 0;
+        group_widths
+            .iter()
             .enumerate()
-            .map(|(i, data)| TraceTableFragment {
-                index: i,
-                offset: i * fragment_length,
-                data,
+            .map(|(i, &width)| {
+                let group = TraceColumnGroup {
+                    index: i,
+                    offset,
+                    data: columns.by_ref().take(width).collect(),
+                };
+                offset += width;
+                group
             })
             .collect()
     }
 
+    // EXPORT
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a CSV representation of the main segment of this execution trace with values
+    /// encoded using the specified encoding.
+    ///
+    /// If the trace has metadata, the first line of the output is a `# meta: 0x...` comment
+    /// containing hex-encoded metadata. The rest of the output has the same format as the output
+    /// of [ColMatrix::to_csv()] method.
+    pub fn to_csv(&self, encoding: ElementEncoding) -> String {
+        let mut result = String::new();
+        if !self.meta.is_empty() {
+            result.push_str(CSV_META_PREFIX);
+            result.push_str(&encode_hex(&self.meta));
+            result.push('\n');
+        }
+        result.push_str(&self.trace.to_csv(encoding));
+        result
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -323,10 +552,72 @@ impl<B: StarkField> TraceTable<B> {
     }
 }
 
+// SERIALIZATION
+// ================================================================================================
+
+impl<B: StarkField> TraceTable<B> {
+    /// Returns a trace table instantiated from the provided main trace segment and metadata.
+    ///
+    /// Unlike [TraceTable::init()] this returns an error instead of panicking if the trace
+    /// dimensions or metadata are invalid.
+    fn from_parts(trace: ColMatrix<B>, meta: Vec<u8>) -> Result<Self, DeserializationError> {
+        if trace.num_cols() > TraceInfo::MAX_TRACE_WIDTH {
+            return Err(DeserializationError::InvalidValue(format!(
+                "execution trace width cannot be greater than {}, but was {}",
+                TraceInfo::MAX_TRACE_WIDTH,
+                trace.num_cols()
+            )));
+        }
+        let length = trace.num_rows();
+        if length < TraceInfo::MIN_TRACE_LENGTH || length.ilog2() > B::TWO_ADICITY {
+            return Err(DeserializationError::InvalidValue(format!(
+                "execution trace length must be between {} and 2^{}, but was {}",
+                TraceInfo::MIN_TRACE_LENGTH,
+                B::TWO_ADICITY,
+                length
+            )));
+        }
+        if meta.len() > TraceInfo::MAX_META_LENGTH {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of metadata bytes cannot be greater than {}, but was {}",
+                TraceInfo::MAX_META_LENGTH,
+                meta.len()
+            )));
+        }
+
+        let mut result = Self::init(trace.into_columns());
+        result.meta = meta;
+        Ok(result)
+    }
+}
+
+impl<B: StarkField, A: AuxTraceBuilder<B>> Serializable for TraceTable<B, A> {
+    /// Serializes the main segment and metadata of `self` and writes the resulting bytes into
+    /// the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u16(self.meta.len() as u16);
+        target.write_bytes(&self.meta);
+        self.trace.write_into(target);
+    }
+}
+
+impl<B: StarkField> Deserializable for TraceTable<B> {
+    /// Reads a trace table from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid trace table could not be read from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let meta_len = source.read_u16()? as usize;
+        let meta = source.read_vec(meta_len)?;
+        let trace = ColMatrix::read_from(source)?;
+        Self::from_parts(trace, meta)
+    }
+}
+
 // TRACE TRAIT IMPLEMENTATION
 // ================================================================================================
 
-impl<B: StarkField> Trace for TraceTable<B> {
+impl<B: StarkField, A: AuxTraceBuilder<B>> Trace for TraceTable<B, A> {
     type BaseField = B;
 
     fn layout(&self) -> &TraceLayout {
@@ -353,13 +644,28 @@ impl<B: StarkField> Trace for TraceTable<B> {
 
     fn build_aux_segment<E>(
         &mut self,
-        _aux_segments: &[ColMatrix<E>],
-        _rand_elements: &[E],
+        aux_segments: &[ColMatrix<E>],
+        rand_elements: &[E],
     ) -> Option<ColMatrix<E>>
     where
         E: FieldElement<BaseField = Self::BaseField>,
     {
-        None
+        if aux_segments.len() >= self.layout.num_aux_segments() {
+            return None;
+        }
+
+        let aux_segment =
+            self.aux_builder.build_aux_segment(&self.trace, aux_segments, rand_elements);
+        let segment_idx = aux_segments.len();
+        assert_eq!(
+            aux_segment.num_cols(),
+            self.layout.get_aux_segment_width(segment_idx),
+            "auxiliary trace segment {} must have {} columns, but had {}",
+            segment_idx,
+            self.layout.get_aux_segment_width(segment_idx),
+            aux_segment.num_cols()
+        );
+        Some(aux_segment)
     }
 }
 
@@ -371,17 +677,18 @@ impl<B: StarkField> Trace for TraceTable<B> {
 /// the fragment, directly updates the data in the underlying execution trace.
 ///
 /// A fragment cannot be instantiated directly but is created by executing
-/// [TraceTable::fragments()] method.
+/// [TraceTable::fragments()] method. Fragments of auxiliary trace segments can be created via
+/// the [segment_fragments()] function.
 ///
 /// A fragment always contains contiguous rows, and the number of rows is guaranteed to be a power
 /// of two.
-pub struct TraceTableFragment<'a, B: StarkField> {
+pub struct TraceTableFragment<'a, B: FieldElement> {
     index: usize,
     offset: usize,
     data: Vec<&'a mut [B]>,
 }
 
-impl<'a, B: StarkField> TraceTableFragment<'a, B> {
+impl<'a, B: FieldElement> TraceTableFragment<'a, B> {
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -435,6 +742,23 @@ impl<'a, B: StarkField> TraceTableFragment<'a, B> {
         }
     }
 
+    /// Fills all rows in the fragment using the provided row closure.
+    ///
+    /// The closure receives the index of the row in the context of this fragment (starting with
+    /// 0), and a mutable reference to the state of the row initialized to all zeros. Contents of
+    /// the state are copied into the row of the fragment after the closure returns.
+    pub fn fill_rows<F>(&mut self, mut fill_row: F)
+    where
+        F: FnMut(usize, &mut [B]),
+    {
+        let mut state = vec![B::ZERO; self.width()];
+        for i in 0..self.length() {
+            state.fill(B::ZERO);
+            fill_row(i, &mut state);
+            self.update_row(i, &state);
+        }
+    }
+
     /// Updates a single row in the fragment with provided data.
     pub fn update_row(&mut self, row_idx: usize, row_data: &[B]) {
         for (column, &value) in self.data.iter_mut().zip(row_data) {
@@ -442,3 +766,286 @@ impl<'a, B: StarkField> TraceTableFragment<'a, B> {
         }
     }
 }
+
+/// Breaks the provided trace segment into mutable fragments.
+///
+/// This works identically to [TraceTable::fragments()] method, but can be applied to any trace
+/// segment. This is intended to be used primarily by [AuxTraceBuilder] implementations to build
+/// auxiliary trace segments in parallel.
+///
+/// # Panics
+/// Panics if `fragment_length` is smaller than 2, greater than the length of the segment,
+/// or is not a power of two.
+#[cfg(not(feature = "concurrent"))]
+pub fn segment_fragments<E: FieldElement>(
+    segment: &mut ColMatrix<E>,
+    fragment_length: usize,
+) -> alloc::vec::IntoIter<TraceTableFragment<'_, E>> {
+    build_fragments(segment, fragment_length).into_iter()
+}
+
+/// Breaks the provided trace segment into mutable fragments.
+///
+/// This works identically to [TraceTable::fragments()] method, but can be applied to any trace
+/// segment. This is intended to be used primarily by [AuxTraceBuilder] implementations to build
+/// auxiliary trace segments in parallel.
+///
+/// # Panics
+/// Panics if `fragment_length` is smaller than 2, greater than the length of the segment,
+/// or is not a power of two.
+#[cfg(feature = "concurrent")]
+pub fn segment_fragments<E: FieldElement>(
+    segment: &mut ColMatrix<E>,
+    fragment_length: usize,
+) -> rayon::vec::IntoIter<TraceTableFragment<'_, E>> {
+    build_fragments(segment, fragment_length).into_par_iter()
+}
+
+/// Returns a vector of fragments of the provided segment each covering the number of steps
+/// specified by the `fragment_length` parameter.
+fn build_fragments<E: FieldElement>(
+    segment: &mut ColMatrix<E>,
+    fragment_length: usize,
+) -> Vec<TraceTableFragment<'_, E>> {
+    assert!(
+        fragment_length >= MIN_FRAGMENT_LENGTH,
+        "fragment length must be at least {MIN_FRAGMENT_LENGTH}, but was {fragment_length}"
+    );
+    assert!(
+        fragment_length <= segment.num_rows(),
+        "length of a fragment cannot exceed {}, but was {}",
+        segment.num_rows(),
+        fragment_length
+    );
+    assert!(fragment_length.is_power_of_two(), "fragment length must be a power of 2");
+    let num_fragments = segment.num_rows() / fragment_length;
+
+    let mut fragment_data = (0..num_fragments).map(|_| Vec::new()).collect::<Vec<_>>();
+    segment.columns_mut().for_each(|column| {
+        for (i, fragment) in column.chunks_mut(fragment_length).enumerate() {
+            fragment_data[i].push(fragment);
+        }
+    });
+
+    fragment_data
+        .into_iter()
+        .enumerate()
+        .map(|(i, data)| TraceTableFragment {
+            index: i,
+            offset: i * fragment_length,
+            data,
+        })
+        .collect()
+}
+
+// TRACE COLUMN GROUPS
+// ================================================================================================
+/// A set of consecutive columns of an execution trace.
+///
+/// A column group is a "view" into the specific execution trace. Updating data in the group,
+/// directly updates the data in the underlying execution trace.
+///
+/// A column group cannot be instantiated directly but is created by executing
+/// [TraceTable::column_groups()] method.
+///
+/// A column group always contains contiguous columns, and spans all rows of the trace.
+pub struct TraceColumnGroup<'a, B: StarkField> {
+    index: usize,
+    offset: usize,
+    data: Vec<&'a mut [B]>,
+}
+
+impl<'a, B: StarkField> TraceColumnGroup<'a, B> {
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the index of this column group.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the index of the first column of this group in the context of the original
+    /// execution trace.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the number of rows in this column group (same as the length of the underlying
+    /// execution trace).
+    pub fn length(&self) -> usize {
+        self.data[0].len()
+    }
+
+    /// Returns the number of columns in this column group.
+    pub fn width(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns a mutable reference to the column at the specified index in the context of this
+    /// group.
+    pub fn column_mut(&mut self, col_idx: usize) -> &mut [B] {
+        self.data[col_idx]
+    }
+
+    // DATA MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Fills all rows in the column group.
+    ///
+    /// The semantics of this method are identical to the semantics of the [TraceTable::fill()]
+    /// method, except that the state passed to the closures contains only the columns of this
+    /// group.
+    pub fn fill<I, U>(&mut self, init: I, mut update: U)
+    where
+        I: FnOnce(&mut [B]),
+        U: FnMut(usize, &mut [B]),
+    {
+        let mut state = vec![B::ZERO; self.width()];
+        init(&mut state);
+        self.update_row(0, &state);
+
+        for i in 0..self.length() - 1 {
+            update(i, &mut state);
+            self.update_row(i + 1, &state);
+        }
+    }
+
+    /// Updates a single row in the column group with provided data.
+    pub fn update_row(&mut self, row_idx: usize, row_data: &[B]) {
+        for (column, &value) in self.data.iter_mut().zip(row_data) {
+            column[row_idx] = value;
+        }
+    }
+}
+
+// TRACE TABLE BUILDER
+// ================================================================================================
+/// Builds a [TraceTable] by appending rows one at a time.
+///
+/// This is useful when the length of the execution trace is not known in advance. When the
+/// trace is built, it is padded to the next power of two (but no fewer than 8 rows). By default,
+/// the padding rows are copies of the last row appended to the builder; custom padding can be
+/// specified via [TraceTableBuilder::build_with_padding()] method.
+#[derive(Debug, Clone)]
+pub struct TraceTableBuilder<B: StarkField> {
+    columns: Vec<Vec<B>>,
+    meta: Vec<u8>,
+}
+
+impl<B: StarkField> TraceTableBuilder<B> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new builder for an execution trace of the specified width.
+    ///
+    /// # Panics
+    /// Panics if `width` is zero or greater than 255.
+    pub fn new(width: usize) -> Self {
+        Self::with_meta(width, vec![])
+    }
+
+    /// Returns a new builder for an execution trace of the specified width, and with the
+    /// specified metadata.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `width` is zero or greater than 255.
+    /// * Length of `meta` is greater than 65535;
+    pub fn with_meta(width: usize, meta: Vec<u8>) -> Self {
+        assert!(width > 0, "execution trace must consist of at least one column");
+        assert!(
+            width <= TraceInfo::MAX_TRACE_WIDTH,
+            "execution trace width cannot be greater than {}, but was {}",
+            TraceInfo::MAX_TRACE_WIDTH,
+            width
+        );
+        assert!(
+            meta.len() <= TraceInfo::MAX_META_LENGTH,
+            "number of metadata bytes cannot be greater than {}, but was {}",
+            TraceInfo::MAX_META_LENGTH,
+            meta.len()
+        );
+        Self {
+            columns: (0..width).map(|_| Vec::new()).collect(),
+            meta,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of columns in the trace being built.
+    pub fn width(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Returns the number of rows appended to this builder so far.
+    pub fn num_rows(&self) -> usize {
+        self.columns[0].len()
+    }
+
+    // DATA MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Appends a row to the end of the trace being built.
+    ///
+    /// # Panics
+    /// Panics if the length of `row` is not equal to the width of the trace.
+    pub fn push_row(&mut self, row: &[B]) {
+        assert_eq!(
+            row.len(),
+            self.width(),
+            "row must contain {} values, but contained {}",
+            self.width(),
+            row.len()
+        );
+        for (column, &value) in self.columns.iter_mut().zip(row) {
+            column.push(value);
+        }
+    }
+
+    // TRACE BUILDING
+    // --------------------------------------------------------------------------------------------
+
+    /// Builds the execution trace padding it with copies of the last appended row.
+    ///
+    /// # Panics
+    /// Panics if no rows have been appended to this builder, or if the padded length of the trace
+    /// is greater than the biggest multiplicative subgroup in the field `B`.
+    pub fn build(self) -> TraceTable<B> {
+        self.build_with_padding(|_, _| {})
+    }
+
+    /// Builds the execution trace using the provided closure to generate the padding rows.
+    ///
+    /// The closure receives the index of the padding row in the context of the entire trace and
+    /// a mutable reference to the state of the row. The state is initialized to the contents of
+    /// the previous row of the trace, and is copied into the trace after the closure returns.
+    ///
+    /// # Panics
+    /// Panics if no rows have been appended to this builder, or if the padded length of the trace
+    /// is greater than the biggest multiplicative subgroup in the field `B`.
+    pub fn build_with_padding<P>(mut self, mut pad_row: P) -> TraceTable<B>
+    where
+        P: FnMut(usize, &mut [B]),
+    {
+        let num_rows = self.num_rows();
+        assert!(num_rows > 0, "execution trace must contain at least one row");
+        let trace_length = num_rows.next_power_of_two().max(TraceInfo::MIN_TRACE_LENGTH);
+
+        let mut state: Vec<B> = self.columns.iter().map(|column| column[num_rows - 1]).collect();
+        for column in self.columns.iter_mut() {
+            column.reserve_exact(trace_length - num_rows);
+        }
+        for row_idx in num_rows..trace_length {
+            pad_row(row_idx, &mut state);
+            for (column, &value) in self.columns.iter_mut().zip(state.iter()) {
+                column.push(value);
+            }
+        }
+
+        let mut trace = TraceTable::init(self.columns);
+        trace.set_meta(self.meta);
+        trace
+    }
+}
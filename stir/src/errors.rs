@@ -0,0 +1,79 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::fmt;
+
+use crypto::RandomCoinError;
+
+// VERIFIER ERROR
+// ================================================================================================
+
+/// Defines errors which can occur during STIR proof verification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerifierError {
+    /// Attempt to draw a random value from a public coin failed.
+    RandomCoinError(RandomCoinError),
+    /// Evaluation domain implied by the maximum polynomial degree is too small for the protocol
+    /// to be executed with the specified options.
+    DomainTooSmall(usize),
+    /// Number of round commitments sent by the prover does not match the number of STIR rounds.
+    NumCommitmentsMismatch(usize, usize),
+    /// Number of digests in the commitment for one of the rounds is inconsistent with the Merkle
+    /// cap height specified in the options.
+    CommitmentSizeMismatch(usize, usize, usize),
+    /// Number of out-of-domain evaluations sent by the prover does not match the number of STIR
+    /// rounds.
+    NumOodEvaluationsMismatch(usize, usize),
+    /// Number of final polynomial coefficients does not match the degree expected after the last
+    /// STIR round.
+    FinalPolySizeMismatch(usize, usize),
+    /// Number of query positions does not match the number of provided evaluations.
+    NumPositionEvaluationMismatch(usize, usize),
+    /// Evaluations at queried positions did not match the commitment made by the prover.
+    LayerCommitmentMismatch,
+    /// Provided evaluations of the initial function did not match the values committed to by
+    /// the prover.
+    InitialEvaluationsMismatch,
+    /// Folded evaluations of the last committed function did not match the final polynomial.
+    InvalidFinalFolding,
+}
+
+impl fmt::Display for VerifierError {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RandomCoinError(err) => {
+                write!(f, "failed to draw a random value from the public coin: {err}")
+            }
+            Self::DomainTooSmall(size) => {
+                write!(f, "evaluation domain of size {size} is too small for the specified STIR options")
+            }
+            Self::NumCommitmentsMismatch(expected, actual) => {
+                write!(f, "expected {expected} STIR round commitments, but {actual} were provided")
+            }
+            Self::CommitmentSizeMismatch(round, expected, actual) => {
+                write!(f, "expected commitment for STIR round {round} to consist of {expected} digests, but was {actual}")
+            }
+            Self::NumOodEvaluationsMismatch(expected, actual) => {
+                write!(f, "expected {expected} out-of-domain evaluations, but {actual} were provided")
+            }
+            Self::FinalPolySizeMismatch(expected, actual) => {
+                write!(f, "expected final polynomial with {expected} coefficients, but {actual} were provided")
+            }
+            Self::NumPositionEvaluationMismatch(num_positions, num_evaluations) => write!(f,
+                "the number of query positions must be the same as the number of polynomial evaluations, but {num_positions} and {num_evaluations} were provided"
+            ),
+            Self::LayerCommitmentMismatch => {
+                write!(f, "STIR queries did not match the commitment made by the prover")
+            }
+            Self::InitialEvaluationsMismatch => {
+                write!(f, "evaluations of the initial function did not match the committed values")
+            }
+            Self::InvalidFinalFolding => {
+                write!(f, "folding of the last committed function is inconsistent with the final polynomial")
+            }
+        }
+    }
+}
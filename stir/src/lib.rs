@@ -0,0 +1,81 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! This crate contains an implementation of the STIR protocol which can be used by the Winterfell
+//! STARK prover and verifier as an alternative to FRI.
+//!
+//! STIR stands for Shift To Improve Rate, and, like FRI, it is an Interactive Oracle Proof of
+//! Proximity to Reed-Solomon codes: given a commitment to a set of evaluations of some function
+//! over domain *D*, the verifier can be convinced that the function is a polynomial of degree at
+//! most *d*, by making a small number of queries to the commitment.
+//!
+//! In every round of FRI, both the degree of the tested function and the size of its evaluation
+//! domain are reduced by the folding factor. In every round of STIR, the degree is reduced by the
+//! folding factor, but the size of the domain is reduced only by a factor of 2. Thus, the rate of
+//! the code improves from round to round, and the number of queries needed in later rounds to
+//! achieve the desired level of security decreases. For the same level of security, this usually
+//! results in smaller proofs than FRI.
+//!
+//! # Proof generation
+//! STIR proofs are generated by a [StirProver] in two steps:
+//!
+//! 1. First, the commit phase of the protocol is executed via
+//!    [build_layers()](prover::StirProver::build_layers()) function. During this phase, the prover
+//!    commits to the tested function and to the function of every STIR round, and writes the
+//!    commitments, out-of-domain evaluations, and the final polynomial into the [ProverChannel].
+//!    All random values and query indexes of the rounds are drawn from the channel as well.
+//! 2. Then, the query phase of the protocol is executed via
+//!    [build_proof()](prover::StirProver::build_proof()) function. The output of this function is
+//!    an instance of the [StirProof] struct. When STIR is executed as a part of the STARK protocol,
+//!    STIR proof is included into a STARK proof.
+//!
+//! # Proof verification
+//! STIR proofs are verified by a [StirVerifier] as follows:
+//! 1. First, a STIR proof needs to be converted into a [VerifierChannel]. This crate provides a
+//!    default implementation of the verifier channel, but when STIR proof verification is
+//!    executed as a part of the larger STARK protocol, STARK verifier handles this conversion.
+//! 2. Then, a [StirVerifier] should be instantiated (via [new()](StirVerifier::new()) function).
+//!    This will execute the commit phase of the STIR protocol from the verifier's perspective.
+//! 3. Finally, the query phase of the STIR protocol should be executed via
+//!    [verify()](StirVerifier::verify()) function. Note that evaluations of the tested function
+//!    at the queried positions are provided to the [verify()](StirVerifier::verify()) function
+//!    directly.
+//!
+//! # Protocol parameters
+//! The current implementation supports executing STIR protocol with dynamically configurable
+//! parameters including:
+//!
+//! * Base STARK field,
+//! * Extension field,
+//! * Domain blowup factor,
+//! * Hash function (used for Merkle tree commitments),
+//! * Folding factor (used for degree reduction in each STIR round),
+//! * Maximum degree of the final polynomial,
+//! * Number of queries against the tested function.
+//!
+//! # References
+//! * [STIR: Reed–Solomon Proximity Testing with Fewer Queries](https://eprint.iacr.org/2024/390)
+
+#![no_std]
+
+#[macro_use]
+extern crate alloc;
+
+mod prover;
+pub use prover::{DefaultProverChannel, ProverChannel, StirProver};
+
+mod verifier;
+pub use verifier::{DefaultVerifierChannel, StirVerifier, VerifierChannel};
+
+mod options;
+pub use options::StirOptions;
+
+mod proof;
+pub use proof::StirProof;
+
+mod errors;
+pub use errors::VerifierError;
+
+mod utils;
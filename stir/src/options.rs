@@ -0,0 +1,227 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+use math::StarkField;
+
+// STIR OPTIONS
+// ================================================================================================
+
+/// STIR protocol config options for proof generation and verification.
+#[derive(Clone, PartialEq, Eq)]
+pub struct StirOptions {
+    folding_factor: usize,
+    folding_schedule: Vec<usize>,
+    remainder_max_degree: usize,
+    blowup_factor: usize,
+    num_queries: usize,
+    merkle_cap_height: usize,
+    merkle_arity: usize,
+}
+
+impl StirOptions {
+    /// Returns a new [StirOptions] struct instantiated with the specified parameters.
+    ///
+    /// `num_queries` specifies the number of queries made against the first committed function;
+    /// the number of queries made in subsequent rounds is derived from it so that every round
+    /// provides roughly the same level of security (see
+    /// [num_round_queries()](StirOptions::num_round_queries)).
+    ///
+    /// # Panics
+    /// Panics if:
+    /// - `blowup_factor` is not a power of two or is smaller than 2.
+    /// - `folding_factor` is not 2, 4, 8, or 16.
+    /// - `remainder_max_degree` is not one less than a power of two.
+    /// - `num_queries` is zero.
+    pub fn new(
+        blowup_factor: usize,
+        folding_factor: usize,
+        remainder_max_degree: usize,
+        num_queries: usize,
+    ) -> Self {
+        assert!(
+            blowup_factor.is_power_of_two() && blowup_factor >= 2,
+            "blowup factor must be a power of two greater than 1, but was {blowup_factor}"
+        );
+        assert_folding_factor_supported(folding_factor);
+        assert!(
+            (remainder_max_degree + 1).is_power_of_two(),
+            "remainder degree must be one less than a power of two, but was {remainder_max_degree}"
+        );
+        assert!(num_queries > 0, "number of queries must be greater than zero");
+        StirOptions {
+            folding_factor,
+            folding_schedule: Vec::new(),
+            remainder_max_degree,
+            blowup_factor,
+            num_queries,
+            merkle_cap_height: 0,
+            merkle_arity: 2,
+        }
+    }
+
+    /// Updates the provided [StirOptions] instance to fold functions in the first STIR rounds
+    /// using the specified folding schedule.
+    ///
+    /// The i-th entry of the schedule specifies the folding factor for the function committed
+    /// to in the i-th round; rounds beyond the end of the schedule use the `folding_factor`
+    /// specified when the options were created.
+    ///
+    /// # Panics
+    /// Panics if any of the factors in the schedule is not 2, 4, 8, or 16.
+    pub fn with_folding_schedule(mut self, folding_schedule: Vec<usize>) -> Self {
+        for &folding_factor in folding_schedule.iter() {
+            assert_folding_factor_supported(folding_factor);
+        }
+        self.folding_schedule = folding_schedule;
+        self
+    }
+
+    /// Updates the provided [StirOptions] instance to commit to round functions using Merkle tree
+    /// caps of the specified height instead of Merkle tree roots.
+    ///
+    /// Commitments which are too small to have a cap at the specified height are made using
+    /// caps one level below the root of the tree.
+    pub fn with_merkle_cap_height(mut self, merkle_cap_height: usize) -> Self {
+        self.merkle_cap_height = merkle_cap_height;
+        self
+    }
+
+    /// Updates the provided [StirOptions] instance to commit to round functions using Merkle
+    /// trees of the specified arity.
+    ///
+    /// # Panics
+    /// Panics if `merkle_arity` is not a power of two between 2 and 16.
+    pub fn with_merkle_arity(mut self, merkle_arity: usize) -> Self {
+        assert!(
+            merkle_arity.is_power_of_two() && (2..=16).contains(&merkle_arity),
+            "Merkle tree arity {merkle_arity} is not supported"
+        );
+        self.merkle_arity = merkle_arity;
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the offset by which all evaluation domains of the protocol are shifted.
+    ///
+    /// Currently, the offset is hard-coded to be the primitive element in the field specified by
+    /// type parameter `B`.
+    pub fn domain_offset<B: StarkField>(&self) -> B {
+        B::GENERATOR
+    }
+
+    /// Returns the factor by which the degree of a function is reduced in the rounds which are
+    /// not covered by the folding schedule.
+    pub fn folding_factor(&self) -> usize {
+        self.folding_factor
+    }
+
+    /// Returns the folding factors used in the first STIR rounds.
+    ///
+    /// The schedule is empty if all rounds use the same `folding_factor`.
+    pub fn folding_schedule(&self) -> &[usize] {
+        &self.folding_schedule
+    }
+
+    /// Returns the factor by which the function committed to in the specified round is folded.
+    ///
+    /// Round 0 refers to the function over the initial evaluation domain.
+    pub fn folding_factor_at(&self, round: usize) -> usize {
+        self.folding_schedule.get(round).copied().unwrap_or(self.folding_factor)
+    }
+
+    /// Returns maximum allowed degree of the final polynomial.
+    pub fn remainder_max_degree(&self) -> usize {
+        self.remainder_max_degree
+    }
+
+    /// Returns the blowup factor of the initial evaluation domain.
+    pub fn blowup_factor(&self) -> usize {
+        self.blowup_factor
+    }
+
+    /// Returns the number of queries made against the function over the initial evaluation
+    /// domain.
+    pub fn num_queries(&self) -> usize {
+        self.num_queries
+    }
+
+    /// Returns the height of Merkle tree caps used to commit to round functions.
+    pub fn merkle_cap_height(&self) -> usize {
+        self.merkle_cap_height
+    }
+
+    /// Returns the arity of Merkle trees used to commit to round functions.
+    pub fn merkle_arity(&self) -> usize {
+        self.merkle_arity
+    }
+
+    // ROUND PARAMETERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of STIR rounds for an initial evaluation domain of the specified size.
+    ///
+    /// In every round the prover commits to one new function. Thus, a proof for a domain of the
+    /// specified size contains `num_rounds + 1` commitments: one to the function over the initial
+    /// domain, and one for each round.
+    pub fn num_rounds(&self, domain_size: usize) -> usize {
+        let mut result = 0;
+        while self.round_degree(domain_size, result + 1) > self.remainder_max_degree + 1 {
+            result += 1;
+        }
+        result
+    }
+
+    /// Returns the size of the evaluation domain of the function committed to in the specified
+    /// round.
+    ///
+    /// Domain sizes are halved with every round, while degrees are reduced by the folding factor.
+    pub fn round_domain_size(&self, domain_size: usize, round: usize) -> usize {
+        domain_size >> round
+    }
+
+    /// Returns the degree bound (i.e., the maximum degree plus one) of the function committed to
+    /// in the specified round.
+    ///
+    /// For the round following the last STIR round, this is the number of coefficients of the
+    /// final polynomial.
+    pub fn round_degree(&self, domain_size: usize, round: usize) -> usize {
+        (0..round).fold(domain_size / self.blowup_factor, |degree, i| {
+            (degree / self.folding_factor_at(i)).max(1)
+        })
+    }
+
+    /// Returns the number of queries made against the function committed to in the specified
+    /// round.
+    ///
+    /// Every query against a function of rate ρ provides roughly log2(1/ρ) bits of security.
+    /// Since the rate decreases with every round, fewer queries are needed in later rounds to
+    /// match the security of `num_queries` queries against the function over the initial domain.
+    /// The number of queries is limited by the number of distinct cosets which can be queried.
+    pub fn num_round_queries(&self, domain_size: usize, round: usize) -> usize {
+        let num_cosets = self.round_domain_size(domain_size, round) / self.folding_factor_at(round);
+        let num_queries = if round == 0 {
+            self.num_queries
+        } else {
+            let target_bits = self.num_queries * self.blowup_factor.ilog2() as usize;
+            let round_blowup =
+                self.round_domain_size(domain_size, round) / self.round_degree(domain_size, round);
+            target_bits.div_ceil(round_blowup.ilog2() as usize)
+        };
+        num_queries.min(num_cosets.saturating_sub(1))
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn assert_folding_factor_supported(folding_factor: usize) {
+    assert!(
+        folding_factor == 2 || folding_factor == 4 || folding_factor == 8 || folding_factor == 16,
+        "folding factor {folding_factor} is not supported"
+    );
+}
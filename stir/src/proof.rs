@@ -0,0 +1,378 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::StirOptions;
+use alloc::{string::ToString, vec::Vec};
+use crypto::{ElementHasher, HashDomain, Hasher, VectorCommitment, VectorCommitmentOptions};
+use math::FieldElement;
+use utils::{
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader,
+};
+
+// STIR PROOF
+// ================================================================================================
+
+/// A proof generated by a STIR prover.
+///
+/// A STIR proof contains:
+/// * Evaluations of the function committed to at the initial evaluation domain and of the
+///   function committed to in every STIR round at the positions queried by the verifier, together
+///   with opening proofs (e.g., Merkle authentication paths) against the corresponding
+///   commitments. Evaluations needed to compute a single folded value are grouped together.
+/// * Out-of-domain evaluations of the function committed to in every STIR round.
+/// * Coefficients of the final polynomial.
+///
+/// The commitments themselves are not included into the proof; they are sent to the verifier
+/// via the prover channel (see [ProverChannel](crate::ProverChannel)).
+///
+/// Internally, all values are stored as sequences of bytes. Thus, to retrieve the values,
+/// [parse_layers()](StirProof::parse_layers), [parse_ood_evaluations()](StirProof::parse_ood_evaluations),
+/// and [parse_final_poly()](StirProof::parse_final_poly) functions should be used.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct StirProof {
+    layers: Vec<StirProofLayer>,
+    ood_evaluations: Vec<u8>,
+    final_poly: Vec<u8>,
+}
+
+impl StirProof {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Creates a new instance of [StirProof] from the specified parameters.
+    ///
+    /// # Panics
+    /// Panics if `final_poly` is empty or the number of its coefficients is not a power of two.
+    pub(crate) fn new<E: FieldElement>(
+        layers: Vec<StirProofLayer>,
+        ood_evaluations: Vec<E>,
+        final_poly: Vec<E>,
+    ) -> Self {
+        assert!(
+            final_poly.len().is_power_of_two(),
+            "size of the final polynomial must be a power of two, but was {}",
+            final_poly.len()
+        );
+
+        let mut ood_bytes = Vec::with_capacity(E::ELEMENT_BYTES * ood_evaluations.len());
+        ood_bytes.write_many(&ood_evaluations);
+        let mut final_poly_bytes = Vec::with_capacity(E::ELEMENT_BYTES * final_poly.len());
+        final_poly_bytes.write_many(&final_poly);
+
+        StirProof {
+            layers,
+            ood_evaluations: ood_bytes,
+            final_poly: final_poly_bytes,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of committed functions for which this proof contains queried values.
+    ///
+    /// This is equal to the number of STIR rounds plus one.
+    pub fn num_layers(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Returns the size of this proof in bytes.
+    pub fn size(&self) -> usize {
+        // +1 for number of layers, +2 for each of the out-of-domain evaluations and final
+        // polynomial lengths
+        self.layers
+            .iter()
+            .fold(self.ood_evaluations.len() + self.final_poly.len() + 5, |acc, layer| {
+                acc + layer.size()
+            })
+    }
+
+    // PARSING
+    // --------------------------------------------------------------------------------------------
+
+    /// Decomposes this proof into vectors of query values for each committed function and
+    /// corresponding opening proofs.
+    ///
+    /// The `domain_size` parameter specifies the size of the initial evaluation domain, while
+    /// `commitment_options` specifies the options with which all commitments were built by the
+    /// prover. Vector commitments are expected to be built using the scheme specified by type
+    /// parameter `V`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The number of layers in this proof is not consistent with the specified `domain_size`
+    ///   and `options`.
+    /// * Any of the layers could not be parsed correctly.
+    #[allow(clippy::type_complexity)]
+    pub fn parse_layers<H, E, V>(
+        self,
+        domain_size: usize,
+        options: &StirOptions,
+        commitment_options: VectorCommitmentOptions,
+    ) -> Result<(Vec<Vec<E>>, Vec<V::MultiProof>), DeserializationError>
+    where
+        E: FieldElement,
+        H: ElementHasher<BaseField = E::BaseField>,
+        V: VectorCommitment<H>,
+    {
+        assert!(domain_size.is_power_of_two(), "domain size must be a power of two");
+        let num_layers = options.num_rounds(domain_size) + 1;
+        if self.layers.len() != num_layers {
+            return Err(DeserializationError::InvalidValue(format!(
+                "expected {num_layers} STIR layers, but {} were provided",
+                self.layers.len()
+            )));
+        }
+
+        let mut layer_proofs = Vec::with_capacity(self.layers.len());
+        let mut layer_queries = Vec::with_capacity(self.layers.len());
+
+        for (i, layer) in self.layers.into_iter().enumerate() {
+            let folding_factor = options.folding_factor_at(i);
+            let num_cosets = options.round_domain_size(domain_size, i) / folding_factor;
+            if num_cosets < 2 {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "STIR layer {i} must contain at least two cosets"
+                )));
+            }
+            // the cap height is reduced for layers which are too small for a cap of the
+            // specified height, in the same way as it is done by the prover
+            let cap_height = commitment_options.cap_height().min(num_cosets.ilog2() as usize - 1);
+            let layer_options =
+                VectorCommitmentOptions::new(commitment_options.arity(), cap_height);
+            let (qv, mp) = layer
+                .parse::<H, E, V>(num_cosets, folding_factor, layer_options)
+                .map_err(|err| {
+                    DeserializationError::InvalidValue(format!(
+                        "failed to parse STIR layer {i}: {err}"
+                    ))
+                })?;
+            layer_proofs.push(mp);
+            layer_queries.push(qv);
+        }
+
+        Ok((layer_queries, layer_proofs))
+    }
+
+    /// Returns a vector of out-of-domain evaluations parsed from this proof.
+    ///
+    /// # Errors
+    /// Returns an error if the evaluations could not be parsed into field elements.
+    pub fn parse_ood_evaluations<E: FieldElement>(&self) -> Result<Vec<E>, DeserializationError> {
+        parse_elements(&self.ood_evaluations, "out-of-domain evaluations")
+    }
+
+    /// Returns coefficients of the final polynomial parsed from this proof.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The number of coefficients is not a power of two.
+    /// * The coefficients could not be parsed into field elements.
+    pub fn parse_final_poly<E: FieldElement>(&self) -> Result<Vec<E>, DeserializationError> {
+        let final_poly: Vec<E> = parse_elements(&self.final_poly, "final polynomial")?;
+        if !final_poly.len().is_power_of_two() {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of final polynomial coefficients must be a power of two, but {} was implied",
+                final_poly.len()
+            )));
+        }
+        Ok(final_poly)
+    }
+}
+
+// SERIALIZATION / DESERIALIZATION
+// ------------------------------------------------------------------------------------------------
+
+impl Serializable for StirProof {
+    /// Serializes `self` and writes the resulting bytes into the `target` writer.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        // write layers
+        target.write_u8(self.layers.len() as u8);
+        for layer in self.layers.iter() {
+            layer.write_into(target);
+        }
+
+        // write out-of-domain evaluations
+        target.write_u16(self.ood_evaluations.len() as u16);
+        target.write_bytes(&self.ood_evaluations);
+
+        // write final polynomial
+        target.write_u16(self.final_poly.len() as u16);
+        target.write_bytes(&self.final_poly);
+    }
+}
+
+impl Deserializable for StirProof {
+    /// Reads a STIR proof from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid proof could not be read from the source.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        // read layers
+        let num_layers = source.read_u8()? as usize;
+        let layers = source.read_many(num_layers)?;
+
+        // read out-of-domain evaluations
+        let num_ood_bytes = source.read_u16()? as usize;
+        let ood_evaluations = source.read_vec(num_ood_bytes)?;
+
+        // read final polynomial
+        let num_final_poly_bytes = source.read_u16()? as usize;
+        let final_poly = source.read_vec(num_final_poly_bytes)?;
+
+        Ok(StirProof {
+            layers,
+            ood_evaluations,
+            final_poly,
+        })
+    }
+}
+
+// STIR PROOF LAYER
+// ================================================================================================
+
+/// Values of a single committed function at the cosets queried by the verifier, together with
+/// an opening proof against the commitment to the function.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct StirProofLayer {
+    values: Vec<u8>,
+    paths: Vec<u8>,
+}
+
+impl StirProofLayer {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Creates a new proof layer from the specified query values and opening proof.
+    ///
+    /// `query_values` must contain the values of all queried cosets one after another.
+    pub fn new<H: Hasher, E: FieldElement, V: VectorCommitment<H>>(
+        query_values: &[E],
+        proof: V::MultiProof,
+    ) -> Self {
+        assert!(!query_values.is_empty(), "query values cannot be empty");
+
+        let mut value_bytes = Vec::with_capacity(E::ELEMENT_BYTES * query_values.len());
+        value_bytes.write_many(query_values);
+
+        // the opened items are not serialized because they can be reconstructed from hashes of
+        // query values
+        StirProofLayer {
+            values: value_bytes,
+            paths: V::serialize_multiproof(&proof),
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the size of this proof layer in bytes.
+    pub fn size(&self) -> usize {
+        // +4 for length of values, +4 for length of paths
+        self.values.len() + 4 + self.paths.len() + 4
+    }
+
+    // PARSING
+    // --------------------------------------------------------------------------------------------
+
+    /// Decomposes this layer into a vector of query values and an opening proof.
+    pub fn parse<H, E, V>(
+        self,
+        num_cosets: usize,
+        folding_factor: usize,
+        commitment_options: VectorCommitmentOptions,
+    ) -> Result<(Vec<E>, V::MultiProof), DeserializationError>
+    where
+        E: FieldElement,
+        H: ElementHasher<BaseField = E::BaseField>,
+        V: VectorCommitment<H>,
+    {
+        // make sure the number of value bytes can be parsed into a whole number of queries
+        let num_query_bytes = E::ELEMENT_BYTES * folding_factor;
+        if self.values.len() % num_query_bytes != 0 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of value bytes ({}) does not divide into whole number of queries",
+                self.values.len(),
+            )));
+        }
+
+        let num_queries = self.values.len() / num_query_bytes;
+        if num_queries == 0 {
+            return Err(DeserializationError::InvalidValue(
+                "a STIR layer must contain at least one query".to_string(),
+            ));
+        }
+        let mut hashed_queries = Vec::with_capacity(num_queries);
+        let mut query_values = Vec::with_capacity(num_queries * folding_factor);
+
+        // read bytes corresponding to each query, convert them into field elements, and also
+        // hash them to build the opened items of the opening proof
+        let mut reader = SliceReader::new(&self.values);
+        for _ in 0..num_queries {
+            let mut qe = reader.read_many(folding_factor)?;
+            hashed_queries.push(H::hash_elements_in_domain(&qe, HashDomain::Leaf));
+            query_values.append(&mut qe);
+        }
+
+        // build the opening proof
+        let mut reader = SliceReader::new(&self.paths);
+        let proof =
+            V::deserialize_multiproof(&mut reader, hashed_queries, num_cosets, commitment_options)?;
+        if reader.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+
+        Ok((query_values, proof))
+    }
+}
+
+impl Serializable for StirProofLayer {
+    /// Serializes `self` and writes the resulting bytes into the `target` writer.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u32(self.values.len() as u32);
+        target.write_bytes(&self.values);
+        target.write_u32(self.paths.len() as u32);
+        target.write_bytes(&self.paths);
+    }
+}
+
+impl Deserializable for StirProofLayer {
+    /// Reads a single proof layer from the specified `source` and returns the result.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let num_value_bytes = source.read_u32()?;
+        if num_value_bytes == 0 {
+            return Err(DeserializationError::InvalidValue(
+                "a STIR proof layer must contain at least one queried evaluation".to_string(),
+            ));
+        }
+        let values = source.read_vec(num_value_bytes as usize)?;
+        let num_paths_bytes = source.read_u32()?;
+        let paths = source.read_vec(num_paths_bytes as usize)?;
+
+        Ok(StirProofLayer { values, paths })
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Parses the specified bytes into a vector of field elements.
+fn parse_elements<E: FieldElement>(
+    bytes: &[u8],
+    name: &str,
+) -> Result<Vec<E>, DeserializationError> {
+    if bytes.len() % E::ELEMENT_BYTES != 0 {
+        return Err(DeserializationError::InvalidValue(format!(
+            "number of {name} bytes ({}) does not divide into whole number of field elements",
+            bytes.len()
+        )));
+    }
+    let mut reader = SliceReader::new(bytes);
+    let elements = reader.read_many(bytes.len() / E::ELEMENT_BYTES).map_err(|err| {
+        DeserializationError::InvalidValue(format!("failed to parse {name}: {err}"))
+    })?;
+    if reader.has_more_bytes() {
+        return Err(DeserializationError::UnconsumedBytes);
+    }
+    Ok(elements)
+}
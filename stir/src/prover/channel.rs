@@ -0,0 +1,166 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use crypto::{ElementHasher, HashDomain, Hasher, MerkleTree, RandomCoin, VectorCommitment};
+use math::FieldElement;
+
+// PROVER CHANNEL TRAIT
+// ================================================================================================
+
+/// Defines an interface for a channel over which a prover communicates with a verifier.
+///
+/// In every STIR round, the prover uses this channel to send a commitment to the function of the
+/// round and the evaluation of this function at an out-of-domain point to the verifier, and to
+/// draw the random values and query indexes used to build the function of the next round.
+///
+/// In the interactive version of the protocol, the verifier chooses these values uniformly at
+/// random. In the non-interactive version, the values are drawn pseudo-randomly based on the
+/// data the prover has written into the channel up to this point.
+pub trait ProverChannel<E: FieldElement> {
+    /// Hash function used by the prover to commit to function evaluations.
+    type Hasher: ElementHasher<BaseField = E::BaseField>;
+
+    /// Sends a commitment to the evaluations of a function over the domain of a STIR round.
+    ///
+    /// The commitment is a vector commitment (by default, a cap of a Merkle tree) built by first
+    /// transposing evaluations into a two-dimensional matrix where each row contains values
+    /// needed to compute a single value of the folded function, and then hashing each row of the
+    /// matrix into a single item of the committed vector.
+    fn commit_stir_layer(
+        &mut self,
+        layer_cap: &[<<Self as ProverChannel<E>>::Hasher as Hasher>::Digest],
+    );
+
+    /// Sends the evaluation of the function committed to in the current STIR round at the
+    /// out-of-domain point drawn after the commitment.
+    fn send_stir_ood_evaluation(&mut self, value: E);
+
+    /// Sends the coefficients of the final polynomial.
+    fn send_stir_final_poly(&mut self, poly: &[E]);
+
+    /// Returns a random value drawn uniformly at random from the entire field.
+    fn draw_stir_challenge(&mut self) -> E;
+
+    /// Returns a list of `num_queries` indexes drawn uniformly at random from a domain of the
+    /// specified size.
+    ///
+    /// The returned list may contain duplicates.
+    fn draw_stir_query_indexes(&mut self, num_queries: usize, domain_size: usize) -> Vec<usize>;
+}
+
+// DEFAULT PROVER CHANNEL IMPLEMENTATION
+// ================================================================================================
+
+/// Provides a default implementation of the [ProverChannel] trait.
+///
+/// Layer commitments are absorbed into the public coin using the digest computed by the vector
+/// commitment scheme `V` (by default, a Merkle tree); out-of-domain evaluations and the final
+/// polynomial are absorbed by hashing them.
+///
+/// Though this implementation is intended primarily for testing purposes, it can be used in
+/// production use cases as well.
+pub struct DefaultProverChannel<E, H, R, V = MerkleTree<H>>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+    V: VectorCommitment<H>,
+{
+    public_coin: R,
+    commitments: Vec<Vec<H::Digest>>,
+    domain_size: usize,
+    num_queries: usize,
+    _field_element: PhantomData<E>,
+    _vector_commitment: PhantomData<V>,
+}
+
+impl<E, H, R, V> DefaultProverChannel<E, H, R, V>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+    V: VectorCommitment<H>,
+{
+    /// Returns a new prover channel instantiated from the specified parameters.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `domain_size` is smaller than 8 or is not a power of two.
+    /// * `num_queries` is zero.
+    pub fn new(domain_size: usize, num_queries: usize) -> Self {
+        assert!(domain_size >= 8, "domain size must be at least 8, but was {domain_size}");
+        assert!(
+            domain_size.is_power_of_two(),
+            "domain size must be a power of two, but was {domain_size}"
+        );
+        assert!(num_queries > 0, "number of queries must be greater than zero");
+        DefaultProverChannel {
+            public_coin: RandomCoin::new(&[]),
+            commitments: Vec::new(),
+            domain_size,
+            num_queries,
+            _field_element: PhantomData,
+            _vector_commitment: PhantomData,
+        }
+    }
+
+    /// Draws a set of positions at which the evaluations of the function over the initial
+    /// domain should be queried.
+    ///
+    /// The positions are pseudo-randomly generated based on the values the prover has written
+    /// into this channel and a PoW nonce.
+    ///
+    /// # Panics
+    /// Panics if the specified number of unique positions could not be drawn from the specified
+    /// domain. Both number of queried positions and domain size are specified during
+    /// construction of the channel.
+    pub fn draw_query_positions(&mut self, nonce: u64) -> Vec<usize> {
+        self.public_coin
+            .draw_integers(self.num_queries, self.domain_size, nonce)
+            .expect("failed to draw query position")
+    }
+
+    /// Returns a list of layer commitments written by the prover into this channel.
+    pub fn layer_commitments(&self) -> &[Vec<H::Digest>] {
+        &self.commitments
+    }
+}
+
+impl<E, H, R, V> ProverChannel<E> for DefaultProverChannel<E, H, R, V>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+    V: VectorCommitment<H>,
+{
+    type Hasher = H;
+
+    fn commit_stir_layer(&mut self, layer_cap: &[H::Digest]) {
+        self.commitments.push(layer_cap.to_vec());
+        self.public_coin.reseed(V::commitment_digest(layer_cap));
+    }
+
+    fn send_stir_ood_evaluation(&mut self, value: E) {
+        self.public_coin
+            .reseed(H::hash_elements_in_domain(&[value], HashDomain::Transcript));
+    }
+
+    fn send_stir_final_poly(&mut self, poly: &[E]) {
+        self.public_coin
+            .reseed(H::hash_elements_in_domain(poly, HashDomain::Transcript));
+    }
+
+    fn draw_stir_challenge(&mut self) -> E {
+        self.public_coin.draw().expect("failed to draw STIR challenge")
+    }
+
+    fn draw_stir_query_indexes(&mut self, num_queries: usize, domain_size: usize) -> Vec<usize> {
+        self.public_coin
+            .draw_integers(num_queries, domain_size, 0)
+            .expect("failed to draw STIR query indexes")
+    }
+}
@@ -0,0 +1,361 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{
+    proof::{StirProof, StirProofLayer},
+    utils::{fold_poly, get_folded_point, sort_and_dedup, transpose_and_hash},
+    StirOptions,
+};
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use crypto::{ElementHasher, Hasher, MerkleTree, VectorCommitment, VectorCommitmentOptions};
+use math::{fft, polynom, FieldElement, StarkField};
+
+mod channel;
+pub use channel::{DefaultProverChannel, ProverChannel};
+
+#[cfg(test)]
+mod tests;
+
+// TYPES AND INTERFACES
+// ================================================================================================
+
+/// Implements the prover component of the STIR protocol.
+///
+/// Given evaluations of a function *f* over domain *D* (`evaluations`), a STIR prover generates
+/// a proof that *f* is a polynomial of some bounded degree *d*, such that *d* < |*D*| / *blowup_factor*.
+///
+/// STIR (Shift To Improve Rate) is an alternative to FRI: as in FRI, the degree of the tested
+/// function is reduced by a folding factor in every round; but unlike FRI, the size of the
+/// evaluation domain is reduced only by a factor of 2. Thus, the rate of the code the committed
+/// functions belong to decreases with every round, and fewer queries are needed in later rounds
+/// to achieve the same level of security. This usually results in smaller proofs.
+///
+/// The prover is parametrized with the following types:
+///
+/// * `B` specifies the base field of the STARK protocol.
+/// * `E` specifies the field in which the STIR protocol is executed. This can be the same as the
+///   base field `B`, but it can also be an extension of the base field in cases when the base
+///   field is too small to provide desired security level for the STIR protocol.
+/// * `C` specifies the type used to simulate prover-verifier interaction.
+/// * `H` specifies the hash function used to build commitments. The same hash function must be
+///   used in the prover channel to generate pseudo random values.
+/// * `V` specifies the vector commitment scheme used to commit to round functions. By default,
+///   this is a [MerkleTree].
+///
+/// Proof generation is performed in two phases: commit phase and query phase.
+///
+/// # Commit phase
+/// During the commit phase, which is executed via [build_layers()](StirProver::build_layers())
+/// function, the prover first commits to `evaluations` and draws a folding randomness r_fold
+/// from the channel. Then, in every round i, the prover:
+/// 1. Folds the polynomial f_(i-1) of the previous round using r_fold into a polynomial g_i,
+///    evaluates g_i over the domain of the round (half the size of the domain of the previous
+///    round), and commits to the evaluations.
+/// 2. Draws an out-of-domain point r_out and sends g_i(r_out) to the verifier.
+/// 3. Draws a combination randomness r_comb, the folding randomness for the next round, and a
+///    set of query indexes into the domain of the folded function f_(i-1). The points at these
+///    indexes, together with r_out, form the set G_i.
+/// 4. Computes the polynomial f_i of the round as the quotient of g_i by the vanishing
+///    polynomial of G_i, multiplied by 1 + r_comb * x + ... + (r_comb * x)^|G_i| to correct its
+///    degree.
+///
+/// The verifier can compute evaluations of f_i from evaluations of g_i and the answers at G_i
+/// which, in turn, are computed by folding queried evaluations of f_(i-1). After the last round,
+/// the prover folds the polynomial of the round into the final polynomial which is sent to the
+/// verifier in the clear, and draws a set of query indexes used to check consistency of the
+/// final polynomial with the last committed function.
+///
+/// # Query phase
+/// In the query phase, which is executed via [build_proof()](StirProver::build_proof()) function,
+/// the prover receives a set of positions in the domain *D* from the verifier, and decommits
+/// evaluations of *f* at these positions as well as evaluations of all committed functions at the
+/// indexes drawn during the commit phase.
+///
+/// Calling [build_layers()](StirProver::build_layers()) when the internal state is dirty, or
+/// calling [build_proof()](StirProver::build_proof()) on a clean state will result in a panic.
+pub struct StirProver<B, E, C, H, V = MerkleTree<H>>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+    C: ProverChannel<E, Hasher = H>,
+    H: ElementHasher<BaseField = B>,
+    V: VectorCommitment<H>,
+{
+    options: StirOptions,
+    layers: Vec<StirLayer<E, H, V>>,
+    ood_evaluations: Vec<E>,
+    final_poly: Vec<E>,
+    _channel: PhantomData<C>,
+}
+
+struct StirLayer<E: FieldElement, H: Hasher, V: VectorCommitment<H>> {
+    commitment: V,
+    evaluations: Vec<E>,
+    folding_factor: usize,
+    query_indexes: Vec<usize>,
+    _hasher: PhantomData<H>,
+}
+
+// PROVER IMPLEMENTATION
+// ================================================================================================
+
+impl<B, E, C, H, V> StirProver<B, E, C, H, V>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+    C: ProverChannel<E, Hasher = H>,
+    H: ElementHasher<BaseField = B>,
+    V: VectorCommitment<H>,
+{
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new STIR prover instantiated with the provided `options`.
+    pub fn new(options: StirOptions) -> Self {
+        StirProver {
+            options,
+            layers: Vec::new(),
+            ood_evaluations: Vec::new(),
+            final_poly: Vec::new(),
+            _channel: PhantomData,
+        }
+    }
+
+    // ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the options used by this prover.
+    pub fn options(&self) -> &StirOptions {
+        &self.options
+    }
+
+    /// Returns offset of the domains over which STIR protocol is executed by this prover.
+    pub fn domain_offset(&self) -> B {
+        self.options.domain_offset()
+    }
+
+    /// Returns the number of functions committed to during the last execution of the
+    /// [build_layers()](StirProver::build_layers()) method.
+    ///
+    /// This is equal to the number of STIR rounds plus one.
+    pub fn num_layers(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Clears internally stored layers.
+    pub fn reset(&mut self) {
+        self.layers.clear();
+        self.ood_evaluations.clear();
+        self.final_poly.clear();
+    }
+
+    // COMMIT PHASE
+    // --------------------------------------------------------------------------------------------
+    /// Executes the commit phase of the STIR protocol.
+    ///
+    /// `evaluations` must contain evaluations of a polynomial of degree smaller than
+    /// `evaluations.len() / blowup_factor` over the domain shifted by the domain offset. All
+    /// commitments, out-of-domain evaluations and the final polynomial are written into the
+    /// channel.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The prover state is dirty (the vector of layers is not empty).
+    /// * The number of evaluations is not a power of two.
+    /// * The domain of any of the rounds is too small to be folded into at least two cosets.
+    pub fn build_layers(&mut self, channel: &mut C, mut evaluations: Vec<E>) {
+        assert!(
+            self.layers.is_empty(),
+            "a prior proof generation request has not been completed yet"
+        );
+        let domain_size = evaluations.len();
+        assert!(
+            domain_size.is_power_of_two(),
+            "number of evaluations must be a power of two, but was {domain_size}"
+        );
+        let offset = self.domain_offset();
+        let num_rounds = self.options.num_rounds(domain_size);
+
+        // commit to the evaluations over the initial domain, and interpolate them to get the
+        // polynomial of the initial function
+        self.commit_layer(channel, &evaluations, 0, domain_size);
+        let inv_twiddles = fft::get_inv_twiddles::<B>(domain_size);
+        fft::interpolate_poly_with_offset(&mut evaluations, &inv_twiddles, offset);
+        evaluations.truncate(self.options.round_degree(domain_size, 0));
+        let mut poly = evaluations;
+        let mut r_fold = channel.draw_stir_challenge();
+
+        for round in 1..=num_rounds {
+            // fold the polynomial of the previous round, evaluate the result over the domain of
+            // this round, and commit to the evaluations
+            let g = fold_poly(&poly, self.options.folding_factor_at(round - 1), r_fold);
+            let round_domain_size = self.options.round_domain_size(domain_size, round);
+            let twiddles = fft::get_twiddles::<B>(g.len());
+            let g_evaluations =
+                fft::evaluate_poly_with_offset(&g, &twiddles, offset, round_domain_size / g.len());
+            self.commit_layer(channel, &g_evaluations, round, domain_size);
+
+            // send the evaluation of the folded polynomial at an out-of-domain point
+            let r_out = channel.draw_stir_challenge();
+            let beta = polynom::eval(&g, r_out);
+            channel.send_stir_ood_evaluation(beta);
+            self.ood_evaluations.push(beta);
+
+            // draw randomness for the next round, and query the function of the previous round
+            let r_comb = channel.draw_stir_challenge();
+            r_fold = channel.draw_stir_challenge();
+            let shift_points = self.draw_queries(channel, round - 1, domain_size);
+
+            // divide the folded polynomial by the vanishing polynomial of the out-of-domain and
+            // shift points, and correct the degree of the quotient
+            let mut quotient = g;
+            for point in core::iter::once(r_out).chain(shift_points.into_iter().map(E::from)) {
+                polynom::syn_div_in_place(&mut quotient, 1, point);
+            }
+            let num_points = self.layers[round - 1].query_indexes.len() + 1;
+            poly = correct_degree(&quotient, r_comb, num_points);
+        }
+
+        // fold the polynomial of the last round into the final polynomial and send it to the
+        // verifier; then, query the function of the last round
+        self.final_poly = fold_poly(&poly, self.options.folding_factor_at(num_rounds), r_fold);
+        channel.send_stir_final_poly(&self.final_poly);
+        self.draw_queries(channel, num_rounds, domain_size);
+    }
+
+    /// Commits to the evaluations of the function of the specified round.
+    ///
+    /// The evaluations are transposed so that all evaluations needed to compute a single value
+    /// of the folded function are committed to in a single leaf.
+    fn commit_layer(
+        &mut self,
+        channel: &mut C,
+        evaluations: &[E],
+        round: usize,
+        domain_size: usize,
+    ) {
+        let folding_factor = self.options.folding_factor_at(round);
+        let num_cosets = evaluations.len() / folding_factor;
+        assert!(
+            num_cosets >= 2,
+            "domain of size {domain_size} is too small for {} STIR rounds",
+            self.options.num_rounds(domain_size)
+        );
+
+        let (evaluations, hashed_evaluations) =
+            transpose_and_hash::<E, H>(evaluations, folding_factor);
+        let cap_height = self.options.merkle_cap_height().min(num_cosets.ilog2() as usize - 1);
+        let commitment_options =
+            VectorCommitmentOptions::new(self.options.merkle_arity(), cap_height);
+        let commitment = V::with_options(hashed_evaluations, commitment_options)
+            .expect("failed to construct STIR layer commitment");
+        channel.commit_stir_layer(commitment.commitment());
+
+        self.layers.push(StirLayer {
+            commitment,
+            evaluations,
+            folding_factor,
+            query_indexes: Vec::new(),
+            _hasher: PhantomData,
+        });
+    }
+
+    /// Draws query indexes into the domain of the folded function of the specified round,
+    /// records them in the layer of the round, and returns the points of the folded domain at
+    /// these indexes.
+    fn draw_queries(&mut self, channel: &mut C, round: usize, domain_size: usize) -> Vec<B> {
+        let round_domain_size = self.options.round_domain_size(domain_size, round);
+        let folding_factor = self.options.folding_factor_at(round);
+        let num_queries = self.options.num_round_queries(domain_size, round);
+        let indexes = sort_and_dedup(
+            channel.draw_stir_query_indexes(num_queries, round_domain_size / folding_factor),
+        );
+
+        let offset = self.domain_offset();
+        let points = indexes
+            .iter()
+            .map(|&idx| get_folded_point(round_domain_size, offset, folding_factor, idx))
+            .collect();
+        self.layers[round].query_indexes = indexes;
+        points
+    }
+
+    // QUERY PHASE
+    // --------------------------------------------------------------------------------------------
+    /// Executes query phase of STIR protocol.
+    ///
+    /// For each committed function, evaluations at the cosets queried during the commit phase
+    /// are recorded into the proof together with opening proofs against the commitments. For the
+    /// function over the initial domain, the cosets containing the specified `positions` are
+    /// decommitted as well. The final polynomial is included into the proof in its entirety.
+    ///
+    /// # Panics
+    /// Panics is the prover state is clean (no STIR layers have been build yet).
+    pub fn build_proof(&mut self, positions: &[usize]) -> StirProof {
+        assert!(!self.layers.is_empty(), "STIR layers have not been built yet");
+
+        let mut layers = Vec::with_capacity(self.layers.len());
+        for (i, layer) in self.layers.iter().enumerate() {
+            let num_cosets = layer.evaluations.len() / layer.folding_factor;
+            let indexes = if i == 0 {
+                let mut indexes = layer.query_indexes.clone();
+                indexes.extend(positions.iter().map(|&p| p % num_cosets));
+                sort_and_dedup(indexes)
+            } else {
+                layer.query_indexes.clone()
+            };
+            layers.push(query_layer(layer, &indexes));
+        }
+
+        let proof = StirProof::new(layers, self.ood_evaluations.clone(), self.final_poly.clone());
+
+        // clear layers so that another proof can be generated
+        self.reset();
+
+        proof
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Builds a single proof layer by querying the evaluations of the specified layer at the
+/// cosets with the specified indexes.
+fn query_layer<E, H, V>(layer: &StirLayer<E, H, V>, indexes: &[usize]) -> StirProofLayer
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H>,
+{
+    let proof = layer
+        .commitment
+        .open_many(indexes)
+        .expect("failed to generate an opening proof for STIR layer queries");
+
+    let mut queried_values = Vec::with_capacity(indexes.len() * layer.folding_factor);
+    for &idx in indexes.iter() {
+        let start = idx * layer.folding_factor;
+        queried_values.extend_from_slice(&layer.evaluations[start..start + layer.folding_factor]);
+    }
+
+    StirProofLayer::new::<H, E, V>(&queried_values, proof)
+}
+
+/// Multiplies the specified polynomial by 1 + r * x + ... + (r * x)^`num_points` and truncates
+/// the result to the length of the original polynomial.
+///
+/// When `poly` is a quotient by a vanishing polynomial of `num_points` points, the degree of the
+/// result is the same as the degree of the polynomial before the division.
+fn correct_degree<E: FieldElement>(poly: &[E], r: E, num_points: usize) -> Vec<E> {
+    let mut correction = Vec::with_capacity(num_points + 1);
+    let mut power = E::ONE;
+    for _ in 0..=num_points {
+        correction.push(power);
+        power *= r;
+    }
+    let mut result = polynom::mul(poly, &correction);
+    result.truncate(poly.len());
+    result
+}
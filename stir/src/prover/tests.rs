@@ -0,0 +1,167 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{DefaultProverChannel, StirProver};
+use crate::{
+    verifier::{DefaultVerifierChannel, StirVerifier},
+    StirOptions, StirProof, VerifierError,
+};
+use alloc::vec::Vec;
+use crypto::{
+    hashers::Blake3_256, DefaultRandomCoin, Hasher, MerkleTree, RandomCoin, VectorCommitmentOptions,
+};
+use math::{fft, fields::f128::BaseElement, FieldElement};
+use utils::{Deserializable, Serializable, SliceReader};
+
+type Blake3 = Blake3_256<BaseElement>;
+
+// PROVE/VERIFY TEST
+// ================================================================================================
+
+#[test]
+fn stir_folding_2() {
+    let options = StirOptions::new(8, 2, 7, 32);
+    stir_prove_verify(12, options);
+}
+
+#[test]
+fn stir_folding_4() {
+    let options = StirOptions::new(8, 4, 15, 32);
+    stir_prove_verify(12, options);
+}
+
+#[test]
+fn stir_folding_16() {
+    let options = StirOptions::new(4, 16, 7, 32);
+    stir_prove_verify(12, options);
+}
+
+#[test]
+fn stir_folding_4_with_cap_and_arity() {
+    let options = StirOptions::new(8, 4, 15, 32).with_merkle_cap_height(3).with_merkle_arity(4);
+    stir_prove_verify(12, options);
+}
+
+#[test]
+fn stir_folding_schedule() {
+    let options = StirOptions::new(8, 4, 7, 32).with_folding_schedule(vec![16, 8]);
+    stir_prove_verify(12, options);
+}
+
+#[test]
+fn stir_invalid_evaluations() {
+    let trace_length = 1 << 10;
+    let options = StirOptions::new(8, 4, 15, 32);
+    let domain_size = trace_length * options.blowup_factor();
+
+    // evaluations of a random function are not close to any low-degree polynomial
+    let evaluations: Vec<BaseElement> = rand_utils::rand_vector(domain_size);
+    let (proof, commitments, positions) = build_proof(&evaluations, &options);
+    let result = verify_proof(
+        proof.clone(),
+        commitments.clone(),
+        &evaluations,
+        trace_length - 1,
+        &positions,
+        &options,
+    );
+    assert!(result.is_err());
+
+    // verification fails if the queried evaluations do not match the committed ones
+    let evaluations = build_evaluations(trace_length, &options);
+    let (proof, commitments, positions) = build_proof(&evaluations, &options);
+    let mut invalid_evaluations = evaluations.clone();
+    invalid_evaluations[positions[0]] += BaseElement::ONE;
+    let result = verify_proof(
+        proof,
+        commitments,
+        &invalid_evaluations,
+        trace_length - 1,
+        &positions,
+        &options,
+    );
+    assert_eq!(Err(VerifierError::InitialEvaluationsMismatch), result);
+}
+
+// TEST UTILS
+// ================================================================================================
+
+pub fn build_prover_channel(
+    domain_size: usize,
+    options: &StirOptions,
+) -> DefaultProverChannel<BaseElement, Blake3, DefaultRandomCoin<Blake3>> {
+    DefaultProverChannel::new(domain_size, options.num_queries())
+}
+
+pub fn build_evaluations(trace_length: usize, options: &StirOptions) -> Vec<BaseElement> {
+    let p = (0..trace_length as u128).map(BaseElement::new).collect::<Vec<_>>();
+    let twiddles = fft::get_twiddles::<BaseElement>(trace_length);
+    fft::evaluate_poly_with_offset(&p, &twiddles, options.domain_offset(), options.blowup_factor())
+}
+
+pub fn build_proof(
+    evaluations: &[BaseElement],
+    options: &StirOptions,
+) -> (StirProof, Vec<Vec<<Blake3 as Hasher>::Digest>>, Vec<usize>) {
+    let mut channel = build_prover_channel(evaluations.len(), options);
+    let mut prover = StirProver::<_, _, _, _, MerkleTree<Blake3>>::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.to_vec());
+    let positions = channel.draw_query_positions(0);
+    let proof = prover.build_proof(&positions);
+    (proof, channel.layer_commitments().to_vec(), positions)
+}
+
+pub fn verify_proof(
+    proof: StirProof,
+    commitments: Vec<Vec<<Blake3 as Hasher>::Digest>>,
+    evaluations: &[BaseElement],
+    max_degree: usize,
+    positions: &[usize],
+    options: &StirOptions,
+) -> Result<(), VerifierError> {
+    // test proof serialization / deserialization
+    let mut proof_bytes = Vec::new();
+    proof.write_into(&mut proof_bytes);
+
+    let mut reader = SliceReader::new(&proof_bytes);
+    let proof = StirProof::read_from(&mut reader).unwrap();
+
+    // verify the proof
+    let mut channel = DefaultVerifierChannel::<BaseElement, Blake3, MerkleTree<Blake3>>::new(
+        proof,
+        commitments,
+        evaluations.len(),
+        options,
+        VectorCommitmentOptions::new(options.merkle_arity(), options.merkle_cap_height()),
+    )
+    .map_err(|_| VerifierError::LayerCommitmentMismatch)?;
+    let mut coin = DefaultRandomCoin::<Blake3>::new(&[]);
+    let verifier = StirVerifier::new(&mut channel, &mut coin, options.clone(), max_degree)?;
+    let queried_evaluations = positions.iter().map(|&p| evaluations[p]).collect::<Vec<_>>();
+    verifier.verify(&mut channel, &queried_evaluations, positions)
+}
+
+fn stir_prove_verify(trace_length_e: usize, options: StirOptions) {
+    let trace_length = 1 << trace_length_e;
+    let evaluations = build_evaluations(trace_length, &options);
+
+    // generate the proof and make sure it can be verified
+    let (proof, commitments, positions) = build_proof(&evaluations, &options);
+    let max_degree = trace_length - 1;
+    let result = verify_proof(
+        proof.clone(),
+        commitments.clone(),
+        &evaluations,
+        max_degree,
+        &positions,
+        &options,
+    );
+    assert!(result.is_ok(), "{:}", result.err().unwrap());
+
+    // make sure proof fails for invalid degree
+    let result =
+        verify_proof(proof, commitments, &evaluations, max_degree / 2, &positions, &options);
+    assert!(result.is_err());
+}
@@ -0,0 +1,119 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+use crypto::{ElementHasher, HashDomain};
+use math::{polynom, FieldElement, StarkField};
+
+// DOMAIN HELPERS
+// ================================================================================================
+
+/// Returns the points of the coset folded into the element at `coset_idx` of the folded domain.
+///
+/// The source domain is the coset of the multiplicative subgroup of size `domain_size` shifted by
+/// `offset`; the returned points are all x in this domain such that x^`folding_factor` is the
+/// `coset_idx`-th element of the folded domain. The points are listed in the same order in which
+/// their evaluations are committed to in a single leaf.
+pub fn get_coset_points<B: StarkField>(
+    domain_size: usize,
+    offset: B,
+    folding_factor: usize,
+    coset_idx: usize,
+) -> Vec<B> {
+    let g = B::get_root_of_unity(domain_size.ilog2());
+    let g_step = g.exp_vartime(((domain_size / folding_factor) as u32).into());
+    let mut x = offset * g.exp_vartime((coset_idx as u32).into());
+    let mut result = Vec::with_capacity(folding_factor);
+    for _ in 0..folding_factor {
+        result.push(x);
+        x *= g_step;
+    }
+    result
+}
+
+/// Returns the `coset_idx`-th element of the domain resulting from folding the coset of the
+/// multiplicative subgroup of size `domain_size` shifted by `offset` by `folding_factor`.
+pub fn get_folded_point<B: StarkField>(
+    domain_size: usize,
+    offset: B,
+    folding_factor: usize,
+    coset_idx: usize,
+) -> B {
+    let g = B::get_root_of_unity(domain_size.ilog2());
+    (offset * g.exp_vartime((coset_idx as u32).into())).exp_vartime((folding_factor as u32).into())
+}
+
+// FOLDING
+// ================================================================================================
+
+/// Folds the polynomial with the specified coefficients by `folding_factor` using the random
+/// value `alpha`.
+///
+/// That is, for f(x) = f_0(x^k) + x * f_1(x^k) + ... + x^(k - 1) * f_(k - 1)(x^k), returns the
+/// coefficients of f_0(x) + alpha * f_1(x) + ... + alpha^(k - 1) * f_(k - 1)(x).
+pub fn fold_poly<E: FieldElement>(poly: &[E], folding_factor: usize, alpha: E) -> Vec<E> {
+    let alpha_powers = (0..folding_factor)
+        .scan(E::ONE, |power, _| {
+            let result = *power;
+            *power *= alpha;
+            Some(result)
+        })
+        .collect::<Vec<_>>();
+    let result_len = (poly.len() / folding_factor).max(1);
+    (0..result_len)
+        .map(|i| {
+            poly.iter()
+                .skip(i * folding_factor)
+                .zip(alpha_powers.iter())
+                .fold(E::ZERO, |acc, (&coeff, &power)| acc + coeff * power)
+        })
+        .collect()
+}
+
+/// Folds the evaluations of a function over a single coset into the evaluation of the folded
+/// function at the point to which the coset is mapped.
+///
+/// This is done by interpolating the evaluations over the coset `points`, and evaluating the
+/// resulting polynomial at `alpha`.
+pub fn fold_coset<E: FieldElement>(points: &[E::BaseField], values: &[E], alpha: E) -> E {
+    let xs = points.iter().map(|&x| E::from(x)).collect::<Vec<_>>();
+    let poly = polynom::interpolate(&xs, values, false);
+    polynom::eval(&poly, alpha)
+}
+
+// COMMITMENT HELPERS
+// ================================================================================================
+
+/// Rearranges evaluations over a domain into rows such that evaluations needed to compute a
+/// single folded value are next to each other, and returns the rearranged evaluations flattened
+/// into a single vector together with the hashes of each row.
+pub fn transpose_and_hash<E, H>(
+    evaluations: &[E],
+    folding_factor: usize,
+) -> (Vec<E>, Vec<H::Digest>)
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    let num_rows = evaluations.len() / folding_factor;
+    let mut transposed = Vec::with_capacity(evaluations.len());
+    for i in 0..num_rows {
+        for j in 0..folding_factor {
+            transposed.push(evaluations[i + j * num_rows]);
+        }
+    }
+    let hashes = transposed
+        .chunks(folding_factor)
+        .map(|row| H::hash_elements_in_domain(row, HashDomain::Leaf))
+        .collect();
+    (transposed, hashes)
+}
+
+/// Sorts the specified indexes and removes duplicates from them.
+pub fn sort_and_dedup(mut indexes: Vec<usize>) -> Vec<usize> {
+    indexes.sort_unstable();
+    indexes.dedup();
+    indexes
+}
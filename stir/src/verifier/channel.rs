@@ -0,0 +1,177 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{StirOptions, StirProof, VerifierError};
+use alloc::vec::Vec;
+use crypto::{ElementHasher, Hasher, MerkleTree, VectorCommitment, VectorCommitmentOptions};
+use math::FieldElement;
+use utils::DeserializationError;
+
+// VERIFIER CHANNEL TRAIT
+// ================================================================================================
+
+/// Defines an interface for a channel over which a verifier communicates with a prover.
+///
+/// This trait abstracts away implementation specifics of the [StirProof] struct. Thus, instead of
+/// dealing with STIR proofs directly, the verifier can read the data as if it was sent by the
+/// prover via an interactive channel.
+///
+/// Note: that reading removes the data from the channel. Thus, reading duplicated values from
+/// the channel should not be possible.
+pub trait VerifierChannel<E: FieldElement> {
+    /// Hash function used by the prover to commit to function evaluations.
+    type Hasher: ElementHasher<BaseField = E::BaseField>;
+
+    /// Vector commitment scheme used by the prover to commit to function evaluations.
+    type VectorCommitment: VectorCommitment<Self::Hasher>;
+
+    // REQUIRED METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Reads and removes from the channel all layer commitments sent by the prover.
+    ///
+    /// The first commitment is the commitment to the evaluations of the function over the initial
+    /// domain; every subsequent commitment is the commitment made in the corresponding STIR
+    /// round.
+    fn read_stir_layer_commitments(
+        &mut self,
+    ) -> Vec<Vec<<<Self as VerifierChannel<E>>::Hasher as Hasher>::Digest>>;
+
+    /// Reads and removes from the channel the out-of-domain evaluations sent by the prover in
+    /// every STIR round.
+    fn read_stir_ood_evaluations(&mut self) -> Vec<E>;
+
+    /// Reads and removes from the channel the coefficients of the final polynomial.
+    fn read_stir_final_poly(&mut self) -> Vec<E>;
+
+    /// Reads and removes from the channel evaluations of the next committed function at the
+    /// queried cosets.
+    fn take_next_stir_layer_queries(&mut self) -> Vec<E>;
+
+    /// Reads and removes from the channel the opening proof (e.g., Merkle authentication paths)
+    /// for queried evaluations of the next committed function.
+    fn take_next_stir_layer_proof(
+        &mut self,
+    ) -> <Self::VectorCommitment as VectorCommitment<Self::Hasher>>::MultiProof;
+
+    // PROVIDED METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns evaluations of the next committed function at the cosets with the specified
+    /// indexes, one vector of `folding_factor` evaluations per coset.
+    ///
+    /// This also checks if the values are valid against the provided layer commitment.
+    ///
+    /// # Errors
+    /// Returns an error if query values did not match layer commitment.
+    fn read_stir_layer_queries(
+        &mut self,
+        indexes: &[usize],
+        commitment: &[<<Self as VerifierChannel<E>>::Hasher as Hasher>::Digest],
+        folding_factor: usize,
+    ) -> Result<Vec<Vec<E>>, VerifierError> {
+        let layer_proof = self.take_next_stir_layer_proof();
+        <Self::VectorCommitment as VectorCommitment<Self::Hasher>>::verify_many(
+            commitment,
+            indexes,
+            &layer_proof,
+        )
+        .map_err(|_| VerifierError::LayerCommitmentMismatch)?;
+
+        let layer_queries = self.take_next_stir_layer_queries();
+        if layer_queries.len() != indexes.len() * folding_factor {
+            return Err(VerifierError::LayerCommitmentMismatch);
+        }
+        Ok(layer_queries.chunks(folding_factor).map(|values| values.to_vec()).collect())
+    }
+}
+
+// DEFAULT VERIFIER CHANNEL IMPLEMENTATION
+// ================================================================================================
+
+/// Provides a default implementation of the [VerifierChannel] trait.
+///
+/// Default verifier channel can be instantiated directly from a [StirProof] struct. Layer
+/// commitments are expected to be built using the vector commitment scheme `V` (by default, a
+/// Merkle tree).
+///
+/// Though this implementation is primarily intended for testing purposes, it can be used in
+/// production use cases as well.
+pub struct DefaultVerifierChannel<
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H> = MerkleTree<H>,
+> {
+    layer_commitments: Vec<Vec<H::Digest>>,
+    layer_proofs: Vec<V::MultiProof>,
+    layer_queries: Vec<Vec<E>>,
+    ood_evaluations: Vec<E>,
+    final_poly: Vec<E>,
+}
+
+impl<E, H, V> DefaultVerifierChannel<E, H, V>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H>,
+{
+    /// Builds a new verifier channel from the specified [StirProof].
+    ///
+    /// `options` must be the STIR options with which the proof was generated, and
+    /// `commitment_options` must be the options with which the prover built layer commitments.
+    ///
+    /// # Errors
+    /// Returns an error if the specified `proof` could not be parsed correctly.
+    pub fn new(
+        proof: StirProof,
+        layer_commitments: Vec<Vec<H::Digest>>,
+        domain_size: usize,
+        options: &StirOptions,
+        commitment_options: VectorCommitmentOptions,
+    ) -> Result<Self, DeserializationError> {
+        let ood_evaluations = proof.parse_ood_evaluations()?;
+        let final_poly = proof.parse_final_poly()?;
+        let (layer_queries, layer_proofs) =
+            proof.parse_layers::<H, E, V>(domain_size, options, commitment_options)?;
+
+        Ok(DefaultVerifierChannel {
+            layer_commitments,
+            layer_proofs,
+            layer_queries,
+            ood_evaluations,
+            final_poly,
+        })
+    }
+}
+
+impl<E, H, V> VerifierChannel<E> for DefaultVerifierChannel<E, H, V>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H>,
+{
+    type Hasher = H;
+    type VectorCommitment = V;
+
+    fn read_stir_layer_commitments(&mut self) -> Vec<Vec<H::Digest>> {
+        self.layer_commitments.drain(..).collect()
+    }
+
+    fn read_stir_ood_evaluations(&mut self) -> Vec<E> {
+        core::mem::take(&mut self.ood_evaluations)
+    }
+
+    fn read_stir_final_poly(&mut self) -> Vec<E> {
+        core::mem::take(&mut self.final_poly)
+    }
+
+    fn take_next_stir_layer_queries(&mut self) -> Vec<E> {
+        self.layer_queries.remove(0)
+    }
+
+    fn take_next_stir_layer_proof(&mut self) -> V::MultiProof {
+        self.layer_proofs.remove(0)
+    }
+}
@@ -0,0 +1,386 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Contains an implementation of STIR verifier and associated components.
+
+use crate::{
+    utils::{fold_coset, get_coset_points, get_folded_point, sort_and_dedup},
+    StirOptions, VerifierError,
+};
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use crypto::{ElementHasher, HashDomain, RandomCoin, VectorCommitment, VectorCommitmentOptions};
+use math::{polynom, FieldElement};
+
+mod channel;
+pub use channel::{DefaultVerifierChannel, VerifierChannel};
+
+// STIR VERIFIER
+// ================================================================================================
+/// Implements the verifier component of the STIR protocol.
+///
+/// Given a small number of evaluations of some function *f* over domain *D* and a STIR proof, a
+/// STIR verifier determines whether *f* is a polynomial of some bounded degree *d*, such that
+/// *d* < |*D*| / *blowup_factor*.
+///
+/// The verifier is parametrized by the following types:
+///
+/// * `E` specifies the field in which the STIR protocol is executed.
+/// * `C` specifies the type used to simulate prover-verifier interaction. This type is used
+///   as an abstraction for a [StirProof](crate::StirProof). Meaning, the verifier does not
+///   consume a STIR proof directly, but reads it via [VerifierChannel] interface.
+/// * `H` specifies the Hash function used by the prover to commit to function evaluations.
+/// * `R` specifies the public coin used to draw random values.
+///
+/// Proof verification is performed in two phases: commit phase and query phase.
+///
+/// # Commit phase
+/// During the commit phase, which is executed when the verifier is instantiated via
+/// [new()](StirVerifier::new()) function, the verifier reads layer commitments, out-of-domain
+/// evaluations and the final polynomial from the channel, and uses them to update the public coin
+/// and to draw all random values and query indexes in the same order as the prover did.
+///
+/// # Query phase
+/// During the query phase, which is executed via [verify()](StirVerifier::verify()) function,
+/// the verifier reads evaluations of all committed functions at the queried cosets, and checks
+/// that:
+/// * The evaluations are valid against the layer commitments.
+/// * The provided evaluations of *f* are consistent with the first commitment.
+/// * Folding the evaluations of the function of the last round results in evaluations of the
+///   final polynomial. Evaluations of the function of every round are computed from committed
+///   evaluations and from answers derived by folding the evaluations of the previous round.
+pub struct StirVerifier<E, C, H, R>
+where
+    E: FieldElement,
+    C: VerifierChannel<E, Hasher = H>,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+{
+    max_poly_degree: usize,
+    domain_size: usize,
+    options: StirOptions,
+    layer_commitments: Vec<Vec<H::Digest>>,
+    folding_randomness: Vec<E>,
+    query_indexes: Vec<Vec<usize>>,
+    rounds: Vec<RoundState<E>>,
+    final_poly: Vec<E>,
+    _channel: PhantomData<C>,
+    _public_coin: PhantomData<R>,
+}
+
+impl<E, C, H, R> StirVerifier<E, C, H, R>
+where
+    E: FieldElement,
+    C: VerifierChannel<E, Hasher = H>,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+{
+    /// Returns a new instance of STIR verifier created from the specified parameters.
+    ///
+    /// The `max_poly_degree` parameter specifies the highest polynomial degree accepted by the
+    /// returned verifier. In combination with `blowup_factor` from the `options` parameter,
+    /// `max_poly_degree` also defines the domain over which the tested polynomial is evaluated.
+    ///
+    /// Creating a STIR verifier executes the commit phase of the STIR protocol from the
+    /// verifier's perspective. Thus, an instance of STIR verifier can be used to verify only a
+    /// single proof.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The domain implied by `max_poly_degree` is too small for the specified `options`.
+    /// * The number of commitments, out-of-domain evaluations, or coefficients of the final
+    ///   polynomial read from the channel is inconsistent with `max_poly_degree` and `options`.
+    /// * The number of digests in any of the round commitments is inconsistent with the Merkle
+    ///   cap height specified in the `options` parameter.
+    /// * An error was encountered while drawing a random value from the coin.
+    pub fn new(
+        channel: &mut C,
+        public_coin: &mut R,
+        options: StirOptions,
+        max_poly_degree: usize,
+    ) -> Result<Self, VerifierError> {
+        // infer evaluation domain info and make sure all rounds can be executed over it
+        let domain_size = (max_poly_degree + 1).next_power_of_two() * options.blowup_factor();
+        let num_rounds = options.num_rounds(domain_size);
+        for round in 0..=num_rounds {
+            let round_domain_size = options.round_domain_size(domain_size, round);
+            if round_domain_size / options.folding_factor_at(round) < 2 {
+                return Err(VerifierError::DomainTooSmall(domain_size));
+            }
+        }
+
+        // read the data sent by the prover and make sure it is consistent with the options
+        let layer_commitments = channel.read_stir_layer_commitments();
+        if layer_commitments.len() != num_rounds + 1 {
+            return Err(VerifierError::NumCommitmentsMismatch(
+                num_rounds + 1,
+                layer_commitments.len(),
+            ));
+        }
+        for (round, commitment) in layer_commitments.iter().enumerate() {
+            // the cap height is reduced for rounds in which the tree is too small for a cap of
+            // the specified height, in the same way as it is done by the prover
+            let num_cosets =
+                options.round_domain_size(domain_size, round) / options.folding_factor_at(round);
+            let cap_height = options.merkle_cap_height().min(num_cosets.ilog2() as usize - 1);
+            let commitment_options =
+                VectorCommitmentOptions::new(options.merkle_arity(), cap_height);
+            let expected_len = C::VectorCommitment::commitment_len(num_cosets, commitment_options);
+            if commitment.len() != expected_len {
+                return Err(VerifierError::CommitmentSizeMismatch(
+                    round,
+                    expected_len,
+                    commitment.len(),
+                ));
+            }
+        }
+        let ood_evaluations = channel.read_stir_ood_evaluations();
+        if ood_evaluations.len() != num_rounds {
+            return Err(VerifierError::NumOodEvaluationsMismatch(
+                num_rounds,
+                ood_evaluations.len(),
+            ));
+        }
+        let final_poly = channel.read_stir_final_poly();
+        let final_poly_size = options.round_degree(domain_size, num_rounds + 1);
+        if final_poly.len() != final_poly_size {
+            return Err(VerifierError::FinalPolySizeMismatch(final_poly_size, final_poly.len()));
+        }
+
+        // replay the transcript of the commit phase to draw all random values
+        public_coin.reseed(C::VectorCommitment::commitment_digest(&layer_commitments[0]));
+        let mut folding_randomness = Vec::with_capacity(num_rounds + 1);
+        let mut query_indexes = Vec::with_capacity(num_rounds + 1);
+        let mut rounds = Vec::with_capacity(num_rounds);
+        folding_randomness.push(public_coin.draw().map_err(VerifierError::RandomCoinError)?);
+        for (round, &ood_evaluation) in (1..=num_rounds).zip(ood_evaluations.iter()) {
+            public_coin.reseed(C::VectorCommitment::commitment_digest(&layer_commitments[round]));
+            let ood_point = public_coin.draw().map_err(VerifierError::RandomCoinError)?;
+            public_coin
+                .reseed(H::hash_elements_in_domain(&[ood_evaluation], HashDomain::Transcript));
+            let comb_randomness = public_coin.draw().map_err(VerifierError::RandomCoinError)?;
+            folding_randomness.push(public_coin.draw().map_err(VerifierError::RandomCoinError)?);
+            query_indexes.push(draw_queries(public_coin, &options, domain_size, round - 1)?);
+            rounds.push(RoundState {
+                ood_point,
+                ood_evaluation,
+                comb_randomness,
+                points: Vec::new(),
+                answers_poly: Vec::new(),
+            });
+        }
+        public_coin.reseed(H::hash_elements_in_domain(&final_poly, HashDomain::Transcript));
+        query_indexes.push(draw_queries(public_coin, &options, domain_size, num_rounds)?);
+
+        Ok(StirVerifier {
+            max_poly_degree,
+            domain_size,
+            options,
+            layer_commitments,
+            folding_randomness,
+            query_indexes,
+            rounds,
+            final_poly,
+            _channel: PhantomData,
+            _public_coin: PhantomData,
+        })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns maximum degree of a polynomial accepted by this verifier.
+    pub fn max_poly_degree(&self) -> usize {
+        self.max_poly_degree
+    }
+
+    /// Returns size of the domain over which the tested polynomial is evaluated.
+    pub fn domain_size(&self) -> usize {
+        self.domain_size
+    }
+
+    /// Returns the number of STIR rounds executed by this verifier.
+    pub fn num_rounds(&self) -> usize {
+        self.rounds.len()
+    }
+
+    /// Returns protocol configuration options for this verifier.
+    pub fn options(&self) -> &StirOptions {
+        &self.options
+    }
+
+    // VERIFICATION PROCEDURE
+    // --------------------------------------------------------------------------------------------
+    /// Executes the query phase of the STIR protocol.
+    ///
+    /// Returns `Ok(())` if values in the `evaluations` slice represent evaluations of a polynomial
+    /// with degree <= `max_poly_degree` at x coordinates specified by the `positions` slice.
+    ///
+    /// Thus, `positions` parameter represents the positions in the evaluation domain at which the
+    /// verifier queries the tested function, and `evaluations` specifies the evaluations of the
+    /// function at these positions.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The length of `evaluations` is not equal to the length of `positions`.
+    /// * Evaluations of any of the committed functions did not match the commitments.
+    /// * The specified `evaluations` did not match the values committed to by the prover.
+    /// * Folding the function of the last round did not result in the final polynomial.
+    pub fn verify(
+        mut self,
+        channel: &mut C,
+        evaluations: &[E],
+        positions: &[usize],
+    ) -> Result<(), VerifierError> {
+        if evaluations.len() != positions.len() {
+            return Err(VerifierError::NumPositionEvaluationMismatch(
+                positions.len(),
+                evaluations.len(),
+            ));
+        }
+
+        let offset = self.options.domain_offset::<E::BaseField>();
+        let num_rounds = self.rounds.len();
+        for layer in 0..=num_rounds {
+            let domain_size = self.options.round_domain_size(self.domain_size, layer);
+            let folding_factor = self.options.folding_factor_at(layer);
+            let num_cosets = domain_size / folding_factor;
+            let query_indexes = &self.query_indexes[layer];
+
+            // read queried evaluations of the function committed to in this layer; for the
+            // initial function, the cosets containing the specified positions are read as well
+            let indexes = if layer == 0 {
+                let mut indexes = query_indexes.clone();
+                indexes.extend(positions.iter().map(|&p| p % num_cosets));
+                sort_and_dedup(indexes)
+            } else {
+                query_indexes.clone()
+            };
+            let layer_values = channel.read_stir_layer_queries(
+                &indexes,
+                &self.layer_commitments[layer],
+                folding_factor,
+            )?;
+
+            // make sure the evaluations of the initial function match the committed values
+            if layer == 0 {
+                for (&position, &evaluation) in positions.iter().zip(evaluations) {
+                    let row = indexes
+                        .binary_search(&(position % num_cosets))
+                        .expect("position must be among the queried cosets");
+                    if layer_values[row][position / num_cosets] != evaluation {
+                        return Err(VerifierError::InitialEvaluationsMismatch);
+                    }
+                }
+            }
+
+            // fold evaluations of the function of this layer at all queried cosets; for layers
+            // other than the first one, the committed evaluations are first converted into the
+            // evaluations of the function of the round
+            let mut folded_values = Vec::with_capacity(query_indexes.len());
+            let mut folded_points = Vec::with_capacity(query_indexes.len());
+            for &idx in query_indexes.iter() {
+                let row =
+                    indexes.binary_search(&idx).expect("index must be among the queried cosets");
+                let points = get_coset_points(domain_size, offset, folding_factor, idx);
+                let values = if layer == 0 {
+                    layer_values[row].clone()
+                } else {
+                    let round = &self.rounds[layer - 1];
+                    points
+                        .iter()
+                        .zip(layer_values[row].iter())
+                        .map(|(&x, &value)| round.evaluate(E::from(x), value))
+                        .collect()
+                };
+                folded_values.push(fold_coset(&points, &values, self.folding_randomness[layer]));
+                folded_points.push(E::from(get_folded_point(
+                    domain_size,
+                    offset,
+                    folding_factor,
+                    idx,
+                )));
+            }
+
+            if layer < num_rounds {
+                // the folded values are the answers at the shift points of the next round
+                self.rounds[layer].set_answers(folded_points, folded_values);
+            } else {
+                // the folded values must be evaluations of the final polynomial
+                for (&point, &value) in folded_points.iter().zip(folded_values.iter()) {
+                    if polynom::eval(&self.final_poly, point) != value {
+                        return Err(VerifierError::InvalidFinalFolding);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// ROUND STATE
+// ================================================================================================
+
+/// Randomness and answers of a single STIR round needed to compute evaluations of the function of
+/// the round from the committed evaluations.
+struct RoundState<E: FieldElement> {
+    ood_point: E,
+    ood_evaluation: E,
+    comb_randomness: E,
+    points: Vec<E>,
+    answers_poly: Vec<E>,
+}
+
+impl<E: FieldElement> RoundState<E> {
+    /// Sets the answers at the shift points of this round, and interpolates the polynomial
+    /// through all answers of the round (including the answer at the out-of-domain point).
+    fn set_answers(&mut self, shift_points: Vec<E>, shift_answers: Vec<E>) {
+        let mut points = vec![self.ood_point];
+        points.extend(shift_points);
+        let mut answers = vec![self.ood_evaluation];
+        answers.extend(shift_answers);
+        self.answers_poly = polynom::interpolate(&points, &answers, false);
+        self.points = points;
+    }
+
+    /// Returns the evaluation of the function of this round at `x`, given the evaluation of the
+    /// committed function at `x`.
+    ///
+    /// This computes (g(x) - ans(x)) / V(x) * (1 + r * x + ... + (r * x)^|G|), where g is the
+    /// committed function, ans is the polynomial through the answers of the round, V is the
+    /// vanishing polynomial of the set of points G of the round, and r is the combination
+    /// randomness.
+    fn evaluate(&self, x: E, value: E) -> E {
+        let vanishing = self.points.iter().fold(E::ONE, |acc, &point| acc * (x - point));
+        let num_points = self.points.len() as u32;
+        let rx = self.comb_randomness * x;
+        let correction = if rx == E::ONE {
+            E::from(num_points + 1)
+        } else {
+            (rx.exp_vartime((num_points + 1).into()) - E::ONE) / (rx - E::ONE)
+        };
+        (value - polynom::eval(&self.answers_poly, x)) / vanishing * correction
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Draws query indexes into the domain of the folded function of the specified round.
+fn draw_queries<R: RandomCoin>(
+    public_coin: &mut R,
+    options: &StirOptions,
+    domain_size: usize,
+    round: usize,
+) -> Result<Vec<usize>, VerifierError> {
+    let num_cosets =
+        options.round_domain_size(domain_size, round) / options.folding_factor_at(round);
+    let num_queries = options.num_round_queries(domain_size, round);
+    let indexes = public_coin
+        .draw_integers(num_queries, num_cosets, 0)
+        .map_err(VerifierError::RandomCoinError)?;
+    Ok(sort_and_dedup(indexes))
+}
@@ -5,13 +5,14 @@
 
 use crate::VerifierError;
 use air::{
-    proof::{Queries, StarkProof, Table},
-    Air, EvaluationFrame,
+    proof::{LowDegreeProof, Queries, StarkProof, Table},
+    Air, EvaluationFrame, LowDegreeTest,
 };
 use alloc::{string::ToString, vec::Vec};
-use crypto::{BatchMerkleProof, ElementHasher, MerkleTree};
+use crypto::{ElementHasher, VectorCommitment, VectorCommitmentOptions};
 use fri::VerifierChannel as FriVerifierChannel;
 use math::{FieldElement, StarkField};
+use stir::VerifierChannel as StirVerifierChannel;
 
 // VERIFIER CHANNEL
 // ================================================================================================
@@ -20,28 +21,45 @@ use math::{FieldElement, StarkField};
 ///
 /// A channel is instantiated for a specific proof, which is parsed into structs over the
 /// appropriate field (specified by type parameter `E`). This also validates that the proof is
-/// well-formed in the context of the computation for the specified [Air].
-pub struct VerifierChannel<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
+/// well-formed in the context of the computation for the specified [Air], with all commitments
+/// built using the vector commitment scheme specified by type parameter `V`.
+pub struct VerifierChannel<
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H>,
+> {
     // trace queries
-    trace_roots: Vec<H::Digest>,
-    trace_queries: Option<TraceQueries<E, H>>,
+    trace_caps: Vec<Vec<H::Digest>>,
+    trace_queries: Option<TraceQueries<E, H, V>>,
     // constraint queries
-    constraint_root: H::Digest,
-    constraint_queries: Option<ConstraintQueries<E, H>>,
+    constraint_cap: Vec<H::Digest>,
+    constraint_queries: Option<ConstraintQueries<E, H, V>>,
+    // low-degree proof
+    ldt_caps: Option<Vec<Vec<H::Digest>>>,
+    ldt_layer_proofs: Vec<V::MultiProof>,
+    ldt_layer_queries: Vec<Vec<E>>,
     // FRI proof
-    fri_roots: Option<Vec<H::Digest>>,
-    fri_layer_proofs: Vec<BatchMerkleProof<H>>,
-    fri_layer_queries: Vec<Vec<E>>,
     fri_remainder: Option<Vec<E>>,
     fri_num_partitions: usize,
+    fri_pow_nonces: Vec<u64>,
+    // STIR proof
+    stir_ood_evaluations: Option<Vec<E>>,
+    stir_final_poly: Option<Vec<E>>,
     // out-of-domain frame
     ood_trace_frame: Option<TraceOodFrame<E>>,
     ood_constraint_evaluations: Option<Vec<E>>,
     // query proof-of-work
     pow_nonce: u64,
+    // DEEP coefficients proof-of-work
+    deep_pow_nonce: u64,
 }
 
-impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChannel<E, H> {
+impl<E, H, V> VerifierChannel<E, H, V>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H>,
+{
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     /// Creates and returns a new [VerifierChannel] initialized from the specified `proof`.
@@ -56,8 +74,9 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
             trace_queries,
             constraint_queries,
             ood_frame,
-            fri_proof,
+            ldt_proof,
             pow_nonce,
+            deep_pow_nonce,
         } = proof;
 
         // make sure AIR and proof base fields are the same
@@ -70,25 +89,105 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
         let main_trace_width = air.trace_layout().main_trace_width();
         let aux_trace_width = air.trace_layout().aux_trace_width();
         let lde_domain_size = air.lde_domain_size();
-        let fri_options = air.options().to_fri_options();
+
+        // the cap height is reduced when the LDE domain is too small for a cap of the specified
+        // height, in the same way as it is done by the prover
+        let cap_height =
+            air.options().merkle_cap_height().min(lde_domain_size.ilog2() as usize - 1);
+        let trace_commitment_options =
+            VectorCommitmentOptions::new(air.options().trace_merkle_arity(), cap_height);
+        let constraint_commitment_options =
+            VectorCommitmentOptions::new(air.options().constraint_merkle_arity(), cap_height);
+        let ldt_commitment_options = VectorCommitmentOptions::new(
+            air.options().fri_merkle_arity(),
+            air.options().merkle_cap_height(),
+        );
+
+        // make sure the low-degree proof was generated using the protocol specified in the options
+        if ldt_proof.low_degree_test() != air.options().low_degree_test() {
+            return Err(VerifierError::ProofDeserializationError(
+                "low-degree proof is inconsistent with proof options".to_string(),
+            ));
+        }
+        let num_ldt_layers = match air.options().low_degree_test() {
+            LowDegreeTest::Fri => air.options().to_fri_options().num_fri_layers(lde_domain_size),
+            LowDegreeTest::Stir => air.options().to_stir_options().num_rounds(lde_domain_size),
+        };
 
         // --- parse commitments ------------------------------------------------------------------
-        let (trace_roots, constraint_root, fri_roots) = commitments
-            .parse::<H>(num_trace_segments, fri_options.num_fri_layers(lde_domain_size))
+        let (trace_caps, constraint_cap, ldt_caps) = commitments
+            .parse::<H>(num_trace_segments, num_ldt_layers)
             .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
 
+        // make sure trace and constraint commitments consist of as many digests as implied by
+        // the cap height; commitments to low-degree proof layers are checked by FRI and STIR
+        // verifiers
+        let trace_cap_len = V::commitment_len(lde_domain_size, trace_commitment_options);
+        let constraint_cap_len = V::commitment_len(lde_domain_size, constraint_commitment_options);
+        if trace_caps.iter().any(|cap| cap.len() != trace_cap_len)
+            || constraint_cap.len() != constraint_cap_len
+        {
+            return Err(VerifierError::ProofDeserializationError(
+                "number of digests in trace or constraint commitments is inconsistent with Merkle cap height"
+                    .to_string(),
+            ));
+        }
+
         // --- parse trace and constraint queries -------------------------------------------------
-        let trace_queries = TraceQueries::new(trace_queries, air, num_unique_queries as usize)?;
-        let constraint_queries =
-            ConstraintQueries::new(constraint_queries, air, num_unique_queries as usize)?;
-
-        // --- parse FRI proofs -------------------------------------------------------------------
-        let fri_num_partitions = fri_proof.num_partitions();
-        let fri_remainder = fri_proof
-            .parse_remainder()
-            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
-        let (fri_layer_queries, fri_layer_proofs) = fri_proof
-            .parse_layers::<H, E>(lde_domain_size, fri_options.folding_factor())
+        let trace_queries = TraceQueries::new(
+            trace_queries,
+            air,
+            num_unique_queries as usize,
+            trace_commitment_options,
+        )?;
+        let constraint_queries = ConstraintQueries::new(
+            constraint_queries,
+            air,
+            num_unique_queries as usize,
+            constraint_commitment_options,
+        )?;
+
+        // --- parse low-degree proof -------------------------------------------------------------
+        let mut fri_num_partitions = 0;
+        let mut fri_remainder = None;
+        let mut fri_pow_nonces = Vec::new();
+        let mut stir_ood_evaluations = None;
+        let mut stir_final_poly = None;
+        let (ldt_layer_queries, ldt_layer_proofs) =
+            match ldt_proof {
+                LowDegreeProof::Fri(fri_proof) => {
+                    let fri_options = air.options().to_fri_options();
+                    if fri_proof.remainder_format() != fri_options.remainder_format() {
+                        return Err(VerifierError::ProofDeserializationError(
+                            "FRI remainder format is inconsistent with proof options".to_string(),
+                        ));
+                    }
+                    fri_num_partitions = fri_proof.num_partitions();
+                    fri_pow_nonces = fri_proof.pow_nonces().to_vec();
+                    fri_remainder = Some(fri_proof.parse_remainder().map_err(|err| {
+                        VerifierError::ProofDeserializationError(err.to_string())
+                    })?);
+                    fri_proof.parse_layers::<H, E, V>(
+                        lde_domain_size,
+                        &fri_options,
+                        ldt_commitment_options,
+                    )
+                }
+                LowDegreeProof::Stir(stir_proof) => {
+                    stir_ood_evaluations =
+                        Some(stir_proof.parse_ood_evaluations().map_err(|err| {
+                            VerifierError::ProofDeserializationError(err.to_string())
+                        })?);
+                    stir_final_poly = Some(stir_proof.parse_final_poly().map_err(|err| {
+                        VerifierError::ProofDeserializationError(err.to_string())
+                    })?);
+                    stir_proof.parse_layers::<H, E, V>(
+                        lde_domain_size,
+                        &air.options().to_stir_options(),
+                        ldt_commitment_options,
+                    )
+                }
+            }
             .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
 
         // --- parse out-of-domain evaluation frame -----------------------------------------------
@@ -100,22 +199,29 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
 
         Ok(VerifierChannel {
             // trace queries
-            trace_roots,
+            trace_caps,
             trace_queries: Some(trace_queries),
             // constraint queries
-            constraint_root,
+            constraint_cap,
             constraint_queries: Some(constraint_queries),
+            // low-degree proof
+            ldt_caps: Some(ldt_caps),
+            ldt_layer_proofs,
+            ldt_layer_queries,
             // FRI proof
-            fri_roots: Some(fri_roots),
-            fri_layer_proofs,
-            fri_layer_queries,
-            fri_remainder: Some(fri_remainder),
+            fri_remainder,
             fri_num_partitions,
+            fri_pow_nonces,
+            // STIR proof
+            stir_ood_evaluations,
+            stir_final_poly,
             // out-of-domain evaluation
             ood_trace_frame: Some(ood_trace_frame),
             ood_constraint_evaluations: Some(ood_constraint_evaluations),
             // query seed
             pow_nonce,
+            // DEEP coefficients seed
+            deep_pow_nonce,
         })
     }
 
@@ -124,15 +230,17 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
 
     /// Returns execution trace commitments sent by the prover.
     ///
-    /// For computations requiring multiple trace segment, the returned slice will contain a
-    /// commitment for each trace segment.
-    pub fn read_trace_commitments(&self) -> &[H::Digest] {
-        &self.trace_roots
+    /// Each commitment is a Merkle tree cap. For computations requiring multiple trace segment,
+    /// the returned slice will contain a commitment for each trace segment.
+    pub fn read_trace_commitments(&self) -> &[Vec<H::Digest>] {
+        &self.trace_caps
     }
 
     /// Returns constraint evaluation commitment sent by the prover.
-    pub fn read_constraint_commitment(&self) -> H::Digest {
-        self.constraint_root
+    ///
+    /// The commitment is a Merkle tree cap.
+    pub fn read_constraint_commitment(&self) -> &[H::Digest] {
+        &self.constraint_cap
     }
 
     /// Returns trace polynomial evaluations at out-of-domain points z and z * g, where g is the
@@ -155,6 +263,12 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
         self.pow_nonce
     }
 
+    /// Returns proof-of-work nonce sent by the prover before DEEP composition coefficients
+    /// were drawn.
+    pub fn read_deep_pow_nonce(&self) -> u64 {
+        self.deep_pow_nonce
+    }
+
     /// Returns trace states at the specified positions of the LDE domain. This also checks if
     /// the trace states are valid against the trace commitment sent by the prover.
     ///
@@ -169,8 +283,8 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
         let queries = self.trace_queries.take().expect("already read");
 
         // make sure the states included in the proof correspond to the trace commitment
-        for (root, proof) in self.trace_roots.iter().zip(queries.query_proofs.iter()) {
-            MerkleTree::verify_batch(root, positions, proof)
+        for (cap, proof) in self.trace_caps.iter().zip(queries.query_proofs.iter()) {
+            V::verify_many(cap, positions, proof)
                 .map_err(|_| VerifierError::TraceQueryDoesNotMatchCommitment)?;
         }
 
@@ -186,7 +300,7 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
     ) -> Result<Table<E>, VerifierError> {
         let queries = self.constraint_queries.take().expect("already read");
 
-        MerkleTree::verify_batch(&self.constraint_root, positions, &queries.query_proofs)
+        V::verify_many(&self.constraint_cap, positions, &queries.query_proofs)
             .map_err(|_| VerifierError::ConstraintQueryDoesNotMatchCommitment)?;
 
         Ok(queries.evaluations)
@@ -196,27 +310,33 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
 // FRI VERIFIER CHANNEL IMPLEMENTATION
 // ================================================================================================
 
-impl<E, H> FriVerifierChannel<E> for VerifierChannel<E, H>
+impl<E, H, V> FriVerifierChannel<E> for VerifierChannel<E, H, V>
 where
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H>,
 {
     type Hasher = H;
+    type VectorCommitment = V;
 
     fn read_fri_num_partitions(&self) -> usize {
         self.fri_num_partitions
     }
 
-    fn read_fri_layer_commitments(&mut self) -> Vec<H::Digest> {
-        self.fri_roots.take().expect("already read")
+    fn read_fri_layer_commitments(&mut self) -> Vec<Vec<H::Digest>> {
+        self.ldt_caps.take().expect("already read")
     }
 
-    fn take_next_fri_layer_proof(&mut self) -> BatchMerkleProof<H> {
-        self.fri_layer_proofs.remove(0)
+    fn read_fri_pow_nonces(&mut self) -> Vec<u64> {
+        self.fri_pow_nonces.drain(..).collect()
+    }
+
+    fn take_next_fri_layer_proof(&mut self) -> V::MultiProof {
+        self.ldt_layer_proofs.remove(0)
     }
 
     fn take_next_fri_layer_queries(&mut self) -> Vec<E> {
-        self.fri_layer_queries.remove(0)
+        self.ldt_layer_queries.remove(0)
     }
 
     fn take_fri_remainder(&mut self) -> Vec<E> {
@@ -224,27 +344,70 @@ where
     }
 }
 
+// STIR VERIFIER CHANNEL IMPLEMENTATION
+// ================================================================================================
+
+impl<E, H, V> StirVerifierChannel<E> for VerifierChannel<E, H, V>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H>,
+{
+    type Hasher = H;
+    type VectorCommitment = V;
+
+    fn read_stir_layer_commitments(&mut self) -> Vec<Vec<H::Digest>> {
+        self.ldt_caps.take().expect("already read")
+    }
+
+    fn read_stir_ood_evaluations(&mut self) -> Vec<E> {
+        self.stir_ood_evaluations.take().expect("already read")
+    }
+
+    fn read_stir_final_poly(&mut self) -> Vec<E> {
+        self.stir_final_poly.take().expect("already read")
+    }
+
+    fn take_next_stir_layer_queries(&mut self) -> Vec<E> {
+        self.ldt_layer_queries.remove(0)
+    }
+
+    fn take_next_stir_layer_proof(&mut self) -> V::MultiProof {
+        self.ldt_layer_proofs.remove(0)
+    }
+}
+
 // TRACE QUERIES
 // ================================================================================================
 
 /// Container of trace query data, including:
 /// * Queried states for all trace segments.
-/// * Merkle authentication paths for all queries.
+/// * Opening proofs for all queries.
 ///
 /// Trace states for all auxiliary segments are stored in a single table.
-struct TraceQueries<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
-    query_proofs: Vec<BatchMerkleProof<H>>,
+struct TraceQueries<
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H>,
+> {
+    query_proofs: Vec<V::MultiProof>,
     main_states: Table<E::BaseField>,
     aux_states: Option<Table<E>>,
 }
 
-impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceQueries<E, H> {
+impl<E, H, V> TraceQueries<E, H, V>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H>,
+{
     /// Parses the provided trace queries into trace states in the specified field and
-    /// corresponding Merkle authentication paths.
+    /// corresponding opening proofs.
     pub fn new<A: Air<BaseField = E::BaseField>>(
         mut queries: Vec<Queries>,
         air: &A,
         num_queries: usize,
+        commitment_options: VectorCommitmentOptions,
     ) -> Result<Self, VerifierError> {
         assert_eq!(
             queries.len(),
@@ -255,11 +418,17 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceQueries<E
         );
 
         // parse main trace segment queries; parsing also validates that hashes of each table row
-        // form the leaves of Merkle authentication paths in the proofs
+        // form the items opened by the proofs
         let main_segment_width = air.trace_layout().main_trace_width();
         let main_segment_queries = queries.remove(0);
         let (main_segment_query_proofs, main_segment_states) = main_segment_queries
-            .parse::<H, E::BaseField>(air.lde_domain_size(), num_queries, main_segment_width)
+            .parse::<H, E::BaseField, V>(
+                air.lde_domain_size(),
+                num_queries,
+                main_segment_width,
+                air.options().trace_partition_size(),
+                commitment_options,
+            )
             .map_err(|err| {
                 VerifierError::ProofDeserializationError(format!(
                     "main trace segment query deserialization failed: {err}"
@@ -270,14 +439,20 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceQueries<E
         let mut query_proofs = vec![main_segment_query_proofs];
 
         // parse auxiliary trace segment queries (if any), and merge resulting tables into a
-        // single table; parsing also validates that hashes of each table row form the leaves
-        // of Merkle authentication paths in the proofs
+        // single table; parsing also validates that hashes of each table row form the items
+        // opened by the proofs
         let aux_trace_states = if air.trace_info().is_multi_segment() {
             let mut aux_trace_states = Vec::new();
             for (i, segment_queries) in queries.into_iter().enumerate() {
                 let segment_width = air.trace_layout().get_aux_segment_width(i);
                 let (segment_query_proof, segment_trace_states) = segment_queries
-                    .parse::<H, E>(air.lde_domain_size(), num_queries, segment_width)
+                    .parse::<H, E, V>(
+                        air.lde_domain_size(),
+                        num_queries,
+                        segment_width,
+                        air.options().trace_partition_size(),
+                        commitment_options,
+                    )
                     .map_err(|err| {
                         VerifierError::ProofDeserializationError(format!(
                             "auxiliary trace segment query deserialization failed: {err}"
@@ -307,24 +482,40 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceQueries<E
 
 /// Container of constraint evaluation query data, including:
 /// * Queried constraint evaluation values.
-/// * Merkle authentication paths for all queries.
-struct ConstraintQueries<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
-    query_proofs: BatchMerkleProof<H>,
+/// * Opening proof for all queries.
+struct ConstraintQueries<
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H>,
+> {
+    query_proofs: V::MultiProof,
     evaluations: Table<E>,
 }
 
-impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> ConstraintQueries<E, H> {
+impl<E, H, V> ConstraintQueries<E, H, V>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H>,
+{
     /// Parses the provided constraint queries into evaluations in the specified field and
-    /// corresponding Merkle authentication paths.
+    /// corresponding opening proof.
     pub fn new<A: Air<BaseField = E::BaseField>>(
         queries: Queries,
         air: &A,
         num_queries: usize,
+        commitment_options: VectorCommitmentOptions,
     ) -> Result<Self, VerifierError> {
         let constraint_frame_width = air.context().num_constraint_composition_columns();
 
         let (query_proofs, evaluations) = queries
-            .parse::<H, E>(air.lde_domain_size(), num_queries, constraint_frame_width)
+            .parse::<H, E, V>(
+                air.lde_domain_size(),
+                num_queries,
+                constraint_frame_width,
+                air.options().constraint_partition_size(),
+                commitment_options,
+            )
             .map_err(|err| {
                 VerifierError::ProofDeserializationError(format!(
                     "constraint evaluation query deserialization failed: {err}"
@@ -37,10 +37,18 @@ pub enum VerifierError {
     /// coin resolves to a value which does not meet the proof-of-work threshold specified by the
     // proof options.
     QuerySeedProofOfWorkVerificationFailed,
+    /// This error occurs when the proof-of-work nonce sent by the prover before DEEP composition
+    /// coefficients are drawn does not meet the commit-phase proof-of-work threshold specified
+    /// by the proof options.
+    DeepProofOfWorkVerificationFailed,
     /// This error occurs when the DEEP composition polynomial evaluations derived from trace and
     /// constraint evaluation queries do not represent a polynomial of the degree expected by the
     /// verifier.
     FriVerificationFailed(fri::VerifierError),
+    /// This error occurs when the DEEP composition polynomial evaluations derived from trace and
+    /// constraint evaluation queries do not represent a polynomial of the degree expected by the
+    /// verifier, and STIR protocol was used as the low-degree test.
+    StirVerificationFailed(stir::VerifierError),
     /// This error occurs when the parameters, that were used to generate the proof, do not provide
     /// a conjectured security level greater than or equal to the conjectured security level
     /// expected by the verifier.
@@ -82,9 +90,15 @@ impl fmt::Display for VerifierError {
             Self::QuerySeedProofOfWorkVerificationFailed => {
                 write!(f, "query seed proof-of-work verification failed")
             }
+            Self::DeepProofOfWorkVerificationFailed => {
+                write!(f, "DEEP composition coefficients proof-of-work verification failed")
+            }
             Self::FriVerificationFailed(err) => {
                 write!(f, "verification of low-degree proof failed: {err}")
             }
+            Self::StirVerificationFailed(err) => {
+                write!(f, "verification of low-degree proof failed: {err}")
+            }
             Self::InsufficientConjecturedSecurity(minimal_security, proof_security)=> {
                 write!(f, "insufficient proof security level: expected at least {minimal_security} bits of conjectured security, but was {proof_security} bits")
             }
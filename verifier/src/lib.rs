@@ -34,8 +34,8 @@ extern crate alloc;
 pub use air::{
     proof::StarkProof, Air, AirContext, Assertion, AuxTraceRandElements, BoundaryConstraint,
     BoundaryConstraintGroup, ConstraintCompositionCoefficients, ConstraintDivisor,
-    DeepCompositionCoefficients, EvaluationFrame, FieldExtension, ProofOptions, TraceInfo,
-    TransitionConstraintDegree,
+    DeepCompositionCoefficients, EvaluationFrame, FieldExtension, LowDegreeTest, ProofOptions,
+    TraceInfo, TransitionConstraintDegree,
 };
 
 pub use math;
@@ -55,9 +55,10 @@ pub use utils::{
 };
 
 pub use crypto;
-use crypto::{ElementHasher, Hasher, RandomCoin};
+use crypto::{ElementHasher, HashDomain, Hasher, RandomCoin, VectorCommitment};
 
 use fri::FriVerifier;
+use stir::StirVerifier;
 
 mod channel;
 use channel::VerifierChannel;
@@ -79,14 +80,17 @@ pub use errors::VerifierError;
 /// the provided `proof` attests to the correct execution of the computation against public inputs
 /// specified by `pub_inputs`. If the verification is successful, `Ok(())` is returned.
 ///
+/// The `VC` type parameter specifies the vector commitment scheme the prover used to commit to
+/// the execution trace, constraint evaluations, and FRI layers (e.g.,
+/// [MerkleTree](crypto::MerkleTree)).
+///
 /// # Errors
 /// Returns an error if combination of the provided proof and public inputs does not attest to
 /// a correct execution of the computation. This could happen for many various reasons, including:
 /// - The specified proof was generated for a different computation.
 /// - The specified proof was generated for this computation but for different public inputs.
 /// - The specified proof was generated with parameters not providing an acceptable security level.
-#[rustfmt::skip]
-pub fn verify<AIR, HashFn, RandCoin>(
+pub fn verify<AIR, HashFn, RandCoin, VC>(
     proof: StarkProof,
     pub_inputs: AIR::PublicInputs,
     acceptable_options: &AcceptableOptions,
@@ -95,10 +99,38 @@ where
     AIR: Air,
     HashFn: ElementHasher<BaseField = AIR::BaseField>,
     RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+    VC: VectorCommitment<HashFn>,
+{
+    verify_with_coin::<AIR, HashFn, RandCoin, VC>(proof, pub_inputs, acceptable_options).0
+}
+
+/// Verifies that the specified computation was executed correctly against the specified inputs,
+/// and returns the result of the verification together with the public coin used by the verifier.
+///
+/// The verification is performed in the same way as in [verify()]. The coin is returned in the
+/// state it was in when the verification completed or failed; it is `None` only if the
+/// verification failed before the coin was instantiated (e.g., because the proof was generated
+/// with unacceptable parameters). This is useful when `RandCoin` is a
+/// [RecordingRandomCoin](crypto::RecordingRandomCoin): the transcript of all interactions with
+/// the coin can be compared against the transcript recorded by the prover to find the step at
+/// which the two diverged.
+#[rustfmt::skip]
+pub fn verify_with_coin<AIR, HashFn, RandCoin, VC>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    acceptable_options: &AcceptableOptions,
+) -> (Result<(), VerifierError>, Option<RandCoin>)
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+    VC: VectorCommitment<HashFn>,
 {
     // check that `proof` was generated with an acceptable set of parameters from the point of view
     // of the verifier
-    acceptable_options.validate::<HashFn>(&proof)?;
+    if let Err(err) = acceptable_options.validate::<HashFn>(&proof) {
+        return (Err(err), None);
+    }
 
     // build a seed for the public coin; the initial seed is a hash of the proof context and the
     // public inputs, but as the protocol progresses, the coin will be reseeded with the info
@@ -113,25 +145,31 @@ where
     // of static dispatch for selecting two generic parameter: extension field and hash function.
     match air.options().field_extension() {
         FieldExtension::None => {
-            let public_coin = RandCoin::new(&public_coin_seed);
-            let channel = VerifierChannel::new(&air, proof)?;
-            perform_verification::<AIR, AIR::BaseField, HashFn, RandCoin>(air, channel, public_coin)
+            let mut public_coin = RandCoin::new(&public_coin_seed);
+            let result = VerifierChannel::new(&air, proof).and_then(|channel| {
+                perform_verification::<AIR, AIR::BaseField, HashFn, RandCoin, VC>(air, channel, &mut public_coin)
+            });
+            (result, Some(public_coin))
         },
         FieldExtension::Quadratic => {
             if !<QuadExtension<AIR::BaseField>>::is_supported() {
-                return Err(VerifierError::UnsupportedFieldExtension(2));
+                return (Err(VerifierError::UnsupportedFieldExtension(2)), None);
             }
-            let public_coin = RandCoin::new(&public_coin_seed);
-            let channel = VerifierChannel::new(&air, proof)?;
-            perform_verification::<AIR, QuadExtension<AIR::BaseField>, HashFn, RandCoin>(air, channel, public_coin)
+            let mut public_coin = RandCoin::new(&public_coin_seed);
+            let result = VerifierChannel::new(&air, proof).and_then(|channel| {
+                perform_verification::<AIR, QuadExtension<AIR::BaseField>, HashFn, RandCoin, VC>(air, channel, &mut public_coin)
+            });
+            (result, Some(public_coin))
         },
         FieldExtension::Cubic => {
             if !<CubeExtension<AIR::BaseField>>::is_supported() {
-                return Err(VerifierError::UnsupportedFieldExtension(3));
+                return (Err(VerifierError::UnsupportedFieldExtension(3)), None);
             }
-            let public_coin = RandCoin::new(&public_coin_seed);
-            let channel = VerifierChannel::new(&air, proof)?;
-            perform_verification::<AIR, CubeExtension<AIR::BaseField>, HashFn, RandCoin>(air, channel, public_coin)
+            let mut public_coin = RandCoin::new(&public_coin_seed);
+            let result = VerifierChannel::new(&air, proof).and_then(|channel| {
+                perform_verification::<AIR, CubeExtension<AIR::BaseField>, HashFn, RandCoin, VC>(air, channel, &mut public_coin)
+            });
+            (result, Some(public_coin))
         },
     }
 }
@@ -140,16 +178,17 @@ where
 // ================================================================================================
 /// Performs the actual verification by reading the data from the `channel` and making sure it
 /// attests to a correct execution of the computation specified by the provided `air`.
-fn perform_verification<A, E, H, R>(
+fn perform_verification<A, E, H, R, V>(
     air: A,
-    mut channel: VerifierChannel<E, H>,
-    mut public_coin: R,
+    mut channel: VerifierChannel<E, H, V>,
+    public_coin: &mut R,
 ) -> Result<(), VerifierError>
 where
     A: Air,
     E: FieldElement<BaseField = A::BaseField>,
     H: ElementHasher<BaseField = A::BaseField>,
     R: RandomCoin<BaseField = A::BaseField, Hasher = H>,
+    V: VectorCommitment<H>,
 {
     // 1 ----- trace commitment -------------------------------------------------------------------
     // Read the commitments to evaluations of the trace polynomials over the LDE domain sent by the
@@ -162,22 +201,24 @@ where
     // constraint composition polynomial.
     let trace_commitments = channel.read_trace_commitments();
 
-    // reseed the coin with the commitment to the main trace segment
-    public_coin.reseed(trace_commitments[0]);
+    // reseed the coin with the commitment to the main trace segment; since commitments may
+    // consist of several digests (e.g., Merkle tree caps), the coin is reseeded with the digests
+    // which bind these commitments (e.g., the tree roots implied by the caps)
+    public_coin.reseed(V::commitment_digest(&trace_commitments[0]));
 
     // process auxiliary trace segments (if any), to build a set of random elements for each segment
     let mut aux_trace_rand_elements = AuxTraceRandElements::<E>::new();
     for (i, commitment) in trace_commitments.iter().skip(1).enumerate() {
         let rand_elements = air
-            .get_aux_trace_segment_random_elements(i, &mut public_coin)
+            .get_aux_trace_segment_random_elements(i, public_coin)
             .map_err(|_| VerifierError::RandomCoinError)?;
         aux_trace_rand_elements.add_segment_elements(rand_elements);
-        public_coin.reseed(*commitment);
+        public_coin.reseed(V::commitment_digest(commitment));
     }
 
     // build random coefficients for the composition polynomial
     let constraint_coeffs = air
-        .get_constraint_composition_coefficients(&mut public_coin)
+        .get_constraint_composition_coefficients(public_coin)
         .map_err(|_| VerifierError::RandomCoinError)?;
 
     // 2 ----- constraint commitment --------------------------------------------------------------
@@ -187,7 +228,7 @@ where
     // to the prover, and the prover evaluates trace and constraint composition polynomials at z,
     // and sends the results back to the verifier.
     let constraint_commitment = channel.read_constraint_commitment();
-    public_coin.reseed(constraint_commitment);
+    public_coin.reseed(V::commitment_digest(constraint_commitment));
     let z = public_coin.draw::<E>().map_err(|_| VerifierError::RandomCoinError)?;
 
     // 3 ----- OOD consistency check --------------------------------------------------------------
@@ -208,7 +249,8 @@ where
         aux_trace_rand_elements,
         z,
     );
-    public_coin.reseed(H::hash_elements(ood_trace_frame.values()));
+    public_coin
+        .reseed(H::hash_elements_in_domain(ood_trace_frame.values(), HashDomain::Transcript));
 
     // read evaluations of composition polynomial columns sent by the prover, and reduce them into
     // a single value by computing \sum_{i=0}^{m-1}(z^(i * l) * value_i), where value_i is the
@@ -225,20 +267,31 @@ where
             .fold(E::ZERO, |result, (i, &value)| {
                 result + z.exp_vartime(((i * (air.trace_length())) as u32).into()) * value
             });
-    public_coin.reseed(H::hash_elements(&ood_constraint_evaluations));
+    public_coin
+        .reseed(H::hash_elements_in_domain(&ood_constraint_evaluations, HashDomain::Transcript));
 
     // finally, make sure the values are the same
     if ood_constraint_evaluation_1 != ood_constraint_evaluation_2 {
         return Err(VerifierError::InconsistentOodConstraintEvaluations);
     }
 
-    // 4 ----- FRI commitments --------------------------------------------------------------------
+    // 4 ----- low-degree test commitments -------------------------------------------------------
     // draw coefficients for computing DEEP composition polynomial from the public coin; in the
     // interactive version of the protocol, the verifier sends these coefficients to the prover
     // and the prover uses them to compute the DEEP composition polynomial. the prover, then
-    // applies FRI protocol to the evaluations of the DEEP composition polynomial.
+    // applies FRI (or STIR) protocol to the evaluations of the DEEP composition polynomial.
+    // if commit-phase grinding is enabled, the proof-of-work nonce sent by the prover must be
+    // checked and absorbed into the public coin before the coefficients are drawn.
+    let commit_grinding_factor = air.options().commit_grinding_factor();
+    if commit_grinding_factor > 0 {
+        let deep_pow_nonce = channel.read_deep_pow_nonce();
+        if public_coin.check_leading_zeros(deep_pow_nonce) < commit_grinding_factor {
+            return Err(VerifierError::DeepProofOfWorkVerificationFailed);
+        }
+        public_coin.reseed_with_int(deep_pow_nonce);
+    }
     let deep_coefficients = air
-        .get_deep_composition_coefficients::<E, R>(&mut public_coin)
+        .get_deep_composition_coefficients::<E, R>(public_coin)
         .map_err(|_| VerifierError::RandomCoinError)?;
 
     // instantiates a FRI verifier with the FRI layer commitments read from the channel. From the
@@ -246,14 +299,31 @@ where
     // The verifier uses these commitments to update the public coin and draw random points alpha
     // from them; in the interactive version of the protocol, the verifier sends these alphas to
     // the prover, and the prover uses them to compute and commit to the subsequent FRI layers.
-    let fri_verifier = FriVerifier::new(
-        &mut channel,
-        &mut public_coin,
-        air.options().to_fri_options(),
-        air.trace_poly_degree(),
-    )
-    .map_err(VerifierError::FriVerificationFailed)?;
-    // TODO: make sure air.lde_domain_size() == fri_verifier.domain_size()
+    // When STIR is used, the verifier additionally reads out-of-domain evaluations and the final
+    // polynomial, and draws query indexes for all STIR rounds.
+    let ldt_verifier = match air.options().low_degree_test() {
+        LowDegreeTest::Fri => {
+            let fri_verifier = FriVerifier::new(
+                &mut channel,
+                public_coin,
+                air.options().to_fri_options(),
+                air.trace_poly_degree(),
+            )
+            .map_err(VerifierError::FriVerificationFailed)?;
+            LowDegreeVerifier::Fri(fri_verifier)
+        }
+        LowDegreeTest::Stir => {
+            let stir_verifier = StirVerifier::new(
+                &mut channel,
+                public_coin,
+                air.options().to_stir_options(),
+                air.trace_poly_degree(),
+            )
+            .map_err(VerifierError::StirVerificationFailed)?;
+            LowDegreeVerifier::Stir(stir_verifier)
+        }
+    };
+    // TODO: make sure air.lde_domain_size() == ldt_verifier.domain_size()
 
     // 5 ----- trace and constraint queries -------------------------------------------------------
     // read proof-of-work nonce sent by the prover
@@ -299,9 +369,29 @@ where
     // 7 ----- Verify low-degree proof -------------------------------------------------------------
     // make sure that evaluations of the DEEP composition polynomial we computed in the previous
     // step are in fact evaluations of a polynomial of degree equal to trace polynomial degree
-    fri_verifier
-        .verify(&mut channel, &deep_evaluations, &query_positions)
-        .map_err(VerifierError::FriVerificationFailed)
+    match ldt_verifier {
+        LowDegreeVerifier::Fri(fri_verifier) => fri_verifier
+            .verify(&mut channel, &deep_evaluations, &query_positions)
+            .map_err(VerifierError::FriVerificationFailed),
+        LowDegreeVerifier::Stir(stir_verifier) => stir_verifier
+            .verify(&mut channel, &deep_evaluations, &query_positions)
+            .map_err(VerifierError::StirVerificationFailed),
+    }
+}
+
+// LOW-DEGREE VERIFIER
+// ================================================================================================
+
+/// Verifier for the protocol used to test the DEEP composition polynomial for low degree.
+enum LowDegreeVerifier<E, C, H, R>
+where
+    E: FieldElement,
+    C: fri::VerifierChannel<E, Hasher = H> + stir::VerifierChannel<E, Hasher = H>,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+{
+    Fri(FriVerifier<E, C, H, R>),
+    Stir(StirVerifier<E, C, H, R>),
 }
 
 // ACCEPTABLE OPTIONS
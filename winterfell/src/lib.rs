@@ -255,7 +255,7 @@
 //!
 //! ```no_run
 //! use winterfell::{
-//!     crypto::{hashers::Blake3_256, DefaultRandomCoin},
+//!     crypto::{hashers::Blake3_256, DefaultRandomCoin, MerkleTree},
 //!     math::{fields::f128::BaseElement, FieldElement, ToElements},
 //!     matrix::ColMatrix,
 //!     DefaultTraceLde, ProofOptions, Prover, StarkDomain, Trace, TracePolyTable, TraceTable,
@@ -343,6 +343,7 @@
 //!     type Trace = TraceTable<Self::BaseField>;
 //!     type HashFn = Blake3_256<Self::BaseField>;
 //!     type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+//!     type VC = MerkleTree<Self::HashFn>;
 //!     type TraceLde<E: FieldElement<BaseField = Self::BaseField>> = DefaultTraceLde<E, Self::HashFn>;
 //!     type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
 //!         DefaultConstraintEvaluator<'a, Self::Air, E>;
@@ -389,7 +390,7 @@
 //!
 //! ```
 //! # use winterfell::{
-//! #    crypto::{hashers::Blake3_256, DefaultRandomCoin},
+//! #    crypto::{hashers::Blake3_256, DefaultRandomCoin, MerkleTree},
 //! #    math::{fields::f128::BaseElement, FieldElement, ToElements},
 //! #    matrix::ColMatrix,
 //! #    Air, AirContext, Assertion, ByteWriter, DefaultConstraintEvaluator, DefaultTraceLde,
@@ -483,6 +484,7 @@
 //! #    type Trace = TraceTable<Self::BaseField>;
 //! #    type HashFn = Blake3_256<Self::BaseField>;
 //! #    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+//! #    type VC = MerkleTree<Self::HashFn>;
 //! #    type TraceLde<E: FieldElement<BaseField = Self::BaseField>> = DefaultTraceLde<E, Self::HashFn>;
 //! #    type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
 //! #        DefaultConstraintEvaluator<'a, Self::Air, E>;
@@ -551,7 +553,8 @@
 //! let pub_inputs = PublicInputs { start, result };
 //! assert!(winterfell::verify::<WorkAir,
 //!                              Blake3_256<BaseElement>,
-//!                              DefaultRandomCoin<Blake3_256<BaseElement>>
+//!                              DefaultRandomCoin<Blake3_256<BaseElement>>,
+//!                              MerkleTree<Blake3_256<BaseElement>>
 //!                             >(proof, pub_inputs, &min_opts).is_ok());
 //! ```
 //!
@@ -581,12 +584,13 @@
 #![no_std]
 
 pub use prover::{
-    crypto, iterators, math, matrix, Air, AirContext, Assertion, AuxTraceRandElements,
-    BoundaryConstraint, BoundaryConstraintGroup, ByteReader, ByteWriter, CompositionPolyTrace,
-    ConstraintCompositionCoefficients, ConstraintDivisor, ConstraintEvaluator,
-    DeepCompositionCoefficients, DefaultConstraintEvaluator, DefaultTraceLde, Deserializable,
-    DeserializationError, EvaluationFrame, FieldExtension, ProofOptions, Prover, ProverError,
-    Serializable, SliceReader, StarkDomain, StarkProof, Trace, TraceInfo, TraceLayout, TraceLde,
-    TracePolyTable, TraceTable, TraceTableFragment, TransitionConstraintDegree,
+    crypto, iterators, math, matrix, segment_fragments, Air, AirContext, Assertion,
+    AuxTraceBuilder, AuxTraceRandElements, BoundaryConstraint, BoundaryConstraintGroup, ByteReader,
+    ByteWriter, CompositionPolyTrace, ConstraintCompositionCoefficients, ConstraintDivisor,
+    ConstraintEvaluator, DeepCompositionCoefficients, DefaultConstraintEvaluator, DefaultTraceLde,
+    Deserializable, DeserializationError, EvaluationFrame, FieldExtension, FnAuxTraceBuilder,
+    LowDegreeTest, NoAuxTraceBuilder, ProofOptions, Prover, ProverError, Serializable, SliceReader,
+    StarkDomain, StarkProof, Trace, TraceColumnGroup, TraceInfo, TraceLayout, TraceLde,
+    TracePolyTable, TraceTable, TraceTableBuilder, TraceTableFragment, TransitionConstraintDegree,
 };
-pub use verifier::{verify, AcceptableOptions, VerifierError};
+pub use verifier::{verify, verify_with_coin, AcceptableOptions, VerifierError};